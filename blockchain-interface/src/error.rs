@@ -48,6 +48,8 @@ pub enum BlockchainError {
     InvalidEpoch,
     #[error("Accounts diff not found")]
     AccountsDiffNotFound,
+    #[error("Staking contract history not available for block {0}")]
+    StakingHistoryNotFound(u32),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -87,6 +89,8 @@ pub enum PushError {
     EquivocationAlreadyIncluded(EquivocationLocator),
     #[error("Accounts trie is incomplete and thus cannot be verified.")]
     IncompleteAccountsTrie,
+    #[error("Block does not match the trusted checkpoint")]
+    UntrustedCheckpoint,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
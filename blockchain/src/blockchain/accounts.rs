@@ -199,6 +199,10 @@ impl Blockchain {
             panic!("Failed to revert {block} - {e:?}");
         }
 
+        // The staking contract changes of the block no longer apply.
+        self.chain_store
+            .remove_staking_diff(txn.raw(), block.block_number());
+
         let total_size = self
             .history_store
             .remove_block(txn.raw(), block, inherents)
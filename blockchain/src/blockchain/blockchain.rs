@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
 use nimiq_account::{Accounts, BlockLog};
 use nimiq_block::Block;
@@ -9,18 +9,24 @@ use nimiq_database::{
 };
 use nimiq_genesis::NetworkInfo;
 use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
 use nimiq_primitives::{
-    coin::Coin, networks::NetworkId, policy::Policy, slots_allocation::Validators, trie::TrieItem,
+    coin::Coin,
+    networks::NetworkId,
+    policy::Policy,
+    slots_allocation::Validators,
+    trie::{trie_diff::TrieDiff, TrieItem},
 };
 use nimiq_utils::time::OffsetTime;
 use tokio::sync::broadcast::{channel as broadcast, Sender as BroadcastSender};
 
+use super::staking_history::staking_checkpoint;
 #[cfg(feature = "metrics")]
 use crate::chain_metrics::BlockchainMetrics;
 use crate::{
     blockchain_state::BlockchainState, chain_store::ChainStore, history::HistoryStore,
     history_store_proxy::HistoryStoreProxy, interface::HistoryInterface,
-    reward::genesis_parameters, HistoryStoreIndex,
+    reward::genesis_parameters, HistoryStoreIndex, SecondaryIndexKind,
 };
 
 const BROADCAST_MAX_CAPACITY: usize = 256;
@@ -72,6 +78,12 @@ pub struct BlockchainConfig {
     pub max_epochs_stored: u32,
     /// Enables/Disables indices in the history store.
     pub index_history: bool,
+    /// The secondary indexes that are maintained in the history store. Only effective if
+    /// `index_history` is set.
+    pub secondary_indexes: BTreeSet<SecondaryIndexKind>,
+    /// Determines which part of the history of finalized epochs is kept. Only effective if
+    /// `keep_history` is set.
+    pub history_pruning: HistoryPruning,
 }
 
 impl Default for BlockchainConfig {
@@ -80,10 +92,24 @@ impl Default for BlockchainConfig {
             keep_history: true,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
             index_history: true,
+            secondary_indexes: BTreeSet::new(),
+            history_pruning: HistoryPruning::default(),
         }
     }
 }
 
+/// The pruning policy for the history of finalized epochs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum HistoryPruning {
+    /// The full history is kept.
+    #[default]
+    KeepAll,
+    /// Only historic transactions that involve one of the given addresses are kept. The history
+    /// trees are kept in full, so the history roots of the pruned epochs stay available.
+    /// The staking contract of pruned epochs can only be queried at their election blocks.
+    KeepAddresses(BTreeSet<Address>),
+}
+
 /// Implements methods to start a Blockchain.
 impl Blockchain {
     /// Creates a new blockchain from a given environment and network ID.
@@ -137,10 +163,13 @@ impl Blockchain {
         }
 
         let history_store = if config.index_history {
-            Arc::new(HistoryStoreProxy::WithIndex(HistoryStoreIndex::new(
-                env.clone(),
-                network_id,
-            )))
+            Arc::new(HistoryStoreProxy::WithIndex(
+                HistoryStoreIndex::with_secondary_indexes(
+                    env.clone(),
+                    network_id,
+                    &config.secondary_indexes,
+                ),
+            ))
         } else {
             Arc::new(HistoryStoreProxy::WithoutIndex(Box::new(HistoryStore::new(
                 env.clone(),
@@ -339,6 +368,17 @@ impl Blockchain {
         let mut txn = env.write_transaction();
         accounts.init(&mut (&mut txn).into(), genesis_accounts);
 
+        // History nodes keep the genesis staking contract as the first staking checkpoint.
+        if config.keep_history {
+            let checkpoint = staking_checkpoint(&accounts.tree, &txn);
+            chain_store.put_staking_history(
+                &mut txn,
+                genesis_block_number,
+                &TrieDiff::default(),
+                Some(checkpoint),
+            );
+        }
+
         // Store genesis block.
         chain_store.put_chain_info(&mut txn, &head_hash, &main_chain, true);
         chain_store.set_head(&mut txn, &head_hash);
@@ -394,6 +434,90 @@ impl Blockchain {
     pub fn write_transaction(&self) -> MdbxWriteTransaction {
         self.db.write_transaction()
     }
+
+    /// Returns the database of the blockchain. This allows to e.g. create snapshots of the
+    /// database without holding the blockchain lock.
+    pub fn db(&self) -> &MdbxDatabase {
+        &self.db
+    }
+
+    /// Prunes the history of a finalized epoch according to the configured history pruning.
+    /// The staking diffs of the epoch are pruned as well, only its staking checkpoint is kept.
+    pub(crate) fn prune_history(&self, txn: &mut MdbxWriteTransaction, epoch_number: u32) {
+        if let HistoryPruning::KeepAddresses(addresses) = &self.config.history_pruning {
+            let num_pruned = self
+                .history_store
+                .prune_history(txn, epoch_number, addresses);
+            self.chain_store.prune_staking_diffs(txn, epoch_number);
+            debug!(epoch_number, num_pruned, "Pruned history");
+        }
+    }
+
+    /// Checks that the given database belongs to the given network, i.e. that its genesis block
+    /// is the one of that network. Tools that open a node's database offline use this to make
+    /// sure they operate on the intended network.
+    pub fn verify_database_network(
+        env: MdbxDatabase,
+        network_id: NetworkId,
+    ) -> Result<(), BlockchainError> {
+        let chain_store = Self::database_chain_store(env, network_id);
+        Self::verify_chain_store_network(&chain_store, network_id)
+    }
+
+    fn database_chain_store(env: MdbxDatabase, network_id: NetworkId) -> ChainStore {
+        let history_store = Arc::new(HistoryStoreProxy::WithoutIndex(Box::new(HistoryStore::new(
+            env.clone(),
+            network_id,
+        ))
+            as Box<dyn HistoryInterface + Sync + Send>));
+        ChainStore::new(env, history_store)
+    }
+
+    fn verify_chain_store_network(
+        chain_store: &ChainStore,
+        network_id: NetworkId,
+    ) -> Result<(), BlockchainError> {
+        let genesis_block = chain_store
+            .get_block_at(Policy::genesis_block_number(), false, None)
+            .map_err(|_| BlockchainError::FailedLoadingMainChain)?;
+        if genesis_block.network() != network_id {
+            return Err(BlockchainError::InvalidGenesisBlock);
+        }
+        Ok(())
+    }
+
+    /// Checks that the given database contains a chain of the given network whose head block
+    /// matches the stored accounts trie. This is used to validate database snapshots before they
+    /// are restored. Returns the head block.
+    pub fn verify_database(
+        env: MdbxDatabase,
+        network_id: NetworkId,
+    ) -> Result<Block, BlockchainError> {
+        let chain_store = Self::database_chain_store(env.clone(), network_id);
+        Self::verify_chain_store_network(&chain_store, network_id)?;
+
+        let head_hash = chain_store
+            .get_head(None)
+            .ok_or(BlockchainError::FailedLoadingMainChain)?;
+        let head = chain_store
+            .get_chain_info(&head_hash, false, None)
+            .map_err(|_| BlockchainError::FailedLoadingMainChain)?
+            .head;
+
+        // The accounts trie must be complete and match the state root of the head block.
+        let accounts = Accounts::new(env);
+        match accounts.get_root_hash(None) {
+            Some(accounts_hash) if head.state_root() == &accounts_hash => Ok(head),
+            accounts_hash => {
+                log::error!(
+                    "Head block's state root: {:?}, Account state root: {:?}",
+                    head.state_root(),
+                    accounts_hash
+                );
+                Err(BlockchainError::InconsistentState)
+            }
+        }
+    }
 }
 
 pub trait TransactionVerificationCache: Send + Sync {
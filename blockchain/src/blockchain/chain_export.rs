@@ -0,0 +1,357 @@
+use std::io::{self, Read, Write};
+
+use nimiq_block::{Block, MacroBlock};
+use nimiq_blockchain_interface::{
+    AbstractBlockchain, BlockchainError, Direction, PushError, PushResult,
+};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_serde::DeserializeError;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{interface::HistoryInterface, Blockchain, HistoryTreeChunk, CHUNK_SIZE};
+
+/// Magic bytes at the start of every chain export file.
+pub const CHAIN_EXPORT_MAGIC: [u8; 4] = *b"NIMC";
+/// The current version of the chain export format.
+pub const CHAIN_EXPORT_VERSION: u16 = 1;
+/// The maximum serialized size of an item in a chain export file. Larger items are rejected
+/// before they are read.
+pub const CHAIN_EXPORT_MAX_ITEM_SIZE: usize = 64 * 1024 * 1024;
+
+/// The header of a chain export file. It is followed by length-prefixed [`ChainExportItem`]s.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainExportHeader {
+    pub magic: [u8; 4],
+    pub version: u16,
+    pub network_id: NetworkId,
+    /// The first exported epoch.
+    pub first_epoch: u32,
+    /// The last exported epoch.
+    pub last_epoch: u32,
+}
+
+/// An item of a chain export file.
+#[derive(Serialize, Deserialize)]
+pub enum ChainExportItem {
+    /// A macro block (election or checkpoint) with the history chunks that were added to the
+    /// epoch's history tree since the previous macro block of the same epoch. The chunks are
+    /// proven against the history root of the macro block.
+    BatchSet {
+        block: MacroBlock,
+        first_chunk_index: u64,
+        chunks: Vec<HistoryTreeChunk>,
+    },
+    /// A micro block after the last exported macro block.
+    MicroBlock(Block),
+}
+
+/// Summary of a chain export or import.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChainExportSummary {
+    /// The number of macro blocks that were exported or imported.
+    pub num_macro_blocks: usize,
+    /// The number of micro blocks that were exported or imported.
+    pub num_micro_blocks: usize,
+    /// The number of history items that were exported or imported.
+    pub num_history_items: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum ChainExportError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Failed to deserialize: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Item too large: {0} bytes")]
+    ItemTooLarge(usize),
+    #[error("Not a chain export file")]
+    InvalidMagic,
+    #[error("Unsupported chain export version: {0}")]
+    UnsupportedVersion(u16),
+    #[error("Chain export is for network {found}, expected {expected}")]
+    NetworkMismatch {
+        expected: NetworkId,
+        found: NetworkId,
+    },
+    #[error("Invalid epoch range: {0}..={1}")]
+    InvalidEpochRange(u32, u32),
+    #[error("Blockchain error: {0}")]
+    Blockchain(#[from] BlockchainError),
+    #[error("History for block {0} is not available")]
+    MissingHistory(u32),
+    #[error("History chunk {chunk_index} of block {block_number} failed to verify")]
+    InvalidHistoryChunk { block_number: u32, chunk_index: u64 },
+    #[error("Failed to push block {0}: {1}")]
+    Push(u32, PushError),
+}
+
+/// Implements the export of a range of epochs to a portable file and its offline import.
+/// The import doesn't trust the file: history chunks are verified against the history root of
+/// their macro block, macro blocks are pushed with [`Blockchain::push_history_sync`] and micro
+/// blocks with [`Blockchain::push`], so they go through the same checks as blocks received from
+/// the network.
+impl Blockchain {
+    /// Exports the epochs `first_epoch..=last_epoch` to the given writer. If the range includes
+    /// the current epoch, its macro blocks and the micro blocks after the last macro block are
+    /// exported as well. The data is read from a single database transaction.
+    pub fn export_chain<W: Write>(
+        &self,
+        writer: &mut W,
+        first_epoch: u32,
+        last_epoch: u32,
+    ) -> Result<ChainExportSummary, ChainExportError> {
+        // The genesis block is known to every node, so the first epoch to export is 1.
+        let first_epoch = first_epoch.max(1);
+        let last_epoch = last_epoch.min(self.epoch_number());
+        if first_epoch > last_epoch {
+            return Err(ChainExportError::InvalidEpochRange(first_epoch, last_epoch));
+        }
+
+        let header = ChainExportHeader {
+            magic: CHAIN_EXPORT_MAGIC,
+            version: CHAIN_EXPORT_VERSION,
+            network_id: self.network_id,
+            first_epoch,
+            last_epoch,
+        };
+        write_item(writer, &header)?;
+
+        let txn = self.read_transaction();
+        let mut summary = ChainExportSummary::default();
+
+        for epoch in first_epoch..=last_epoch {
+            // For the current epoch, we export everything up to the latest macro block.
+            let block_number = Policy::election_block_of(epoch)
+                .expect("Epoch must be valid")
+                .min(self.macro_head().block_number());
+
+            let macro_hashes = self
+                .chain_store
+                .get_epoch_chunks(block_number, Some(&txn))?;
+
+            let mut prev_history_len = 0;
+            for macro_hash in macro_hashes {
+                let block = self
+                    .chain_store
+                    .get_block(&macro_hash, true, Some(&txn))?
+                    .unwrap_macro();
+
+                let history_len = self
+                    .history_store
+                    .prove_num_leaves(block.block_number(), Some(&txn))
+                    .map_err(|_| ChainExportError::MissingHistory(block.block_number()))?
+                    .size();
+
+                // Chunks are aligned to the chunk size, so the first chunk might overlap with
+                // the history of the previous macro block.
+                let first_chunk_index = prev_history_len / CHUNK_SIZE as u64;
+                let mut chunks = vec![];
+                for chunk_index in first_chunk_index..history_len.div_ceil(CHUNK_SIZE as u64) {
+                    let chunk = self
+                        .history_store
+                        .prove_chunk(
+                            block.epoch_number(),
+                            block.block_number(),
+                            CHUNK_SIZE,
+                            chunk_index as usize,
+                            Some(&txn),
+                        )
+                        .ok_or(ChainExportError::MissingHistory(block.block_number()))?;
+                    summary.num_history_items += chunk.history.len();
+                    chunks.push(chunk);
+                }
+                prev_history_len = history_len;
+
+                write_item(
+                    writer,
+                    &ChainExportItem::BatchSet {
+                        block,
+                        first_chunk_index,
+                        chunks,
+                    },
+                )?;
+                summary.num_macro_blocks += 1;
+            }
+        }
+
+        // Export the micro blocks after the last macro block if the current epoch was exported.
+        if last_epoch == self.epoch_number() {
+            let micro_blocks = self.chain_store.get_blocks(
+                &self.macro_head_hash(),
+                self.block_number() - self.macro_head().block_number(),
+                true,
+                Direction::Forward,
+                Some(&txn),
+            )?;
+            for block in micro_blocks {
+                write_item(writer, &ChainExportItem::MicroBlock(block))?;
+                summary.num_micro_blocks += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Imports a chain export from the given reader. All data is verified before it is applied.
+    /// Blocks that are already known are skipped, so an import can be resumed.
+    pub fn import_chain<R: Read>(
+        blockchain: &RwLock<Blockchain>,
+        reader: &mut R,
+    ) -> Result<ChainExportSummary, ChainExportError> {
+        let header: ChainExportHeader = read_item(reader)?
+            .ok_or_else(|| ChainExportError::Io(io::Error::from(io::ErrorKind::UnexpectedEof)))?;
+        if header.magic != CHAIN_EXPORT_MAGIC {
+            return Err(ChainExportError::InvalidMagic);
+        }
+        if header.version != CHAIN_EXPORT_VERSION {
+            return Err(ChainExportError::UnsupportedVersion(header.version));
+        }
+        let network_id = blockchain.read().network_id;
+        if header.network_id != network_id {
+            return Err(ChainExportError::NetworkMismatch {
+                expected: network_id,
+                found: header.network_id,
+            });
+        }
+
+        info!(
+            first_epoch = header.first_epoch,
+            last_epoch = header.last_epoch,
+            "Importing chain"
+        );
+
+        let mut summary = ChainExportSummary::default();
+        while let Some(item) = read_item(reader)? {
+            match item {
+                ChainExportItem::BatchSet {
+                    block,
+                    first_chunk_index,
+                    chunks,
+                } => {
+                    let block_number = block.block_number();
+
+                    let mut history = vec![];
+                    for (chunk_index, mut chunk) in (first_chunk_index..).zip(chunks) {
+                        let leaf_index = chunk_index as usize * CHUNK_SIZE;
+                        if !chunk
+                            .verify(&block.header.history_root, leaf_index)
+                            .unwrap_or(false)
+                        {
+                            return Err(ChainExportError::InvalidHistoryChunk {
+                                block_number,
+                                chunk_index,
+                            });
+                        }
+                        history.append(&mut chunk.history);
+                    }
+
+                    let num_history_items = history.len();
+                    let result = Blockchain::push_history_sync(
+                        blockchain.upgradable_read(),
+                        Block::Macro(block),
+                        &history,
+                    )
+                    .map_err(|error| ChainExportError::Push(block_number, error))?;
+                    debug!(
+                        block_number,
+                        num_history_items,
+                        ?result,
+                        "Imported macro block"
+                    );
+
+                    if result != PushResult::Known && result != PushResult::Ignored {
+                        summary.num_macro_blocks += 1;
+                        summary.num_history_items += num_history_items;
+                    }
+                }
+                ChainExportItem::MicroBlock(block) => {
+                    let block_number = block.block_number();
+                    let result = Blockchain::push(blockchain.upgradable_read(), block)
+                        .map_err(|error| ChainExportError::Push(block_number, error))?;
+                    debug!(block_number, ?result, "Imported micro block");
+
+                    if result != PushResult::Known && result != PushResult::Ignored {
+                        summary.num_micro_blocks += 1;
+                    }
+                }
+            }
+        }
+
+        info!(
+            num_macro_blocks = summary.num_macro_blocks,
+            num_micro_blocks = summary.num_micro_blocks,
+            num_history_items = summary.num_history_items,
+            "Imported chain"
+        );
+
+        Ok(summary)
+    }
+}
+
+/// Writes an item prefixed with its serialized length. Items that [`read_item`] would reject
+/// are not written.
+fn write_item<W: Write, T: nimiq_serde::Serialize>(
+    writer: &mut W,
+    item: &T,
+) -> Result<(), ChainExportError> {
+    let bytes = item.serialize_to_vec();
+    if bytes.len() > CHAIN_EXPORT_MAX_ITEM_SIZE {
+        return Err(ChainExportError::ItemTooLarge(bytes.len()));
+    }
+    let len =
+        u32::try_from(bytes.len()).map_err(|_| ChainExportError::ItemTooLarge(bytes.len()))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Reads a length-prefixed item. Returns `None` at the end of the input, i.e. if the input ends
+/// before the length prefix of the next item. Truncated items are an error.
+fn read_item<R: Read, T: nimiq_serde::Deserialize>(
+    reader: &mut R,
+) -> Result<Option<T>, ChainExportError> {
+    let mut len = [0u8; 4];
+    loop {
+        match reader.read(&mut len[..1]) {
+            Ok(0) => return Ok(None),
+            Ok(_) => break,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+    reader.read_exact(&mut len[1..])?;
+
+    let len = u32::from_be_bytes(len) as usize;
+    if len > CHAIN_EXPORT_MAX_ITEM_SIZE {
+        return Err(ChainExportError::ItemTooLarge(len));
+    }
+
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(Some(T::deserialize_all(&bytes)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_item, write_item, ChainExportError, CHAIN_EXPORT_MAX_ITEM_SIZE};
+
+    #[test]
+    fn it_does_not_write_items_that_cannot_be_read() {
+        // The serialized item includes a length prefix, so it is larger than the maximum.
+        let item = vec![0u8; CHAIN_EXPORT_MAX_ITEM_SIZE];
+        let mut export = vec![];
+        assert!(matches!(
+            write_item(&mut export, &item),
+            Err(ChainExportError::ItemTooLarge(len)) if len > CHAIN_EXPORT_MAX_ITEM_SIZE
+        ));
+        assert!(export.is_empty());
+
+        // Items up to the maximum size round-trip.
+        let item = vec![1u8; 1024];
+        write_item(&mut export, &item).unwrap();
+        let read: Option<Vec<u8>> = read_item(&mut export.as_slice()).unwrap();
+        assert_eq!(read, Some(item));
+    }
+}
@@ -0,0 +1,233 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use nimiq_account::Accounts;
+use nimiq_database::{
+    mdbx::MdbxDatabase,
+    traits::{Database, WriteTransaction},
+};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use serde::Serialize;
+
+use crate::{
+    chain_store::ChainStore, history::HistoryStore, history_store_proxy::HistoryStoreProxy,
+    interface::HistoryInterface, Blockchain, HistoryStoreIndex, SecondaryIndexKind,
+};
+
+/// The maximum number of errors that are reported per check. Further errors are only counted.
+const MAX_REPORTED_ERRORS: usize = 100;
+
+/// The name of the table that is only present if the history index is enabled.
+const HISTORY_INDEX_TABLE: &str = "LeafIndexByTxHash";
+
+/// The result of a single database check.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbCheckResult {
+    /// The name of the check.
+    pub name: &'static str,
+    /// The number of entries that were checked.
+    pub num_checked: usize,
+    /// The number of inconsistencies that were found.
+    pub num_errors: usize,
+    /// A description of the first inconsistencies that were found.
+    pub errors: Vec<String>,
+    /// The reason why the check was skipped, if it was.
+    pub skipped: Option<String>,
+}
+
+impl DbCheckResult {
+    fn new(name: &'static str) -> Self {
+        DbCheckResult {
+            name,
+            num_checked: 0,
+            num_errors: 0,
+            errors: vec![],
+            skipped: None,
+        }
+    }
+
+    fn skipped(name: &'static str, reason: &str) -> Self {
+        DbCheckResult {
+            skipped: Some(reason.to_string()),
+            ..Self::new(name)
+        }
+    }
+
+    /// Records an inconsistency.
+    pub(crate) fn error(&mut self, error: String) {
+        self.num_errors += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(error);
+        }
+    }
+
+    /// Returns true if no inconsistencies were found.
+    pub fn is_ok(&self) -> bool {
+        self.num_errors == 0
+    }
+}
+
+/// The report of a database check.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbCheckReport {
+    pub network_id: NetworkId,
+    /// The block number of the head block, if it could be loaded.
+    pub head_block_number: Option<u32>,
+    /// The hash of the head block, if it could be loaded.
+    pub head_hash: Option<Blake2bHash>,
+    /// The results of the individual checks.
+    pub checks: Vec<DbCheckResult>,
+    /// Whether the history index was rebuilt.
+    pub index_repaired: bool,
+}
+
+impl DbCheckReport {
+    /// Returns true if none of the checks found an inconsistency.
+    pub fn is_ok(&self) -> bool {
+        self.checks.iter().all(DbCheckResult::is_ok)
+    }
+}
+
+impl Blockchain {
+    /// Checks the consistency of the given database without loading a blockchain from it:
+    /// the chain info links, the accounts trie root against the head block, the history roots
+    /// against the macro blocks, the history index (including any secondary indexes) and the
+    /// validity store.
+    ///
+    /// The database can be opened read-only. If `repair_index` is set and the history index is
+    /// inconsistent, it is rebuilt along with its secondary indexes, which requires write access.
+    pub fn check_database(
+        env: MdbxDatabase,
+        network_id: NetworkId,
+        repair_index: bool,
+    ) -> DbCheckReport {
+        // All stores are opened before the read transaction is started, because opening them
+        // might require a write transaction on a writable database.
+        let table_names = env.read_transaction().table_names().unwrap_or_default();
+        let has_table = |table_name: &str| table_names.iter().any(|name| name == table_name);
+        // Secondary indexes are checked along with the history index if they exist.
+        let secondary_indexes: BTreeSet<_> = SecondaryIndexKind::ALL
+            .into_iter()
+            .filter(|kind| has_table(kind.index().table_name()))
+            .collect();
+        let index = has_table(HISTORY_INDEX_TABLE)
+            .then(|| HistoryStoreIndex::open(env.clone(), network_id, &secondary_indexes));
+        let accounts = Accounts::new(env.clone());
+        let history_store = HistoryStore::new(env.clone(), network_id);
+        let chain_store = ChainStore::new(
+            env.clone(),
+            Arc::new(HistoryStoreProxy::WithoutIndex(Box::new(
+                HistoryStore::new(env.clone(), network_id),
+            ))),
+        );
+        let txn = env.read_transaction();
+
+        let mut report = DbCheckReport {
+            network_id,
+            head_block_number: None,
+            head_hash: None,
+            checks: vec![],
+            index_repaired: false,
+        };
+
+        // Chain info links.
+        let mut result = DbCheckResult::new("chainLinks");
+        let macro_blocks = chain_store.check_chain_links(&txn, &mut result);
+        report.checks.push(result);
+
+        let head = chain_store
+            .get_head(Some(&txn))
+            .and_then(|hash| chain_store.get_chain_info(&hash, false, Some(&txn)).ok());
+        if let Some(head) = &head {
+            report.head_block_number = Some(head.head.block_number());
+            report.head_hash = Some(head.head.hash());
+        }
+
+        // Accounts trie root.
+        report.checks.push(match &head {
+            Some(head) => {
+                let mut result = DbCheckResult::new("accountsRoot");
+                result.num_checked = 1;
+                match accounts.get_root_hash(Some(&txn)) {
+                    Some(root) if &root == head.head.state_root() => {}
+                    Some(root) => result.error(format!(
+                        "Accounts root {root} doesn't match the state root {} of the head block",
+                        head.head.state_root()
+                    )),
+                    None => result.error("The accounts trie is incomplete".to_string()),
+                }
+                result
+            }
+            None => DbCheckResult::skipped("accountsRoot", "No head block"),
+        });
+
+        // History roots of the macro blocks whose epoch is still in the history store.
+        let mut result = DbCheckResult::new("historyRoots");
+        let (first_bn, _) = history_store.history_store_range(Some(&txn));
+        let first_epoch = Policy::epoch_at(first_bn);
+        for chain_info in macro_blocks {
+            let block = chain_info.head.unwrap_macro_ref();
+            let block_number = block.block_number();
+            if block_number == Policy::genesis_block_number()
+                || Policy::epoch_at(block_number) < first_epoch
+            {
+                continue;
+            }
+            result.num_checked += 1;
+
+            match history_store.prove_num_leaves(block_number, Some(&txn)) {
+                Ok(proof) if !proof.verify(&block.header.history_root) => result.error(format!(
+                    "History root of macro block #{block_number} doesn't match the history store"
+                )),
+                Ok(proof) if proof.size() != chain_info.history_tree_len => result.error(format!(
+                    "Macro block #{block_number} has {} history items, the chain info {}",
+                    proof.size(),
+                    chain_info.history_tree_len
+                )),
+                Ok(_) => {}
+                Err(error) => result.error(format!(
+                    "Failed to prove the history size at macro block #{block_number}: {error:?}"
+                )),
+            }
+        }
+        report.checks.push(result);
+
+        // Validity store.
+        let mut result = DbCheckResult::new("validityStore");
+        history_store.validity_store.check(&txn, &mut result);
+        report.checks.push(result);
+
+        // History index.
+        if let Some(index) = index {
+            let mut result = DbCheckResult::new("historyIndex");
+            index.check_index(&txn, &mut result);
+
+            if !result.is_ok() && repair_index {
+                // The read transaction must be closed before the index can be rebuilt.
+                drop(txn);
+                info!(
+                    num_errors = result.num_errors,
+                    "Rebuilding inconsistent history index"
+                );
+                let mut write_txn = env.write_transaction();
+                index.rebuild_index(&mut write_txn);
+                write_txn.commit();
+                report.index_repaired = true;
+
+                let txn = env.read_transaction();
+                result = DbCheckResult::new("historyIndex");
+                index.check_index(&txn, &mut result);
+            }
+            report.checks.push(result);
+        } else {
+            report.checks.push(DbCheckResult::skipped(
+                "historyIndex",
+                "The history index is disabled",
+            ));
+        }
+
+        report
+    }
+}
@@ -0,0 +1,162 @@
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
+use nimiq_primitives::{coin::Coin, policy::Policy};
+
+use crate::{
+    reward::{batch_delay, block_reward_for_batch, max_reward_for_batch},
+    Blockchain,
+};
+
+/// The number of milliseconds in a (non-leap) year.
+const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+
+/// The supply and the staked amount at a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SupplyInfo {
+    /// The block the values refer to.
+    pub block_number: u32,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+    /// The circulating supply at the timestamp of the block.
+    pub supply: Coin,
+    /// The total amount staked in the staking contract, including validator deposits.
+    /// `None` if the staking contract at the block is not available, see
+    /// [`Blockchain::get_supply_info`].
+    pub staked: Option<Coin>,
+}
+
+/// The block reward for a batch.
+/// It is paid out, together with the transaction fees of the batch, in the macro block of the
+/// next batch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchRewardInfo {
+    /// The batch the reward is for.
+    pub batch_number: u32,
+    /// The macro block that ends the batch.
+    pub block_number: u32,
+    /// The delay of the batch compared to the target block separation time, in milliseconds.
+    pub batch_delay: u64,
+    /// The share of the maximum reward that is paid out due to the delay, in the range [0, 1].
+    pub delay_penalty: f64,
+    /// The reward if the batch had been produced in time.
+    pub max_reward: Coin,
+    /// The actual block reward.
+    pub reward: Coin,
+    /// The transaction fees collected in the batch.
+    pub tx_fees: Coin,
+}
+
+/// The projected yearly yield for the current active stake.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StakingYield {
+    /// The block the projection is based on.
+    pub block_number: u32,
+    /// The total stake of the active validators.
+    pub active_stake: Coin,
+    /// The block rewards that will be paid out in the next year if all batches are produced in
+    /// time. Transaction fees are not included.
+    pub yearly_reward: Coin,
+    /// The yearly reward relative to the active stake, if there is any.
+    pub apr: Option<f64>,
+}
+
+/// Implements methods to query the supply and the staking economics.
+impl Blockchain {
+    /// Returns the supply and the staked amount after the given block.
+    /// The staked amount for past blocks requires the staking history, which only history nodes
+    /// keep. It is reconstructed from at most one epoch of staking diffs on a separate database
+    /// snapshot. If the staking history of the block is not available, the staked amount is
+    /// omitted.
+    pub fn get_supply_info(&self, block_number: u32) -> Result<SupplyInfo, BlockchainError> {
+        let block = self.get_block_at(block_number, false, None)?;
+        let (genesis_supply, genesis_timestamp) = self.get_genesis_parameters();
+        let supply = Policy::supply_at(
+            u64::from(genesis_supply),
+            genesis_timestamp,
+            block.timestamp(),
+        );
+        let staked = match self.staking_history().staking_contract_at(block_number) {
+            Ok(staking_contract) => Some(staking_contract.balance),
+            Err(BlockchainError::StakingHistoryNotFound(_)) => None,
+            Err(error) => return Err(error),
+        };
+
+        Ok(SupplyInfo {
+            block_number,
+            timestamp: block.timestamp(),
+            supply: Coin::from_u64_unchecked(supply),
+            staked,
+        })
+    }
+
+    /// Returns the block reward for the given batch, which must have been finalized.
+    pub fn get_batch_reward_info(
+        &self,
+        batch_number: u32,
+    ) -> Result<BatchRewardInfo, BlockchainError> {
+        let block_number = Policy::macro_block_of(batch_number)
+            .filter(|_| batch_number > 0)
+            .ok_or(BlockchainError::BlockNotFound)?;
+        if block_number > self.block_number() {
+            return Err(BlockchainError::BlockNotFound);
+        }
+
+        let txn = self.read_transaction();
+        let chain_info = self
+            .chain_store
+            .get_chain_info_at(block_number, false, Some(&txn))?;
+        let previous_block =
+            self.get_block_at(Policy::macro_block_before(block_number), false, Some(&txn))?;
+        let header = &chain_info.head.unwrap_macro_ref().header;
+        let previous_header = &previous_block.unwrap_macro_ref().header;
+
+        let (genesis_supply, genesis_timestamp) = self.get_genesis_parameters();
+        let batch_delay = batch_delay(previous_header.timestamp, header.timestamp);
+
+        Ok(BatchRewardInfo {
+            batch_number,
+            block_number,
+            batch_delay,
+            delay_penalty: Policy::batch_delay_penalty(batch_delay),
+            max_reward: max_reward_for_batch(
+                previous_header.timestamp,
+                header.timestamp,
+                genesis_supply,
+                genesis_timestamp,
+            ),
+            reward: block_reward_for_batch(
+                header,
+                previous_header,
+                genesis_supply,
+                genesis_timestamp,
+            ),
+            tx_fees: chain_info.cum_tx_fees,
+        })
+    }
+
+    /// Projects the yearly yield of the current active stake from the supply curve.
+    pub fn get_staking_yield(&self) -> Result<StakingYield, BlockchainError> {
+        let staking_contract = self
+            .get_staking_contract_if_complete(None)
+            .ok_or(BlockchainError::InconsistentState)?;
+        let active_stake: Coin = staking_contract.active_validators.values().copied().sum();
+
+        let (genesis_supply, genesis_timestamp) = self.get_genesis_parameters();
+        let timestamp = self.timestamp();
+        let yearly_reward = max_reward_for_batch(
+            timestamp,
+            timestamp + YEAR_MS,
+            genesis_supply,
+            genesis_timestamp,
+        );
+
+        let apr = (!active_stake.is_zero())
+            .then(|| u64::from(yearly_reward) as f64 / u64::from(active_stake) as f64);
+
+        Ok(StakingYield {
+            block_number: self.block_number(),
+            active_stake,
+            yearly_reward,
+            apr,
+        })
+    }
+}
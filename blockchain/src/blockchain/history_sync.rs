@@ -412,6 +412,11 @@ impl Blockchain {
             return Err(PushError::InvalidBlock(BlockError::AccountsHashMismatch));
         }
 
+        // Prune the history of the previous epoch once an epoch is finalized.
+        if macro_block.is_election() && this.config.keep_history {
+            this.prune_history(&mut txn, macro_block.epoch_number().saturating_sub(1));
+        }
+
         // Give up database transactions and push lock before creating notifications.
         txn.commit();
 
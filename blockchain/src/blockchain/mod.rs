@@ -4,11 +4,16 @@ mod abstract_blockchain;
 pub mod accounts;
 #[allow(clippy::module_inception)]
 pub mod blockchain;
+pub mod chain_export;
+pub mod db_check;
+pub mod economics;
 pub mod history_sync;
 pub mod inherents;
 pub mod push;
 pub(super) mod rebranch_utils;
 pub mod slots;
+pub mod staking_history;
+pub mod staking_rewards;
 pub mod verify;
 pub mod wrappers;
 pub mod zkp_sync;
@@ -23,7 +23,10 @@ use nimiq_trie::WriteTransactionProxy as TrieMdbxWriteTransaction;
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
 use tokio::sync::broadcast::Sender as BroadcastSender;
 
-use super::PostValidationHook;
+use super::{
+    staking_history::{staking_backward_diff, staking_checkpoint},
+    PostValidationHook,
+};
 use crate::{interface::HistoryInterface, Blockchain};
 
 fn send_vec(log_notifier: &BroadcastSender<BlockLog>, logs: Vec<BlockLog>) {
@@ -368,6 +371,8 @@ impl Blockchain {
                 // Prune the History Store.
                 this.history_store
                     .remove_history(&mut txn, Policy::epoch_at(block_number).saturating_sub(1));
+            } else {
+                this.prune_history(&mut txn, Policy::epoch_at(block_number).saturating_sub(1));
             }
         }
 
@@ -624,7 +629,21 @@ impl Blockchain {
                 self.metrics.note_invalid_block();
             })?;
             if is_complete {
-                let recorded_diff = txn.stop_recording().into_forward_diff();
+                let recorded_changes = txn.stop_recording();
+                // History nodes keep the staking contract changes to serve historic queries.
+                if self.config.keep_history {
+                    let staking_diff = staking_backward_diff(&recorded_changes);
+                    let checkpoint = block
+                        .is_election()
+                        .then(|| staking_checkpoint(&self.state.accounts.tree, &txn));
+                    self.chain_store.put_staking_history(
+                        txn.raw(),
+                        block.block_number(),
+                        &staking_diff,
+                        checkpoint,
+                    );
+                }
+                let recorded_diff = recorded_changes.into_forward_diff();
                 self.chain_store
                     .put_accounts_diff(txn.raw(), &block.hash(), &recorded_diff);
             }
@@ -0,0 +1,368 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use nimiq_account::{
+    Account, AccountsTrie, DataStore, Staker, StakingContract, StakingContractStore, Validator,
+};
+use nimiq_blockchain_interface::BlockchainError;
+use nimiq_database::{
+    mdbx::{MdbxDatabase, MdbxReadTransaction},
+    traits::Database,
+};
+use nimiq_keys::Address;
+use nimiq_primitives::{
+    key_nibbles::KeyNibbles,
+    policy::Policy,
+    trie::{
+        trie_diff::{TrieDiff, TrieDiffBuilder},
+        TrieItem,
+    },
+};
+use nimiq_serde::Deserialize;
+
+use crate::{chain_store::ChainStore, Blockchain};
+
+/// Extracts the changes to the staking contract from the changes recorded for a block and returns
+/// them as a backward diff, i.e. with the values from before the block.
+pub(crate) fn staking_backward_diff(changes: &TrieDiffBuilder) -> TrieDiff {
+    let prefix = KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS);
+    TrieDiffBuilder {
+        changes: changes
+            .changes
+            .iter()
+            .filter(|(key, _)| prefix.is_prefix_of(key))
+            .map(|(key, change)| (key.clone(), change.clone()))
+            .collect(),
+    }
+    .into_backward_diff()
+}
+
+/// Returns the keys and values of the staking contract in the accounts trie. History nodes store
+/// them as checkpoint at every election block.
+pub(crate) fn staking_checkpoint(
+    accounts: &AccountsTrie,
+    txn: &MdbxReadTransaction,
+) -> Vec<TrieItem> {
+    accounts.get_subtree_items(txn, &KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS))
+}
+
+/// The state a historic staking state is reconstructed from.
+enum StakingBase {
+    /// The current state in the accounts trie.
+    Current,
+    /// The checkpoint stored for an election block.
+    Checkpoint(BTreeMap<KeyNibbles, Vec<u8>>),
+}
+
+/// The state of the staking contract after a past block.
+/// It is reconstructed by applying the backward staking diffs of all later blocks on top of the
+/// nearest recorded state, which is either the current state or the checkpoint of the block's
+/// epoch. Thus, at most one epoch of staking diffs is applied.
+pub(crate) struct HistoricStakingState<'a, 'txn, 'env> {
+    history: &'a StakingHistory,
+    txn: &'txn MdbxReadTransaction<'env>,
+    /// The block after which the state is reconstructed.
+    block_number: u32,
+    /// The contract prefix in the accounts trie.
+    prefix: KeyNibbles,
+    /// The state the changes are applied to.
+    base: StakingBase,
+    /// The values of all keys that changed since the block, `None` if the key did not exist.
+    changes: BTreeMap<KeyNibbles, Option<Vec<u8>>>,
+}
+
+impl<'a, 'txn, 'env> HistoricStakingState<'a, 'txn, 'env> {
+    pub(crate) fn new(
+        history: &'a StakingHistory,
+        txn: &'txn MdbxReadTransaction<'env>,
+        block_number: u32,
+    ) -> Result<Self, BlockchainError> {
+        let head_block_number = history.head_block_number(txn)?;
+        if block_number > head_block_number {
+            return Err(BlockchainError::BlockNotFound);
+        }
+
+        let mut state = HistoricStakingState {
+            history,
+            txn,
+            block_number: head_block_number,
+            prefix: KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS),
+            base: StakingBase::Current,
+            changes: BTreeMap::new(),
+        };
+        state.rewind(block_number)?;
+
+        Ok(state)
+    }
+
+    /// Moves the state back to after the given block, which must not be after the current one.
+    pub(crate) fn rewind(&mut self, block_number: u32) -> Result<(), BlockchainError> {
+        assert!(
+            block_number <= self.block_number,
+            "Cannot rewind the staking state forward"
+        );
+        if block_number == self.block_number {
+            return Ok(());
+        }
+
+        let epoch_number = Policy::epoch_at(block_number);
+        let history = self
+            .history
+            .chain_store
+            .get_staking_history(epoch_number, Some(self.txn))
+            .filter(|history| history.first_block <= block_number + 1)
+            .ok_or(BlockchainError::StakingHistoryNotFound(block_number))?;
+
+        // Blocks of earlier epochs are reconstructed from the checkpoint of their epoch.
+        if Policy::epoch_at(self.block_number) != epoch_number {
+            let checkpoint = history
+                .checkpoint
+                .ok_or(BlockchainError::StakingHistoryNotFound(block_number))?;
+            self.base = StakingBase::Checkpoint(
+                checkpoint
+                    .into_iter()
+                    .map(|item| (item.key, item.value))
+                    .collect(),
+            );
+            self.changes.clear();
+            self.block_number = history.last_block;
+        } else if history.last_block < self.block_number {
+            return Err(BlockchainError::StakingHistoryNotFound(block_number));
+        }
+
+        // Apply the diffs from the newest to the oldest block, so that the diffs of older blocks
+        // take precedence.
+        let diffs = self
+            .history
+            .chain_store
+            .get_staking_diffs(block_number + 1..=self.block_number, Some(self.txn));
+        for diff in diffs.into_iter().rev() {
+            self.changes.extend(diff.0);
+        }
+        self.block_number = block_number;
+
+        Ok(())
+    }
+
+    /// Returns the value at the given key, which is relative to the contract prefix.
+    fn get<T: Deserialize>(&self, key: &KeyNibbles) -> Result<Option<T>, BlockchainError> {
+        let key = &self.prefix + key;
+        let value = match (self.changes.get(&key), &self.base) {
+            (Some(value), _) => value.as_ref(),
+            (None, StakingBase::Checkpoint(items)) => items.get(&key),
+            (None, StakingBase::Current) => {
+                return self
+                    .history
+                    .accounts
+                    .get(self.txn, &key)
+                    .map_err(|_| BlockchainError::InconsistentState);
+            }
+        };
+
+        value
+            .map(|value| T::deserialize_from_vec(value))
+            .transpose()
+            .map_err(|_| BlockchainError::InconsistentState)
+    }
+
+    pub(crate) fn staking_contract(&self) -> Result<StakingContract, BlockchainError> {
+        match self.get(&KeyNibbles::ROOT)? {
+            Some(Account::Staking(staking_contract)) => Ok(staking_contract),
+            _ => Err(BlockchainError::InconsistentState),
+        }
+    }
+
+    pub(crate) fn validator(
+        &self,
+        address: &Address,
+    ) -> Result<Option<Validator>, BlockchainError> {
+        self.get(&StakingContractStore::validator_key(address))
+    }
+
+    pub(crate) fn staker(&self, address: &Address) -> Result<Option<Staker>, BlockchainError> {
+        self.get(&StakingContractStore::staker_key(address))
+    }
+
+    /// Returns the stakers that delegated to the given validator.
+    /// IMPORTANT: This is a very expensive operation, iterating over all existing stakers in the
+    /// contract.
+    pub(crate) fn stakers_for_validator(
+        &self,
+        address: &Address,
+    ) -> Result<Vec<Staker>, BlockchainError> {
+        // Stakers that did not change since the block are found in the base state. All candidates
+        // are then looked up in the reconstructed state.
+        let mut staker_addresses: BTreeSet<Address> =
+            self.staker_addresses(self.changes.keys()).collect();
+        match &self.base {
+            StakingBase::Current => {
+                let Ok(Some(Account::Staking(staking_contract))) =
+                    self.history.accounts.get(self.txn, &self.prefix)
+                else {
+                    return Err(BlockchainError::InconsistentState);
+                };
+                let data_store =
+                    DataStore::new(&self.history.accounts, &Policy::STAKING_CONTRACT_ADDRESS);
+                staker_addresses.extend(
+                    staking_contract
+                        .get_stakers_for_validator(&data_store.read(self.txn), address)
+                        .into_iter()
+                        .map(|staker| staker.address),
+                );
+            }
+            StakingBase::Checkpoint(items) => {
+                staker_addresses.extend(self.staker_addresses(items.keys()));
+            }
+        }
+
+        let mut stakers = vec![];
+        for staker_address in staker_addresses {
+            match self.staker(&staker_address)? {
+                Some(staker) if staker.delegation.as_ref() == Some(address) => {
+                    stakers.push(staker);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(stakers)
+    }
+
+    /// Returns the addresses of the stakers among the given keys.
+    fn staker_addresses<'k>(
+        &self,
+        keys: impl Iterator<Item = &'k KeyNibbles> + 'k,
+    ) -> impl Iterator<Item = Address> + 'k {
+        // All staker keys share the prefix of the staker key of the first address.
+        let staker_prefix =
+            &self.prefix + &StakingContractStore::staker_key(&Address::START_ADDRESS).slice(0, 2);
+        let address_start = staker_prefix.len();
+        keys.filter(move |key| staker_prefix.is_prefix_of(key))
+            .filter_map(move |key| {
+                let address = key.slice(address_start, key.len()).to_address();
+                debug_assert!(address.is_some(), "Invalid staker key {key}");
+                address
+            })
+    }
+}
+
+/// Queries the state of the staking contract at past blocks.
+///
+/// The queries rely on the staking history that history nodes record: the backward staking diffs
+/// of all blocks and a checkpoint of the staking contract at every election block. Each query
+/// reconstructs the state from at most one epoch of diffs.
+///
+/// The handle does not borrow the blockchain. Every query runs on its own read transaction, which
+/// is a consistent snapshot of the database, so the blockchain lock doesn't need to be held.
+#[derive(Clone)]
+pub struct StakingHistory {
+    db: MdbxDatabase,
+    chain_store: ChainStore,
+    accounts: AccountsTrie,
+}
+
+impl StakingHistory {
+    /// Returns the head block number of the snapshot the transaction reads from.
+    fn head_block_number(&self, txn: &MdbxReadTransaction) -> Result<u32, BlockchainError> {
+        let head_hash = self
+            .chain_store
+            .get_head(Some(txn))
+            .ok_or(BlockchainError::FailedLoadingMainChain)?;
+        let chain_info = self
+            .chain_store
+            .get_chain_info(&head_hash, false, Some(txn))?;
+        Ok(chain_info.head.block_number())
+    }
+
+    /// Returns the staking contract as it was after the given block.
+    pub fn staking_contract_at(
+        &self,
+        block_number: u32,
+    ) -> Result<StakingContract, BlockchainError> {
+        let txn = self.db.read_transaction();
+        HistoricStakingState::new(self, &txn, block_number)?.staking_contract()
+    }
+
+    /// Returns the validator with the given address as it was after the given block.
+    pub fn validator_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Option<Validator>, BlockchainError> {
+        let txn = self.db.read_transaction();
+        HistoricStakingState::new(self, &txn, block_number)?.validator(address)
+    }
+
+    /// Returns the staker with the given address as it was after the given block.
+    pub fn staker_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Option<Staker>, BlockchainError> {
+        let txn = self.db.read_transaction();
+        HistoricStakingState::new(self, &txn, block_number)?.staker(address)
+    }
+
+    /// Returns the stakers that delegated to the given validator after the given block.
+    /// IMPORTANT: This is a very expensive operation, iterating over all existing stakers in the
+    /// contract.
+    pub fn stakers_for_validator_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Vec<Staker>, BlockchainError> {
+        let txn = self.db.read_transaction();
+        HistoricStakingState::new(self, &txn, block_number)?.stakers_for_validator(address)
+    }
+}
+
+/// Implements methods to query the state of the staking contract at past blocks.
+/// See [`StakingHistory`].
+impl Blockchain {
+    /// Returns a handle to query the staking contract at past blocks without holding the
+    /// blockchain lock.
+    pub fn staking_history(&self) -> StakingHistory {
+        StakingHistory {
+            db: self.db.clone(),
+            chain_store: self.chain_store.clone(),
+            accounts: self.state.accounts.tree.clone(),
+        }
+    }
+
+    /// Returns the staking contract as it was after the given block.
+    pub fn get_staking_contract_at(
+        &self,
+        block_number: u32,
+    ) -> Result<StakingContract, BlockchainError> {
+        self.staking_history().staking_contract_at(block_number)
+    }
+
+    /// Returns the validator with the given address as it was after the given block.
+    pub fn get_validator_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Option<Validator>, BlockchainError> {
+        self.staking_history().validator_at(address, block_number)
+    }
+
+    /// Returns the staker with the given address as it was after the given block.
+    pub fn get_staker_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Option<Staker>, BlockchainError> {
+        self.staking_history().staker_at(address, block_number)
+    }
+
+    /// Returns the stakers that delegated to the given validator after the given block.
+    /// IMPORTANT: This is a very expensive operation, iterating over all existing stakers in the
+    /// contract.
+    pub fn get_stakers_for_validator_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Vec<Staker>, BlockchainError> {
+        self.staking_history()
+            .stakers_for_validator_at(address, block_number)
+    }
+}
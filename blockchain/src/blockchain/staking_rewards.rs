@@ -0,0 +1,141 @@
+use std::ops::RangeInclusive;
+
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
+use nimiq_database::mdbx::MdbxReadTransaction;
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_transaction::historic_transaction::HistoricTransactionData;
+
+use super::staking_history::HistoricStakingState;
+use crate::{interface::HistoryInterface, Blockchain};
+
+/// The maximum number of epochs the staker rewards can be computed for in a single call.
+pub const MAX_STAKER_REWARD_EPOCHS: u32 = 30;
+
+/// The rewards that a staker earned in an epoch.
+///
+/// Rewards are paid out to the validator's reward address. The staker's share is computed from
+/// its part of the validator's stake at the election block that started the epoch, which is the
+/// stake the validator's slots were based on. Validators may distribute rewards differently,
+/// e.g. after deducting a fee.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakerEpochReward {
+    /// The epoch the rewards were earned in.
+    pub epoch: u32,
+    /// The validator the staker delegated to at the start of the epoch, if any.
+    pub validator_address: Option<Address>,
+    /// The active balance of the staker at the start of the epoch.
+    pub stake: Coin,
+    /// The total stake of the validator at the start of the epoch.
+    pub validator_stake: Coin,
+    /// The rewards paid out to the validator for the epoch.
+    pub validator_reward: Coin,
+    /// The staker's share of the validator's rewards.
+    pub reward: Coin,
+}
+
+/// Implements methods to account for the rewards earned by stakers.
+impl Blockchain {
+    /// Computes the rewards the given staker earned in each epoch of the given range.
+    ///
+    /// Rewards for a batch are paid out in the macro block of the following batch, so the amounts
+    /// for the current epoch are incomplete. The range may contain at most
+    /// [`MAX_STAKER_REWARD_EPOCHS`] epochs. The stakes at the start of each epoch are read from
+    /// the staking checkpoints of the election blocks, which only history nodes keep.
+    pub fn get_staker_rewards(
+        &self,
+        address: &Address,
+        epochs: RangeInclusive<u32>,
+    ) -> Result<Vec<StakerEpochReward>, BlockchainError> {
+        if *epochs.start() == 0
+            || *epochs.end() > self.epoch_number()
+            || epochs.end().saturating_sub(*epochs.start()) >= MAX_STAKER_REWARD_EPOCHS
+        {
+            return Err(BlockchainError::InvalidEpoch);
+        }
+
+        let staking_history = self.staking_history();
+        let txn = self.read_transaction();
+        let mut staking_state =
+            HistoricStakingState::new(&staking_history, &txn, self.block_number())?;
+        let mut rewards = vec![];
+
+        // Reconstruct the staking states from the newest to the oldest epoch. As they are taken
+        // at election blocks, each of them is read from a staking checkpoint.
+        for epoch in epochs.rev() {
+            let election_block_number =
+                Policy::election_block_of(epoch - 1).ok_or(BlockchainError::InvalidEpoch)?;
+            staking_state.rewind(election_block_number)?;
+
+            let mut reward = StakerEpochReward {
+                epoch,
+                validator_address: None,
+                stake: Coin::ZERO,
+                validator_stake: Coin::ZERO,
+                validator_reward: Coin::ZERO,
+                reward: Coin::ZERO,
+            };
+
+            let delegation = staking_state
+                .staker(address)?
+                .and_then(|staker| Some((staker.delegation?, staker.active_balance)));
+            if let Some((validator_address, stake)) = delegation {
+                let validator = staking_state
+                    .validator(&validator_address)?
+                    .ok_or(BlockchainError::InconsistentState)?;
+
+                reward.stake = stake;
+                reward.validator_stake = validator.total_stake;
+                reward.validator_reward =
+                    self.get_validator_epoch_reward(&validator_address, epoch, &txn);
+                if !reward.validator_stake.is_zero() {
+                    let share = u128::from(u64::from(reward.validator_reward))
+                        * u128::from(u64::from(reward.stake))
+                        / u128::from(u64::from(reward.validator_stake));
+                    reward.reward = Coin::from_u64_unchecked(share as u64);
+                }
+                reward.validator_address = Some(validator_address);
+            }
+
+            rewards.push(reward);
+        }
+
+        rewards.reverse();
+        Ok(rewards)
+    }
+
+    /// Sums up the rewards paid out to the given validator for the batches of the given epoch.
+    fn get_validator_epoch_reward(
+        &self,
+        validator_address: &Address,
+        epoch: u32,
+        txn: &MdbxReadTransaction,
+    ) -> Coin {
+        let batches_per_epoch = u32::from(Policy::batches_per_epoch());
+        let first_batch = (epoch - 1) * batches_per_epoch + 1;
+
+        let mut validator_reward = Coin::ZERO;
+        for batch in first_batch..first_batch + batches_per_epoch {
+            // The rewards for a batch are paid out in the macro block of the next batch.
+            let Some(block_number) = Policy::macro_block_of(batch + 1) else {
+                break;
+            };
+            if block_number > self.block_number() {
+                break;
+            }
+
+            for hist_tx in self
+                .history_store
+                .get_block_transactions(block_number, Some(txn))
+            {
+                if let HistoricTransactionData::Reward(event) = hist_tx.data {
+                    if &event.validator_address == validator_address {
+                        validator_reward += event.value;
+                    }
+                }
+            }
+        }
+
+        validator_reward
+    }
+}
@@ -5,6 +5,7 @@ use nimiq_blockchain_interface::{
     AbstractBlockchain, BlockchainEvent, ChainInfo, PushError, PushResult,
 };
 use nimiq_database::traits::{ReadTransaction, WriteTransaction};
+use nimiq_hash::Blake2bHash;
 use nimiq_primitives::policy::Policy;
 use nimiq_zkp::{verify::verify, NanoProof, ZKP_VERIFYING_DATA};
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
@@ -54,9 +55,7 @@ impl Blockchain {
             .chain_store
             .get_block_at(Policy::genesis_block_number(), true, Some(&read_txn))
             .unwrap();
-        let genesis_macro_block = genesis_block.unwrap_macro_ref();
-        let genesis_hash_blake2s = genesis_macro_block.hash_blake2s();
-        let genesis_hash_blake2b = genesis_macro_block.hash();
+        let genesis_hash_blake2s = genesis_block.unwrap_macro_ref().hash_blake2s();
 
         // Verify the zk proof.
         if !trusted_proof {
@@ -73,11 +72,69 @@ impl Blockchain {
         }
 
         // At this point we know that the block is correct. We just have to push it.
+        read_txn.close();
+
+        Self::reset_to_election_block(this, block, genesis_block, "push_zkp")
+    }
+
+    /// Syncs to a trusted checkpoint. It receives an election block that must match the hash
+    /// supplied by the node operator. Since the operator vouches for the block, there is no
+    /// proof that connects it to the genesis block.
+    /// This brings the node from the genesis block directly to the checkpoint, after which the
+    /// accounts trie can be requested from peers and verified against the block's state root.
+    pub fn push_trusted_checkpoint(
+        this: RwLockUpgradableReadGuard<Self>,
+        block: Block,
+        trusted_hash: &Blake2bHash,
+    ) -> Result<PushResult, PushError> {
+        // Must be the trusted election block.
+        let block_hash_blake2b = block.hash();
+        if !block.is_election() || block_hash_blake2b != *trusted_hash {
+            return Err(PushError::UntrustedCheckpoint);
+        }
+
+        let read_txn = this.read_transaction();
+
+        // Check if we already know this block.
+        if this
+            .chain_store
+            .get_chain_info(&block_hash_blake2b, false, Some(&read_txn))
+            .is_ok()
+        {
+            return Ok(PushResult::Known);
+        }
+
+        if block.block_number() <= this.state.macro_info.head.block_number() {
+            return Ok(PushResult::Ignored);
+        }
+
+        // Perform block intrinsic checks.
+        block.verify(this.network_id)?;
+
+        let genesis_block = this
+            .chain_store
+            .get_block_at(Policy::genesis_block_number(), true, Some(&read_txn))
+            .unwrap();
+
+        read_txn.close();
+
+        Self::reset_to_election_block(this, block, genesis_block, "push_trusted_checkpoint")
+    }
+
+    /// Replaces the chain with the given (already verified) election block on top of the genesis
+    /// block. The accounts trie is reinitialized as incomplete.
+    fn reset_to_election_block(
+        this: RwLockUpgradableReadGuard<Self>,
+        block: Block,
+        genesis_block: Block,
+        kind: &'static str,
+    ) -> Result<PushResult, PushError> {
+        let block_hash_blake2b = block.hash();
+        let genesis_hash_blake2b = genesis_block.hash();
 
         // Create the chain info for the new block.
         let chain_info = ChainInfo::new(block, true);
 
-        read_txn.close();
         let mut txn = this.write_transaction();
 
         this.state
@@ -139,7 +196,7 @@ impl Blockchain {
         debug!(
             block = %this.state.main_chain.head,
             num_transactions,
-            kind = "push_zkp",
+            kind,
             "Accepted block",
         );
 
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 
 use nimiq_account::RevertInfo;
 use nimiq_block::{Block, BlockType, EquivocationProof, MacroBody, MicroBody};
@@ -10,11 +10,16 @@ use nimiq_database::{
 };
 use nimiq_database_value_derive::DbSerializable;
 use nimiq_hash::Blake2bHash;
-use nimiq_primitives::{policy::Policy, trie::trie_diff::TrieDiff};
+use nimiq_primitives::{
+    policy::Policy,
+    trie::{trie_diff::TrieDiff, TrieItem},
+};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{historic_transaction::HistoricTransactionData, reward::RewardTransaction};
 
-use crate::{history::interface::HistoryInterface, history_store_proxy::HistoryStoreProxy};
+use crate::{
+    history::interface::HistoryInterface, history_store_proxy::HistoryStoreProxy, DbCheckResult,
+};
 
 declare_table!(HeadTable, "Head", () => Blake2bHash);
 declare_table!(ChainTable, "ChainData", Blake2bHash => ChainInfo);
@@ -23,6 +28,8 @@ declare_table!(StoredBlockTable, "StoredBlockTable", Blake2bHash => Block);
 declare_table!(HeightIndex, "HeightIndex", u32 => dup(Blake2bHash));
 declare_table!(RevertTable, "Receipts", u32 => RevertInfo);
 declare_table!(AccountsDiffTable, "AccountsDiff", Blake2bHash => TrieDiff);
+declare_table!(StakingDiffTable, "StakingDiff", u32 => TrieDiff);
+declare_table!(StakingHistoryTable, "StakingHistory", u32 => StakingEpochHistory);
 
 /// The non-header content of a block except that transactions are not stored to
 /// optimize blocks storage. This assumes that a block has been pushed and that there
@@ -35,6 +42,22 @@ pub enum PushedBlock {
     },
 }
 
+/// The staking contract history that a history node recorded for an epoch.
+///
+/// The staking diffs of all blocks from `first_block` to `last_block` were recorded, so the
+/// states after the blocks from `first_block - 1` to `last_block` can be reconstructed. Diffs
+/// without changes are not stored.
+#[derive(Clone, Debug, Serialize, Deserialize, DbSerializable)]
+pub struct StakingEpochHistory {
+    /// The first block of the epoch whose staking diff was recorded.
+    pub first_block: u32,
+    /// The last block of the epoch whose staking diff was recorded.
+    pub last_block: u32,
+    /// The keys and values of the staking contract in the accounts trie after the election block
+    /// of the epoch. Only set once the election block was recorded.
+    pub checkpoint: Option<Vec<TrieItem>>,
+}
+
 impl From<&Block> for PushedBlock {
     fn from(block: &Block) -> Self {
         match block {
@@ -114,7 +137,7 @@ impl PushedBlock {
 
 /// A struct that contains the DB tables to store the chain related data such as
 /// chain table, block table, height index table, revert table and accounts diff table.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ChainStore {
     /// Database handle.
     db: MdbxDatabase,
@@ -132,6 +155,10 @@ pub struct ChainStore {
     revert_table: RevertTable,
     /// A database of accounts trie diffs for a block.
     accounts_diff_table: AccountsDiffTable,
+    /// A database of backward diffs of the staking contract indexed by block number.
+    staking_diff_table: StakingDiffTable,
+    /// A database of the recorded staking contract history indexed by epoch number.
+    staking_history_table: StakingHistoryTable,
     /// A reference to the history store to recover micro block transactions.
     history_store: Arc<HistoryStoreProxy>,
 }
@@ -147,6 +174,8 @@ impl ChainStore {
             height_idx: HeightIndex,
             revert_table: RevertTable,
             accounts_diff_table: AccountsDiffTable,
+            staking_diff_table: StakingDiffTable,
+            staking_history_table: StakingHistoryTable,
             history_store,
         };
 
@@ -167,6 +196,12 @@ impl ChainStore {
         chain_store
             .db
             .create_regular_table(&chain_store.accounts_diff_table);
+        chain_store
+            .db
+            .create_regular_table(&chain_store.staking_diff_table);
+        chain_store
+            .db
+            .create_regular_table(&chain_store.staking_history_table);
 
         chain_store
     }
@@ -178,6 +213,8 @@ impl ChainStore {
         txn.clear_table(&self.height_idx);
         txn.clear_table(&self.revert_table);
         txn.clear_table(&self.accounts_diff_table);
+        txn.clear_table(&self.staking_diff_table);
+        txn.clear_table(&self.staking_history_table);
     }
 
     pub fn get_head(&self, txn_option: Option<&MdbxReadTransaction>) -> Option<Blake2bHash> {
@@ -699,4 +736,197 @@ impl ChainStore {
             }
         }
     }
+
+    /// Records the staking contract changes of a block, given as backward diff. For election
+    /// blocks, the state of the staking contract after the block is stored as checkpoint of the
+    /// epoch.
+    pub fn put_staking_history(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        block_height: u32,
+        diff: &TrieDiff,
+        checkpoint: Option<Vec<TrieItem>>,
+    ) {
+        let epoch_number = Policy::epoch_at(block_height);
+
+        // The recorded history of the epoch is only continued if the previous block was recorded.
+        let mut history = txn
+            .get(&self.staking_history_table, &epoch_number)
+            .filter(|history| history.last_block + 1 == block_height)
+            .unwrap_or(StakingEpochHistory {
+                first_block: block_height,
+                last_block: block_height,
+                checkpoint: None,
+            });
+        history.last_block = block_height;
+        history.checkpoint = checkpoint;
+        txn.put_reserve(&self.staking_history_table, &epoch_number, &history);
+
+        if diff.0.is_empty() {
+            txn.remove(&self.staking_diff_table, &block_height);
+        } else {
+            txn.put_reserve(&self.staking_diff_table, &block_height, diff);
+        }
+    }
+
+    /// Gets the recorded staking contract history of an epoch.
+    pub fn get_staking_history(
+        &self,
+        epoch_number: u32,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<StakingEpochHistory> {
+        let txn = txn_option.or_new(&self.db);
+
+        txn.get(&self.staking_history_table, &epoch_number)
+    }
+
+    /// Gets the backward diffs of the staking contract for the blocks in the given range, in
+    /// ascending order of block height. Applying the diff of a block to the state after the block
+    /// yields the state before the block. Blocks without changes have no diff.
+    pub fn get_staking_diffs(
+        &self,
+        block_heights: RangeInclusive<u32>,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Vec<TrieDiff> {
+        let txn = txn_option.or_new(&self.db);
+        let mut cursor = txn.cursor(&self.staking_diff_table);
+
+        let mut diffs = vec![];
+        let mut entry = cursor.set_lowerbound_key(block_heights.start());
+        while let Some((block_height, diff)) = entry {
+            if block_height > *block_heights.end() {
+                break;
+            }
+            diffs.push(diff);
+            entry = cursor.next();
+        }
+
+        diffs
+    }
+
+    /// Removes the recorded staking contract changes of a reverted block.
+    pub fn remove_staking_diff(&self, txn: &mut MdbxWriteTransaction, block_height: u32) {
+        txn.remove(&self.staking_diff_table, &block_height);
+
+        let epoch_number = Policy::epoch_at(block_height);
+        let history = txn
+            .get(&self.staking_history_table, &epoch_number)
+            .filter(|history| history.last_block >= block_height);
+        if let Some(mut history) = history {
+            if history.first_block < block_height {
+                history.last_block = block_height - 1;
+                history.checkpoint = None;
+                txn.put_reserve(&self.staking_history_table, &epoch_number, &history);
+            } else {
+                txn.remove(&self.staking_history_table, &epoch_number);
+            }
+        }
+    }
+
+    /// Removes the staking diffs of a finalized epoch. The checkpoint of the epoch is kept, so
+    /// only the state after its election block can be reconstructed afterwards.
+    pub fn prune_staking_diffs(&self, txn: &mut MdbxWriteTransaction, epoch_number: u32) {
+        let Some(election_block) = Policy::election_block_of(epoch_number) else {
+            return;
+        };
+        let Some(mut history) = txn.get(&self.staking_history_table, &epoch_number) else {
+            return;
+        };
+
+        {
+            let mut cursor = WriteTransaction::cursor(txn, &self.staking_diff_table);
+            let mut pos = cursor.set_lowerbound_key(&history.first_block);
+            while let Some((block_height, _)) = pos {
+                if block_height > election_block {
+                    break;
+                }
+                cursor.remove();
+                pos = cursor.next();
+            }
+        }
+
+        history.first_block = election_block + 1;
+        txn.put_reserve(&self.staking_history_table, &epoch_number, &history);
+    }
+
+    /// Checks the chain infos referenced from the height index: every main chain block must be
+    /// linked to its main chain predecessor and the highest main chain block must be the head.
+    /// Blocks that were pruned are skipped. Returns the chain infos of the main chain macro
+    /// blocks that were found.
+    pub(crate) fn check_chain_links(
+        &self,
+        txn: &MdbxReadTransaction,
+        result: &mut DbCheckResult,
+    ) -> Vec<ChainInfo> {
+        let mut macro_blocks = vec![];
+        let mut prev: Option<(Blake2bHash, ChainInfo)> = None;
+
+        let cursor = txn.dup_cursor(&self.height_idx);
+        for (height, hash) in cursor.into_iter_start() {
+            result.num_checked += 1;
+
+            let Some(mut chain_info) = txn.get(&self.chain_table, &hash) else {
+                result.error(format!(
+                    "Block {hash} at #{height} is in the height index but has no chain info"
+                ));
+                continue;
+            };
+            chain_info.head.populate_cached_hash(hash.clone());
+
+            if chain_info.head.block_number() != height {
+                result.error(format!(
+                    "Block {hash} is indexed at #{height}, but has block number #{}",
+                    chain_info.head.block_number()
+                ));
+            }
+            if !chain_info.on_main_chain {
+                continue;
+            }
+
+            if let Some((prev_hash, prev_info)) = &prev {
+                let prev_height = prev_info.head.block_number();
+                if prev_height == height {
+                    result.error(format!(
+                        "Blocks {prev_hash} and {hash} at #{height} are both on the main chain"
+                    ));
+                } else if prev_height + 1 == height {
+                    if chain_info.head.parent_hash() != prev_hash {
+                        result.error(format!(
+                            "Main chain block {hash} at #{height} doesn't reference its predecessor {prev_hash}"
+                        ));
+                    }
+                    if prev_info.main_chain_successor.as_ref() != Some(&hash) {
+                        result.error(format!(
+                            "Main chain block {prev_hash} at #{prev_height} has successor {:?}, expected {hash}",
+                            prev_info.main_chain_successor
+                        ));
+                    }
+                }
+            }
+
+            if chain_info.head.is_macro() {
+                macro_blocks.push(chain_info.clone());
+            }
+            prev = Some((hash, chain_info));
+        }
+
+        match (prev, self.get_head(Some(txn))) {
+            (Some((hash, chain_info)), Some(head_hash)) => {
+                if hash != head_hash {
+                    result.error(format!(
+                        "Highest main chain block {hash} is not the head {head_hash}"
+                    ));
+                }
+                if let Some(successor) = chain_info.main_chain_successor {
+                    result.error(format!("Head block {hash} has a successor {successor}"));
+                }
+            }
+            (_, None) => result.error("No head block is stored".to_string()),
+            (None, Some(head_hash)) => {
+                result.error(format!("Head block {head_hash} is not in the height index"))
+            }
+        }
+
+        macro_blocks
+    }
 }
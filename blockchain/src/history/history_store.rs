@@ -1,4 +1,4 @@
-use std::{cmp, ops::Range};
+use std::{cmp, collections::BTreeSet, ops::Range};
 
 use nimiq_block::MicroBlock;
 use nimiq_database::{
@@ -11,6 +11,7 @@ use nimiq_database::{
 };
 use nimiq_genesis::NetworkId;
 use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
 use nimiq_mmr::{
     error::Error as MMRError,
     mmr::{
@@ -108,26 +109,23 @@ impl HistoryStore {
         txn_option: Option<&MdbxReadTransaction>,
     ) -> Vec<HistoricTransaction> {
         let mut hist_txs = Vec::with_capacity(leaf_indices.len());
+        if leaf_indices.is_empty() {
+            return hist_txs;
+        }
         let txn = txn_option.or_new(&self.db);
 
         // Get consecutive transactions with fast cursor.
+        // Transactions are missing if the history of the epoch was pruned, so we return the ones
+        // that are still present.
         let mut cursor = txn.dup_cursor(&self.hist_tx_table);
+        let mut next = cursor.set_lowerbound_both(&epoch_number, &leaf_indices.start);
 
-        for (i, leaf_index) in leaf_indices.enumerate() {
-            let (epoch, hist_tx) = if i == 0 {
-                (
-                    epoch_number,
-                    cursor
-                        .set_subkey(&epoch_number, &leaf_index)
-                        .expect("Transaction not found"),
-                )
-            } else {
-                cursor.next_duplicate().expect("Transaction not found")
-            };
-
-            assert_eq!(epoch, epoch_number, "Epoch number mismatch");
-            assert_eq!(hist_tx.index, leaf_index, "Index mismatch");
+        while let Some((epoch, hist_tx)) = next {
+            if epoch != epoch_number || hist_tx.index >= leaf_indices.end {
+                break;
+            }
             hist_txs.push(hist_tx.value);
+            next = cursor.next_duplicate();
         }
 
         hist_txs
@@ -216,6 +214,31 @@ impl HistoryStore {
         txns_size
     }
 
+    /// Removes the historic transactions of the given epoch that don't involve any of the given
+    /// addresses. Returns the removed historic transactions with their leaf indices.
+    pub(crate) fn prune_txns_from_history(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        addresses: &BTreeSet<Address>,
+    ) -> Vec<(u32, HistoricTransaction)> {
+        let cursor = WriteTransaction::dup_cursor(txn, &self.hist_tx_table);
+        let pruned: Vec<(u32, HistoricTransaction)> = cursor
+            .into_iter_dup_of(&epoch_number)
+            .filter(|(_, hist_tx)| !involves_any(&hist_tx.value, addresses))
+            .map(|(_, hist_tx)| (hist_tx.index, hist_tx.value))
+            .collect();
+
+        let mut cursor = WriteTransaction::dup_cursor(txn, &self.hist_tx_table);
+        for (leaf_index, _) in pruned.iter() {
+            if cursor.set_subkey(&epoch_number, leaf_index).is_some() {
+                cursor.remove();
+            }
+        }
+
+        pruned
+    }
+
     pub(crate) fn remove_epoch_from_history(
         &self,
         txn: &mut MdbxWriteTransaction,
@@ -397,6 +420,22 @@ impl HistoryStore {
     }
 }
 
+/// Returns true if the historic transaction involves one of the given addresses.
+fn involves_any(hist_tx: &HistoricTransaction, addresses: &BTreeSet<Address>) -> bool {
+    match &hist_tx.data {
+        HistoricTransactionData::Basic(tx) => {
+            let tx = tx.get_raw_transaction();
+            addresses.contains(&tx.sender) || addresses.contains(&tx.recipient)
+        }
+        HistoricTransactionData::Reward(ev) => {
+            addresses.contains(&ev.reward_address) || addresses.contains(&ev.validator_address)
+        }
+        HistoricTransactionData::Penalize(ev) => addresses.contains(&ev.validator_address),
+        HistoricTransactionData::Jail(ev) => addresses.contains(&ev.validator_address),
+        HistoricTransactionData::Equivocation(_) => false,
+    }
+}
+
 impl HistoryInterface for HistoryStore {
     fn clear(&self, txn: &mut MdbxWriteTransaction) {
         txn.clear_table(&self.hist_tree_table);
@@ -513,6 +552,18 @@ impl HistoryInterface for HistoryStore {
         Some(())
     }
 
+    /// Removes the historic transactions of a given epoch that don't involve any of the given
+    /// addresses. The history tree and the leaf indices of the blocks are kept.
+    fn prune_history(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        addresses: &BTreeSet<Address>,
+    ) -> usize {
+        self.prune_txns_from_history(txn, epoch_number, addresses)
+            .len()
+    }
+
     /// Gets the history tree root for a given epoch.
     fn get_history_tree_root(
         &self,
@@ -1,10 +1,16 @@
-use std::{collections::BTreeMap, ops::Range};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::{Range, RangeInclusive},
+};
 
 use nimiq_block::MicroBlock;
 use nimiq_database::{
     declare_table,
     mdbx::{MdbxDatabase, MdbxReadTransaction, MdbxWriteTransaction, OptionalTransaction},
-    traits::{Database, DupReadCursor, ReadCursor, ReadTransaction, WriteCursor, WriteTransaction},
+    traits::{
+        Database, DupReadCursor, DupTable, ReadCursor, ReadTransaction, Table, WriteCursor,
+        WriteTransaction,
+    },
 };
 use nimiq_genesis::NetworkId;
 use nimiq_hash::Blake2bHash;
@@ -23,14 +29,19 @@ use nimiq_transaction::{
 
 use super::{
     interface::HistoryInterface,
+    secondary_index::{SecondaryIndex, SecondaryIndexKind, SecondaryIndexStore},
     utils::{EpochBasedIndex, OrderedHash},
 };
-use crate::{history::HistoryTreeChunk, interface::HistoryIndexInterface, HistoryStore};
+use crate::{
+    history::HistoryTreeChunk, interface::HistoryIndexInterface, DbCheckResult, HistoryStore,
+};
 
 // `RawTransactonHash` -> `EpochBasedIndex` (`epoch number || leaf_index`)
 declare_table!(TxHashTable, "LeafIndexByTxHash", RawTransactionHash => EpochBasedIndex);
 // `Address` -> `EpochBasedIndex` -> `Blake2bHash`
 declare_table!(AddressTable, "TxHashesByAddress", Address => EpochBasedIndex => Blake2bHash);
+// Name of a secondary index -> `()`
+declare_table!(SecondaryIndexesTable, "SecondaryHistoryIndexes", String => ());
 
 #[derive(Debug)]
 /// A struct that contains databases to store history indices.
@@ -46,25 +57,78 @@ pub struct HistoryStoreIndex {
     address_table: AddressTable,
     /// The history store.
     history_store: HistoryStore,
+    /// The enabled secondary indexes.
+    secondary_indexes: Vec<Box<dyn SecondaryIndexStore>>,
+    /// A database of the names of the secondary indexes that are currently maintained.
+    secondary_indexes_table: SecondaryIndexesTable,
 }
 
 impl HistoryStoreIndex {
     /// Creates a new HistoryStore.
     pub fn new(db: MdbxDatabase, network_id: NetworkId) -> Self {
+        Self::with_secondary_indexes(db, network_id, &BTreeSet::new())
+    }
+
+    /// Creates a new HistoryStore that additionally maintains the given secondary indexes.
+    /// Secondary indexes that weren't maintained before are built from the history store.
+    pub fn with_secondary_indexes(
+        db: MdbxDatabase,
+        network_id: NetworkId,
+        secondary_indexes: &BTreeSet<SecondaryIndexKind>,
+    ) -> Self {
+        let index = Self::open(db, network_id, secondary_indexes);
+        index.rebuild_index_if_necessary();
+        index
+    }
+
+    /// Rebuilds the index and the given secondary indexes from scratch. Secondary indexes that
+    /// are not given are cleared.
+    /// This is meant to be used offline, i.e. while no blockchain uses the database.
+    pub fn rebuild_offline(
+        db: MdbxDatabase,
+        network_id: NetworkId,
+        secondary_indexes: &BTreeSet<SecondaryIndexKind>,
+    ) {
+        let index = Self::open(db, network_id, secondary_indexes);
+        let mut txn = index.db.write_transaction();
+        index.rebuild_index(&mut txn);
+        txn.commit();
+    }
+
+    /// Opens the index without checking if it needs to be rebuilt.
+    pub(crate) fn open(
+        db: MdbxDatabase,
+        network_id: NetworkId,
+        secondary_indexes: &BTreeSet<SecondaryIndexKind>,
+    ) -> Self {
         let index = HistoryStoreIndex {
             history_store: HistoryStore::new(db.clone(), network_id),
             db,
             tx_hash_table: TxHashTable,
             address_table: AddressTable,
+            secondary_indexes: secondary_indexes.iter().map(|kind| kind.index()).collect(),
+            secondary_indexes_table: SecondaryIndexesTable,
         };
 
         index.db.create_regular_table(&index.tx_hash_table);
         index.db.create_dup_table(&index.address_table);
+        index
+            .db
+            .create_regular_table(&index.secondary_indexes_table);
+        for secondary_index in index.secondary_indexes.iter() {
+            secondary_index.create_table(&index.db);
+        }
 
-        index.rebuild_index_if_necessary();
         index
     }
 
+    /// Returns true if the given secondary index is maintained.
+    pub fn has_secondary_index(&self, kind: SecondaryIndexKind) -> bool {
+        self.secondary_indexes
+            .iter()
+            .any(|secondary_index| secondary_index.kind() == kind)
+    }
+
     /// Rebuild index if necessary.
     fn rebuild_index_if_necessary(&self) {
         let mut txn = self.db.write_transaction();
@@ -80,9 +144,49 @@ impl HistoryStoreIndex {
                 self.rebuild_index(&mut txn);
                 debug!("Committing rebuilt index.");
                 txn.commit();
-                info!("Finished rebuilding history index.")
+                info!("Finished rebuilding history index.");
+                return;
             }
         }
+
+        // Build or clear the secondary indexes that were enabled or disabled since the last start.
+        if self.update_secondary_indexes(&mut txn, false) {
+            txn.commit();
+        }
+    }
+
+    /// Builds the enabled secondary indexes that are not maintained yet (or all of them if
+    /// `rebuild_all` is set) and clears the ones that are not enabled anymore, so that they are
+    /// rebuilt once they are enabled again.
+    /// Returns true if any secondary index was changed.
+    fn update_secondary_indexes(&self, txn: &mut MdbxWriteTransaction, rebuild_all: bool) -> bool {
+        let cursor = WriteTransaction::cursor(txn, &self.secondary_indexes_table);
+        let maintained: BTreeSet<SecondaryIndexKind> = cursor
+            .into_iter_start()
+            .filter_map(|(name, _)| name.parse().ok())
+            .collect();
+
+        let mut changed = false;
+        for kind in maintained.iter() {
+            if !self.has_secondary_index(*kind) {
+                info!(index = %kind, "Clearing disabled secondary history index.");
+                kind.index().clear(txn);
+                txn.remove(&self.secondary_indexes_table, &kind.name().to_string());
+                changed = true;
+            }
+        }
+
+        for secondary_index in self.secondary_indexes.iter() {
+            let kind = secondary_index.kind();
+            if rebuild_all || !maintained.contains(&kind) {
+                info!(index = %kind, "Building secondary history index.");
+                secondary_index.rebuild(txn, &self.history_store);
+                txn.put(&self.secondary_indexes_table, &kind.name().to_string(), &());
+                changed = true;
+            }
+        }
+
+        changed
     }
 
     fn remove_txns_from_history(
@@ -97,28 +201,43 @@ impl HistoryStoreIndex {
                 .get_historic_tx(epoch_number, leaf_index, Some(txn));
 
             let Some(hist_tx) = tx_opt else { continue };
+            self.remove_from_index(txn, epoch_number, leaf_index, &hist_tx);
+        }
+    }
 
-            // Remove it from the transaction hash database.
-            let tx_hash = hist_tx.tx_hash();
-            txn.remove(&self.tx_hash_table, &tx_hash);
+    /// Removes a historic transaction from the transaction hash and address databases.
+    fn remove_from_index(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        leaf_index: u32,
+        hist_tx: &HistoricTransaction,
+    ) {
+        // Remove it from the transaction hash database.
+        let tx_hash = hist_tx.tx_hash();
+        txn.remove(&self.tx_hash_table, &tx_hash);
 
-            let ordered_hash = OrderedHash {
-                index: EpochBasedIndex::new(epoch_number, leaf_index),
-                value: tx_hash.into(),
-            };
-            match &hist_tx.data {
-                HistoricTransactionData::Basic(tx) => {
-                    let tx = tx.get_raw_transaction();
-                    txn.remove_item(&self.address_table, &tx.sender, &ordered_hash);
-                    txn.remove_item(&self.address_table, &tx.recipient, &ordered_hash);
-                }
-                HistoricTransactionData::Reward(ev) => {
-                    txn.remove_item(&self.address_table, &ev.reward_address, &ordered_hash);
-                }
-                HistoricTransactionData::Equivocation(_)
-                | HistoricTransactionData::Penalize(_)
-                | HistoricTransactionData::Jail(_) => {}
+        let ordered_hash = OrderedHash {
+            index: EpochBasedIndex::new(epoch_number, leaf_index),
+            value: tx_hash.into(),
+        };
+        match &hist_tx.data {
+            HistoricTransactionData::Basic(tx) => {
+                let tx = tx.get_raw_transaction();
+                txn.remove_item(&self.address_table, &tx.sender, &ordered_hash);
+                txn.remove_item(&self.address_table, &tx.recipient, &ordered_hash);
+            }
+            HistoricTransactionData::Reward(ev) => {
+                txn.remove_item(&self.address_table, &ev.reward_address, &ordered_hash);
             }
+            HistoricTransactionData::Equivocation(_)
+            | HistoricTransactionData::Penalize(_)
+            | HistoricTransactionData::Jail(_) => {}
+        }
+
+        // Remove it from the secondary indexes.
+        for secondary_index in self.secondary_indexes.iter() {
+            secondary_index.remove(txn, epoch_number, leaf_index, hist_tx);
         }
     }
 
@@ -194,6 +313,8 @@ impl HistoryStoreIndex {
         txn.clear_table(&self.tx_hash_table);
         txn.clear_table(&self.address_table);
 
+        self.update_secondary_indexes(txn, true);
+
         // Iterate over all epochs and leafs.
         let mut hashes = BTreeMap::new();
         let mut addresses = BTreeMap::new();
@@ -224,6 +345,171 @@ impl HistoryStoreIndex {
             }
         }
     }
+
+    /// Checks the index against the history store: every historic transaction must be found by
+    /// its hash and every index entry must point to a historic transaction with that hash.
+    pub(crate) fn check_index(&self, txn: &MdbxReadTransaction, result: &mut DbCheckResult) {
+        let cursor = txn.dup_cursor(&self.history_store.hist_tx_table);
+        for (epoch_number, hist_tx) in cursor.into_iter_start() {
+            result.num_checked += 1;
+            let raw_tx_hash = hist_tx.value.tx_hash();
+            if txn.get(&self.tx_hash_table, &raw_tx_hash).is_none() {
+                result.error(format!(
+                    "Historic transaction {} (epoch {epoch_number}, leaf {}) is not indexed",
+                    *raw_tx_hash, hist_tx.index
+                ));
+            }
+        }
+
+        let cursor = txn.cursor(&self.tx_hash_table);
+        for (raw_tx_hash, index) in cursor.into_iter_start() {
+            result.num_checked += 1;
+            if !self.points_to(txn, index, &raw_tx_hash) {
+                result.error(format!(
+                    "Transaction {} is indexed at epoch {}, leaf {}, which doesn't match",
+                    *raw_tx_hash, index.epoch_number, index.index
+                ));
+            }
+        }
+
+        let cursor = txn.dup_cursor(&self.address_table);
+        for (address, ordered_hash) in cursor.into_iter_start() {
+            result.num_checked += 1;
+            let raw_tx_hash = RawTransactionHash::from(ordered_hash.value);
+            if !self.points_to(txn, ordered_hash.index, &raw_tx_hash) {
+                result.error(format!(
+                    "Transaction {} is indexed for address {} at epoch {}, leaf {}, which doesn't match",
+                    *raw_tx_hash,
+                    address.to_user_friendly_address(),
+                    ordered_hash.index.epoch_number,
+                    ordered_hash.index.index
+                ));
+            }
+        }
+
+        for secondary_index in self.secondary_indexes.iter() {
+            secondary_index.check(txn, &self.history_store, result);
+        }
+    }
+
+    /// Returns whether the historic transaction at the given index has the given hash.
+    fn points_to(
+        &self,
+        txn: &MdbxReadTransaction,
+        index: EpochBasedIndex,
+        raw_tx_hash: &RawTransactionHash,
+    ) -> bool {
+        self.history_store
+            .get_historic_tx(index.epoch_number, index.index, Some(txn))
+            .is_some_and(|hist_tx| hist_tx.tx_hash() == *raw_tx_hash)
+    }
+
+    /// Returns a vector containing the hashes of the transactions that are indexed under the given
+    /// key in the secondary index `I`. It fetches the transactions from most recent to least recent
+    /// up to the maximum number given. It allows to give a starting point to fetch the transactions
+    /// from (exclusive). If this hash is given but not found or not indexed under this key, the
+    /// function will return an empty vector.
+    /// Returns `None` if the secondary index is not enabled.
+    pub fn get_tx_hashes_by_secondary_key<I: SecondaryIndex>(
+        &self,
+        key: &<I::Table as Table>::Key,
+        max: u16,
+        start_at: Option<Blake2bHash>,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<Vec<Blake2bHash>> {
+        if !self.has_secondary_index(I::KIND) {
+            return None;
+        }
+
+        Some(self.get_tx_hashes_by_key(&I::Table::default(), key, max, start_at, txn_option))
+    }
+
+    /// Returns a vector containing the hashes of the transactions whose keys in the secondary index
+    /// `I` lie in the given range. It fetches the transactions in ascending key order up to the
+    /// maximum number given.
+    /// Returns `None` if the secondary index is not enabled.
+    pub fn get_tx_hashes_by_secondary_key_range<I: SecondaryIndex>(
+        &self,
+        range: RangeInclusive<<I::Table as Table>::Key>,
+        max: u16,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<Vec<Blake2bHash>> {
+        if !self.has_secondary_index(I::KIND) {
+            return None;
+        }
+
+        let txn = txn_option.or_new(&self.db);
+        let mut cursor = txn.dup_cursor(&I::Table::default());
+
+        let mut tx_hashes = vec![];
+        let mut entry = cursor.set_lowerbound_key(range.start());
+        while let Some((key, ordered_hash)) = entry {
+            if tx_hashes.len() >= max as usize || &key > range.end() {
+                break;
+            }
+            tx_hashes.push(ordered_hash.value);
+            entry = cursor.next();
+        }
+
+        Some(tx_hashes)
+    }
+
+    /// Returns the hashes of the transactions indexed under the given key in the given table,
+    /// from most recent to least recent. See `get_tx_hashes_by_address`.
+    fn get_tx_hashes_by_key<T: DupTable<Value = OrderedHash>>(
+        &self,
+        table: &T,
+        key: &T::Key,
+        max: u16,
+        start_at: Option<Blake2bHash>,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Vec<Blake2bHash> {
+        if max == 0 {
+            return vec![];
+        }
+
+        let txn = txn_option.or_new(&self.db);
+
+        let mut tx_hashes = vec![];
+
+        // Seek to the first transaction hash at the given key. If there's none, stop here.
+        let mut cursor = txn.dup_cursor(table);
+
+        // Find start index.
+        if let Some(hash) = start_at {
+            let raw_tx_hash = RawTransactionHash::from(hash);
+            // A start hash is given, so we get the `EpochBasedIndex` first.
+            let Some(start_index) = txn.get(&self.tx_hash_table, &raw_tx_hash) else {
+                return tx_hashes;
+            };
+
+            // If no entry can be found, return an empty vector.
+            if cursor.set_subkey(key, &start_index).is_none() {
+                return tx_hashes;
+            }
+
+            // We don't add the current hash to the list.
+        } else {
+            // If no start hash is given, we start at the last transaction hash for this key.
+            if cursor.set_key(key).is_none() {
+                return tx_hashes;
+            }
+
+            // Then go to the last transaction hash at the given key and add it to the transaction
+            // hashes list.
+            tx_hashes.push(cursor.last_duplicate().expect("This shouldn't panic since we already verified before that there is at least one transactions at this key!").value);
+        }
+
+        while tx_hashes.len() < max as usize {
+            // Get previous transaction hash.
+            match cursor.prev_duplicate() {
+                Some((_, v)) => tx_hashes.push(v.value),
+                None => break,
+            };
+        }
+
+        tx_hashes
+    }
 }
 
 impl HistoryInterface for HistoryStoreIndex {
@@ -316,6 +602,9 @@ impl HistoryInterface for HistoryStoreIndex {
         self.history_store.clear(txn);
         txn.clear_table(&self.tx_hash_table);
         txn.clear_table(&self.address_table);
+        for secondary_index in self.secondary_indexes.iter() {
+            secondary_index.clear(txn);
+        }
     }
 
     fn length_at(
@@ -471,6 +760,11 @@ impl HistoryInterface for HistoryStoreIndex {
             self.history_store
                 .put_historic_txns(txn, epoch_number, block_number, hist_txs)
         {
+            // Put the historic transactions into the secondary indexes.
+            for secondary_index in self.secondary_indexes.iter() {
+                secondary_index.put(txn, epoch_number, hist_txs, &leaf_idx);
+            }
+
             // Add the historic transactions into the respective database.
             // Sort everything first and then put with a cursor for improved database performance.
             let mut hashes = BTreeMap::new();
@@ -530,6 +824,22 @@ impl HistoryInterface for HistoryStoreIndex {
 
         Some(())
     }
+
+    fn prune_history(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        addresses: &BTreeSet<Address>,
+    ) -> usize {
+        let pruned = self
+            .history_store
+            .prune_txns_from_history(txn, epoch_number, addresses);
+        for (leaf_index, hist_tx) in pruned.iter() {
+            self.remove_from_index(txn, epoch_number, *leaf_index, hist_tx);
+        }
+
+        pruned.len()
+    }
 }
 
 impl HistoryIndexInterface for HistoryStoreIndex {
@@ -559,51 +869,7 @@ impl HistoryIndexInterface for HistoryStoreIndex {
         start_at: Option<Blake2bHash>,
         txn_option: Option<&MdbxReadTransaction>,
     ) -> Vec<Blake2bHash> {
-        if max == 0 {
-            return vec![];
-        }
-
-        let txn = txn_option.or_new(&self.db);
-
-        let mut tx_hashes = vec![];
-
-        // Seek to the first transaction hash at the given address. If there's none, stop here.
-        let mut cursor = txn.dup_cursor(&self.address_table);
-
-        // Find start index.
-        if let Some(hash) = start_at {
-            let raw_tx_hash = RawTransactionHash::from(hash);
-            // A start hash is given, so we get the `EpochBasedIndex` first.
-            let Some(start_index) = txn.get(&self.tx_hash_table, &raw_tx_hash) else {
-                return tx_hashes;
-            };
-
-            // If no entry can be found, return an empty vector.
-            if cursor.set_subkey(address, &start_index).is_none() {
-                return tx_hashes;
-            }
-
-            // We don't add the current hash to the list.
-        } else {
-            // If no start hash is given, we start at the last transaction hash for this address.
-            if cursor.set_key(address).is_none() {
-                return tx_hashes;
-            }
-
-            // Then go to the last transaction hash at the given address and add it to the transaction
-            // hashes list.
-            tx_hashes.push(cursor.last_duplicate().expect("This shouldn't panic since we already verified before that there is at least one transactions at this address!").value);
-        }
-
-        while tx_hashes.len() < max as usize {
-            // Get previous transaction hash.
-            match cursor.prev_duplicate() {
-                Some((_, v)) => tx_hashes.push(v.value),
-                None => break,
-            };
-        }
-
-        tx_hashes
+        self.get_tx_hashes_by_key(&self.address_table, address, max, start_at, txn_option)
     }
 
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
@@ -648,6 +914,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::{StakingTransactionType, StakingTypeIndex, TimestampIndex, ValidatorIndex};
 
     #[test]
     fn prove_num_leaves_works() {
@@ -1273,6 +1540,141 @@ mod tests {
         assert_eq!(query_5[1], *hashes[1]);
     }
 
+    #[test]
+    fn secondary_indexes_work() {
+        // Initialize History Store with secondary indexes.
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let secondary_indexes =
+            BTreeSet::from([SecondaryIndexKind::Validator, SecondaryIndexKind::Timestamp]);
+        let history_store = HistoryStoreIndex::with_secondary_indexes(
+            env.clone(),
+            NetworkId::UnitAlbatross,
+            &secondary_indexes,
+        );
+
+        // Create historic transactions, the timestamps increase with the block number.
+        let genesis_block_number = Policy::genesis_block_number();
+        let mut hist_txs = gen_hist_txs();
+        for hist_tx in hist_txs.iter_mut() {
+            hist_tx.block_time = (hist_tx.block_number - genesis_block_number) as u64;
+        }
+        let hashes: Vec<Blake2bHash> = hist_txs
+            .iter()
+            .map(|hist_tx| hist_tx.tx_hash().into())
+            .collect();
+
+        // Add historic transactions to History Store.
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, genesis_block_number + 0, &hist_txs[..3]);
+        history_store.add_to_history(&mut txn, genesis_block_number + 1, &hist_txs[3..5]);
+        history_store.add_to_history(&mut txn, genesis_block_number + 2, &hist_txs[5..]);
+
+        // Verify the validator index.
+        let validator =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+        let query_1 = history_store.get_tx_hashes_by_secondary_key::<ValidatorIndex>(
+            &Address::burn_address(),
+            99,
+            None,
+            Some(&txn),
+        );
+        assert_eq!(
+            query_1,
+            Some(vec![
+                hashes[7].clone(),
+                hashes[4].clone(),
+                hashes[2].clone()
+            ])
+        );
+
+        let query_2 = history_store.get_tx_hashes_by_secondary_key::<ValidatorIndex>(
+            &validator,
+            99,
+            None,
+            Some(&txn),
+        );
+        assert_eq!(query_2, Some(vec![hashes[9].clone(), hashes[8].clone()]));
+
+        let query_3 = history_store.get_tx_hashes_by_secondary_key::<ValidatorIndex>(
+            &Address::burn_address(),
+            1,
+            Some(hashes[7].clone()),
+            Some(&txn),
+        );
+        assert_eq!(query_3, Some(vec![hashes[4].clone()]));
+
+        // Verify the timestamp index.
+        let query_4 = history_store.get_tx_hashes_by_secondary_key_range::<TimestampIndex>(
+            1..=1,
+            99,
+            Some(&txn),
+        );
+        assert_eq!(query_4, Some(vec![hashes[3].clone(), hashes[4].clone()]));
+
+        let query_5 = history_store.get_tx_hashes_by_secondary_key_range::<TimestampIndex>(
+            0..=u64::MAX,
+            4,
+            Some(&txn),
+        );
+        assert_eq!(query_5, Some(hashes[..4].to_vec()));
+
+        // Indexes that are not enabled can't be queried.
+        let query_6 = history_store.get_tx_hashes_by_secondary_key::<StakingTypeIndex>(
+            &StakingTransactionType::AddStake.into(),
+            99,
+            None,
+            Some(&txn),
+        );
+        assert_eq!(query_6, None);
+
+        // Removing historic transactions removes them from the secondary indexes.
+        let epoch_number = Policy::epoch_at(genesis_block_number);
+        history_store.remove_partial_history(&mut txn, epoch_number, 6);
+
+        let query_7 = history_store.get_tx_hashes_by_secondary_key::<ValidatorIndex>(
+            &Address::burn_address(),
+            99,
+            None,
+            Some(&txn),
+        );
+        assert_eq!(query_7, Some(vec![hashes[4].clone(), hashes[2].clone()]));
+
+        let query_8 = history_store.get_tx_hashes_by_secondary_key_range::<TimestampIndex>(
+            2..=u64::MAX,
+            99,
+            Some(&txn),
+        );
+        assert_eq!(query_8, Some(vec![]));
+        txn.commit();
+
+        // A disabled index is cleared and rebuilt once it is enabled again.
+        HistoryStoreIndex::with_secondary_indexes(
+            env.clone(),
+            NetworkId::UnitAlbatross,
+            &BTreeSet::from([SecondaryIndexKind::Timestamp]),
+        );
+        let txn = env.read_transaction();
+        assert!(txn
+            .dup_cursor(&<ValidatorIndex as SecondaryIndex>::Table::default())
+            .first()
+            .is_none());
+        drop(txn);
+
+        let history_store = HistoryStoreIndex::with_secondary_indexes(
+            env.clone(),
+            NetworkId::UnitAlbatross,
+            &secondary_indexes,
+        );
+        let query_9 = history_store.get_tx_hashes_by_secondary_key::<ValidatorIndex>(
+            &Address::burn_address(),
+            99,
+            None,
+            None,
+        );
+        assert_eq!(query_9, Some(vec![hashes[4].clone(), hashes[2].clone()]));
+    }
+
     #[test]
     fn prove_works() {
         // Initialize History Store.
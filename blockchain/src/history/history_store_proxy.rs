@@ -1,6 +1,9 @@
+use std::collections::BTreeSet;
+
 use nimiq_block::{Block, MicroBlock};
 use nimiq_database::mdbx::{MdbxReadTransaction, MdbxWriteTransaction};
 use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
 use nimiq_mmr::{
     error::Error as MMRError,
     mmr::proof::{RangeProof, SizeProof},
@@ -71,6 +74,24 @@ impl HistoryInterface for HistoryStoreProxy {
         }
     }
 
+    /// Removes the historic transactions of a given epoch that don't involve any of the given
+    /// addresses.
+    fn prune_history(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        addresses: &BTreeSet<Address>,
+    ) -> usize {
+        match self {
+            HistoryStoreProxy::WithIndex(index) => {
+                index.prune_history(txn, epoch_number, addresses)
+            }
+            HistoryStoreProxy::WithoutIndex(store) => {
+                store.prune_history(txn, epoch_number, addresses)
+            }
+        }
+    }
+
     /// Obtains the current history root at the given block.
     fn get_history_tree_root(
         &self,
@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use nimiq_block::{Block, MicroBlock};
 use nimiq_database::mdbx::{MdbxReadTransaction, MdbxWriteTransaction};
 use nimiq_hash::Blake2bHash;
@@ -36,6 +38,16 @@ pub trait HistoryInterface: std::fmt::Debug {
     /// Removes the full history associated with a given epoch.
     fn remove_history(&self, txn: &mut MdbxWriteTransaction, epoch_number: u32) -> Option<()>;
 
+    /// Removes the historic transactions of a given epoch that don't involve any of the given
+    /// addresses. The history tree is kept, such that the history root of the epoch can still be
+    /// proven. Returns the number of removed historic transactions.
+    fn prune_history(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        addresses: &BTreeSet<Address>,
+    ) -> usize;
+
     /// Obtains the current history root at the given block.
     fn get_history_tree_root(
         &self,
@@ -1,6 +1,11 @@
 pub use history_store::HistoryStore;
 pub use history_store_index::HistoryStoreIndex;
 pub use history_tree_chunk::{HistoryTreeChunk, CHUNK_SIZE};
+pub use secondary_index::{
+    HtlcHashRootIndex, SecondaryIndex, SecondaryIndexKind, SecondaryIndexKindParseError,
+    StakingTransactionType, StakingTransactionTypeParseError, StakingTypeIndex, TimestampIndex,
+    ValidatorIndex,
+};
 
 mod history_store;
 mod history_store_index;
@@ -8,5 +13,6 @@ pub mod history_store_proxy;
 mod history_tree_chunk;
 pub mod interface;
 mod mmr_store;
+mod secondary_index;
 mod utils;
 mod validity_store;
@@ -0,0 +1,493 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
+
+use nimiq_database::{
+    declare_table,
+    mdbx::{MdbxDatabase, MdbxReadTransaction, MdbxWriteTransaction},
+    traits::{
+        Database, DupTable, ReadCursor, ReadTransaction, Table, WriteCursor, WriteTransaction,
+    },
+};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::account::AccountType;
+use nimiq_transaction::{
+    account::{
+        htlc_contract::{CreationTransactionData, OutgoingHTLCTransactionProof},
+        staking_contract::{IncomingStakingTransactionData, OutgoingStakingTransactionData},
+    },
+    historic_transaction::{HistoricTransaction, HistoricTransactionData},
+    TransactionFlags,
+};
+use thiserror::Error;
+
+use super::{
+    history_store::HistoryStore,
+    utils::{EpochBasedIndex, OrderedHash},
+};
+use crate::DbCheckResult;
+
+// `Address` (validator address) -> `EpochBasedIndex` -> `Blake2bHash`
+declare_table!(ValidatorTable, "TxHashesByValidator", Address => EpochBasedIndex => Blake2bHash);
+// HTLC hash root (raw bytes) -> `EpochBasedIndex` -> `Blake2bHash`
+declare_table!(
+    HtlcHashRootTable,
+    "TxHashesByHtlcHashRoot",
+    Vec<u8> => EpochBasedIndex => Blake2bHash
+);
+// `StakingTransactionType` (as `u8`) -> `EpochBasedIndex` -> `Blake2bHash`
+declare_table!(StakingTypeTable, "TxHashesByStakingType", u8 => EpochBasedIndex => Blake2bHash);
+// `u64` (block timestamp) -> `EpochBasedIndex` -> `Blake2bHash`
+declare_table!(TimestampTable, "TxHashesByTimestamp", u64 => EpochBasedIndex => Blake2bHash);
+
+/// The secondary indexes that can be maintained in addition to the transaction hash and
+/// address indexes of the `HistoryStoreIndex`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SecondaryIndexKind {
+    /// Reward, penalty and jail inherents by validator address.
+    Validator,
+    /// HTLC creation and redeem transactions by hash root.
+    HtlcHashRoot,
+    /// Staking contract transactions by their type.
+    StakingType,
+    /// All historic transactions by block timestamp.
+    Timestamp,
+}
+
+impl SecondaryIndexKind {
+    /// All kinds of secondary indexes.
+    pub const ALL: [SecondaryIndexKind; 4] = [
+        SecondaryIndexKind::Validator,
+        SecondaryIndexKind::HtlcHashRoot,
+        SecondaryIndexKind::StakingType,
+        SecondaryIndexKind::Timestamp,
+    ];
+
+    /// The name of the secondary index.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SecondaryIndexKind::Validator => "validator",
+            SecondaryIndexKind::HtlcHashRoot => "htlc",
+            SecondaryIndexKind::StakingType => "staking",
+            SecondaryIndexKind::Timestamp => "timestamp",
+        }
+    }
+
+    /// Returns the implementation of the secondary index.
+    pub(crate) fn index(&self) -> Box<dyn SecondaryIndexStore> {
+        match self {
+            SecondaryIndexKind::Validator => Box::new(ValidatorIndex),
+            SecondaryIndexKind::HtlcHashRoot => Box::new(HtlcHashRootIndex),
+            SecondaryIndexKind::StakingType => Box::new(StakingTypeIndex),
+            SecondaryIndexKind::Timestamp => Box::new(TimestampIndex),
+        }
+    }
+}
+
+impl fmt::Display for SecondaryIndexKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid secondary index: {0}")]
+pub struct SecondaryIndexKindParseError(String);
+
+impl FromStr for SecondaryIndexKind {
+    type Err = SecondaryIndexKindParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SecondaryIndexKind::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| SecondaryIndexKindParseError(s.to_string()))
+    }
+}
+
+/// A secondary index maps keys that are derived from a historic transaction to the transaction
+/// hashes, ordered by their position in the history store.
+pub trait SecondaryIndex {
+    /// The kind of the secondary index.
+    const KIND: SecondaryIndexKind;
+
+    /// The table storing the index.
+    type Table: DupTable<Value = OrderedHash> + Default;
+
+    /// Returns the keys under which the given historic transaction is indexed.
+    fn keys(hist_tx: &HistoricTransaction) -> Vec<<Self::Table as Table>::Key>;
+}
+
+/// Indexes reward, penalty and jail inherents by validator address.
+#[derive(Debug)]
+pub struct ValidatorIndex;
+
+impl SecondaryIndex for ValidatorIndex {
+    const KIND: SecondaryIndexKind = SecondaryIndexKind::Validator;
+    type Table = ValidatorTable;
+
+    fn keys(hist_tx: &HistoricTransaction) -> Vec<Address> {
+        match &hist_tx.data {
+            HistoricTransactionData::Reward(ev) => vec![ev.validator_address.clone()],
+            HistoricTransactionData::Penalize(ev) => vec![ev.validator_address.clone()],
+            HistoricTransactionData::Jail(ev) => vec![ev.validator_address.clone()],
+            HistoricTransactionData::Basic(_) | HistoricTransactionData::Equivocation(_) => {
+                vec![]
+            }
+        }
+    }
+}
+
+/// Indexes HTLC creation transactions and HTLC redeem transactions that reveal a pre-image by
+/// the hash root of the HTLC.
+#[derive(Debug)]
+pub struct HtlcHashRootIndex;
+
+impl SecondaryIndex for HtlcHashRootIndex {
+    const KIND: SecondaryIndexKind = SecondaryIndexKind::HtlcHashRoot;
+    type Table = HtlcHashRootTable;
+
+    fn keys(hist_tx: &HistoricTransaction) -> Vec<Vec<u8>> {
+        let HistoricTransactionData::Basic(tx) = &hist_tx.data else {
+            return vec![];
+        };
+        let tx = tx.get_raw_transaction();
+
+        let mut keys = vec![];
+        if tx.recipient_type == AccountType::HTLC
+            && tx.flags.contains(TransactionFlags::CONTRACT_CREATION)
+        {
+            if let Ok(data) = CreationTransactionData::parse(tx) {
+                keys.push(data.hash_root.as_bytes().to_vec());
+            }
+        }
+        if tx.sender_type == AccountType::HTLC {
+            if let Ok(OutgoingHTLCTransactionProof::RegularTransfer { hash_root, .. }) =
+                OutgoingHTLCTransactionProof::parse(tx)
+            {
+                keys.push(hash_root.as_bytes().to_vec());
+            }
+        }
+        keys
+    }
+}
+
+/// The type of a staking contract transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum StakingTransactionType {
+    CreateValidator = 0,
+    UpdateValidator = 1,
+    DeactivateValidator = 2,
+    ReactivateValidator = 3,
+    RetireValidator = 4,
+    CreateStaker = 5,
+    AddStake = 6,
+    UpdateStaker = 7,
+    SetActiveStake = 8,
+    RetireStake = 9,
+    DeleteValidator = 10,
+    RemoveStake = 11,
+}
+
+impl StakingTransactionType {
+    /// All staking transaction types.
+    pub const ALL: [StakingTransactionType; 12] = [
+        StakingTransactionType::CreateValidator,
+        StakingTransactionType::UpdateValidator,
+        StakingTransactionType::DeactivateValidator,
+        StakingTransactionType::ReactivateValidator,
+        StakingTransactionType::RetireValidator,
+        StakingTransactionType::CreateStaker,
+        StakingTransactionType::AddStake,
+        StakingTransactionType::UpdateStaker,
+        StakingTransactionType::SetActiveStake,
+        StakingTransactionType::RetireStake,
+        StakingTransactionType::DeleteValidator,
+        StakingTransactionType::RemoveStake,
+    ];
+
+    /// The name of the staking transaction type.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StakingTransactionType::CreateValidator => "createValidator",
+            StakingTransactionType::UpdateValidator => "updateValidator",
+            StakingTransactionType::DeactivateValidator => "deactivateValidator",
+            StakingTransactionType::ReactivateValidator => "reactivateValidator",
+            StakingTransactionType::RetireValidator => "retireValidator",
+            StakingTransactionType::CreateStaker => "createStaker",
+            StakingTransactionType::AddStake => "addStake",
+            StakingTransactionType::UpdateStaker => "updateStaker",
+            StakingTransactionType::SetActiveStake => "setActiveStake",
+            StakingTransactionType::RetireStake => "retireStake",
+            StakingTransactionType::DeleteValidator => "deleteValidator",
+            StakingTransactionType::RemoveStake => "removeStake",
+        }
+    }
+}
+
+impl From<&IncomingStakingTransactionData> for StakingTransactionType {
+    fn from(data: &IncomingStakingTransactionData) -> Self {
+        match data {
+            IncomingStakingTransactionData::CreateValidator { .. } => Self::CreateValidator,
+            IncomingStakingTransactionData::UpdateValidator { .. } => Self::UpdateValidator,
+            IncomingStakingTransactionData::DeactivateValidator { .. } => Self::DeactivateValidator,
+            IncomingStakingTransactionData::ReactivateValidator { .. } => Self::ReactivateValidator,
+            IncomingStakingTransactionData::RetireValidator { .. } => Self::RetireValidator,
+            IncomingStakingTransactionData::CreateStaker { .. } => Self::CreateStaker,
+            IncomingStakingTransactionData::AddStake { .. } => Self::AddStake,
+            IncomingStakingTransactionData::UpdateStaker { .. } => Self::UpdateStaker,
+            IncomingStakingTransactionData::SetActiveStake { .. } => Self::SetActiveStake,
+            IncomingStakingTransactionData::RetireStake { .. } => Self::RetireStake,
+        }
+    }
+}
+
+impl From<&OutgoingStakingTransactionData> for StakingTransactionType {
+    fn from(data: &OutgoingStakingTransactionData) -> Self {
+        match data {
+            OutgoingStakingTransactionData::DeleteValidator => Self::DeleteValidator,
+            OutgoingStakingTransactionData::RemoveStake => Self::RemoveStake,
+        }
+    }
+}
+
+impl fmt::Display for StakingTransactionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid staking transaction type: {0}")]
+pub struct StakingTransactionTypeParseError(String);
+
+impl FromStr for StakingTransactionType {
+    type Err = StakingTransactionTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StakingTransactionType::ALL
+            .into_iter()
+            .find(|ty| ty.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| StakingTransactionTypeParseError(s.to_string()))
+    }
+}
+
+impl From<StakingTransactionType> for u8 {
+    fn from(ty: StakingTransactionType) -> Self {
+        ty as u8
+    }
+}
+
+/// Indexes staking contract transactions by their type.
+#[derive(Debug)]
+pub struct StakingTypeIndex;
+
+impl SecondaryIndex for StakingTypeIndex {
+    const KIND: SecondaryIndexKind = SecondaryIndexKind::StakingType;
+    type Table = StakingTypeTable;
+
+    fn keys(hist_tx: &HistoricTransaction) -> Vec<u8> {
+        let HistoricTransactionData::Basic(tx) = &hist_tx.data else {
+            return vec![];
+        };
+        let tx = tx.get_raw_transaction();
+
+        let mut keys = vec![];
+        if tx.recipient_type == AccountType::Staking {
+            if let Ok(data) = IncomingStakingTransactionData::parse(tx) {
+                keys.push(StakingTransactionType::from(&data).into());
+            }
+        }
+        if tx.sender_type == AccountType::Staking {
+            if let Ok(data) = OutgoingStakingTransactionData::parse(tx) {
+                keys.push(StakingTransactionType::from(&data).into());
+            }
+        }
+        keys
+    }
+}
+
+/// Indexes all historic transactions by the timestamp of their block.
+#[derive(Debug)]
+pub struct TimestampIndex;
+
+impl SecondaryIndex for TimestampIndex {
+    const KIND: SecondaryIndexKind = SecondaryIndexKind::Timestamp;
+    type Table = TimestampTable;
+
+    fn keys(hist_tx: &HistoricTransaction) -> Vec<u64> {
+        vec![hist_tx.block_time]
+    }
+}
+
+/// The object-safe interface through which the `HistoryStoreIndex` maintains the enabled
+/// secondary indexes. It is implemented for all secondary indexes.
+pub(crate) trait SecondaryIndexStore: fmt::Debug + Send + Sync {
+    /// The kind of the secondary index.
+    fn kind(&self) -> SecondaryIndexKind;
+
+    /// The name of the table of the secondary index.
+    fn table_name(&self) -> &'static str;
+
+    /// Creates the table of the secondary index.
+    fn create_table(&self, db: &MdbxDatabase);
+
+    /// Adds the historic transactions at the given leaf indices to the index.
+    fn put(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        hist_txs: &[HistoricTransaction],
+        leaf_indices: &[u32],
+    );
+
+    /// Removes a historic transaction from the index.
+    fn remove(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        leaf_index: u32,
+        hist_tx: &HistoricTransaction,
+    );
+
+    /// Removes all entries from the index.
+    fn clear(&self, txn: &mut MdbxWriteTransaction);
+
+    /// Rebuilds the index from the historic transactions in the history store.
+    fn rebuild(&self, txn: &mut MdbxWriteTransaction, history_store: &HistoryStore);
+
+    /// Checks that every index entry points to a historic transaction with that hash and that
+    /// has the indexed key.
+    fn check(
+        &self,
+        txn: &MdbxReadTransaction,
+        history_store: &HistoryStore,
+        result: &mut DbCheckResult,
+    );
+}
+
+impl<I: SecondaryIndex + fmt::Debug + Send + Sync> SecondaryIndexStore for I {
+    fn kind(&self) -> SecondaryIndexKind {
+        I::KIND
+    }
+
+    fn table_name(&self) -> &'static str {
+        I::Table::NAME
+    }
+
+    fn create_table(&self, db: &MdbxDatabase) {
+        db.create_dup_table(&I::Table::default());
+    }
+
+    fn put(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        hist_txs: &[HistoricTransaction],
+        leaf_indices: &[u32],
+    ) {
+        // Sort everything first and then put with a cursor for improved database performance.
+        let mut entries = BTreeMap::new();
+        for (hist_tx, leaf_index) in hist_txs.iter().zip(leaf_indices.iter()) {
+            add_entries::<I>(&mut entries, epoch_number, *leaf_index, hist_tx);
+        }
+
+        let mut cursor = WriteTransaction::dup_cursor(txn, &I::Table::default());
+        for (key, ordered_hashes) in entries.iter() {
+            for ordered_hash in ordered_hashes.iter() {
+                cursor.put(key, ordered_hash);
+            }
+        }
+    }
+
+    fn remove(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        leaf_index: u32,
+        hist_tx: &HistoricTransaction,
+    ) {
+        let ordered_hash = OrderedHash {
+            index: EpochBasedIndex::new(epoch_number, leaf_index),
+            value: hist_tx.tx_hash().into(),
+        };
+        for key in I::keys(hist_tx) {
+            txn.remove_item(&I::Table::default(), &key, &ordered_hash);
+        }
+    }
+
+    fn clear(&self, txn: &mut MdbxWriteTransaction) {
+        txn.clear_table(&I::Table::default());
+    }
+
+    fn rebuild(&self, txn: &mut MdbxWriteTransaction, history_store: &HistoryStore) {
+        txn.clear_table(&I::Table::default());
+
+        let mut entries = BTreeMap::new();
+        let cursor = WriteTransaction::dup_cursor(txn, &history_store.hist_tx_table);
+        for (epoch_number, hist_tx) in cursor.into_iter_start() {
+            add_entries::<I>(&mut entries, epoch_number, hist_tx.index, &hist_tx.value);
+        }
+
+        // We insert indices by append, which gives us much better performance.
+        let mut cursor = WriteTransaction::dup_cursor(txn, &I::Table::default());
+        for (key, ordered_hashes) in entries.iter() {
+            for ordered_hash in ordered_hashes.iter() {
+                cursor.append(key, ordered_hash);
+            }
+        }
+    }
+
+    fn check(
+        &self,
+        txn: &MdbxReadTransaction,
+        history_store: &HistoryStore,
+        result: &mut DbCheckResult,
+    ) {
+        let cursor = txn.dup_cursor(&I::Table::default());
+        for (key, ordered_hash) in cursor.into_iter_start() {
+            result.num_checked += 1;
+            let index = ordered_hash.index;
+            let matches = history_store
+                .get_historic_tx(index.epoch_number, index.index, Some(txn))
+                .is_some_and(|hist_tx| {
+                    Blake2bHash::from(hist_tx.tx_hash()) == ordered_hash.value
+                        && I::keys(&hist_tx).contains(&key)
+                });
+            if !matches {
+                result.error(format!(
+                    "Transaction {} is indexed in the {} index at epoch {}, leaf {}, which doesn't match",
+                    ordered_hash.value,
+                    I::KIND,
+                    index.epoch_number,
+                    index.index
+                ));
+            }
+        }
+    }
+}
+
+/// Adds the index entries of a historic transaction to the given entries, which are sorted by key.
+fn add_entries<I: SecondaryIndex>(
+    entries: &mut BTreeMap<<I::Table as Table>::Key, Vec<OrderedHash>>,
+    epoch_number: u32,
+    leaf_index: u32,
+    hist_tx: &HistoricTransaction,
+) {
+    let keys: BTreeSet<_> = I::keys(hist_tx).into_iter().collect();
+    if keys.is_empty() {
+        return;
+    }
+
+    let ordered_hash = OrderedHash {
+        index: EpochBasedIndex::new(epoch_number, leaf_index),
+        value: hist_tx.tx_hash().into(),
+    };
+    for key in keys {
+        entries.entry(key).or_default().push(ordered_hash.clone());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use nimiq_database::{
     declare_table,
     mdbx::{MdbxDatabase, MdbxReadTransaction, MdbxWriteTransaction, OptionalTransaction},
@@ -6,6 +8,8 @@ use nimiq_database::{
 use nimiq_primitives::policy::Policy;
 use nimiq_transaction::historic_transaction::RawTransactionHash;
 
+use crate::DbCheckResult;
+
 // `RawTransactionHash` -> `u32` (block number)
 declare_table!(TxnHashesTable, "ValidityTxnHashes", RawTransactionHash => u32);
 // `u32` (block number) -> `RawTransactionHash`
@@ -165,4 +169,53 @@ impl ValidityStore {
 
         self.prune_validity_store(db_txn)
     }
+
+    /// Checks that both tables of the validity store reference each other and that the store
+    /// doesn't cover more blocks than necessary.
+    pub(crate) fn check(&self, db_txn: &MdbxReadTransaction, result: &mut DbCheckResult) {
+        let mut block_txns = HashSet::new();
+        let cursor = db_txn.dup_cursor(&self.block_txns);
+        for (block_number, hash) in cursor.into_iter_start() {
+            // Empty blocks are tracked with the default hash.
+            if hash == RawTransactionHash::default() {
+                continue;
+            }
+            result.num_checked += 1;
+
+            // A later transaction with the same hash overwrites the block number.
+            match db_txn.get(&self.txn_hashes, &hash) {
+                Some(bn) if bn >= block_number => {}
+                bn => result.error(format!(
+                    "Transaction {} of block #{block_number} has block number {bn:?} in the validity store",
+                    *hash
+                )),
+            }
+            block_txns.insert((block_number, hash));
+        }
+
+        let cursor = db_txn.cursor(&self.txn_hashes);
+        for (hash, block_number) in cursor.into_iter_start() {
+            result.num_checked += 1;
+            if !block_txns.contains(&(block_number, hash.clone())) {
+                result.error(format!(
+                    "Transaction {} is not listed for block #{block_number} in the validity store",
+                    *hash
+                ));
+            }
+        }
+
+        let first_bn = self.first_bn(db_txn);
+        let last_bn = self.last_bn(db_txn);
+        let num_blocks_to_keep =
+            Policy::transaction_validity_window_blocks() + Policy::blocks_per_batch();
+        if first_bn > last_bn {
+            result.error(format!(
+                "First block number #{first_bn} is greater than last block number #{last_bn}"
+            ));
+        } else if last_bn - first_bn + 1 > num_blocks_to_keep {
+            result.error(format!(
+                "Validity store covers blocks #{first_bn} to #{last_bn}, more than {num_blocks_to_keep} blocks"
+            ));
+        }
+    }
 }
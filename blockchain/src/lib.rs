@@ -3,7 +3,15 @@ extern crate log;
 
 pub use block_production::{BlockProducer, BlockProducerError};
 pub use blockchain::{
-    blockchain::{Blockchain, BlockchainConfig, TransactionVerificationCache},
+    blockchain::{Blockchain, BlockchainConfig, HistoryPruning, TransactionVerificationCache},
+    chain_export::{
+        ChainExportError, ChainExportHeader, ChainExportItem, ChainExportSummary,
+        CHAIN_EXPORT_MAGIC, CHAIN_EXPORT_MAX_ITEM_SIZE, CHAIN_EXPORT_VERSION,
+    },
+    db_check::{DbCheckReport, DbCheckResult},
+    economics::{BatchRewardInfo, StakingYield, SupplyInfo},
+    staking_history::StakingHistory,
+    staking_rewards::{StakerEpochReward, MAX_STAKER_REWARD_EPOCHS},
     PostValidationHook,
 };
 pub use history::*;
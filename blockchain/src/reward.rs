@@ -28,6 +28,24 @@ pub fn batch_delay(previous_timestamp: u64, current_timestamp: u64) -> u64 {
     current_timestamp.saturating_sub(target_ts)
 }
 
+/// Compute the maximum block reward for a batch, i.e. the increase of the supply between the
+/// timestamps of the previous and the current macro block.
+pub fn max_reward_for_batch(
+    previous_timestamp: u64,
+    current_timestamp: u64,
+    genesis_supply: Coin,
+    genesis_timestamp: u64,
+) -> Coin {
+    let genesis_supply_u64 = u64::from(genesis_supply);
+
+    let prev_supply = Policy::supply_at(genesis_supply_u64, genesis_timestamp, previous_timestamp);
+
+    let current_supply =
+        Policy::supply_at(genesis_supply_u64, genesis_timestamp, current_timestamp);
+
+    Coin::from_u64_unchecked(current_supply.saturating_sub(prev_supply))
+}
+
 /// Compute the block reward for a batch from the current macro block, the previous macro block,
 /// and the genesis parameters.
 /// This does not include the reward from transaction fees.
@@ -44,15 +62,13 @@ pub fn block_reward_for_batch(
     assert!(current_timestamp >= previous_timestamp);
     assert!(previous_timestamp >= genesis_timestamp);
 
-    let genesis_supply_u64 = u64::from(genesis_supply);
-
-    let prev_supply = Policy::supply_at(genesis_supply_u64, genesis_timestamp, previous_timestamp);
-
-    let current_supply =
-        Policy::supply_at(genesis_supply_u64, genesis_timestamp, current_timestamp);
-
     // This is the maximum rewards that we can pay for this batch
-    let max_rewards = current_supply.saturating_sub(prev_supply);
+    let max_rewards = u64::from(max_reward_for_batch(
+        previous_timestamp,
+        current_timestamp,
+        genesis_supply,
+        genesis_timestamp,
+    ));
 
     // However, there is a penalty if the batch was not produced in time...
     // First we calculate the delay producing the batch:
@@ -0,0 +1,138 @@
+use nimiq_blockchain::{
+    Blockchain, BlockchainConfig, ChainExportError, ChainExportHeader, ChainExportItem,
+    CHAIN_EXPORT_MAX_ITEM_SIZE,
+};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_primitives::policy::Policy;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_test_log::test;
+use nimiq_test_utils::blockchain::{new_volatile_blockchain, produce_blocks_with_txns};
+
+/// Splits a chain export into its header and items.
+fn parse_export(export: &[u8]) -> (ChainExportHeader, Vec<ChainExportItem>) {
+    let mut items = vec![];
+    let mut rest = export;
+    while !rest.is_empty() {
+        let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+        items.push(&rest[4..4 + len]);
+        rest = &rest[4 + len..];
+    }
+    let header = ChainExportHeader::deserialize_all(items[0]).unwrap();
+    let items = items[1..]
+        .iter()
+        .map(|item| ChainExportItem::deserialize_all(item).unwrap())
+        .collect();
+    (header, items)
+}
+
+/// Serializes a chain export from its header and items.
+fn write_export(header: &ChainExportHeader, items: &[ChainExportItem]) -> Vec<u8> {
+    let mut export = vec![];
+    let mut write = |bytes: Vec<u8>| {
+        export.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        export.extend_from_slice(&bytes);
+    };
+    write(header.serialize_to_vec());
+    for item in items {
+        write(item.serialize_to_vec());
+    }
+    export
+}
+
+/// Exports the first epoch of a blockchain.
+fn export_first_epoch() -> Vec<u8> {
+    let blockchain = new_volatile_blockchain(BlockchainConfig::default());
+    produce_blocks_with_txns(&blockchain, Policy::batches_per_epoch() as usize, 0);
+
+    let mut export = vec![];
+    blockchain.read().export_chain(&mut export, 1, 1).unwrap();
+    export
+}
+
+#[test]
+fn chain_export_and_import_works() {
+    // Produce two full epochs, a checkpoint block and a few micro blocks.
+    let blockchain1 = new_volatile_blockchain(BlockchainConfig::default());
+    let num_macro_blocks = (2 * Policy::batches_per_epoch() + 1) as usize;
+    produce_blocks_with_txns(&blockchain1, num_macro_blocks, 3);
+
+    let mut export = vec![];
+    let summary = blockchain1
+        .read()
+        .export_chain(&mut export, 0, u32::MAX)
+        .unwrap();
+    // At least the two election blocks and the checkpoint block.
+    assert!(summary.num_macro_blocks >= 3);
+    assert_eq!(summary.num_micro_blocks, 3);
+
+    // Import into a fresh blockchain.
+    let blockchain2 = new_volatile_blockchain(BlockchainConfig::default());
+    let imported = Blockchain::import_chain(&blockchain2, &mut export.as_slice()).unwrap();
+    assert_eq!(imported, summary);
+    assert_eq!(
+        blockchain2.read().head_hash(),
+        blockchain1.read().head_hash()
+    );
+
+    // Importing again doesn't change anything.
+    let imported = Blockchain::import_chain(&blockchain2, &mut export.as_slice()).unwrap();
+    assert_eq!(imported.num_macro_blocks, 0);
+    assert_eq!(imported.num_micro_blocks, 0);
+    assert_eq!(
+        blockchain2.read().head_hash(),
+        blockchain1.read().head_hash()
+    );
+}
+
+#[test]
+fn chain_import_rejects_tampered_history() {
+    let export = export_first_epoch();
+
+    // Change the time of a historic transaction, which invalidates the chunk's proof.
+    let (header, mut items) = parse_export(&export);
+    let history = items
+        .iter_mut()
+        .find_map(|item| match item {
+            ChainExportItem::BatchSet { chunks, .. } => chunks
+                .iter_mut()
+                .find(|chunk| !chunk.history.is_empty())
+                .map(|chunk| &mut chunk.history),
+            ChainExportItem::MicroBlock(_) => None,
+        })
+        .expect("Export should contain history");
+    history[0].block_time += 1;
+    let export = write_export(&header, &items);
+
+    let blockchain = new_volatile_blockchain(BlockchainConfig::default());
+    assert!(matches!(
+        Blockchain::import_chain(&blockchain, &mut export.as_slice()),
+        Err(ChainExportError::InvalidHistoryChunk { .. })
+    ));
+    assert_eq!(
+        blockchain.read().block_number(),
+        Policy::genesis_block_number()
+    );
+}
+
+#[test]
+fn chain_import_rejects_truncated_and_oversized_items() {
+    let export = export_first_epoch();
+
+    // A truncated length prefix is an error, not the end of the file.
+    let blockchain = new_volatile_blockchain(BlockchainConfig::default());
+    let mut truncated = export.clone();
+    truncated.extend_from_slice(&[0, 0]);
+    assert!(matches!(
+        Blockchain::import_chain(&blockchain, &mut truncated.as_slice()),
+        Err(ChainExportError::Io(error)) if error.kind() == std::io::ErrorKind::UnexpectedEof
+    ));
+
+    // Items larger than the maximum are rejected before they are read.
+    let blockchain = new_volatile_blockchain(BlockchainConfig::default());
+    let mut oversized = export;
+    oversized.extend_from_slice(&(CHAIN_EXPORT_MAX_ITEM_SIZE as u32 + 1).to_be_bytes());
+    assert!(matches!(
+        Blockchain::import_chain(&blockchain, &mut oversized.as_slice()),
+        Err(ChainExportError::ItemTooLarge(len)) if len == CHAIN_EXPORT_MAX_ITEM_SIZE + 1
+    ));
+}
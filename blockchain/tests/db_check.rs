@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_database::{declare_table, traits::Database};
+use nimiq_genesis::NetworkId;
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::blockchain::{new_volatile_blockchain, produce_blocks_with_txns};
+use parking_lot::RwLock;
+
+// Same name as the transaction hash table of the history index.
+declare_table!(IndexTable, "LeafIndexByTxHash", Blake2bHash => u64);
+
+fn produce_blocks(config: BlockchainConfig) -> Arc<RwLock<Blockchain>> {
+    let blockchain = new_volatile_blockchain(config);
+    produce_blocks_with_txns(&blockchain, (Policy::batches_per_epoch() + 1) as usize, 3);
+    blockchain
+}
+
+#[test]
+fn db_check_accepts_consistent_database() {
+    let blockchain = produce_blocks(BlockchainConfig::default());
+    let env = blockchain.read().db().clone();
+
+    let report = Blockchain::check_database(env, NetworkId::UnitAlbatross, false);
+    assert!(report.is_ok(), "{report:#?}");
+    assert_eq!(report.head_hash, Some(blockchain.read().head_hash()));
+    assert!(report.checks.iter().all(|check| check.skipped.is_none()));
+    assert!(!report.index_repaired);
+}
+
+#[test]
+fn db_check_detects_and_repairs_history_index() {
+    let blockchain = produce_blocks(BlockchainConfig {
+        index_history: false,
+        ..Default::default()
+    });
+    let env = blockchain.read().db().clone();
+
+    // Without an index, the index check is skipped.
+    let report = Blockchain::check_database(env.clone(), NetworkId::UnitAlbatross, false);
+    assert!(report.is_ok(), "{report:#?}");
+    let index_check = report
+        .checks
+        .iter()
+        .find(|check| check.name == "historyIndex")
+        .unwrap();
+    assert!(index_check.skipped.is_some());
+
+    // An empty index is inconsistent with the history store.
+    env.create_regular_table(&IndexTable);
+    let report = Blockchain::check_database(env.clone(), NetworkId::UnitAlbatross, false);
+    assert!(!report.is_ok());
+
+    // Rebuilding the index repairs it.
+    let report = Blockchain::check_database(env, NetworkId::UnitAlbatross, true);
+    assert!(report.is_ok(), "{report:#?}");
+    assert!(report.index_repaired);
+}
@@ -0,0 +1,109 @@
+use nimiq_blockchain::BlockchainConfig;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    block_production::TemporaryBlockProducer,
+    blockchain::{new_volatile_blockchain, produce_blocks_with_txns},
+};
+
+#[test]
+fn it_reports_supply_and_batch_rewards() {
+    let temp_producer = TemporaryBlockProducer::new();
+    for _ in 0..2 * Policy::blocks_per_batch() {
+        temp_producer.next_block(vec![], false);
+    }
+
+    let blockchain = temp_producer.blockchain.read();
+    let (genesis_supply, _) = blockchain.get_genesis_parameters();
+
+    // The supply grows over time and the staked amount matches the staking contract.
+    let genesis_info = blockchain
+        .get_supply_info(Policy::genesis_block_number())
+        .unwrap();
+    let head_info = blockchain
+        .get_supply_info(blockchain.block_number())
+        .unwrap();
+    assert_eq!(genesis_info.supply, genesis_supply);
+    assert!(head_info.supply > genesis_info.supply);
+    assert_eq!(
+        head_info.staked,
+        Some(blockchain.get_staking_contract().balance)
+    );
+
+    // The reward of a batch is the supply increase reduced by the delay penalty.
+    for batch_number in 1..=2 {
+        let info = blockchain.get_batch_reward_info(batch_number).unwrap();
+        assert_eq!(
+            info.block_number,
+            Policy::macro_block_of(batch_number).unwrap()
+        );
+        assert!(info.reward <= info.max_reward);
+        assert_eq!(
+            info.reward,
+            Coin::from_u64_unchecked(
+                (u64::from(info.max_reward) as f64 * info.delay_penalty) as u64
+            )
+        );
+    }
+    assert_eq!(
+        blockchain.get_batch_reward_info(3),
+        Err(BlockchainError::BlockNotFound)
+    );
+
+    let staking_yield = blockchain.get_staking_yield().unwrap();
+    assert!(!staking_yield.active_stake.is_zero());
+    assert!(staking_yield.apr.unwrap() > 0.0);
+}
+
+#[test]
+fn it_reports_the_staked_amount_of_past_epochs() {
+    let temp_producer = TemporaryBlockProducer::new();
+
+    // A skip block punishes the validator, which changes the staking contract.
+    temp_producer.next_block(vec![], true);
+    let block_number = temp_producer.blockchain.read().block_number();
+    let staked = temp_producer
+        .blockchain
+        .read()
+        .get_staking_contract()
+        .balance;
+
+    // Move into the next epoch, so that the staked amount is read from the epoch's checkpoint.
+    for _ in 0..Policy::blocks_per_epoch() {
+        temp_producer.next_block(vec![], false);
+    }
+
+    let blockchain = temp_producer.blockchain.read();
+    assert!(Policy::epoch_at(blockchain.block_number()) > Policy::epoch_at(block_number));
+    assert_eq!(
+        blockchain.get_supply_info(block_number).unwrap().staked,
+        Some(staked)
+    );
+}
+
+#[test]
+fn it_omits_the_staked_amount_without_staking_history() {
+    // Full nodes don't keep the staking history.
+    let blockchain = new_volatile_blockchain(BlockchainConfig {
+        keep_history: false,
+        index_history: false,
+        ..Default::default()
+    });
+    produce_blocks_with_txns(&blockchain, 1, 0);
+
+    let blockchain = blockchain.read();
+    let genesis_info = blockchain
+        .get_supply_info(Policy::genesis_block_number())
+        .unwrap();
+    assert_eq!(genesis_info.staked, None);
+
+    // The staked amount at the head is read from the current staking contract.
+    let head_info = blockchain
+        .get_supply_info(blockchain.block_number())
+        .unwrap();
+    assert_eq!(
+        head_info.staked,
+        Some(blockchain.get_staking_contract().balance)
+    );
+}
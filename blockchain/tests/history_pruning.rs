@@ -0,0 +1,122 @@
+use std::collections::BTreeSet;
+
+use nimiq_blockchain::{
+    interface::{HistoryIndexInterface, HistoryInterface},
+    Blockchain, BlockchainConfig, HistoryPruning,
+};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_genesis::NetworkId;
+use nimiq_keys::Address;
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::blockchain::{new_volatile_blockchain, produce_blocks_with_txns};
+use nimiq_transaction::historic_transaction::{HistoricTransaction, HistoricTransactionData};
+
+#[test]
+fn history_is_pruned_for_finalized_epochs() {
+    let blockchain = new_volatile_blockchain(BlockchainConfig {
+        history_pruning: HistoryPruning::KeepAddresses(BTreeSet::new()),
+        ..Default::default()
+    });
+
+    // Finalize two epochs. The history of the first epoch is pruned with the second election block.
+    produce_blocks_with_txns(&blockchain, (2 * Policy::batches_per_epoch()) as usize, 0);
+
+    let blockchain = blockchain.read();
+    let history_store = &blockchain.history_store;
+
+    // No transaction of the first epoch involves one of the kept addresses.
+    assert!(history_store.num_epoch_transactions(1, None) > 0);
+    assert!(history_store.get_epoch_transactions(1, None).is_empty());
+
+    // The history tree of the first epoch is still complete.
+    let election_block = blockchain
+        .get_block_at(Policy::election_block_of(1).unwrap(), false)
+        .unwrap();
+    assert_eq!(
+        history_store.get_history_tree_root(election_block.block_number(), None),
+        Some(election_block.history_root().clone())
+    );
+
+    // The current epoch is not pruned yet.
+    assert_eq!(
+        history_store.get_epoch_transactions(2, None).len(),
+        history_store.num_epoch_transactions(2, None)
+    );
+}
+
+fn involves(hist_tx: &HistoricTransaction, address: &Address) -> bool {
+    match &hist_tx.data {
+        HistoricTransactionData::Basic(tx) => {
+            let tx = tx.get_raw_transaction();
+            &tx.sender == address || &tx.recipient == address
+        }
+        HistoricTransactionData::Reward(ev) => {
+            &ev.reward_address == address || &ev.validator_address == address
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn history_of_kept_addresses_survives_pruning() {
+    // The produced blocks are deterministic, so the unpruned history of a chain with the same
+    // blocks tells which transactions must be kept.
+    let num_macro_blocks = (2 * Policy::batches_per_epoch()) as usize;
+    let unpruned = new_volatile_blockchain(BlockchainConfig::default());
+    produce_blocks_with_txns(&unpruned, num_macro_blocks, 0);
+    let all_txns = unpruned
+        .read()
+        .history_store
+        .get_epoch_transactions(1, None);
+
+    let address = all_txns
+        .iter()
+        .find_map(|hist_tx| match &hist_tx.data {
+            HistoricTransactionData::Basic(tx) => Some(tx.get_raw_transaction().recipient.clone()),
+            _ => None,
+        })
+        .expect("The first epoch contains transactions");
+    let kept_txns: Vec<_> = all_txns
+        .iter()
+        .filter(|hist_tx| involves(hist_tx, &address))
+        .cloned()
+        .collect();
+    assert!(kept_txns.len() < all_txns.len());
+
+    let blockchain = new_volatile_blockchain(BlockchainConfig {
+        history_pruning: HistoryPruning::KeepAddresses(BTreeSet::from([address.clone()])),
+        ..Default::default()
+    });
+    produce_blocks_with_txns(&blockchain, num_macro_blocks, 0);
+    assert_eq!(blockchain.read().head_hash(), unpruned.read().head_hash());
+
+    let blockchain = blockchain.read();
+    let history_store = &blockchain.history_store;
+
+    // Only the transactions involving the kept address remain.
+    assert_eq!(history_store.get_epoch_transactions(1, None), kept_txns);
+    assert_eq!(
+        history_store.num_epoch_transactions(1, None),
+        all_txns.len()
+    );
+
+    // The history index only refers to the remaining transactions.
+    let index = history_store.history_index().unwrap();
+    for hist_tx in all_txns.iter() {
+        let indexed_tx = index.get_hist_tx_by_hash(&hist_tx.tx_hash(), None);
+        if involves(hist_tx, &address) {
+            assert_eq!(indexed_tx.as_ref(), Some(hist_tx));
+        } else {
+            assert_eq!(indexed_tx, None);
+        }
+    }
+    let address_hashes = index.get_tx_hashes_by_address(&address, u16::MAX, None, None);
+    for hist_tx in kept_txns.iter() {
+        assert!(address_hashes.contains(&hist_tx.tx_hash().into()));
+    }
+
+    let report =
+        Blockchain::check_database(blockchain.db().clone(), NetworkId::UnitAlbatross, false);
+    assert!(report.is_ok(), "{report:#?}");
+}
@@ -612,3 +612,61 @@ fn can_push_zkps() {
         );
     }
 }
+
+#[test]
+fn can_push_trusted_checkpoints() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    // Produce a full epoch of blocks.
+    for _ in 0..Policy::blocks_per_epoch() - 2 {
+        temp_producer1.next_block(vec![], false);
+    }
+    let micro_block = temp_producer1.next_block(vec![], false);
+    let election_block = temp_producer1.next_block(vec![], false);
+    let block_number = election_block.block_number();
+    let block_hash = election_block.hash();
+
+    // Blocks that don't match the trusted hash are rejected.
+    let blockchain2 = temp_producer2.blockchain.upgradable_read();
+    let result = Blockchain::push_trusted_checkpoint(
+        blockchain2,
+        election_block.clone(),
+        &Blake2bHash::default(),
+    );
+    assert_eq!(result, Err(PushError::UntrustedCheckpoint));
+
+    // Only election blocks can be trusted checkpoints.
+    let blockchain2 = temp_producer2.blockchain.upgradable_read();
+    let micro_block_hash = micro_block.hash();
+    let result = Blockchain::push_trusted_checkpoint(blockchain2, micro_block, &micro_block_hash);
+    assert_eq!(result, Err(PushError::UntrustedCheckpoint));
+
+    assert_eq!(
+        temp_producer2.blockchain.read().block_number(),
+        Policy::genesis_block_number()
+    );
+
+    // Push the matching checkpoint.
+    let blockchain2 = temp_producer2.blockchain.upgradable_read();
+    let result =
+        Blockchain::push_trusted_checkpoint(blockchain2, election_block.clone(), &block_hash);
+
+    assert_eq!(result, Ok(PushResult::Extended));
+    {
+        let blockchain2_rg = temp_producer2.blockchain.read();
+        assert_eq!(blockchain2_rg.block_number(), block_number);
+        assert_eq!(blockchain2_rg.head_hash(), block_hash);
+
+        assert!(!blockchain2_rg.can_enforce_validity_window());
+        assert_eq!(
+            blockchain2_rg.get_missing_accounts_range(None),
+            Some(KeyNibbles::ROOT..)
+        );
+    }
+
+    // Pushing it again has no effect.
+    let blockchain2 = temp_producer2.blockchain.upgradable_read();
+    let result = Blockchain::push_trusted_checkpoint(blockchain2, election_block, &block_hash);
+    assert_eq!(result, Ok(PushResult::Known));
+}
@@ -0,0 +1,222 @@
+use nimiq_account::{Staker, StakingContract, Validator};
+use nimiq_blockchain::{interface::HistoryInterface, Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, PushResult};
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    block_production::TemporaryBlockProducer,
+    blockchain::{new_volatile_blockchain, validator_address},
+};
+
+/// The staking state of the genesis validator after a block.
+struct StakingState {
+    block_number: u32,
+    staking_contract: StakingContract,
+    validator: Validator,
+    stakers: Vec<Staker>,
+}
+
+fn current_state(temp_producer: &TemporaryBlockProducer) -> StakingState {
+    let blockchain = temp_producer.blockchain.read();
+    let staking_contract = blockchain.get_staking_contract();
+    let data_store = blockchain.get_staking_contract_store();
+    let txn = blockchain.read_transaction();
+    let validator = staking_contract
+        .get_validator(&data_store.read(&txn), &validator_address())
+        .unwrap();
+    let stakers =
+        staking_contract.get_stakers_for_validator(&data_store.read(&txn), &validator_address());
+
+    StakingState {
+        block_number: blockchain.block_number(),
+        staking_contract,
+        validator,
+        stakers,
+    }
+}
+
+#[test]
+fn it_can_query_past_staking_states() {
+    let temp_producer = TemporaryBlockProducer::new();
+
+    // Skip blocks punish the validator and thus change the staking contract.
+    let mut states = vec![current_state(&temp_producer)];
+    for skip_block in [false, true, false, true, false] {
+        temp_producer.next_block(vec![], skip_block);
+        states.push(current_state(&temp_producer));
+    }
+
+    let blockchain = temp_producer.blockchain.read();
+    for state in states {
+        assert_eq!(
+            blockchain.get_staking_contract_at(state.block_number),
+            Ok(state.staking_contract)
+        );
+        assert_eq!(
+            blockchain.get_validator_at(&validator_address(), state.block_number),
+            Ok(Some(state.validator))
+        );
+        assert_eq!(
+            blockchain.get_stakers_for_validator_at(&validator_address(), state.block_number),
+            Ok(state.stakers)
+        );
+    }
+
+    assert_eq!(
+        blockchain.get_staking_contract_at(blockchain.block_number() + 1),
+        Err(BlockchainError::BlockNotFound)
+    );
+}
+
+#[test]
+fn it_can_query_past_staking_states_after_rebranch() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    // [0] - [0] - [0]
+    //    \- [1] - [1]
+    let inferior1 = temp_producer1.next_block(vec![], false);
+    let fork1 = temp_producer2.next_block(vec![], true);
+    let fork1_state = current_state(&temp_producer2);
+
+    let inferior2 = temp_producer1.next_block(vec![], false);
+    let fork2 = temp_producer2.next_block(vec![], false);
+
+    assert_eq!(temp_producer2.push(inferior1), Ok(PushResult::Ignored));
+    assert_eq!(temp_producer2.push(inferior2), Ok(PushResult::Ignored));
+    assert_eq!(temp_producer1.push(fork1), Ok(PushResult::Rebranched));
+    assert_eq!(temp_producer1.push(fork2), Ok(PushResult::Extended));
+
+    // The reverted blocks do not affect the reconstructed states.
+    let blockchain = temp_producer1.blockchain.read();
+    assert_eq!(
+        blockchain.get_staking_contract_at(fork1_state.block_number),
+        Ok(fork1_state.staking_contract)
+    );
+    assert_eq!(
+        blockchain.get_validator_at(&validator_address(), fork1_state.block_number),
+        Ok(Some(fork1_state.validator))
+    );
+}
+
+#[test]
+fn it_can_query_staking_states_of_past_epochs() {
+    let temp_producer = TemporaryBlockProducer::new();
+
+    let mut states = vec![current_state(&temp_producer)];
+    for skip_block in [true, false, true] {
+        temp_producer.next_block(vec![], skip_block);
+        states.push(current_state(&temp_producer));
+    }
+
+    // Finish the epoch and start the next one. The states of the first epoch are then
+    // reconstructed from the checkpoint of its election block.
+    while !Policy::is_election_block_at(temp_producer.blockchain.read().block_number()) {
+        temp_producer.next_block(vec![], false);
+    }
+    states.push(current_state(&temp_producer));
+    temp_producer.next_block(vec![], true);
+    states.push(current_state(&temp_producer));
+
+    let blockchain = temp_producer.blockchain.read();
+    for state in states {
+        assert_eq!(
+            blockchain.get_staking_contract_at(state.block_number),
+            Ok(state.staking_contract)
+        );
+        assert_eq!(
+            blockchain.get_validator_at(&validator_address(), state.block_number),
+            Ok(Some(state.validator))
+        );
+        assert_eq!(
+            blockchain.get_stakers_for_validator_at(&validator_address(), state.block_number),
+            Ok(state.stakers)
+        );
+    }
+}
+
+#[test]
+fn staking_states_of_history_synced_epochs_are_not_available() {
+    let temp_producer = TemporaryBlockProducer::new();
+    while !Policy::is_election_block_at(temp_producer.blockchain.read().block_number()) {
+        temp_producer.next_block(vec![], false);
+    }
+    let election_block = temp_producer.blockchain.read().head();
+    let history = temp_producer
+        .blockchain
+        .read()
+        .history_store
+        .get_epoch_transactions(election_block.epoch_number(), None);
+
+    // A node that adopted the epoch through history sync didn't record the staking diffs of its
+    // blocks.
+    let blockchain = new_volatile_blockchain(BlockchainConfig::default());
+    assert_eq!(
+        Blockchain::push_history_sync(
+            blockchain.upgradable_read(),
+            election_block.clone(),
+            &history
+        ),
+        Ok(PushResult::Extended)
+    );
+
+    let blockchain = blockchain.read();
+    let block_number = election_block.block_number() - 1;
+    assert_eq!(
+        blockchain.get_staking_contract_at(block_number),
+        Err(BlockchainError::StakingHistoryNotFound(block_number))
+    );
+    assert_eq!(
+        blockchain.get_validator_at(&validator_address(), block_number),
+        Err(BlockchainError::StakingHistoryNotFound(block_number))
+    );
+
+    // The state after the head block is still available.
+    assert_eq!(
+        blockchain.get_staking_contract_at(election_block.block_number()),
+        Ok(blockchain.get_staking_contract())
+    );
+}
+
+#[test]
+fn it_computes_staker_rewards() {
+    let temp_producer = TemporaryBlockProducer::new();
+    // The genesis staker delegates to the genesis validator.
+    let staker_address =
+        Address::from_user_friendly_address("NQ39 VBTN P2HX Q3MF KHF1 CCLA G6FS 9B8S VY28")
+            .unwrap();
+
+    // Finalize the first epoch and the first batch of the second one, which pays out the rewards
+    // for the last batch of the first epoch.
+    for _ in 0..Policy::blocks_per_epoch() + Policy::blocks_per_batch() {
+        temp_producer.next_block(vec![], false);
+    }
+
+    let blockchain = temp_producer.blockchain.read();
+    let rewards = blockchain
+        .get_staker_rewards(&staker_address, 1..=2)
+        .unwrap();
+    assert_eq!(rewards.len(), 2);
+
+    let reward = &rewards[0];
+    assert_eq!(reward.epoch, 1);
+    assert_eq!(reward.validator_address, Some(validator_address()));
+    assert!(!reward.stake.is_zero());
+    assert!(!reward.validator_reward.is_zero());
+    assert_eq!(
+        u128::from(u64::from(reward.reward)),
+        u128::from(u64::from(reward.validator_reward)) * u128::from(u64::from(reward.stake))
+            / u128::from(u64::from(reward.validator_stake))
+    );
+
+    // No rewards have been paid out for the current epoch yet.
+    assert_eq!(rewards[1].epoch, 2);
+    assert_eq!(rewards[1].validator_reward, Coin::ZERO);
+    assert_eq!(rewards[1].reward, Coin::ZERO);
+
+    assert_eq!(
+        blockchain.get_staker_rewards(&staker_address, 1..=3),
+        Err(BlockchainError::InvalidEpoch)
+    );
+}
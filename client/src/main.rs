@@ -4,13 +4,21 @@ use log::info;
 use nimiq::prover::prover_main;
 pub use nimiq::{
     client::Client,
-    config::{command_line::CommandLine, config::ClientConfig, config_file::ConfigFile},
+    config::{
+        command_line::{Command, CommandLine},
+        config::ClientConfig,
+        config_file::ConfigFile,
+    },
     error::Error,
     extras::{
+        chain_export::{export_chain, import_chain},
+        database_capacity::DatabaseCapacityMonitor,
         logging::{initialize_logging, log_error_cause_chain},
         metrics_server::NimiqTaskMonitor,
         panic::initialize_panic_reporting,
+        prover_server::run_prover_server,
         signal_handling::initialize_signal_handler,
+        snapshot::{create_snapshot, restore_snapshot},
     },
 };
 use nimiq_utils::spawn;
@@ -59,6 +67,30 @@ async fn main_inner() -> Result<(), Error> {
     let config = builder.build()?;
     log::debug!("Final configuration: {:#?}", config);
 
+    // Early return in case a database snapshot is requested.
+    if let Some(snapshot_path) = &command_line.snapshot {
+        return create_snapshot(&config, snapshot_path, !command_line.snapshot_keep_size);
+    }
+
+    // Restore a database snapshot before the database is opened.
+    if let Some(snapshot_path) = &command_line.restore_snapshot {
+        restore_snapshot(&config, snapshot_path)?;
+    }
+
+    // Early return in case of a maintenance command.
+    match &command_line.command {
+        Some(Command::ExportChain {
+            file,
+            from_epoch,
+            to_epoch,
+        }) => {
+            return export_chain(&config, file, *from_epoch, to_epoch.unwrap_or(u32::MAX));
+        }
+        Some(Command::ImportChain { file }) => return import_chain(&config, file),
+        Some(Command::ProverServer) => return run_prover_server(&config).await,
+        None => {}
+    }
+
     // Clone config for RPC and metrics server
     let rpc_config = config.rpc_server.clone();
     let metrics_config = config.metrics_server.clone();
@@ -100,11 +132,13 @@ async fn main_inner() -> Result<(), Error> {
 
     // Start validator
     let val_metric_monitor = tokio_metrics::TaskMonitor::new();
+    let mut handel_metrics = None;
     if let Some(validator) = client.take_validator() {
         info!("Initializing validator {}", validator.validator_address());
 
         if metrics_enabled {
             let mp_metrics_monitor = validator.get_mempool_monitor();
+            handel_metrics = Some(validator.get_handel_metrics());
             let inst_validator = val_metric_monitor.instrument(validator);
             spawn(inst_validator);
             nimiq_task_metric.push(NimiqTaskMonitor {
@@ -144,6 +178,7 @@ async fn main_inner() -> Result<(), Error> {
             metrics_config.addr,
             client.blockchain(),
             mempool,
+            handel_metrics,
             client.consensus_proxy(),
             client.network(),
             &nimiq_task_metric,
@@ -160,11 +195,15 @@ async fn main_inner() -> Result<(), Error> {
         show_statistics = false;
     }
 
+    let mut database_capacity_monitor = DatabaseCapacityMonitor::new();
+
     // Run periodically
     let mut interval = tokio::time::interval(Duration::from_secs(statistics_interval));
     loop {
         interval.tick().await;
 
+        database_capacity_monitor.check(&client.blockchain());
+
         if show_statistics {
             match client.network().network_info().await {
                 Ok(network_info) => {
@@ -37,6 +37,7 @@ use crate::{
         RequestBlocksProof, RequestSubscribeToAddress, RequestTransactionReceiptsByAddress,
         RequestTransactionsProof, ResponseBlocksProof,
     },
+    sync::peer_stats::{PeerStats, PeerStatsStore},
     ConsensusEvent,
 };
 
@@ -47,6 +48,7 @@ pub struct ConsensusProxy<N: Network> {
     pub(crate) synced_validity_window_flag: Arc<AtomicBool>,
     pub(crate) events: BroadcastSender<ConsensusEvent>,
     pub(crate) request: MpscSender<ConsensusRequest<N>>,
+    pub(crate) sync_peer_stats: PeerStatsStore<N::PeerId>,
 }
 
 impl<N: Network> Clone for ConsensusProxy<N> {
@@ -58,6 +60,7 @@ impl<N: Network> Clone for ConsensusProxy<N> {
             synced_validity_window_flag: Arc::clone(&self.synced_validity_window_flag),
             events: self.events.clone(),
             request: self.request.clone(),
+            sync_peer_stats: self.sync_peer_stats.clone(),
         }
     }
 }
@@ -88,6 +91,11 @@ impl<N: Network> ConsensusProxy<N> {
         BroadcastStream::new(self.events.subscribe())
     }
 
+    /// Returns the request statistics of all peers that we have requested sync data from.
+    pub fn sync_peer_stats(&self) -> Vec<(N::PeerId, PeerStats)> {
+        self.sync_peer_stats.all()
+    }
+
     /// Subscribe to remote address notification events
     pub async fn subscribe_address_notifications(
         &self,
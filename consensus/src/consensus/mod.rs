@@ -318,6 +318,7 @@ impl<N: Network> Consensus<N> {
             synced_validity_window_flag: Arc::clone(&self.synced_validity_window_flag),
             events: self.events.clone(),
             request: self.requests.0.clone(),
+            sync_peer_stats: self.sync.peer_stats(),
         }
     }
 
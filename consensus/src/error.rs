@@ -6,6 +6,8 @@ use thiserror::Error;
 pub enum Error {
     #[error("Blockchain error: {0}")]
     BlockchainError(#[from] BlockchainError),
+    #[error("Trusted checkpoints can only be used with a full blockchain")]
+    TrustedCheckpointRequiresFullBlockchain,
 }
 
 #[derive(Debug, Error)]
@@ -16,6 +16,7 @@ use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::{network::Network, request::RequestError};
 use nimiq_primitives::{networks::NetworkId, policy::Policy, slots_allocation::Validators};
+use nimiq_serde::Serialize;
 use nimiq_transaction::historic_transaction::HistoricTransaction;
 use parking_lot::RwLock;
 use thiserror::Error;
@@ -254,7 +255,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
                 true
             },
             batch_verify_state,
-        );
+        )
+        .with_response_size(|batch_set_info| batch_set_info.serialized_size());
 
         let history_queue = SyncQueue::new(
             Arc::clone(&network),
@@ -269,7 +271,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
                 }
                 .boxed()
             },
-        );
+        )
+        .with_response_size(|(_, chunk)| chunk.serialized_size());
         Self {
             id,
             epoch_ids,
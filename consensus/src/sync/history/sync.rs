@@ -15,6 +15,7 @@ use crate::{
     messages::Checkpoint,
     sync::{
         history::cluster::{SyncCluster, SyncClusterResult},
+        peer_stats::PeerStatsStore,
         syncer::MacroSync,
     },
 };
@@ -56,6 +57,7 @@ pub struct HistoryMacroSync<TNetwork: Network> {
     pub(crate) checkpoint_clusters: VecDeque<SyncCluster<TNetwork>>,
     pub(crate) active_cluster: Option<SyncCluster<TNetwork>>,
     pub(crate) job_queue: VecDeque<Job<TNetwork>>,
+    pub(crate) peer_stats: PeerStatsStore<TNetwork::PeerId>,
     pub(crate) waker: Option<Waker>,
 }
 
@@ -67,6 +69,7 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TNetwork>,
         network_event_rx: SubscribeEvents<TNetwork::PeerId>,
+        peer_stats: PeerStatsStore<TNetwork::PeerId>,
     ) -> Self {
         Self {
             blockchain,
@@ -78,11 +81,19 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
             checkpoint_clusters: VecDeque::new(),
             active_cluster: None,
             job_queue: VecDeque::new(),
+            peer_stats,
             waker: None,
         }
     }
 
+    /// Returns the request statistics of the peers we sync from.
+    pub fn peer_stats(&self) -> &PeerStatsStore<TNetwork::PeerId> {
+        &self.peer_stats
+    }
+
     pub fn remove_peer(&mut self, peer_id: TNetwork::PeerId) {
+        // The peer might not be part of any cluster, but have statistics of its epoch requests.
+        self.peer_stats.remove_peer(&peer_id);
         for cluster in self.epoch_clusters.iter_mut() {
             cluster.remove_peer(&peer_id);
         }
@@ -114,6 +125,7 @@ impl<TNetwork: Network> MacroSync<TNetwork::PeerId> for HistoryMacroSync<TNetwor
         let future = Self::request_epoch_ids(
             Arc::clone(&self.blockchain),
             Arc::clone(&self.network),
+            self.peer_stats.clone(),
             peer_id,
         )
         .boxed();
@@ -8,6 +8,7 @@ use nimiq_network_interface::{
     request::RequestError,
 };
 use nimiq_primitives::policy::Policy;
+use nimiq_serde::Serialize;
 use nimiq_utils::WakerExt as _;
 use parking_lot::RwLock;
 
@@ -20,6 +21,7 @@ use crate::{
             HistoryMacroSync,
         },
         peer_list::PeerList,
+        peer_stats::PeerStatsStore,
         syncer::MacroSync,
     },
 };
@@ -28,6 +30,7 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
     pub(crate) async fn request_epoch_ids(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TNetwork>,
+        peer_stats: PeerStatsStore<TNetwork::PeerId>,
         peer_id: TNetwork::PeerId,
     ) -> Option<EpochIds<TNetwork::PeerId>> {
         let (locators, epoch_number) = {
@@ -49,13 +52,15 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
             (locators, election_head.epoch_number())
         };
 
-        let result = Self::request_macro_chain(
+        let request = Self::request_macro_chain(
             Arc::clone(&network),
             peer_id,
             locators,
             Self::MAX_REQUEST_EPOCHS,
-        )
-        .await;
+        );
+        let result = peer_stats
+            .track(peer_id, request, |response| response.serialized_size())
+            .await;
 
         match result {
             Ok(Err(error)) => {
@@ -363,7 +368,7 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
 
         // Add remaining ids to a new cluster with only the sending peer in it.
         if id_index < epoch_ids.ids.len() {
-            let mut peers = PeerList::default();
+            let mut peers = PeerList::with_stats(self.peer_stats.clone());
             peers.add_peer(sender_peer_id);
             new_clusters.push_back(SyncCluster::for_epoch(
                 Arc::clone(&self.blockchain),
@@ -406,7 +411,7 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
 
             // If there was no suitable cluster, add a new one.
             if !found_cluster {
-                let mut peers = PeerList::default();
+                let mut peers = PeerList::with_stats(self.peer_stats.clone());
                 peers.add_peer(sender_peer_id);
                 let cluster = SyncCluster::for_checkpoint(
                     Arc::clone(&self.blockchain),
@@ -361,6 +361,7 @@ mod tests {
             Arc::clone(&chain),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            Default::default(),
         );
 
         spawn_request_handlers(&net2, &chain);
@@ -400,6 +401,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            Default::default(),
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -440,6 +442,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            Default::default(),
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -473,6 +476,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            Default::default(),
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -507,6 +511,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            Default::default(),
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -548,6 +553,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            Default::default(),
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -599,6 +605,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            Default::default(),
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -662,6 +669,7 @@ mod tests {
                 Arc::clone(&chain_sync),
                 Arc::clone(&net_sync),
                 net_sync.subscribe_events(),
+                Default::default(),
             );
 
             spawn_request_handlers(&net_sync, &chain_sync);
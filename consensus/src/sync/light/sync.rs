@@ -13,6 +13,8 @@ use nimiq_network_interface::{
     network::{CloseReason, Network, SubscribeEvents},
     request::RequestError,
 };
+#[cfg(feature = "full")]
+use nimiq_serde::Serialize;
 use nimiq_utils::{spawn, stream::FuturesUnordered};
 use nimiq_zkp_component::{
     types::{Error, ZKPRequestEvent},
@@ -23,7 +25,7 @@ use parking_lot::RwLock;
 
 use crate::{
     messages::{BlockError, Checkpoint},
-    sync::syncer::MacroSync,
+    sync::{peer_stats::PeerStatsStore, syncer::MacroSync},
 };
 #[cfg(feature = "full")]
 use crate::{
@@ -169,6 +171,8 @@ pub struct LightMacroSync<TNetwork: Network> {
             ),
         >,
     >,
+    /// Request statistics of the peers we sync from
+    pub(crate) peer_stats: PeerStatsStore<TNetwork::PeerId>,
 
     #[cfg(feature = "full")]
     /// The validity (history chunks) queue
@@ -194,6 +198,20 @@ pub struct LightMacroSync<TNetwork: Network> {
     /// A vec of all the peers that we successfully synced with
     pub(crate) synced_validity_peers: Vec<TNetwork::PeerId>,
     #[cfg(feature = "full")]
+    /// The hash of the trusted election block to bootstrap from, until it has been applied
+    pub(crate) trusted_checkpoint: Option<Blake2bHash>,
+    #[cfg(feature = "full")]
+    /// Requests for the trusted checkpoint block
+    pub(crate) trusted_checkpoint_requests: FuturesUnordered<
+        BoxFuture<
+            'static,
+            (
+                Result<Result<Block, BlockError>, RequestError>,
+                TNetwork::PeerId,
+            ),
+        >,
+    >,
+    #[cfg(feature = "full")]
     /// Minimum distance to light sync in #blocks from the peers head.
     pub(crate) full_sync_threshold: u32,
 }
@@ -205,9 +223,10 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
         network_event_rx: SubscribeEvents<TNetwork::PeerId>,
         zkp_component_proxy: ZKPComponentProxy<TNetwork>,
         full_sync_threshold: u32,
+        peer_stats: PeerStatsStore<TNetwork::PeerId>,
     ) -> Self {
         #[cfg(feature = "full")]
-        let peers = Arc::new(RwLock::new(PeerList::default()));
+        let peers = Arc::new(RwLock::new(PeerList::with_stats(peer_stats.clone())));
 
         #[cfg(feature = "full")]
         let validity_queue = SyncQueue::new(
@@ -229,7 +248,8 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
                 }
                 .boxed()
             },
-        );
+        )
+        .with_response_size(|(_, chunk, _)| chunk.serialized_size());
 
         Self {
             blockchain,
@@ -242,6 +262,7 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
             #[cfg(feature = "full")]
             full_sync_threshold,
             block_headers: Default::default(),
+            peer_stats,
             #[cfg(feature = "full")]
             validity_requests: None,
             #[cfg(feature = "full")]
@@ -250,7 +271,31 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
             validity_queue,
             #[cfg(feature = "full")]
             synced_validity_peers: Vec::new(),
+            #[cfg(feature = "full")]
+            trusted_checkpoint: None,
+            #[cfg(feature = "full")]
+            trusted_checkpoint_requests: FuturesUnordered::new(),
+        }
+    }
+
+    #[cfg(feature = "full")]
+    /// Bootstraps from the given trusted election block instead of the ZKP. The block is
+    /// requested from the first peers and applied if its hash matches, the state is then
+    /// synced on top of it.
+    pub fn with_trusted_checkpoint(
+        mut self,
+        block_hash: Blake2bHash,
+    ) -> Result<Self, crate::Error> {
+        if !matches!(self.blockchain, BlockchainProxy::Full(_)) {
+            return Err(crate::Error::TrustedCheckpointRequiresFullBlockchain);
         }
+        self.trusted_checkpoint = Some(block_hash);
+        Ok(self)
+    }
+
+    /// Returns the request statistics of the peers we sync from.
+    pub fn peer_stats(&self) -> &PeerStatsStore<TNetwork::PeerId> {
+        &self.peer_stats
     }
 
     pub fn remove_peer_requests(&mut self, peer_id: TNetwork::PeerId) {
@@ -274,6 +319,18 @@ impl<TNetwork: Network> MacroSync<TNetwork::PeerId> for LightMacroSync<TNetwork>
     const MAX_REQUEST_EPOCHS: u16 = 1000; // TODO: Use other value
 
     fn add_peer(&mut self, peer_id: TNetwork::PeerId) {
+        // As long as the trusted checkpoint hasn't been applied, we request it instead of the zkp.
+        #[cfg(feature = "full")]
+        if let Some(block_hash) = &self.trusted_checkpoint {
+            info!(%peer_id, %block_hash, "Requesting trusted checkpoint from peer");
+
+            let request =
+                Self::request_macro_block(Arc::clone(&self.network), peer_id, block_hash.clone());
+            self.trusted_checkpoint_requests
+                .push(async move { (request.await, peer_id) }.boxed());
+            return;
+        }
+
         info!(%peer_id, "Requesting zkp from peer");
 
         self.zkp_requests
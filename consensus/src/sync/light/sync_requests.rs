@@ -12,6 +12,7 @@ use nimiq_network_interface::{
     },
 };
 use nimiq_primitives::policy::Policy;
+use nimiq_serde::Serialize;
 use nimiq_zkp_component::{
     types::{Error, ZKPRequestEvent},
     zkp_component::ZKPComponentProxy,
@@ -160,19 +161,9 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
         block_hash: Blake2bHash,
     ) {
         let mut peer_requests = PeerMacroRequests::new();
-        let network = Arc::clone(&self.network);
 
         peer_requests.push_request(block_hash.clone());
-
-        self.block_headers.push(
-            async move {
-                (
-                    Self::request_macro_block(network, peer_id, block_hash).await,
-                    peer_id,
-                )
-            }
-            .boxed(),
-        );
+        self.push_macro_block_request(peer_id, block_hash);
 
         self.peer_requests.insert(peer_id, peer_requests);
     }
@@ -244,42 +235,20 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
 
         // Request the election blocks
         for block_hash in epoch_ids.ids {
-            let network = Arc::clone(&self.network);
-            let peer_id = epoch_ids.sender;
-
             log::trace!(
                 %block_hash,
                 "Pushing a new block request",
             );
 
             peer_requests.push_request(block_hash.clone());
-
-            self.block_headers.push(
-                async move {
-                    (
-                        Self::request_macro_block(network, peer_id, block_hash).await,
-                        peer_id,
-                    )
-                }
-                .boxed(),
-            );
+            self.push_macro_block_request(epoch_ids.sender, block_hash);
         }
 
         // Request the checkpoint (if any)
         if let Some(checkpoint) = &epoch_ids.checkpoint {
             let block_hash = checkpoint.clone().hash;
-            let network = Arc::clone(&self.network);
-            let peer_id = epoch_ids.sender;
             peer_requests.push_request(block_hash.clone());
-            self.block_headers.push(
-                async move {
-                    (
-                        Self::request_macro_block(network, peer_id, block_hash).await,
-                        peer_id,
-                    )
-                }
-                .boxed(),
-            );
+            self.push_macro_block_request(epoch_ids.sender, block_hash);
         }
 
         self.peer_requests.insert(epoch_ids.sender, peer_requests);
@@ -287,6 +256,25 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
         None
     }
 
+    /// Requests the given macro block from the peer and records the request in the peer
+    /// statistics.
+    fn push_macro_block_request(&mut self, peer_id: TNetwork::PeerId, block_hash: Blake2bHash) {
+        let request = Self::request_macro_block(Arc::clone(&self.network), peer_id, block_hash);
+        let peer_stats = self.peer_stats.clone();
+
+        self.block_headers.push(
+            async move {
+                (
+                    peer_stats
+                        .track(peer_id, request, |response| response.serialized_size())
+                        .await,
+                    peer_id,
+                )
+            }
+            .boxed(),
+        );
+    }
+
     pub async fn request_macro_block(
         network: Arc<TNetwork>,
         peer_id: TNetwork::PeerId,
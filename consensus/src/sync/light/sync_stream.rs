@@ -34,6 +34,7 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
                 Ok(NetworkEvent::PeerLeft(peer_id)) => {
                     // Remove the peer from internal data structures.
                     self.remove_peer_requests(peer_id);
+                    self.peer_stats.remove_peer(&peer_id);
                 }
                 Ok(NetworkEvent::PeerJoined(peer_id, _)) => {
                     // Query if that peer provides the necessary services for syncing
@@ -164,6 +165,63 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
         Poll::Pending
     }
 
+    #[cfg(feature = "full")]
+    // Polls the requests for the trusted checkpoint. The first valid checkpoint block is applied
+    // to the blockchain, afterwards we continue with requesting epoch ids from the peer.
+    fn poll_trusted_checkpoint(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<MacroSyncReturn<TNetwork::PeerId>>> {
+        while let Poll::Ready(Some(result)) = self.trusted_checkpoint_requests.poll_next_unpin(cx) {
+            match result {
+                (Ok(Ok(block)), peer_id) => {
+                    // Another peer might have provided the checkpoint in the meantime.
+                    if let Some(block_hash) = self.trusted_checkpoint.clone() {
+                        let BlockchainProxy::Full(ref full_blockchain) = self.blockchain else {
+                            unreachable!("Trusted checkpoints require a full blockchain");
+                        };
+
+                        match Blockchain::push_trusted_checkpoint(
+                            full_blockchain.upgradable_read(),
+                            block,
+                            &block_hash,
+                        ) {
+                            Ok(result) => {
+                                log::info!(%block_hash, ?result, "Applied trusted checkpoint to the blockchain");
+                                self.trusted_checkpoint = None;
+                            }
+                            Err(error) => {
+                                log::warn!(?error, %peer_id, "Banning peer because failed applying trusted checkpoint");
+                                self.disconnect_peer(peer_id, CloseReason::MaliciousPeer);
+                                return Poll::Ready(None);
+                            }
+                        }
+                    }
+
+                    let future = Self::request_epoch_ids(
+                        self.blockchain.clone(),
+                        Arc::clone(&self.network),
+                        peer_id,
+                    )
+                    .boxed();
+                    self.epoch_ids_stream.push(future);
+                }
+                (Ok(Err(error)), peer_id) => {
+                    // The peer doesn't know the checkpoint, so we consider it outdated.
+                    log::debug!(%error, %peer_id, "Peer doesn't have the trusted checkpoint");
+                    return Poll::Ready(Some(MacroSyncReturn::Outdated(peer_id)));
+                }
+                (Err(error), peer_id) => {
+                    log::debug!(?error, %peer_id, "Error requesting trusted checkpoint from peer");
+                    self.disconnect_peer(peer_id, CloseReason::Error);
+                    return Poll::Ready(None);
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+
     fn poll_epoch_ids(
         &mut self,
         cx: &mut Context<'_>,
@@ -423,6 +481,11 @@ impl<TNetwork: Network> Stream for LightMacroSync<TNetwork> {
             return Poll::Ready(o);
         }
 
+        #[cfg(feature = "full")]
+        if let Poll::Ready(o) = self.poll_trusted_checkpoint(cx) {
+            return Poll::Ready(o);
+        }
+
         if let Poll::Ready(o) = self.poll_epoch_ids(cx) {
             return Poll::Ready(o);
         }
@@ -539,6 +602,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                Default::default(),
             );
 
             spawn_request_handlers(&net2, &chain2.clone());
@@ -582,6 +646,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                Default::default(),
             );
 
             let zkp_component2 =
@@ -646,6 +711,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                Default::default(),
             );
 
             let zkp_component2 =
@@ -709,6 +775,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                Default::default(),
             );
 
             let zkp_component2 =
@@ -765,6 +832,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                Default::default(),
             );
 
             let zkp_component2 =
@@ -865,6 +933,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                Default::default(),
             );
 
             let zkp_component2 =
@@ -15,6 +15,7 @@ use nimiq_network_interface::{
     request::RequestError,
 };
 use nimiq_primitives::{policy::Policy, slots_allocation::Validators};
+use nimiq_serde::Serialize;
 use nimiq_utils::spawn;
 use parking_lot::RwLock;
 use thiserror::Error;
@@ -262,7 +263,10 @@ impl<N: Network> BlockRequestComponent<N> {
                     true
                 },
                 (),
-            ),
+            )
+            .with_response_size(|response| {
+                response.blocks.iter().map(Block::serialized_size).sum()
+            }),
             peers,
             include_body,
             pending_requests: BTreeSet::new(),
@@ -13,13 +13,9 @@ use nimiq_bls::cache::PublicKeyCache;
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::network::Network;
 use nimiq_utils::spawn;
-use parking_lot::Mutex;
-#[cfg(feature = "full")]
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
-#[cfg(feature = "full")]
-use crate::sync::peer_list::PeerList;
 use crate::{
     consensus::ResolveBlockRequest,
     sync::{
@@ -30,6 +26,7 @@ use crate::{
             },
             queue::{LiveSyncQueue, QueueConfig},
         },
+        peer_list::PeerList,
         syncer::LiveSyncEvent,
     },
 };
@@ -96,7 +93,6 @@ impl<N: Network> BlockQueueProxy<N> {
         self.queue.lock().num_buffered_blocks()
     }
 
-    #[cfg(feature = "full")]
     pub(crate) fn peer_list(&self) -> Arc<RwLock<PeerList<N>>> {
         self.queue.lock().peer_list()
     }
@@ -3,6 +3,7 @@ use std::{sync::Arc, time::Duration};
 use futures::future::BoxFuture;
 use nimiq_network_interface::network::Network;
 use nimiq_primitives::{trie::trie_diff::TrieDiff, TreeProof};
+use nimiq_serde::Serialize;
 use nimiq_time::sleep;
 use parking_lot::RwLock;
 use tokio::sync::Semaphore;
@@ -78,13 +79,15 @@ impl<N: Network> DiffRequestComponent<N> {
                     };
                     current_peer_index.increment();
 
-                    let result = network
-                        .request(
-                            RequestTrieDiff {
-                                block_hash: block_hash.clone(),
-                            },
-                            peer_id,
-                        )
+                    let request = network.request(
+                        RequestTrieDiff {
+                            block_hash: block_hash.clone(),
+                        },
+                        peer_id,
+                    );
+                    let peer_stats = peers.read().stats().clone();
+                    let result = peer_stats
+                        .track(peer_id, request, |response| response.serialized_size())
                         .await;
 
                     num_tries += 1;
@@ -7,6 +7,7 @@ use std::{
 use futures::{future::BoxFuture, FutureExt, Stream, StreamExt};
 use nimiq_network_interface::{network::Network, request::RequestError};
 use nimiq_primitives::key_nibbles::KeyNibbles;
+use nimiq_serde::Serialize;
 use parking_lot::RwLock;
 
 use super::{RequestChunk, ResponseChunk};
@@ -65,7 +66,8 @@ impl<N: Network> ChunkRequestComponent<N> {
                 true
             },
             (),
-        );
+        )
+        .with_response_size(|(response, _, _)| response.serialized_size());
 
         ChunkRequestComponent { sync_queue, peers }
     }
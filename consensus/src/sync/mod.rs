@@ -3,6 +3,7 @@ pub mod history;
 pub mod light;
 pub mod live;
 pub mod peer_list;
+pub mod peer_stats;
 mod sync_queue;
 pub mod syncer;
 pub mod syncer_proxy;
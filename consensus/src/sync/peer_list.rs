@@ -4,6 +4,8 @@ use futures::future::BoxFuture;
 use nimiq_network_interface::network::Network;
 use tokio::sync::Notify;
 
+use super::peer_stats::PeerStatsStore;
+
 /// A list of peers to be used while syncing.
 /// This contains an ordered list of peers as well as a hashmap.
 /// This data structure ensures both are maintained consistently.
@@ -13,6 +15,8 @@ pub struct PeerList<N: Network> {
     peers: Vec<N::PeerId>,
     /// Used to notify listeners when the peer list becomes nonempty.
     notify_nonempty: Arc<Notify>,
+    /// Request statistics of the peers, shared with other peer lists.
+    stats: PeerStatsStore<N::PeerId>,
 }
 
 /// Stores an index into a [`PeerList`].
@@ -22,12 +26,14 @@ pub struct PeerList<N: Network> {
 #[derive(Clone)]
 pub struct PeerListIndex {
     index: usize,
+    /// The number of peers selected via [`PeerList::select_peer`] with this index.
+    num_selections: usize,
 }
 
 impl Default for PeerListIndex {
     fn default() -> PeerListIndex {
         // This works because `usize::MAX.wrapping_add(1)` is `0`.
-        PeerListIndex { index: usize::MAX }
+        PeerListIndex::new(usize::MAX)
     }
 }
 
@@ -49,7 +55,10 @@ impl fmt::Display for PeerListIndex {
 
 impl PeerListIndex {
     pub fn new(index: usize) -> Self {
-        PeerListIndex { index }
+        PeerListIndex {
+            index,
+            num_selections: 0,
+        }
     }
 
     pub fn increment(&mut self) {
@@ -63,6 +72,7 @@ impl<N: Network> Default for PeerList<N> {
             peers_set: Default::default(),
             peers: Default::default(),
             notify_nonempty: Default::default(),
+            stats: Default::default(),
         }
     }
 }
@@ -73,11 +83,28 @@ impl<N: Network> Clone for PeerList<N> {
             peers_set: self.peers_set.clone(),
             peers: self.peers.clone(),
             notify_nonempty: Default::default(),
+            stats: self.stats.clone(),
         }
     }
 }
 
 impl<N: Network> PeerList<N> {
+    /// Every n-th call to [`PeerList::select_peer`] picks the next peer round-robin instead of
+    /// the best one, such that the statistics of the other peers are kept up to date.
+    const PROBE_INTERVAL: usize = 4;
+
+    /// Creates an empty peer list that records request statistics into the given store.
+    pub fn with_stats(stats: PeerStatsStore<N::PeerId>) -> Self {
+        Self {
+            stats,
+            ..Default::default()
+        }
+    }
+
+    pub fn stats(&self) -> &PeerStatsStore<N::PeerId> {
+        &self.stats
+    }
+
     pub fn add_peer(&mut self, peer_id: N::PeerId) -> bool {
         if self.peers_set.insert(peer_id) {
             if self.peers.is_empty() {
@@ -109,9 +136,12 @@ impl<N: Network> PeerList<N> {
         &self.peers_set
     }
 
+    /// Removes a peer from the list. Peers are removed once they left or misbehaved, so their
+    /// request statistics are dropped as well.
     pub fn remove_peer(&mut self, peer_id: &N::PeerId) -> bool {
         if self.peers_set.remove(peer_id) {
             self.peers.retain(|element| element != peer_id);
+            self.stats.remove_peer(peer_id);
             return true;
         }
         false
@@ -140,6 +170,32 @@ impl<N: Network> PeerList<N> {
         Some(self.peers[peer_index.index])
     }
 
+    /// Selects the peer to send the next request to and updates `peer_index` to point to it.
+    ///
+    /// The peer with the best [selection score](PeerStatsStore::selection_score) is chosen,
+    /// except for every [`Self::PROBE_INTERVAL`]-th selection, which falls back to round-robin.
+    pub fn select_peer(&self, peer_index: &mut PeerListIndex) -> Option<N::PeerId> {
+        if self.peers.is_empty() {
+            return None;
+        }
+
+        peer_index.num_selections = peer_index.num_selections.wrapping_add(1);
+        if peer_index.num_selections % Self::PROBE_INTERVAL == 0 {
+            return self.increment_and_get(peer_index);
+        }
+
+        let best = self
+            .peers
+            .iter()
+            .map(|peer_id| self.stats.selection_score(peer_id))
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)?;
+
+        peer_index.index = best;
+        Some(self.peers[best])
+    }
+
     /// Returns a future that resolves when the list becomes nonempty.
     ///
     /// Returns `None` is the list has peers already.
@@ -158,3 +214,44 @@ impl<N: Network, I: SliceIndex<[N::PeerId]>> Index<I> for PeerList<N> {
         self.peers.index(index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nimiq_network_mock::{MockNetwork, MockPeerId};
+
+    use super::{PeerList, PeerListIndex};
+    use crate::sync::peer_stats::PeerStatsStore;
+
+    #[test]
+    fn it_selects_peers_with_lower_latency_and_fewer_failures() {
+        let stats = PeerStatsStore::default();
+        let mut peers = PeerList::<MockNetwork>::with_stats(stats.clone());
+        let (slow, fast, unreliable) = (MockPeerId(1), MockPeerId(2), MockPeerId(3));
+        peers.add_peer(slow);
+        peers.add_peer(fast);
+        peers.add_peer(unreliable);
+
+        // Peers that were not measured yet are probed first.
+        stats.record(slow, Duration::from_millis(500), Some(1024));
+        stats.record(fast, Duration::from_millis(50), Some(1024));
+        let mut index = PeerListIndex::default();
+        assert_eq!(peers.select_peer(&mut index), Some(unreliable));
+
+        // The peer with the lower latency is preferred.
+        stats.record(unreliable, Duration::from_millis(50), None);
+        let mut index = PeerListIndex::default();
+        assert_eq!(peers.select_peer(&mut index), Some(fast));
+        assert_eq!(peers.get(&index), Some(fast));
+
+        // Among peers with the same latency, the one with fewer failures is preferred.
+        stats.record(unreliable, Duration::from_millis(50), Some(1024));
+        peers.remove_peer(&slow);
+        assert_eq!(peers.select_peer(&mut index), Some(fast));
+
+        // Removed peers lose their statistics.
+        assert!(stats.get(&slow).is_none());
+        assert!(stats.get(&fast).is_some());
+    }
+}
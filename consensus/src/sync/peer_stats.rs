@@ -0,0 +1,272 @@
+use std::{collections::HashMap, future::Future, hash::Hash, sync::Arc, time::Duration};
+
+use instant::Instant;
+use parking_lot::RwLock;
+
+/// Request statistics that are collected for a single peer while syncing.
+///
+/// Latency, throughput and error rate are exponentially weighted moving averages, such that
+/// recent measurements dominate and a peer that slows down is detected quickly.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PeerStats {
+    /// The number of completed requests (successful or not).
+    pub num_requests: u64,
+    /// The number of failed requests.
+    pub num_failures: u64,
+    /// The number of requests currently in flight.
+    pub num_pending: u64,
+    /// The average time it took the peer to respond.
+    pub avg_latency: Option<Duration>,
+    /// The average throughput of successful responses in bytes per second.
+    pub avg_throughput: f64,
+    /// The fraction of recent requests that failed, in `[0, 1]`.
+    pub error_rate: f64,
+}
+
+impl PeerStats {
+    /// The weight of a new measurement in the moving averages.
+    const SMOOTHING_FACTOR: f64 = 0.2;
+
+    /// Returns the score of this peer, higher is better. Returns `None` if we don't have any
+    /// measurements for this peer yet.
+    ///
+    /// The score is the throughput (or the inverse latency if no response sizes were measured)
+    /// scaled down by the error rate and the number of requests that are currently in flight.
+    /// The latter spreads concurrent requests across the fast peers instead of piling them all
+    /// onto the single best one.
+    pub fn score(&self) -> Option<f64> {
+        let avg_latency = self.avg_latency?;
+        let speed = if self.avg_throughput > 0.0 {
+            self.avg_throughput
+        } else {
+            1.0 / avg_latency.as_secs_f64().max(0.001)
+        };
+        Some(speed * (1.0 - self.error_rate) / (1 + self.num_pending) as f64)
+    }
+
+    fn record(&mut self, latency: Duration, num_bytes: Option<usize>) {
+        self.num_requests += 1;
+
+        let failed = num_bytes.is_none();
+        if failed {
+            self.num_failures += 1;
+        }
+
+        if self.num_requests == 1 {
+            self.avg_latency = Some(latency);
+            self.error_rate = if failed { 1.0 } else { 0.0 };
+        } else {
+            let avg_latency = self.avg_latency.unwrap_or(latency).as_secs_f64();
+            self.avg_latency = Some(Duration::from_secs_f64(Self::ewma(
+                avg_latency,
+                latency.as_secs_f64(),
+            )));
+            self.error_rate = Self::ewma(self.error_rate, if failed { 1.0 } else { 0.0 });
+        }
+
+        if let Some(num_bytes) = num_bytes.filter(|&num_bytes| num_bytes > 0) {
+            let throughput = num_bytes as f64 / latency.as_secs_f64().max(0.001);
+            self.avg_throughput = if self.avg_throughput > 0.0 {
+                Self::ewma(self.avg_throughput, throughput)
+            } else {
+                throughput
+            };
+        }
+    }
+
+    fn ewma(average: f64, value: f64) -> f64 {
+        average + Self::SMOOTHING_FACTOR * (value - average)
+    }
+}
+
+/// A shared collection of [`PeerStats`] that is used by all syncers of a node.
+///
+/// Cloning the store yields a handle to the same underlying statistics.
+#[derive(Debug)]
+pub struct PeerStatsStore<TPeerId> {
+    stats: Arc<RwLock<HashMap<TPeerId, PeerStats>>>,
+}
+
+impl<TPeerId> Default for PeerStatsStore<TPeerId> {
+    fn default() -> Self {
+        Self {
+            stats: Default::default(),
+        }
+    }
+}
+
+impl<TPeerId> Clone for PeerStatsStore<TPeerId> {
+    fn clone(&self) -> Self {
+        Self {
+            stats: Arc::clone(&self.stats),
+        }
+    }
+}
+
+impl<TPeerId: Clone + Eq + Hash> PeerStatsStore<TPeerId> {
+    /// Returns the statistics of the given peer, if any were recorded.
+    pub fn get(&self, peer_id: &TPeerId) -> Option<PeerStats> {
+        self.stats.read().get(peer_id).cloned()
+    }
+
+    /// Returns the score used to rank the given peer when selecting a peer for a request.
+    ///
+    /// Peers that were not measured yet rank above all measured peers, so that they get probed.
+    /// Among those, peers with fewer requests in flight rank higher.
+    pub fn selection_score(&self, peer_id: &TPeerId) -> f64 {
+        match self.stats.read().get(peer_id) {
+            Some(stats) => stats
+                .score()
+                .unwrap_or(f64::MAX / (1 + stats.num_pending) as f64),
+            None => f64::MAX,
+        }
+    }
+
+    /// Returns the statistics of all peers.
+    pub fn all(&self) -> Vec<(TPeerId, PeerStats)> {
+        self.stats
+            .read()
+            .iter()
+            .map(|(peer_id, stats)| (peer_id.clone(), stats.clone()))
+            .collect()
+    }
+
+    /// Drops the statistics of a peer, e.g. after it disconnected.
+    pub fn remove_peer(&self, peer_id: &TPeerId) {
+        self.stats.write().remove(peer_id);
+    }
+
+    /// Records a finished request to the given peer.
+    #[cfg(test)]
+    pub(crate) fn record(&self, peer_id: TPeerId, latency: Duration, num_bytes: Option<usize>) {
+        self.stats
+            .write()
+            .entry(peer_id)
+            .or_default()
+            .record(latency, num_bytes);
+    }
+
+    /// Starts tracking a request to the given peer. The request is recorded once the returned
+    /// [`PendingRequest`] is finished. If it is dropped without being finished, the request is
+    /// discarded without affecting the statistics.
+    pub fn start_request(&self, peer_id: TPeerId) -> PendingRequest<TPeerId> {
+        self.stats
+            .write()
+            .entry(peer_id.clone())
+            .or_default()
+            .num_pending += 1;
+
+        PendingRequest {
+            store: self.clone(),
+            peer_id,
+            started_at: Instant::now(),
+            finished: false,
+        }
+    }
+
+    /// Awaits the given request future and records its latency, response size and outcome
+    /// for the given peer.
+    pub async fn track<T, E, F>(
+        self,
+        peer_id: TPeerId,
+        request: F,
+        response_size: fn(&T) -> usize,
+    ) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        let pending = self.start_request(peer_id);
+        let result = request.await;
+        match &result {
+            Ok(response) => pending.succeeded(response_size(response)),
+            Err(_) => pending.failed(),
+        }
+        result
+    }
+}
+
+/// A request that is currently in flight, see [`PeerStatsStore::start_request`].
+pub struct PendingRequest<TPeerId: Clone + Eq + Hash> {
+    store: PeerStatsStore<TPeerId>,
+    peer_id: TPeerId,
+    started_at: Instant,
+    finished: bool,
+}
+
+impl<TPeerId: Clone + Eq + Hash> PendingRequest<TPeerId> {
+    /// Records a successful response of `num_bytes` bytes.
+    pub fn succeeded(mut self, num_bytes: usize) {
+        self.finish(Some(num_bytes));
+    }
+
+    /// Records a failed request.
+    pub fn failed(mut self) {
+        self.finish(None);
+    }
+
+    fn finish(&mut self, num_bytes: Option<usize>) {
+        let latency = self.started_at.elapsed();
+        let mut stats = self.store.stats.write();
+        let stats = stats.entry(self.peer_id.clone()).or_default();
+        stats.num_pending = stats.num_pending.saturating_sub(1);
+        stats.record(latency, num_bytes);
+        self.finished = true;
+    }
+}
+
+impl<TPeerId: Clone + Eq + Hash> Drop for PendingRequest<TPeerId> {
+    fn drop(&mut self) {
+        if !self.finished {
+            if let Some(stats) = self.store.stats.write().get_mut(&self.peer_id) {
+                stats.num_pending = stats.num_pending.saturating_sub(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{PeerStats, PeerStatsStore};
+
+    #[test]
+    fn it_prefers_fast_and_reliable_peers() {
+        let mut fast = PeerStats::default();
+        fast.record(Duration::from_millis(100), Some(100_000));
+        let mut slow = PeerStats::default();
+        slow.record(Duration::from_millis(1000), Some(100_000));
+        assert!(fast.score().unwrap() > slow.score().unwrap());
+
+        let mut unreliable = fast.clone();
+        unreliable.record(Duration::from_millis(100), None);
+        assert_eq!(unreliable.num_failures, 1);
+        assert!(unreliable.score().unwrap() < fast.score().unwrap());
+
+        assert_eq!(PeerStats::default().score(), None);
+    }
+
+    #[test]
+    fn it_tracks_pending_requests() {
+        let store = PeerStatsStore::<u32>::default();
+
+        let first = store.start_request(1);
+        let second = store.start_request(1);
+        assert_eq!(store.get(&1).unwrap().num_pending, 2);
+
+        first.succeeded(1024);
+        let stats = store.get(&1).unwrap();
+        assert_eq!(stats.num_pending, 1);
+        assert_eq!(stats.num_requests, 1);
+        assert!(stats.avg_latency.is_some());
+
+        // Dropped requests are not counted.
+        drop(second);
+        let stats = store.get(&1).unwrap();
+        assert_eq!(stats.num_pending, 0);
+        assert_eq!(stats.num_requests, 1);
+
+        store.remove_peer(&1);
+        assert!(store.get(&1).is_none());
+    }
+}
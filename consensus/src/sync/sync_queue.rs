@@ -80,6 +80,7 @@ type RequestFn<TId, TNetwork, TOutput, TError> = fn(
     <TNetwork as Network>::PeerId,
 ) -> BoxFuture<'static, Result<TOutput, TError>>;
 type VerifyFn<TId, TOutput, TVerifyState> = fn(&TId, &mut TOutput, &mut TVerifyState) -> bool;
+type ResponseSizeFn<TOutput> = fn(&TOutput) -> usize;
 
 /// The SyncQueue will request a list of ids from a set of peers
 /// and implements an ordered stream over the resulting objects.
@@ -104,6 +105,7 @@ pub struct SyncQueue<
     request_fn: RequestFn<TId, TNetwork, TOutput, TError>,
     verify_fn: VerifyFn<TId, TOutput, TVerifyState>,
     verify_state: TVerifyState,
+    response_size_fn: ResponseSizeFn<TOutput>,
     waker: Option<Waker>,
 }
 
@@ -167,10 +169,33 @@ where
             request_fn,
             verify_fn,
             verify_state: initial_verify_state,
+            response_size_fn: |_| 0,
             waker: None,
         }
     }
 
+    /// Sets the function used to determine the size of a response in bytes. The sizes are used
+    /// to measure the throughput of the peers, which influences which peers are requested.
+    pub fn with_response_size(mut self, response_size_fn: ResponseSizeFn<TOutput>) -> Self {
+        self.response_size_fn = response_size_fn;
+        self
+    }
+
+    fn request_from_peer(
+        &self,
+        id: TId,
+        peer_id: TNetwork::PeerId,
+    ) -> BoxFuture<'static, Option<Result<TOutput, TError>>> {
+        let request = (self.request_fn)(id, Arc::clone(&self.network), peer_id);
+        self.peers
+            .read()
+            .stats()
+            .clone()
+            .track(peer_id, request, self.response_size_fn)
+            .map(Some)
+            .boxed()
+    }
+
     fn try_push_futures(&mut self) {
         // Determine number of new futures required to maintain desired_pending_size.
         let num_ids_to_request = cmp::min(
@@ -200,7 +225,7 @@ where
                 None => self
                     .peers
                     .read()
-                    .increment_and_get(&mut self.current_peer_index)
+                    .select_peer(&mut self.current_peer_index)
                     .map(|peer_id| (peer_id, self.current_peer_index.clone())),
             };
 
@@ -215,9 +240,7 @@ where
                     );
 
                     OrderWrapper {
-                        data: (self.request_fn)(id.clone(), Arc::clone(&self.network), peer_id)
-                            .map(Some)
-                            .boxed(),
+                        data: self.request_from_peer(id.clone(), peer_id),
                         id,
                         index: self.next_incoming_index,
                         peer: peer_index,
@@ -289,9 +312,7 @@ where
         );
 
         let wrapper = OrderWrapper {
-            data: (self.request_fn)(id.clone(), Arc::clone(&self.network), peer)
-                .map(Some)
-                .boxed(),
+            data: self.request_from_peer(id.clone(), peer),
             id,
             index,
             peer: peer_index,
@@ -10,6 +10,8 @@ use futures::{Stream, StreamExt};
 use nimiq_block::Block;
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_bls::cache::PublicKeyCache;
+#[cfg(feature = "full")]
+use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::network::{Network, SubscribeEvents};
 #[cfg(feature = "full")]
 use nimiq_primitives::policy::Policy;
@@ -17,11 +19,6 @@ use nimiq_zkp_component::zkp_component::ZKPComponentProxy;
 use parking_lot::Mutex;
 use pin_project::pin_project;
 
-#[cfg(feature = "full")]
-use crate::sync::{
-    history::HistoryMacroSync,
-    live::{diff_queue::DiffQueue, state_queue::StateQueue, StateLiveSync},
-};
 use crate::{
     consensus::ResolveBlockRequest,
     sync::{
@@ -31,9 +28,18 @@ use crate::{
             queue::QueueConfig,
             BlockLiveSync,
         },
+        peer_stats::PeerStatsStore,
         syncer::{LiveSyncPushEvent, Syncer},
     },
 };
+#[cfg(feature = "full")]
+use crate::{
+    error::Error,
+    sync::{
+        history::HistoryMacroSync,
+        live::{diff_queue::DiffQueue, state_queue::StateQueue, StateLiveSync},
+    },
+};
 
 macro_rules! gen_syncer_match {
     ($self: ident, $f: ident $(, $arg:expr )*) => {
@@ -86,6 +92,9 @@ impl<N: Network> SyncerProxy<N> {
         )
         .await;
 
+        // The macro sync records its requests into the same statistics as the live sync.
+        let peer_stats = block_queue.peer_list().read().stats().clone();
+
         let live_sync = BlockLiveSync::with_queue(
             blockchain_proxy.clone(),
             Arc::clone(&network),
@@ -93,7 +102,12 @@ impl<N: Network> SyncerProxy<N> {
             bls_cache,
         );
 
-        let macro_sync = HistoryMacroSync::new(blockchain, Arc::clone(&network), network_event_rx);
+        let macro_sync = HistoryMacroSync::new(
+            blockchain,
+            Arc::clone(&network),
+            network_event_rx,
+            peer_stats,
+        );
 
         Self::History(Syncer::new(
             blockchain_proxy,
@@ -112,7 +126,8 @@ impl<N: Network> SyncerProxy<N> {
         zkp_component_proxy: ZKPComponentProxy<N>,
         network_event_rx: SubscribeEvents<N::PeerId>,
         full_sync_threshold: u32,
-    ) -> Self {
+        trusted_checkpoint: Option<Blake2bHash>,
+    ) -> Result<Self, Error> {
         let mut queue_config = QueueConfig::default();
         let min_queue_size = full_sync_threshold + Policy::blocks_per_batch() * 2;
         queue_config.window_ahead_max = max(min_queue_size, queue_config.window_ahead_max);
@@ -130,6 +145,8 @@ impl<N: Network> SyncerProxy<N> {
             BlockchainProxy::Light(_) => unreachable!(),
         };
 
+        let peer_stats = block_queue.peer_list().read().stats().clone();
+
         let diff_queue = DiffQueue::with_block_queue(Arc::clone(&network), block_queue);
 
         let state_queue = StateQueue::with_diff_queue(
@@ -148,20 +165,24 @@ impl<N: Network> SyncerProxy<N> {
 
         // The task executor that is supplied for the light macro sync variant is tokio
         // because the full sync is not supported in wasm
-        let macro_sync = LightMacroSync::new(
+        let mut macro_sync = LightMacroSync::new(
             blockchain_proxy.clone(),
             Arc::clone(&network),
             network_event_rx,
             zkp_component_proxy,
             full_sync_threshold,
+            peer_stats,
         );
+        if let Some(trusted_checkpoint) = trusted_checkpoint {
+            macro_sync = macro_sync.with_trusted_checkpoint(trusted_checkpoint)?;
+        }
 
-        Self::Full(Syncer::new(
+        Ok(Self::Full(Syncer::new(
             blockchain_proxy,
             network,
             live_sync,
             macro_sync,
-        ))
+        )))
     }
 
     /// Creates a new instance of a `SyncerProxy` for the `Light` variant
@@ -184,6 +205,8 @@ impl<N: Network> SyncerProxy<N> {
         )
         .await;
 
+        let peer_stats = block_queue.peer_list().read().stats().clone();
+
         let live_sync = BlockLiveSync::with_queue(
             blockchain_proxy.clone(),
             Arc::clone(&network),
@@ -197,6 +220,7 @@ impl<N: Network> SyncerProxy<N> {
             network_event_rx,
             zkp_component_proxy,
             0, // Since the light sync does not keep state, we ignore the threshold.
+            peer_stats,
         );
 
         Self::Light(Syncer::new(
@@ -235,6 +259,17 @@ impl<N: Network> SyncerProxy<N> {
     pub fn resolve_block(&mut self, request: ResolveBlockRequest<N>) {
         gen_syncer_match!(self, resolve_block, request)
     }
+
+    /// Returns the request statistics of the peers we sync from
+    pub fn peer_stats(&self) -> PeerStatsStore<N::PeerId> {
+        match self {
+            #[cfg(feature = "full")]
+            SyncerProxy::History(syncer) => syncer.macro_sync.peer_stats().clone(),
+            #[cfg(feature = "full")]
+            SyncerProxy::Full(syncer) => syncer.macro_sync.peer_stats().clone(),
+            SyncerProxy::Light(syncer) => syncer.macro_sync.peer_stats().clone(),
+        }
+    }
 }
 
 impl<N: Network> Stream for SyncerProxy<N> {
@@ -52,19 +52,19 @@ async fn syncer(
             )
             .await
         }
-        SyncMode::Full => {
-            SyncerProxy::new_full(
-                blockchain.clone(),
-                Arc::clone(network),
-                Arc::new(Mutex::new(PublicKeyCache::new(
-                    TESTING_BLS_CACHE_MAX_CAPACITY,
-                ))),
-                zkp_prover.proxy(),
-                network.subscribe_events(),
-                0,
-            )
-            .await
-        }
+        SyncMode::Full => SyncerProxy::new_full(
+            blockchain.clone(),
+            Arc::clone(network),
+            Arc::new(Mutex::new(PublicKeyCache::new(
+                TESTING_BLS_CACHE_MAX_CAPACITY,
+            ))),
+            zkp_prover.proxy(),
+            network.subscribe_events(),
+            0,
+            None,
+        )
+        .await
+        .unwrap(),
         SyncMode::Light => {
             SyncerProxy::new_light(
                 blockchain.clone(),
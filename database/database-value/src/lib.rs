@@ -159,6 +159,7 @@ macro_rules! impl_num_traits {
     };
 }
 
+impl_num_traits!(u8);
 impl_num_traits!(u16);
 impl_num_traits!(u32);
 impl_num_traits!(u64);
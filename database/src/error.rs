@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,4 +8,6 @@ pub enum Error {
     CreateDirectory(#[from] std::io::Error),
     #[error("Mdbx error: {0}")]
     Mdbx(#[from] libmdbx::Error),
+    #[error("Snapshot target directory is not empty: {}", .0.display())]
+    SnapshotTargetNotEmpty(PathBuf),
 }
@@ -1,5 +1,6 @@
 mod error;
 pub mod mdbx;
+pub mod memory;
 /// Abstraction for methods related to the database.
 pub mod traits;
 pub mod utils;
@@ -13,6 +13,10 @@ use crate::{
 const GIGABYTE: usize = 1024 * 1024 * 1024;
 const TERABYTE: usize = GIGABYTE * 1024;
 
+/// The default maximum number of tables. This is also the default of the node, such that tools
+/// opening a node's database with the default config can open all of its tables.
+pub const DEFAULT_MAX_TABLES: u32 = 32;
+
 /// Database config options.
 pub struct DatabaseConfig {
     /// The maximum number of tables that can be opened.
@@ -29,12 +33,15 @@ pub struct DatabaseConfig {
     pub growth_step: Option<isize>,
     /// The threshold of unused space, after which the database file will be shrunk.
     pub shrink_threshold: Option<isize>,
+    /// Opens an existing database without write access. Tables are not created and write
+    /// transactions cannot be started.
+    pub read_only: bool,
 }
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
         DatabaseConfig {
-            max_tables: Some(20),
+            max_tables: Some(DEFAULT_MAX_TABLES as u64),
             max_readers: None,
             no_rdahead: true,
             // Default max database size: 2TB
@@ -43,22 +50,29 @@ impl Default for DatabaseConfig {
             // Default growth step: 4GB
             growth_step: Some(4 * GIGABYTE as isize),
             shrink_threshold: None,
+            read_only: false,
         }
     }
 }
 
 impl From<DatabaseConfig> for libmdbx::DatabaseOptions {
     fn from(value: DatabaseConfig) -> Self {
-        libmdbx::DatabaseOptions {
-            max_tables: value.max_tables,
-            max_readers: value.max_readers,
-            no_rdahead: value.no_rdahead,
-            mode: libmdbx::Mode::ReadWrite(libmdbx::ReadWriteOptions {
+        let mode = if value.read_only {
+            libmdbx::Mode::ReadOnly
+        } else {
+            libmdbx::Mode::ReadWrite(libmdbx::ReadWriteOptions {
                 sync_mode: libmdbx::SyncMode::Durable,
                 min_size: value.size.as_ref().map(|r| r.start),
                 max_size: value.size.map(|r| r.end),
                 ..Default::default()
-            }),
+            })
+        };
+
+        libmdbx::DatabaseOptions {
+            max_tables: value.max_tables,
+            max_readers: value.max_readers,
+            no_rdahead: value.no_rdahead,
+            mode,
             ..Default::default()
         }
     }
@@ -69,15 +83,24 @@ impl From<DatabaseConfig> for libmdbx::DatabaseOptions {
 #[derive(Clone, Debug)]
 pub struct MdbxDatabase {
     /// The database handle.
-    db: Arc<libmdbx::Database<NoWriteMap>>,
+    pub(super) db: Arc<libmdbx::Database<NoWriteMap>>,
     /// For volatile databases, this is the temporary directory handle,
     /// which will clean up on `Drop`.
     temp_dir: Option<Arc<TempDir>>,
+    /// Whether the database was opened read-only.
+    read_only: bool,
+    /// The configured maximum size of the database file in bytes, if any.
+    max_size: Option<usize>,
 }
 
 impl MdbxDatabase {
     /// Create a table with additional flags.
     fn create_table<T: Table>(&self, _table: &T, mut flags: libmdbx::TableFlags) {
+        // Tables of a read-only database must already exist.
+        if self.read_only {
+            return;
+        }
+
         // Ensure `CREATE` flag is set.
         flags.insert(libmdbx::TableFlags::CREATE);
 
@@ -96,7 +119,16 @@ impl MdbxDatabase {
 
     /// Creates a new database at the given path.
     pub fn new<P: AsRef<Path>>(path: P, config: DatabaseConfig) -> Result<Self, Error> {
-        fs::create_dir_all(path.as_ref()).map_err(Error::CreateDirectory)?;
+        let read_only = config.read_only;
+        let max_size = config
+            .size
+            .as_ref()
+            .map(|size| size.end)
+            .filter(|&end| end > 0)
+            .map(|end| end as usize);
+        if !read_only {
+            fs::create_dir_all(path.as_ref()).map_err(Error::CreateDirectory)?;
+        }
 
         let db =
             libmdbx::Database::open_with_options(path, libmdbx::DatabaseOptions::from(config))?;
@@ -108,6 +140,8 @@ impl MdbxDatabase {
         let mdbx = MdbxDatabase {
             db: Arc::new(db),
             temp_dir: None,
+            read_only,
+            max_size,
         };
 
         Ok(mdbx)
@@ -121,6 +155,16 @@ impl MdbxDatabase {
 
         Ok(mdbx)
     }
+
+    /// Returns whether the database was opened read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns the configured maximum size of the database file in bytes, if any.
+    pub fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
 }
 
 impl Database for MdbxDatabase {
@@ -1,9 +1,11 @@
 mod cursor;
 mod database;
 mod iterators;
+mod snapshot;
+mod stats;
 mod transaction;
 
-pub use self::{cursor::*, database::*, iterators::*, transaction::*};
+pub use self::{cursor::*, database::*, iterators::*, snapshot::*, stats::*, transaction::*};
 use crate::traits::Database;
 
 /// A helper trait that is implemented on `Option<&T>` with `T: AsRef<MdbxReadTransaction<'db>>`.
@@ -38,289 +40,152 @@ mod tests {
     use super::*;
     use crate::{
         declare_table,
-        traits::{Database, DupReadCursor, ReadCursor, ReadTransaction, WriteTransaction},
+        traits::{tests, Database, ReadCursor, ReadTransaction, WriteTransaction},
     };
 
     declare_table!(TestTable, "test", String => String);
-    declare_table!(DupTestTable, "dup_test", String => dup(u32));
     declare_table!(U32DupTable, "u32_dup", u32 => dup(u32));
-    declare_table!(U32Table, "u32_nodup", u32 => u32);
+
+    fn new_volatile() -> MdbxDatabase {
+        MdbxDatabase::new_volatile(DatabaseConfig {
+            max_tables: Some(2),
+            ..Default::default()
+        })
+        .unwrap()
+    }
 
     #[test]
     fn it_can_save_basic_objects() {
-        let tempdir = tempdir().unwrap();
-        {
-            let db = MdbxDatabase::new(
-                tempdir.path().join("test"),
-                DatabaseConfig {
-                    max_tables: Some(1),
-                    ..Default::default()
-                },
-            )
-            .unwrap();
-            let table = TestTable {};
-            db.create_regular_table(&table);
-
-            // Read non-existent value.
-            {
-                let tx = db.read_transaction();
-                assert!(tx.get(&table, &"test".to_string()).is_none());
-            }
-
-            // Read non-existent value.
-            let mut tx = db.write_transaction();
-            assert!(tx.get(&table, &"test".to_string()).is_none());
-
-            // Write and read value.
-            tx.put(&table, &"test".to_string(), &"one".to_string());
-            assert_eq!(tx.get(&table, &"test".to_string()), Some("one".to_string()));
-            // Overwrite and read value.
-            tx.put(&table, &"test".to_string(), &"two".to_string());
-            assert_eq!(tx.get(&table, &"test".to_string()), Some("two".to_string()));
-            tx.commit();
-
-            // Read value.
-            let tx = db.read_transaction();
-            assert_eq!(tx.get(&table, &"test".to_string()), Some("two".to_string()));
-            tx.close();
-
-            // Remove value.
-            let mut tx = db.write_transaction();
-            tx.remove(&table, &"test".to_string());
-            assert!(tx.get(&table, &"test".to_string()).is_none());
-            tx.commit();
-
-            // Check removal.
-            {
-                let tx = db.read_transaction();
-                assert!(tx.get(&table, &"test".to_string()).is_none());
-            }
-
-            // Write and abort.
-            let mut tx = db.write_transaction();
-            tx.put(&table, &"test".to_string(), &"one".to_string());
-            tx.abort();
-
-            // Check aborted transaction.
-            let tx = db.read_transaction();
-            assert!(tx.get(&table, &"test".to_string()).is_none());
-        }
+        tests::it_can_save_basic_objects(new_volatile());
     }
 
     #[test]
     fn isolation_test() {
-        let tempdir = tempdir().unwrap();
-        {
-            let db = MdbxDatabase::new(
-                tempdir.path().join("test2"),
-                DatabaseConfig {
-                    max_tables: Some(1),
-                    ..Default::default()
-                },
-            )
-            .unwrap();
-            let table = TestTable {};
-            db.create_regular_table(&table);
-
-            // Read non-existent value.
-            let tx = db.read_transaction();
-            assert!(tx.get(&table, &"test".to_string()).is_none());
-
-            // WriteTransaction.
-            let mut txw = db.write_transaction();
-            assert!(txw.get(&table, &"test".to_string()).is_none());
-            txw.put(&table, &"test".to_string(), &"one".to_string());
-            assert_eq!(
-                txw.get(&table, &"test".to_string()),
-                Some("one".to_string())
-            );
-
-            // ReadTransaction should still have the old state.
-            assert!(tx.get(&table, &"test".to_string()).is_none());
+        tests::isolation_test(new_volatile());
+    }
 
-            // Commit WriteTransaction.
-            txw.commit();
+    #[test]
+    fn duplicates_test() {
+        tests::duplicates_test(new_volatile());
+    }
 
-            // ReadTransaction should still have the old state.
-            assert!(tx.get(&table, &"test".to_string()).is_none());
+    #[test]
+    fn cursor_test() {
+        tests::cursor_test(new_volatile());
+    }
 
-            // Have a new ReadTransaction read the new state.
-            let tx2 = db.read_transaction();
-            assert_eq!(
-                tx2.get(&table, &"test".to_string()),
-                Some("one".to_string())
-            );
-        }
-        tempdir.close().unwrap();
+    #[test]
+    fn it_correctly_orders_u32() {
+        tests::it_correctly_orders_u32(new_volatile());
     }
 
     #[test]
-    fn duplicates_test() {
+    fn it_can_create_snapshots() {
         let tempdir = tempdir().unwrap();
         {
             let db = MdbxDatabase::new(
-                tempdir.path().join("test3"),
+                tempdir.path().join("test6"),
                 DatabaseConfig {
-                    max_tables: Some(1),
+                    max_tables: Some(2),
                     ..Default::default()
                 },
             )
             .unwrap();
-            let table = DupTestTable {};
-            db.create_dup_table(&table);
-
-            // Write one value.
-            let mut txw = db.write_transaction();
-            assert!(txw.get(&table, &"test".to_string()).is_none());
-            txw.put(&table, &"test".to_string(), &125);
-            assert_eq!(txw.get(&table, &"test".to_string()), Some(125));
-            txw.commit();
-
-            // Have a new ReadTransaction read the new state.
-            {
-                let tx = db.read_transaction();
-                assert_eq!(tx.get(&table, &"test".to_string()), Some(125));
-            }
-
-            // Write a second smaller value.
-            let mut txw = db.write_transaction();
-            assert_eq!(txw.get(&table, &"test".to_string()), Some(125));
-            txw.put(&table, &"test".to_string(), &12);
-            assert_eq!(txw.get(&table, &"test".to_string()), Some(12));
-            txw.commit();
-
-            // Have a new ReadTransaction read the smaller value.
-            {
-                let tx = db.read_transaction();
-                assert_eq!(tx.get(&table, &"test".to_string()), Some(12));
-            }
+            let table = TestTable {};
+            let dup_table = U32DupTable {};
+            db.create_regular_table(&table);
+            db.create_dup_table(&dup_table);
 
-            // Remove smaller value and write larger value.
+            // Write some values.
             let mut txw = db.write_transaction();
-            assert_eq!(txw.get(&table, &"test".to_string()), Some(12));
-            txw.remove_item(&table, &"test".to_string(), &12);
-            txw.put(&table, &"test".to_string(), &5783);
-            assert_eq!(txw.get(&table, &"test".to_string()), Some(125));
+            txw.put(&table, &"test1".to_string(), &"one".to_string());
+            txw.put(&table, &"test2".to_string(), &"two".to_string());
+            txw.put(&dup_table, &256, &3);
+            txw.put(&dup_table, &3, &3);
+            txw.put(&dup_table, &256, &2);
             txw.commit();
 
-            // Have a new ReadTransaction read the smaller value.
-            {
-                let tx = db.read_transaction();
-                assert_eq!(tx.get(&table, &"test".to_string()), Some(125));
-            }
+            let snapshot_path = tempdir.path().join("snapshot");
+            let info = db.snapshot(&snapshot_path, true).unwrap();
+            assert_eq!(info.num_tables, 2);
+            assert_eq!(info.num_entries, 5);
 
-            // Remove everything.
-            let mut txw = db.write_transaction();
-            assert_eq!(txw.get(&table, &"test".to_string()), Some(125));
-            txw.remove(&table, &"test".to_string());
-            assert!(txw.get(&table, &"test".to_string()).is_none());
-            txw.commit();
+            // The target must be empty.
+            assert!(db.snapshot(&snapshot_path, true).is_err());
 
-            // Have a new ReadTransaction read the new state.
-            {
-                let tx = db.read_transaction();
-                assert!(tx.get(&table, &"test".to_string()).is_none());
-            }
-        }
-        tempdir.close().unwrap();
-    }
-
-    #[test]
-    fn cursor_test() {
-        let tempdir = tempdir().unwrap();
-        {
-            let db = MdbxDatabase::new(
-                tempdir.path().join("test4"),
+            let snapshot = MdbxDatabase::new(
+                &snapshot_path,
                 DatabaseConfig {
-                    max_tables: Some(1),
+                    max_tables: Some(2),
                     ..Default::default()
                 },
             )
             .unwrap();
-            let table = DupTestTable {};
-            db.create_dup_table(&table);
-
-            let test1: String = "test1".to_string();
-            let test2: String = "test2".to_string();
-
-            // Write some values.
-            let mut txw = db.write_transaction();
-            assert!(txw.get(&table, &"test".to_string()).is_none());
-            txw.put(&table, &"test1".to_string(), &125);
-            txw.put(&table, &"test1".to_string(), &12);
-            txw.put(&table, &"test1".to_string(), &5783);
-            txw.put(&table, &"test2".to_string(), &5783);
-            txw.commit();
-
-            // Have a new ReadTransaction read the new state.
-            let tx = db.read_transaction();
-            let mut cursor = tx.dup_cursor(&table);
-            assert_eq!(cursor.first(), Some((test1.clone(), 12)));
-            assert_eq!(cursor.last(), Some((test2.clone(), 5783)));
-            assert_eq!(cursor.prev(), Some((test1.clone(), 5783)));
-            assert_eq!(cursor.first_duplicate(), Some(12));
-            assert_eq!(cursor.next_duplicate(), Some((test1.clone(), 125)));
-            assert_eq!(cursor.prev_duplicate(), Some((test1.clone(), 12)));
-            assert_eq!(cursor.next_no_duplicate(), Some((test2.clone(), 5783)));
-            assert!(cursor.set_key(&"test".to_string()).is_none());
-            assert_eq!(cursor.set_key(&"test1".to_string()), Some(12));
-            assert_eq!(cursor.count_duplicates(), 3);
-            assert_eq!(cursor.last_duplicate(), Some(5783));
+            snapshot.create_regular_table(&table);
+            snapshot.create_dup_table(&dup_table);
 
-            assert_eq!(cursor.get_current(), Some((test1.clone(), 5783)));
+            let tx = snapshot.read_transaction();
+            assert_eq!(
+                tx.get(&table, &"test1".to_string()),
+                Some("one".to_string())
+            );
+            assert_eq!(
+                tx.get(&table, &"test2".to_string()),
+                Some("two".to_string())
+            );
 
-            assert_eq!(cursor.get_current(), Some((test1, 5783)));
-            assert!(cursor.prev_no_duplicate().is_none());
-            assert_eq!(cursor.next(), Some((test2, 5783)));
+            let mut cursor = tx.dup_cursor(&dup_table);
+            assert_eq!(cursor.first(), Some((3, 3)));
+            assert_eq!(cursor.next(), Some((256, 2)));
+            assert_eq!(cursor.next(), Some((256, 3)));
+            assert_eq!(cursor.next(), None);
         }
         tempdir.close().unwrap();
     }
 
     #[test]
-    fn it_correctly_orders_u32() {
+    fn it_reports_table_stats() {
         let tempdir = tempdir().unwrap();
         {
             let db = MdbxDatabase::new(
-                tempdir.path().join("test5"),
+                tempdir.path().join("test7"),
                 DatabaseConfig {
                     max_tables: Some(2),
                     ..Default::default()
                 },
             )
             .unwrap();
+            let table = TestTable {};
             let dup_table = U32DupTable {};
-            let table = U32Table {};
-            db.create_dup_table(&dup_table);
             db.create_regular_table(&table);
+            db.create_dup_table(&dup_table);
 
-            // Write some values.
             let mut txw = db.write_transaction();
-
-            txw.put(&table, &256, &2);
-            txw.put(&table, &3, &2);
-
+            txw.put(&table, &"test1".to_string(), &"one".to_string());
             txw.put(&dup_table, &256, &3);
-            txw.put(&dup_table, &3, &3);
             txw.put(&dup_table, &256, &2);
-            txw.put(&dup_table, &3, &2);
+            txw.put(&dup_table, &3, &3);
             txw.commit();
 
-            // Have a new ReadTransaction read the new state.
-            let tx = db.read_transaction();
-
-            let mut cursor = tx.cursor(&table);
-            assert_eq!(cursor.first(), Some((3, 2)));
-            assert_eq!(cursor.last(), Some((256, 2)));
-
-            let mut cursor = tx.dup_cursor(&dup_table);
-            assert_eq!(cursor.first(), Some((3, 2)));
-            assert_eq!(cursor.last(), Some((256, 3)));
-            assert_eq!(cursor.prev(), Some((256, 2)));
-            assert_eq!(cursor.prev(), Some((3, 3)));
-            assert_eq!(cursor.first_duplicate(), Some(2));
-            assert_eq!(cursor.last_duplicate(), Some(3));
-            assert_eq!(cursor.next_duplicate(), None);
-            assert_eq!(cursor.next_no_duplicate(), Some((256, 2)));
+            let stats = db.stats().unwrap();
+            assert_eq!(
+                stats.max_size,
+                DatabaseConfig::default().size.map(|size| size.end as usize)
+            );
+            assert!(stats.allocated_pages >= stats.freelist_pages);
+            assert!(stats.capacity_usage().unwrap() < CAPACITY_WARNING_THRESHOLD);
+
+            let table_stats = |name: &str| {
+                stats
+                    .tables
+                    .iter()
+                    .find(|table| table.name == name)
+                    .cloned()
+                    .unwrap()
+            };
+            assert_eq!(table_stats("test").entries, 1);
+            assert_eq!(table_stats("u32_dup").entries, 3);
+            assert!(table_stats("u32_dup").num_pages() > 0);
         }
         tempdir.close().unwrap();
     }
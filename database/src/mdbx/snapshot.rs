@@ -0,0 +1,153 @@
+use std::{borrow::Cow, fs, path::Path};
+
+use libmdbx::{NoWriteMap, TableFlags, TransactionKind, WriteFlags};
+use log::{debug, info};
+
+use super::{DatabaseConfig, MdbxDatabase, MdbxReadTransaction};
+use crate::{traits::Database, Error};
+
+/// The number of entries that are copied per write transaction on the target database.
+const SNAPSHOT_BATCH_SIZE: usize = 100_000;
+
+/// Summary of a database snapshot.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    /// The number of tables that were copied.
+    pub num_tables: usize,
+    /// The total number of entries that were copied.
+    pub num_entries: usize,
+}
+
+impl MdbxDatabase {
+    /// Writes a consistent copy of the database to the given directory, which must not exist or
+    /// be empty. The database can be written to while the snapshot is created, the copy reflects
+    /// the state at the time the snapshot started.
+    ///
+    /// If `compact` is set, the copy is sized to its content. Otherwise its minimum size is the
+    /// space currently used by this database, so that it can be filled without growing.
+    pub fn snapshot<P: AsRef<Path>>(&self, path: P, compact: bool) -> Result<SnapshotInfo, Error> {
+        let path = path.as_ref();
+        if path.exists() && fs::read_dir(path)?.next().is_some() {
+            return Err(Error::SnapshotTargetNotEmpty(path.to_path_buf()));
+        }
+
+        let default_config = DatabaseConfig::default();
+        let max_size = default_config.size.as_ref().map_or(0, |size| size.end);
+        let min_size = if compact {
+            0
+        } else {
+            // Page numbers are 0-based.
+            let used_pages = self.db.info()?.last_pgno() + 1;
+            (used_pages * self.db.stat()?.page_size() as usize) as isize
+        };
+
+        // The read transaction is kept open for the whole copy, such that all tables are copied
+        // from the same state.
+        let txn = self.read_transaction();
+        let num_tables = txn.table_names()?.len();
+
+        let target = MdbxDatabase::new(
+            path,
+            DatabaseConfig {
+                max_tables: Some(num_tables.max(1) as u64),
+                size: Some(min_size..max_size.max(min_size)),
+                ..default_config
+            },
+        )?;
+
+        info!(path = %path.display(), compact, "Creating database snapshot");
+        let snapshot_info = txn.copy_to(&target)?;
+        info!(
+            path = %path.display(),
+            num_tables = snapshot_info.num_tables,
+            num_entries = snapshot_info.num_entries,
+            "Created database snapshot"
+        );
+
+        Ok(snapshot_info)
+    }
+}
+
+impl<'db> MdbxReadTransaction<'db> {
+    /// Returns the names of all tables in the database.
+    pub fn table_names(&self) -> Result<Vec<String>, Error> {
+        match self {
+            MdbxReadTransaction::Read(txn) => table_names(txn.raw()),
+            MdbxReadTransaction::Write(txn) => table_names(txn.raw()),
+        }
+    }
+
+    /// Copies all tables, as seen by this transaction, into the given target database.
+    /// Tables that don't exist in the target database are created with the same flags.
+    pub fn copy_to(&self, target: &MdbxDatabase) -> Result<SnapshotInfo, Error> {
+        match self {
+            MdbxReadTransaction::Read(txn) => copy_tables(txn.raw(), target),
+            MdbxReadTransaction::Write(txn) => copy_tables(txn.raw(), target),
+        }
+    }
+}
+
+pub(super) fn table_names<K: TransactionKind>(
+    txn: &libmdbx::Transaction<'_, K, NoWriteMap>,
+) -> Result<Vec<String>, Error> {
+    // The keys of the unnamed main table are the names of all other tables.
+    let main_table = txn.open_table(None)?;
+    let mut cursor = txn.cursor(&main_table)?;
+
+    let mut names = vec![];
+    for item in cursor.iter_start::<Cow<[u8]>, ()>() {
+        let (name, _) = item?;
+        names.push(String::from_utf8_lossy(&name).into_owned());
+    }
+    Ok(names)
+}
+
+fn copy_tables<K: TransactionKind>(
+    txn: &libmdbx::Transaction<'_, K, NoWriteMap>,
+    target: &MdbxDatabase,
+) -> Result<SnapshotInfo, Error> {
+    let mut snapshot_info = SnapshotInfo::default();
+
+    for name in table_names(txn)? {
+        let table = txn.open_table(Some(&name))?;
+        let flags = txn.table_flags(&table)?;
+
+        // Entries are read in order, so they can be appended to the target table.
+        let write_flags = if flags.contains(TableFlags::DUP_SORT) {
+            WriteFlags::APPEND | WriteFlags::APPENDDUP
+        } else {
+            WriteFlags::APPEND
+        };
+
+        let mut cursor = txn.cursor(&table)?;
+        let mut entries = cursor.iter_start::<Cow<[u8]>, Cow<[u8]>>();
+        let mut num_entries = 0;
+        loop {
+            let target_txn = target.db.begin_rw_txn()?;
+            let target_table = target_txn.create_table(Some(&name), flags)?;
+
+            let mut num_batch_entries = 0;
+            for entry in entries.by_ref() {
+                let (key, value) = entry?;
+                target_txn.put(&target_table, key, value, write_flags)?;
+
+                num_batch_entries += 1;
+                if num_batch_entries == SNAPSHOT_BATCH_SIZE {
+                    break;
+                }
+            }
+            target_txn.commit()?;
+
+            num_entries += num_batch_entries;
+            if num_batch_entries < SNAPSHOT_BATCH_SIZE {
+                break;
+            }
+        }
+
+        debug!(table = name, num_entries, "Copied table");
+        snapshot_info.num_tables += 1;
+        snapshot_info.num_entries += num_entries;
+    }
+
+    Ok(snapshot_info)
+}
@@ -0,0 +1,143 @@
+use libmdbx::{NoWriteMap, TransactionKind};
+
+use super::{snapshot::table_names, MdbxDatabase, MdbxReadTransaction};
+use crate::{traits::Database, Error};
+
+/// The share of the maximum database size above which operators should be warned.
+pub const CAPACITY_WARNING_THRESHOLD: f64 = 0.9;
+
+/// Size and page usage of a single table.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableStats {
+    /// The name of the table.
+    pub name: String,
+    /// The number of entries (counting each duplicate).
+    pub entries: usize,
+    /// The depth of the table's B-tree.
+    pub depth: u32,
+    /// The number of internal (non-leaf) pages.
+    pub branch_pages: usize,
+    /// The number of leaf pages.
+    pub leaf_pages: usize,
+    /// The number of overflow pages, used for large values.
+    pub overflow_pages: usize,
+}
+
+impl TableStats {
+    /// Returns the total number of pages used by the table.
+    pub fn num_pages(&self) -> usize {
+        self.branch_pages + self.leaf_pages + self.overflow_pages
+    }
+}
+
+/// Statistics about a database and its tables.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DatabaseStats {
+    /// The size of a database page in bytes.
+    pub page_size: u32,
+    /// The current size of the memory map in bytes.
+    pub map_size: usize,
+    /// The configured maximum size of the database file in bytes, if any.
+    pub max_size: Option<usize>,
+    /// The number of pages allocated in the database file.
+    pub allocated_pages: usize,
+    /// The number of allocated pages that are free for reuse.
+    pub freelist_pages: usize,
+    /// The number of reader slots in use.
+    pub num_readers: usize,
+    /// The maximum number of reader slots.
+    pub max_readers: usize,
+    /// The id of the most recent transaction.
+    pub last_txn_id: usize,
+    /// Statistics for every table in the database.
+    pub tables: Vec<TableStats>,
+}
+
+impl DatabaseStats {
+    /// Returns the number of bytes allocated in the database file.
+    pub fn allocated_size(&self) -> usize {
+        self.allocated_pages * self.page_size as usize
+    }
+
+    /// Returns the number of bytes in use, i.e. allocated pages that are not on the freelist.
+    pub fn used_size(&self) -> usize {
+        self.allocated_pages.saturating_sub(self.freelist_pages) * self.page_size as usize
+    }
+
+    /// Returns the share of the maximum database size that is allocated, if a maximum is
+    /// configured.
+    pub fn capacity_usage(&self) -> Option<f64> {
+        capacity_usage(self.allocated_size(), self.max_size)
+    }
+}
+
+impl MdbxDatabase {
+    /// Collects statistics about the database and all its tables.
+    /// This traverses the freelist, so it should not be called too frequently.
+    pub fn stats(&self) -> Result<DatabaseStats, Error> {
+        let info = self.db.info()?;
+        let stat = self.db.stat()?;
+        let freelist_pages = self.db.freelist()?;
+        let tables = self.read_transaction().table_stats()?;
+
+        Ok(DatabaseStats {
+            page_size: stat.page_size(),
+            map_size: info.map_size(),
+            max_size: self.max_size(),
+            // Page numbers are 0-based.
+            allocated_pages: info.last_pgno() + 1,
+            freelist_pages,
+            num_readers: info.num_readers(),
+            max_readers: info.max_readers(),
+            last_txn_id: info.last_txnid(),
+            tables,
+        })
+    }
+
+    /// Returns the share of the maximum database size that is allocated, if a maximum is
+    /// configured. Unlike `stats`, this is cheap to call.
+    pub fn capacity_usage(&self) -> Result<Option<f64>, Error> {
+        let info = self.db.info()?;
+        let stat = self.db.stat()?;
+        let allocated_size = (info.last_pgno() + 1) * stat.page_size() as usize;
+
+        Ok(capacity_usage(allocated_size, self.max_size()))
+    }
+}
+
+impl<'db> MdbxReadTransaction<'db> {
+    /// Returns the statistics of all tables in the database, as seen by this transaction.
+    pub fn table_stats(&self) -> Result<Vec<TableStats>, Error> {
+        match self {
+            MdbxReadTransaction::Read(txn) => table_stats(txn.raw()),
+            MdbxReadTransaction::Write(txn) => table_stats(txn.raw()),
+        }
+    }
+}
+
+fn table_stats<K: TransactionKind>(
+    txn: &libmdbx::Transaction<'_, K, NoWriteMap>,
+) -> Result<Vec<TableStats>, Error> {
+    let mut tables = vec![];
+
+    for name in table_names(txn)? {
+        let table = txn.open_table(Some(&name))?;
+        let stat = txn.table_stat(&table)?;
+        tables.push(TableStats {
+            name,
+            entries: stat.entries(),
+            depth: stat.depth(),
+            branch_pages: stat.branch_pages(),
+            leaf_pages: stat.leaf_pages(),
+            overflow_pages: stat.overflow_pages(),
+        });
+    }
+
+    Ok(tables)
+}
+
+fn capacity_usage(allocated_size: usize, max_size: Option<usize>) -> Option<f64> {
+    max_size
+        .filter(|&max_size| max_size > 0)
+        .map(|max_size| allocated_size as f64 / max_size as f64)
+}
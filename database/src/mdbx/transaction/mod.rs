@@ -29,6 +29,10 @@ where
     pub(super) fn open_table<T: Table>(&self, _table: &T) -> libmdbx::Table {
         self.txn.open_table(Some(T::NAME)).unwrap()
     }
+
+    pub(in crate::mdbx) fn raw(&self) -> &libmdbx::Transaction<'db, Kind, NoWriteMap> {
+        &self.txn
+    }
 }
 
 impl<'db, Kind> ReadTransaction<'db> for MdbxTransaction<'db, Kind>
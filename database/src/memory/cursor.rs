@@ -0,0 +1,261 @@
+use std::{cell::RefCell, marker::PhantomData};
+
+use nimiq_database_value::{AsDatabaseBytes, FromDatabaseBytes};
+
+use super::{
+    table::{open_table, open_table_mut, Entry, MemoryTable, Tables},
+    MemoryIntoIter,
+};
+use crate::traits::{
+    DupReadCursor, DupSubKey, DupTable, DupTableValue, DupWriteCursor, ReadCursor, Row, Table,
+    WriteCursor,
+};
+
+/// A cursor for navigating the entries within a table of a `MemoryDatabase`.
+/// The cursor remembers its position by the encoded key and value of the current entry,
+/// so it stays valid while the table is modified.
+pub struct MemoryCursor<'txn, T: Table> {
+    tables: &'txn RefCell<Tables>,
+    writable: bool,
+    position: Option<(Vec<u8>, Vec<u8>)>,
+    _table: PhantomData<T>,
+}
+
+impl<'txn, T: Table> MemoryCursor<'txn, T> {
+    pub(super) fn new(tables: &'txn RefCell<Tables>, writable: bool) -> Self {
+        MemoryCursor {
+            tables,
+            writable,
+            position: None,
+            _table: PhantomData,
+        }
+    }
+
+    /// Moves the cursor to the entry selected by `f`, which receives the current position.
+    /// If no entry is selected, the cursor position remains unchanged.
+    pub(super) fn navigate<F>(&mut self, f: F) -> Option<Row<T>>
+    where
+        F: for<'a> FnOnce(&'a MemoryTable, Option<Entry<'a>>) -> Option<Entry<'a>>,
+    {
+        let tables = self.tables.borrow();
+        let table = open_table(&tables, T::NAME);
+
+        let position = self
+            .position
+            .as_ref()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()));
+        let (key, value) = f(table, position)?;
+
+        let row = (
+            FromDatabaseBytes::from_key_bytes(&table.decode_key(key)),
+            FromDatabaseBytes::from_value_bytes(value),
+        );
+        self.position = Some((key.to_vec(), value.to_vec()));
+        Some(row)
+    }
+
+    /// Modifies the table this cursor operates on.
+    fn modify<R>(&mut self, f: impl FnOnce(&mut MemoryTable) -> R) -> R {
+        assert!(
+            self.writable,
+            "Cannot modify a table from a read transaction"
+        );
+        let mut tables = self.tables.borrow_mut();
+        f(open_table_mut(&mut tables, T::NAME))
+    }
+
+    fn encode_key(&self, key: &T::Key) -> Vec<u8> {
+        let tables = self.tables.borrow();
+        open_table(&tables, T::NAME).encode_key(&AsDatabaseBytes::as_key_bytes(key))
+    }
+}
+
+impl<'txn, T: Table> Clone for MemoryCursor<'txn, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tables: self.tables,
+            writable: self.writable,
+            position: self.position.clone(),
+            _table: PhantomData,
+        }
+    }
+}
+
+impl<'txn, T: Table> ReadCursor<'txn, T> for MemoryCursor<'txn, T> {
+    type IntoIter = MemoryIntoIter<'txn, T>;
+
+    fn first(&mut self) -> Option<Row<T>> {
+        self.navigate(|table, _| table.first())
+    }
+
+    fn last(&mut self) -> Option<Row<T>> {
+        self.navigate(|table, _| table.last())
+    }
+
+    fn next(&mut self) -> Option<Row<T>> {
+        // Like mdbx, an unpositioned cursor starts at the first entry.
+        self.navigate(|table, position| match position {
+            Some((key, value)) => table.next(key, value),
+            None => table.first(),
+        })
+    }
+
+    fn prev(&mut self) -> Option<Row<T>> {
+        // Like mdbx, an unpositioned cursor starts at the last entry.
+        self.navigate(|table, position| match position {
+            Some((key, value)) => table.prev(key, value),
+            None => table.last(),
+        })
+    }
+
+    fn get_current(&mut self) -> Option<Row<T>> {
+        self.navigate(|table, position| {
+            let (key, value) = position?;
+            table.get_both(key, value)
+        })
+    }
+
+    fn set_key(&mut self, key: &T::Key) -> Option<T::Value> {
+        let key = self.encode_key(key);
+        let (_, value) = self.navigate(|table, _| table.first_dup(&key))?;
+        Some(value)
+    }
+
+    fn set_lowerbound_key(&mut self, key: &T::Key) -> Option<Row<T>> {
+        let key = self.encode_key(key);
+        self.navigate(|table, _| table.lowerbound_key(&key))
+    }
+
+    fn into_iter_start(mut self) -> Self::IntoIter {
+        let first = self.first();
+        MemoryIntoIter::new(self, first, false)
+    }
+
+    fn into_iter_from(mut self, key: &T::Key) -> Self::IntoIter {
+        let first = self.set_lowerbound_key(key);
+        MemoryIntoIter::new(self, first, false)
+    }
+}
+
+impl<'txn, T: DupTable> DupReadCursor<'txn, T> for MemoryCursor<'txn, T> {
+    fn first_duplicate(&mut self) -> Option<T::Value> {
+        let (_, value) = self.navigate(|table, position| table.first_dup(position?.0))?;
+        Some(value)
+    }
+
+    fn last_duplicate(&mut self) -> Option<T::Value> {
+        let (_, value) = self.navigate(|table, position| table.last_dup(position?.0))?;
+        Some(value)
+    }
+
+    fn next_duplicate(&mut self) -> Option<Row<T>> {
+        self.navigate(|table, position| {
+            let (key, value) = position?;
+            table.next_dup(key, value)
+        })
+    }
+
+    fn next_no_duplicate(&mut self) -> Option<Row<T>> {
+        self.navigate(|table, position| match position {
+            Some((key, _)) => table.next_nodup(key),
+            None => table.first(),
+        })
+    }
+
+    fn prev_duplicate(&mut self) -> Option<Row<T>> {
+        self.navigate(|table, position| {
+            let (key, value) = position?;
+            table.prev_dup(key, value)
+        })
+    }
+
+    fn prev_no_duplicate(&mut self) -> Option<Row<T>> {
+        self.navigate(|table, position| match position {
+            Some((key, _)) => table.prev_nodup(key),
+            None => table.last(),
+        })
+    }
+
+    fn set_subkey(&mut self, key: &T::Key, subkey: &DupSubKey<T>) -> Option<T::Value>
+    where
+        T::Value: DupTableValue,
+    {
+        let value = self.set_lowerbound_subkey(key, subkey)?;
+        if value.subkey() == subkey {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn set_lowerbound_both(&mut self, key: &T::Key, subkey: &DupSubKey<T>) -> Option<Row<T>>
+    where
+        T::Value: DupTableValue,
+    {
+        let key = self.encode_key(key);
+        let subkey = AsDatabaseBytes::as_value_bytes(subkey);
+        self.navigate(|table, _| table.lowerbound_both(&key, &subkey))
+    }
+
+    fn set_lowerbound_subkey(&mut self, key: &T::Key, subkey: &DupSubKey<T>) -> Option<T::Value>
+    where
+        T::Value: DupTableValue,
+    {
+        let key = self.encode_key(key);
+        let subkey = AsDatabaseBytes::as_value_bytes(subkey);
+        let (_, value) = self.navigate(|table, _| table.lowerbound_value(&key, &subkey))?;
+        Some(value)
+    }
+
+    fn count_duplicates(&mut self) -> usize {
+        let tables = self.tables.borrow();
+        match self.position {
+            Some((ref key, _)) => open_table(&tables, T::NAME).count_dups(key),
+            None => 0,
+        }
+    }
+
+    fn into_iter_dup_of(mut self, key: &T::Key) -> Self::IntoIter {
+        let key = self.encode_key(key);
+        let first = self.navigate(|table, _| table.first_dup(&key));
+        MemoryIntoIter::new(self, first, true)
+    }
+}
+
+impl<'txn, T: Table> WriteCursor<'txn, T> for MemoryCursor<'txn, T> {
+    fn put(&mut self, key: &T::Key, value: &T::Value) {
+        let key = self.encode_key(key);
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+        self.modify(|table| table.insert(key.clone(), value.clone()));
+        self.position = Some((key, value));
+    }
+
+    fn append(&mut self, key: &T::Key, value: &T::Value) {
+        let key = self.encode_key(key);
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+        self.modify(|table| table.append(key.clone(), value.clone()));
+        self.position = Some((key, value));
+    }
+
+    fn remove(&mut self) {
+        // The cursor keeps its position, so that `next` moves to the entry after the removed one.
+        if let Some((key, value)) = self.position.clone() {
+            self.modify(|table| table.remove_item(&key, &value));
+        }
+    }
+}
+
+impl<'txn, T: DupTable> DupWriteCursor<'txn, T> for MemoryCursor<'txn, T> {
+    fn append_dup(&mut self, key: &T::Key, value: &T::Value) {
+        let key = self.encode_key(key);
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+        self.modify(|table| table.append_dup(key.clone(), value.clone()));
+        self.position = Some((key, value));
+    }
+
+    fn remove_all_dup(&mut self) {
+        if let Some((key, _)) = self.position.clone() {
+            self.modify(|table| table.remove(&key));
+        }
+    }
+}
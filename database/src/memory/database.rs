@@ -0,0 +1,90 @@
+use std::{
+    any::TypeId,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use log::debug;
+
+use super::{table::Tables, MemoryReadTransaction, MemoryTable, MemoryWriteTransaction};
+use crate::traits::{Database, DupTable, RegularTable, Table};
+
+#[derive(Debug, Default)]
+struct MemoryDatabaseInner {
+    /// The latest committed state of all tables.
+    tables: RwLock<Tables>,
+    /// Serializes write transactions, like mdbx does.
+    writer: Mutex<()>,
+}
+
+/// A purely in-memory database.
+/// It keeps all data on the heap and does not touch the file system.
+///
+/// Read transactions operate on a snapshot of the latest committed state.
+/// Only one write transaction can be open at a time; starting another one blocks
+/// until the first is committed or aborted.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryDatabase {
+    inner: Arc<MemoryDatabaseInner>,
+}
+
+impl MemoryDatabase {
+    /// Creates a new, empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a table if it does not exist yet.
+    fn create_table<T: Table>(&self, _table: &T, dup: bool) {
+        // Tables must not be created while a write transaction is in progress,
+        // as its commit would discard them.
+        let _writer = self.inner.writer.lock().unwrap();
+
+        // Integer keys are sorted numerically, same as for mdbx.
+        let key_type = TypeId::of::<T::Key>();
+        let integer_key = key_type == TypeId::of::<u32>() || key_type == TypeId::of::<u64>();
+
+        debug!("Creating table: {}, dup: {}", T::NAME, dup);
+        self.inner
+            .tables
+            .write()
+            .unwrap()
+            .entry(T::NAME)
+            .or_insert_with(|| MemoryTable::new(dup, integer_key));
+    }
+
+    /// Replaces the committed state with the given tables and applies their changes.
+    pub(super) fn commit(&self, mut tables: Tables) {
+        let mut committed = self.inner.tables.write().unwrap();
+        // Release the previous state first, so that tables not used by any read transaction are
+        // updated in place instead of being copied.
+        *committed = Tables::new();
+        for table in tables.values_mut() {
+            table.apply_changes();
+        }
+        *committed = tables;
+    }
+}
+
+impl Database for MemoryDatabase {
+    type ReadTransaction<'db> = MemoryReadTransaction<'db>;
+
+    type WriteTransaction<'db> = MemoryWriteTransaction<'db>;
+
+    fn create_regular_table<T: RegularTable>(&self, table: &T) {
+        self.create_table(table, false)
+    }
+
+    fn create_dup_table<T: DupTable>(&self, table: &T) {
+        self.create_table(table, true)
+    }
+
+    fn read_transaction(&self) -> Self::ReadTransaction<'_> {
+        MemoryReadTransaction::new(self.inner.tables.read().unwrap().clone(), false)
+    }
+
+    fn write_transaction(&self) -> Self::WriteTransaction<'_> {
+        let writer = self.inner.writer.lock().unwrap();
+        let tables = self.inner.tables.read().unwrap().clone();
+        MemoryWriteTransaction::new(self, writer, tables)
+    }
+}
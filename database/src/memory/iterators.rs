@@ -0,0 +1,45 @@
+use super::MemoryCursor;
+use crate::traits::{Row, Table};
+
+/// Iterates over database entries (key, value pairs) by moving a cursor.
+pub struct MemoryIntoIter<'txn, T: Table> {
+    cursor: MemoryCursor<'txn, T>,
+    /// The entry to be returned next, the cursor is positioned on it.
+    next: Option<Row<T>>,
+    /// Whether to only iterate over the duplicates of the initial key.
+    dup_only: bool,
+}
+
+impl<'txn, T: Table> MemoryIntoIter<'txn, T> {
+    pub(super) fn new(
+        cursor: MemoryCursor<'txn, T>,
+        first: Option<Row<T>>,
+        dup_only: bool,
+    ) -> Self {
+        MemoryIntoIter {
+            cursor,
+            next: first,
+            dup_only,
+        }
+    }
+}
+
+impl<'txn, T: Table> Iterator for MemoryIntoIter<'txn, T> {
+    type Item = Row<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.next.take()?;
+        self.next = if self.dup_only {
+            self.cursor.navigate(|table, position| {
+                let (key, value) = position?;
+                table.next_dup(key, value)
+            })
+        } else {
+            self.cursor.navigate(|table, position| {
+                let (key, value) = position?;
+                table.next(key, value)
+            })
+        };
+        Some(item)
+    }
+}
@@ -0,0 +1,195 @@
+mod cursor;
+mod database;
+mod iterators;
+mod table;
+mod transaction;
+
+use self::table::MemoryTable;
+pub use self::{cursor::*, database::*, iterators::*, transaction::*};
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{
+        declare_table,
+        mdbx::{DatabaseConfig, MdbxDatabase},
+        traits::{
+            tests, Database, DupReadCursor, DupWriteCursor, ReadCursor, ReadTransaction,
+            WriteCursor, WriteTransaction,
+        },
+        utils::IndexedValue,
+    };
+
+    declare_table!(U32DupTable, "u32_dup", u32 => dup(u32));
+    declare_table!(U32Table, "u32_nodup", u32 => u32);
+    declare_table!(IndexedTable, "indexed", u32 => u32 => String);
+
+    #[test]
+    fn it_can_save_basic_objects() {
+        tests::it_can_save_basic_objects(MemoryDatabase::new());
+    }
+
+    #[test]
+    fn isolation_test() {
+        tests::isolation_test(MemoryDatabase::new());
+    }
+
+    #[test]
+    fn duplicates_test() {
+        tests::duplicates_test(MemoryDatabase::new());
+    }
+
+    #[test]
+    fn cursor_test() {
+        tests::cursor_test(MemoryDatabase::new());
+    }
+
+    #[test]
+    fn it_correctly_orders_u32() {
+        tests::it_correctly_orders_u32(MemoryDatabase::new());
+    }
+
+    #[test]
+    fn it_can_modify_through_cursors() {
+        let db = MemoryDatabase::new();
+        let table = IndexedTable {};
+        db.create_dup_table(&table);
+
+        let txw = db.write_transaction();
+        let mut cursor = WriteTransaction::dup_cursor(&txw, &table);
+        cursor.append_dup(&1, &IndexedValue::new(0, "a".to_string()));
+        cursor.append_dup(&1, &IndexedValue::new(2, "b".to_string()));
+        cursor.append_dup(&1, &IndexedValue::new(4, "c".to_string()));
+        cursor.append(&2, &IndexedValue::new(1, "d".to_string()));
+
+        assert_eq!(
+            cursor.set_subkey(&1, &2),
+            Some(IndexedValue::new(2, "b".to_string()))
+        );
+        assert!(cursor.set_subkey(&1, &3).is_none());
+        assert_eq!(
+            cursor.set_lowerbound_subkey(&1, &3),
+            Some(IndexedValue::new(4, "c".to_string()))
+        );
+        assert_eq!(
+            cursor.set_lowerbound_both(&1, &5),
+            Some((2, IndexedValue::new(1, "d".to_string())))
+        );
+
+        // Remove the middle entry and continue iterating from there.
+        assert!(cursor.set_subkey(&1, &2).is_some());
+        cursor.remove();
+        assert_eq!(
+            cursor.next(),
+            Some((1, IndexedValue::new(4, "c".to_string())))
+        );
+        cursor.remove_all_dup();
+        assert_eq!(
+            cursor.next(),
+            Some((2, IndexedValue::new(1, "d".to_string())))
+        );
+        txw.commit();
+
+        let tx = db.read_transaction();
+        let cursor = tx.dup_cursor(&table);
+        assert_eq!(
+            cursor.into_iter_start().collect::<Vec<_>>(),
+            vec![(2, IndexedValue::new(1, "d".to_string()))]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_rejects_out_of_order_appends() {
+        let db = MemoryDatabase::new();
+        let table = U32Table {};
+        db.create_regular_table(&table);
+
+        let mut txw = db.write_transaction();
+        txw.append(&table, &256, &1);
+        txw.append(&table, &3, &1);
+    }
+
+    #[test]
+    fn it_behaves_like_mdbx() {
+        let mdbx = MdbxDatabase::new_volatile(DatabaseConfig {
+            max_tables: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+        let memory = MemoryDatabase::new();
+        let table = U32DupTable {};
+        mdbx.create_dup_table(&table);
+        memory.create_dup_table(&table);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..20 {
+            let mut mdbx_txn = mdbx.write_transaction();
+            let mut memory_txn = memory.write_transaction();
+            for _ in 0..50 {
+                let key = rng.gen_range(0..300);
+                let value = rng.gen_range(0..10);
+                match rng.gen_range(0..4) {
+                    0 => {
+                        mdbx_txn.remove(&table, &key);
+                        memory_txn.remove(&table, &key);
+                    }
+                    1 => {
+                        mdbx_txn.remove_item(&table, &key, &value);
+                        memory_txn.remove_item(&table, &key, &value);
+                    }
+                    _ => {
+                        mdbx_txn.put(&table, &key, &value);
+                        memory_txn.put(&table, &key, &value);
+                    }
+                }
+            }
+
+            // Uncommitted changes are visible within the write transaction.
+            assert_eq!(
+                ReadTransaction::dup_cursor(&*mdbx_txn, &table)
+                    .into_iter_start()
+                    .collect::<Vec<_>>(),
+                ReadTransaction::dup_cursor(&*memory_txn, &table)
+                    .into_iter_start()
+                    .collect::<Vec<_>>()
+            );
+
+            mdbx_txn.commit();
+            memory_txn.commit();
+
+            let mdbx_txn = mdbx.read_transaction();
+            let memory_txn = memory.read_transaction();
+            assert_eq!(
+                mdbx_txn
+                    .dup_cursor(&table)
+                    .into_iter_start()
+                    .collect::<Vec<_>>(),
+                memory_txn
+                    .dup_cursor(&table)
+                    .into_iter_start()
+                    .collect::<Vec<_>>()
+            );
+
+            let key = rng.gen_range(0..300);
+            assert_eq!(mdbx_txn.get(&table, &key), memory_txn.get(&table, &key));
+
+            let mut mdbx_cursor = mdbx_txn.dup_cursor(&table);
+            let mut memory_cursor = memory_txn.dup_cursor(&table);
+            let row = mdbx_cursor.set_lowerbound_key(&key);
+            assert_eq!(row, memory_cursor.set_lowerbound_key(&key));
+            if row.is_some() {
+                assert_eq!(
+                    mdbx_cursor.count_duplicates(),
+                    memory_cursor.count_duplicates()
+                );
+                assert_eq!(
+                    mdbx_cursor.next_no_duplicate(),
+                    memory_cursor.next_no_duplicate()
+                );
+            }
+        }
+    }
+}
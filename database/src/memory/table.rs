@@ -0,0 +1,288 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+    sync::Arc,
+};
+
+/// A reference to a stored entry in its encoded form.
+pub(super) type Entry<'a> = (&'a [u8], &'a [u8]);
+
+/// The values of a key, sorted by their encoded bytes.
+type Values = BTreeSet<Vec<u8>>;
+
+/// The entries of a table, sorted by their encoded key.
+type Entries = BTreeMap<Vec<u8>, Values>;
+
+/// The set of tables visible to a transaction.
+pub(super) type Tables = BTreeMap<&'static str, MemoryTable>;
+
+/// The in-memory representation of a table.
+/// Regular tables are stored the same way as dup tables, but hold at most one value per key.
+///
+/// The committed entries are shared between transactions. A write transaction records its
+/// changes separately and only applies them to the committed entries on commit, so writing
+/// doesn't copy the table.
+#[derive(Clone, Debug, Default)]
+pub(super) struct MemoryTable {
+    /// Whether the table allows duplicate keys.
+    dup: bool,
+    /// Whether the keys are native-endian integers, which mdbx sorts numerically.
+    integer_key: bool,
+    /// The committed entries.
+    committed: Arc<Entries>,
+    /// The values of the keys changed by a write transaction. Removed keys have no values.
+    changes: Entries,
+}
+
+impl MemoryTable {
+    pub(super) fn new(dup: bool, integer_key: bool) -> Self {
+        MemoryTable {
+            dup,
+            integer_key,
+            committed: Arc::default(),
+            changes: BTreeMap::new(),
+        }
+    }
+
+    /// Converts key bytes into their stored form.
+    /// Integer keys are stored in big-endian order, so that the lexicographic order of the
+    /// stored bytes matches the numeric order used by mdbx.
+    pub(super) fn encode_key(&self, key: &[u8]) -> Vec<u8> {
+        let mut key = key.to_vec();
+        if self.integer_key && cfg!(target_endian = "little") {
+            key.reverse();
+        }
+        key
+    }
+
+    /// Converts stored key bytes back into the representation returned by `as_key_bytes`.
+    pub(super) fn decode_key(&self, key: &[u8]) -> Vec<u8> {
+        // The conversion is its own inverse.
+        self.encode_key(key)
+    }
+
+    /// Returns the values of the given key.
+    fn get(&self, key: &[u8]) -> Option<(&[u8], &Values)> {
+        match self.changes.get_key_value(key) {
+            Some((key, values)) => (!values.is_empty()).then_some((key, values)),
+            None => self.committed.get_key_value(key),
+        }
+        .map(|(key, values)| (key.as_slice(), values))
+    }
+
+    /// Returns the first key in the given range with its values, or the last one if `rev` is set.
+    fn find(&self, range: (Bound<&[u8]>, Bound<&[u8]>), rev: bool) -> Option<(&[u8], &Values)> {
+        let mut changed = self
+            .changes
+            .range::<[u8], _>(range)
+            .filter(|(_, values)| !values.is_empty());
+        // Changed keys are covered by the changes.
+        let mut committed = self
+            .committed
+            .range::<[u8], _>(range)
+            .filter(|(key, _)| !self.changes.contains_key(key.as_slice()));
+
+        let (changed, committed) = if rev {
+            (changed.next_back(), committed.next_back())
+        } else {
+            (changed.next(), committed.next())
+        };
+        let (key, values) = match (changed, committed) {
+            (Some(changed), Some(committed)) => {
+                if (changed.0 < committed.0) != rev {
+                    changed
+                } else {
+                    committed
+                }
+            }
+            (changed, committed) => changed.or(committed)?,
+        };
+        Some((key, values))
+    }
+
+    /// Returns the values of the given key for modification.
+    fn values_mut(&mut self, key: Vec<u8>) -> &mut Values {
+        let committed = &self.committed;
+        self.changes
+            .entry(key)
+            .or_insert_with_key(|key| committed.get(key).cloned().unwrap_or_default())
+    }
+
+    pub(super) fn first(&self) -> Option<Entry> {
+        let (key, values) = self.find((Bound::Unbounded, Bound::Unbounded), false)?;
+        Some((key, values.first()?))
+    }
+
+    pub(super) fn last(&self) -> Option<Entry> {
+        let (key, values) = self.find((Bound::Unbounded, Bound::Unbounded), true)?;
+        Some((key, values.last()?))
+    }
+
+    /// Returns the entry with exactly the given key and value.
+    pub(super) fn get_both(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        let (key, values) = self.get(key)?;
+        Some((key, values.get(value)?))
+    }
+
+    pub(super) fn first_dup(&self, key: &[u8]) -> Option<Entry> {
+        let (key, values) = self.get(key)?;
+        Some((key, values.first()?))
+    }
+
+    pub(super) fn last_dup(&self, key: &[u8]) -> Option<Entry> {
+        let (key, values) = self.get(key)?;
+        Some((key, values.last()?))
+    }
+
+    pub(super) fn next_dup(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        let (key, values) = self.get(key)?;
+        let value = values
+            .range::<[u8], _>((Bound::Excluded(value), Bound::Unbounded))
+            .next()?;
+        Some((key, value))
+    }
+
+    pub(super) fn prev_dup(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        let (key, values) = self.get(key)?;
+        let value = values
+            .range::<[u8], _>((Bound::Unbounded, Bound::Excluded(value)))
+            .next_back()?;
+        Some((key, value))
+    }
+
+    /// Returns the first duplicate of the next key.
+    pub(super) fn next_nodup(&self, key: &[u8]) -> Option<Entry> {
+        let (key, values) = self.find((Bound::Excluded(key), Bound::Unbounded), false)?;
+        Some((key, values.first()?))
+    }
+
+    /// Returns the last duplicate of the previous key.
+    pub(super) fn prev_nodup(&self, key: &[u8]) -> Option<Entry> {
+        let (key, values) = self.find((Bound::Unbounded, Bound::Excluded(key)), true)?;
+        Some((key, values.last()?))
+    }
+
+    pub(super) fn next(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        self.next_dup(key, value).or_else(|| self.next_nodup(key))
+    }
+
+    pub(super) fn prev(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        self.prev_dup(key, value).or_else(|| self.prev_nodup(key))
+    }
+
+    /// Returns the first entry with a key >= `key`.
+    pub(super) fn lowerbound_key(&self, key: &[u8]) -> Option<Entry> {
+        let (key, values) = self.find((Bound::Included(key), Bound::Unbounded), false)?;
+        Some((key, values.first()?))
+    }
+
+    /// Returns the first entry with a key == `key` and a value >= `value`.
+    pub(super) fn lowerbound_value(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        let (key, values) = self.get(key)?;
+        let value = values
+            .range::<[u8], _>((Bound::Included(value), Bound::Unbounded))
+            .next()?;
+        Some((key, value))
+    }
+
+    /// Returns the first entry with a key == `key` and a value >= `value`,
+    /// or the first entry of the next key if there is none.
+    pub(super) fn lowerbound_both(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        self.lowerbound_value(key, value)
+            .or_else(|| self.next_nodup(key))
+    }
+
+    pub(super) fn count_dups(&self, key: &[u8]) -> usize {
+        self.get(key).map_or(0, |(_, values)| values.len())
+    }
+
+    /// Inserts an entry. In regular tables, this replaces the existing value of the key.
+    pub(super) fn insert(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        if self.dup {
+            self.values_mut(key).insert(value);
+        } else {
+            self.changes.insert(key, BTreeSet::from([value]));
+        }
+    }
+
+    /// Inserts an entry that must not sort before the last entry of the table.
+    /// In dup tables, the key may be equal to the last key.
+    pub(super) fn append(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        if let Some((last_key, _)) = self.last() {
+            assert!(
+                key.as_slice() >= last_key,
+                "Appended key must not be smaller than the last key"
+            );
+        }
+        self.insert(key, value);
+    }
+
+    /// Inserts an entry that must not sort before the last entry of the table,
+    /// comparing both key and value.
+    pub(super) fn append_dup(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        if let Some((last_key, last_value)) = self.last() {
+            assert!(
+                (key.as_slice(), value.as_slice()) >= (last_key, last_value),
+                "Appended entry must not be smaller than the last entry"
+            );
+        }
+        self.insert(key, value);
+    }
+
+    /// Removes all values of the given key.
+    pub(super) fn remove(&mut self, key: &[u8]) {
+        if self.committed.contains_key(key) {
+            self.changes.insert(key.to_vec(), BTreeSet::new());
+        } else {
+            self.changes.remove(key);
+        }
+    }
+
+    /// Removes a single entry. Regular tables ignore the value and remove the key.
+    pub(super) fn remove_item(&mut self, key: &[u8], value: &[u8]) {
+        if !self.dup {
+            self.remove(key);
+            return;
+        }
+
+        if self.get_both(key, value).is_some() {
+            self.values_mut(key.to_vec()).remove(value);
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.committed = Arc::default();
+        self.changes.clear();
+    }
+
+    /// Applies the changes to the committed entries. The committed entries are only copied if
+    /// they are still used by another transaction.
+    pub(super) fn apply_changes(&mut self) {
+        if self.changes.is_empty() {
+            return;
+        }
+
+        let committed = Arc::make_mut(&mut self.committed);
+        for (key, values) in std::mem::take(&mut self.changes) {
+            if values.is_empty() {
+                committed.remove(&key);
+            } else {
+                committed.insert(key, values);
+            }
+        }
+    }
+}
+
+/// Looks up a table that must have been created before.
+pub(super) fn open_table<'a>(tables: &'a Tables, name: &str) -> &'a MemoryTable {
+    tables
+        .get(name)
+        .unwrap_or_else(|| panic!("Table {name} does not exist"))
+}
+
+/// Looks up a table for writing.
+pub(super) fn open_table_mut<'a>(tables: &'a mut Tables, name: &str) -> &'a mut MemoryTable {
+    tables
+        .get_mut(name)
+        .unwrap_or_else(|| panic!("Table {name} does not exist"))
+}
@@ -0,0 +1,203 @@
+use std::{cell::RefCell, marker::PhantomData, ops::Deref, sync::MutexGuard};
+
+use nimiq_database_value::{AsDatabaseBytes, FromDatabaseBytes, IntoDatabaseValue};
+
+use super::{
+    table::{open_table, open_table_mut, MemoryTable, Tables},
+    MemoryCursor, MemoryDatabase,
+};
+use crate::traits::{DupTable, ReadTransaction, RegularTable, Table, WriteTransaction};
+
+/// A transaction on a `MemoryDatabase`.
+/// It holds its own view of the tables, which is only published on commit.
+#[derive(Debug)]
+pub struct MemoryReadTransaction<'db> {
+    tables: RefCell<Tables>,
+    /// Whether cursors of this transaction may modify the tables.
+    writable: bool,
+    _db: PhantomData<&'db MemoryDatabase>,
+}
+
+impl<'db> MemoryReadTransaction<'db> {
+    pub(super) fn new(tables: Tables, writable: bool) -> Self {
+        MemoryReadTransaction {
+            tables: RefCell::new(tables),
+            writable,
+            _db: PhantomData,
+        }
+    }
+
+    fn table_mut<T: Table>(&mut self, _table: &T) -> &mut MemoryTable {
+        open_table_mut(self.tables.get_mut(), T::NAME)
+    }
+}
+
+impl<'db> AsRef<MemoryReadTransaction<'db>> for MemoryReadTransaction<'db> {
+    fn as_ref(&self) -> &MemoryReadTransaction<'db> {
+        self
+    }
+}
+
+impl<'db> ReadTransaction<'db> for MemoryReadTransaction<'db> {
+    type Cursor<'txn, T: Table>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    type DupCursor<'txn, T: DupTable>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    fn get<T: Table>(&self, _table: &T, key: &T::Key) -> Option<T::Value> {
+        let tables = self.tables.borrow();
+        let table = open_table(&tables, T::NAME);
+
+        let key = table.encode_key(&AsDatabaseBytes::as_key_bytes(key));
+        let (_, value) = table.first_dup(&key)?;
+        Some(FromDatabaseBytes::from_value_bytes(value))
+    }
+
+    fn cursor<'txn, T: RegularTable>(&'txn self, _table: &T) -> Self::Cursor<'txn, T> {
+        MemoryCursor::new(&self.tables, self.writable)
+    }
+
+    fn dup_cursor<'txn, T: DupTable>(&'txn self, _table: &T) -> Self::DupCursor<'txn, T> {
+        MemoryCursor::new(&self.tables, self.writable)
+    }
+}
+
+/// A write transaction on a `MemoryDatabase`.
+/// It holds the database's writer lock until it is committed or aborted.
+pub struct MemoryWriteTransaction<'db> {
+    txn: MemoryReadTransaction<'db>,
+    db: &'db MemoryDatabase,
+    _writer: MutexGuard<'db, ()>,
+}
+
+impl<'db> MemoryWriteTransaction<'db> {
+    pub(super) fn new(
+        db: &'db MemoryDatabase,
+        writer: MutexGuard<'db, ()>,
+        tables: Tables,
+    ) -> Self {
+        MemoryWriteTransaction {
+            txn: MemoryReadTransaction::new(tables, true),
+            db,
+            _writer: writer,
+        }
+    }
+}
+
+impl<'db> ReadTransaction<'db> for MemoryWriteTransaction<'db> {
+    type Cursor<'txn, T: Table>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    type DupCursor<'txn, T: DupTable>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    fn get<T: Table>(&self, table: &T, key: &T::Key) -> Option<T::Value> {
+        self.txn.get(table, key)
+    }
+
+    fn cursor<'txn, T: RegularTable>(&'txn self, table: &T) -> Self::Cursor<'txn, T> {
+        ReadTransaction::cursor(&self.txn, table)
+    }
+
+    fn dup_cursor<'txn, T: DupTable>(&'txn self, table: &T) -> Self::DupCursor<'txn, T> {
+        ReadTransaction::dup_cursor(&self.txn, table)
+    }
+}
+
+impl<'db> WriteTransaction<'db> for MemoryWriteTransaction<'db> {
+    type WriteCursor<'txn, T: Table>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    type DupWriteCursor<'txn, T: DupTable>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    fn put_reserve<T: RegularTable>(&mut self, table: &T, key: &T::Key, value: &T::Value)
+    where
+        T::Value: IntoDatabaseValue,
+    {
+        let table = self.txn.table_mut(table);
+
+        let key = table.encode_key(&AsDatabaseBytes::as_key_bytes(key));
+        let mut bytes = vec![0; IntoDatabaseValue::database_byte_size(value)];
+        IntoDatabaseValue::copy_into_database(value, &mut bytes);
+
+        table.insert(key, bytes);
+    }
+
+    fn put<T: Table>(&mut self, table: &T, key: &T::Key, value: &T::Value) {
+        let table = self.txn.table_mut(table);
+
+        let key = table.encode_key(&AsDatabaseBytes::as_key_bytes(key));
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+
+        table.insert(key, value);
+    }
+
+    fn append<T: Table>(&mut self, table: &T, key: &T::Key, value: &T::Value) {
+        let table = self.txn.table_mut(table);
+
+        let key = table.encode_key(&AsDatabaseBytes::as_key_bytes(key));
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+
+        table.append(key, value);
+    }
+
+    fn remove<T: Table>(&mut self, table: &T, key: &T::Key) {
+        let table = self.txn.table_mut(table);
+
+        let key = table.encode_key(&AsDatabaseBytes::as_key_bytes(key));
+        table.remove(&key);
+    }
+
+    fn remove_item<T: Table>(&mut self, table: &T, key: &T::Key, value: &T::Value) {
+        let table = self.txn.table_mut(table);
+
+        let key = table.encode_key(&AsDatabaseBytes::as_key_bytes(key));
+        table.remove_item(&key, &AsDatabaseBytes::as_value_bytes(value));
+    }
+
+    fn commit(self) {
+        let MemoryWriteTransaction { txn, db, _writer } = self;
+        db.commit(txn.tables.into_inner());
+        // The writer lock is only released after the new state has been published.
+    }
+
+    fn cursor<'txn, T: RegularTable>(&'txn self, table: &T) -> Self::WriteCursor<'txn, T> {
+        ReadTransaction::cursor(&self.txn, table)
+    }
+
+    fn dup_cursor<'txn, T: DupTable>(&'txn self, table: &T) -> Self::DupWriteCursor<'txn, T> {
+        ReadTransaction::dup_cursor(&self.txn, table)
+    }
+
+    fn clear_table<T: Table>(&mut self, table: &T) {
+        self.txn.table_mut(table).clear();
+    }
+}
+
+impl<'db> Deref for MemoryWriteTransaction<'db> {
+    type Target = MemoryReadTransaction<'db>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.txn
+    }
+}
+
+impl<'db> AsRef<MemoryReadTransaction<'db>> for MemoryWriteTransaction<'db> {
+    fn as_ref(&self) -> &MemoryReadTransaction<'db> {
+        &self.txn
+    }
+}
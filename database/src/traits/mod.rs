@@ -10,3 +10,6 @@ pub use transaction::*;
 
 pub type Row<T> = (<T as Table>::Key, <T as Table>::Value);
 pub type DupSubKey<T> = <<T as Table>::Value as DupTableValue>::SubKey;
+
+#[cfg(test)]
+pub(crate) mod tests;
@@ -7,8 +7,14 @@ use futures::{
     future::{BoxFuture, FutureExt},
     stream::{BoxStream, Stream, StreamExt},
 };
+#[cfg(feature = "metrics")]
+use instant::Instant;
+#[cfg(feature = "metrics")]
+use nimiq_time::now;
 use nimiq_time::{interval, Interval};
 
+#[cfg(feature = "metrics")]
+use crate::identity::WeightRegistry;
 use crate::{
     config::Config,
     contribution::AggregatableContribution,
@@ -74,6 +80,14 @@ where
     /// The final result of the aggregation once it has been produced.
     /// A `Some(_)` value here indicates that the aggregation has finished.
     final_result: Option<P::Contribution>,
+
+    /// The time at which this aggregation was started.
+    #[cfg(feature = "metrics")]
+    started_at: Instant,
+
+    /// Whether the best aggregate has reached the threshold of the metrics.
+    #[cfg(feature = "metrics")]
+    threshold_reached: bool,
 }
 
 impl<TId, P, N> Aggregation<TId, P, N>
@@ -90,7 +104,7 @@ where
         network: N,
     ) -> Self {
         // Create the sender, buffering a single message per recipient.
-        let sender = NetworkHelper::new(protocol.partitioner().size(), network);
+        let sender = NetworkHelper::new(protocol.partitioner().size(), network, &config);
 
         // Invoke the partitioner to create the level structure of peers.
         let levels = Level::create_levels(
@@ -115,7 +129,7 @@ where
         // Regardless of level completion consecutive levels need to be activated at some point.
         // Activate levels every time this interval ticks, if the level has not already been
         // activated due to level completion.
-        let start_level_interval = interval(config.level_timeout);
+        let start_level_interval = interval(config.current_level_timeout());
 
         // Every `config.update_interval` send level updates to corresponding peers no matter the
         // aggregation's progression (makes sure other peers can catch up).
@@ -133,6 +147,10 @@ where
             periodic_update_interval,
             current_verification: None,
             final_result: None,
+            #[cfg(feature = "metrics")]
+            started_at: now(),
+            #[cfg(feature = "metrics")]
+            threshold_reached: false,
         }
     }
 
@@ -176,7 +194,25 @@ where
         }
 
         // Reset the level timeout.
-        self.start_level_interval = interval(self.config.level_timeout);
+        self.start_level_interval = interval(self.config.current_level_timeout());
+    }
+
+    /// Records the time it took to reach the threshold of the metrics, if `aggregate` reaches it
+    /// for the first time.
+    #[cfg(feature = "metrics")]
+    fn note_threshold(&mut self, aggregate: &P::Contribution) {
+        let Some(metrics) = &self.config.metrics else {
+            return;
+        };
+        if self.threshold_reached {
+            return;
+        }
+
+        let weight = self.protocol.registry().signature_weight(aggregate);
+        if weight.is_some_and(|weight| weight >= metrics.threshold()) {
+            self.threshold_reached = true;
+            metrics.note_threshold_reached(now().saturating_duration_since(self.started_at));
+        }
     }
 
     fn num_contributors(&self, aggregate: &P::Contribution) -> usize {
@@ -439,6 +475,11 @@ where
         // Poll the input stream for new level updates.
         let evaluator = self.protocol.evaluator();
         while let Poll::Ready(Some(update)) = self.input_stream.poll_next_unpin(cx) {
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = &self.config.metrics {
+                metrics.note_level_update_received(update.level());
+            }
+
             // Verify the level update.
             if let Err(error) = evaluator.verify(&update) {
                 warn!(
@@ -483,6 +524,10 @@ where
                         ?contribution,
                         "Rejecting invalid contribution"
                     );
+                    #[cfg(feature = "metrics")]
+                    if let Some(metrics) = &self.config.metrics {
+                        metrics.note_invalid_contribution(&result);
+                    }
                     self.network.ban_node(contribution.origin);
                 }
             }
@@ -510,7 +555,7 @@ where
             while let Poll::Ready(Some(contribution)) =
                 self.pending_contributions.poll_next_unpin(cx)
             {
-                if !contribution.trusted() {
+                if contribution.trusted() {
                     // No need to verify the trusted contribution.
                     // Apply it and return the new best aggregate.
                     best_aggregate = Some(self.apply_contribution(contribution));
@@ -535,6 +580,10 @@ where
                         ?contribution,
                         "Rejecting invalid contribution"
                     );
+                    #[cfg(feature = "metrics")]
+                    if let Some(metrics) = &self.config.metrics {
+                        metrics.note_invalid_contribution(&result);
+                    }
                     self.network.ban_node(contribution.origin);
                 }
             }
@@ -555,6 +604,9 @@ where
             return Poll::Pending;
         };
 
+        #[cfg(feature = "metrics")]
+        self.note_threshold(&best_aggregate);
+
         // If the best aggregate is a full aggregation, this aggregation is finished.
         if self.is_complete_aggregate(&best_aggregate) {
             debug!(
@@ -1,5 +1,9 @@
 use std::time::Duration;
 
+#[cfg(feature = "metrics")]
+use crate::metrics::AggregationMetrics;
+use crate::timeout::AdaptiveTimeout;
+
 /// Handel configuration settings
 #[derive(Clone, Debug)]
 pub struct Config {
@@ -11,6 +15,24 @@ pub struct Config {
 
     /// Number of peers that are contacted at each level.
     pub peer_count: usize,
+
+    /// If set, the level timeout is derived from the latency observed when sending level
+    /// updates. `level_timeout` is then only used until a latency has been observed.
+    pub adaptive_timeout: Option<AdaptiveTimeout>,
+
+    /// Metrics to report the progress of the aggregation to.
+    #[cfg(feature = "metrics")]
+    pub metrics: Option<AggregationMetrics>,
+}
+
+impl Config {
+    /// Returns the time to wait for a level to complete before starting the next level.
+    pub fn current_level_timeout(&self) -> Duration {
+        match &self.adaptive_timeout {
+            Some(adaptive_timeout) => adaptive_timeout.level_timeout(self.level_timeout),
+            None => self.level_timeout,
+        }
+    }
 }
 
 impl Default for Config {
@@ -19,6 +41,9 @@ impl Default for Config {
             update_interval: Duration::from_millis(500),
             level_timeout: Duration::from_millis(400),
             peer_count: 2,
+            adaptive_timeout: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
         }
     }
 }
@@ -13,11 +13,14 @@ pub mod contribution;
 pub mod evaluator;
 pub mod identity;
 pub mod level;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod network;
 pub mod partitioner;
 pub(crate) mod pending_contributions;
 pub mod protocol;
 pub mod store;
+pub mod timeout;
 pub mod update;
 pub mod verifier;
 
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use prometheus_client::{
+    encoding::{EncodeLabelSet, EncodeLabelValue},
+    metrics::{counter::Counter, family::Family, histogram::Histogram},
+    registry::Registry,
+};
+
+use crate::verifier::VerificationResult;
+
+/// Metrics of all Handel aggregations of a node.
+#[derive(Clone, Debug)]
+pub struct HandelMetrics {
+    threshold_durations: Family<KindLabels, Histogram>,
+    level_updates_sent: Family<LevelLabels, Counter>,
+    level_updates_received: Family<LevelLabels, Counter>,
+    invalid_contributions: Family<InvalidContributionLabels, Counter>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct KindLabels {
+    kind: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct LevelLabels {
+    kind: String,
+    level: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct InvalidContributionLabels {
+    kind: String,
+    reason: InvalidContributionReason,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+enum InvalidContributionReason {
+    UnknownSigner,
+    Forged,
+}
+
+impl Default for HandelMetrics {
+    fn default() -> Self {
+        HandelMetrics {
+            threshold_durations: Family::new_with_constructor(|| {
+                Histogram::new([0.05, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0, 10.0].into_iter())
+            }),
+            level_updates_sent: Default::default(),
+            level_updates_received: Default::default(),
+            invalid_contributions: Default::default(),
+        }
+    }
+}
+
+impl HandelMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "threshold_durations",
+            "Time from starting an aggregation until its aggregate reached the threshold",
+            self.threshold_durations.clone(),
+        );
+
+        registry.register(
+            "level_updates_sent",
+            "Number of level updates sent",
+            self.level_updates_sent.clone(),
+        );
+
+        registry.register(
+            "level_updates_received",
+            "Number of level updates received",
+            self.level_updates_received.clone(),
+        );
+
+        registry.register(
+            "invalid_contributions",
+            "Number of contributions rejected by the verifier",
+            self.invalid_contributions.clone(),
+        );
+    }
+
+    /// Returns the metrics for aggregations of the given `kind`. Those aggregations reach their
+    /// threshold once their aggregate carries a weight of at least `threshold`.
+    pub fn aggregation(&self, kind: &str, threshold: usize) -> AggregationMetrics {
+        AggregationMetrics {
+            metrics: self.clone(),
+            kind: kind.to_string(),
+            threshold,
+        }
+    }
+}
+
+/// Metrics of a single kind of aggregation, e.g. the Tendermint or the skip block aggregations.
+#[derive(Clone, Debug)]
+pub struct AggregationMetrics {
+    metrics: HandelMetrics,
+    kind: String,
+    threshold: usize,
+}
+
+impl AggregationMetrics {
+    pub(crate) fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub(crate) fn note_threshold_reached(&self, duration: Duration) {
+        self.metrics
+            .threshold_durations
+            .get_or_create(&KindLabels {
+                kind: self.kind.clone(),
+            })
+            .observe(duration.as_secs_f64());
+    }
+
+    pub(crate) fn note_level_update_sent(&self, level: usize) {
+        self.metrics
+            .level_updates_sent
+            .get_or_create(&self.level_labels(level))
+            .inc();
+    }
+
+    pub(crate) fn note_level_update_received(&self, level: usize) {
+        self.metrics
+            .level_updates_received
+            .get_or_create(&self.level_labels(level))
+            .inc();
+    }
+
+    pub(crate) fn note_invalid_contribution(&self, result: &VerificationResult) {
+        let reason = match result {
+            VerificationResult::Ok => return,
+            VerificationResult::UnknownSigner { .. } => InvalidContributionReason::UnknownSigner,
+            VerificationResult::Forged => InvalidContributionReason::Forged,
+        };
+        self.metrics
+            .invalid_contributions
+            .get_or_create(&InvalidContributionLabels {
+                kind: self.kind.clone(),
+                reason,
+            })
+            .inc();
+    }
+
+    fn level_labels(&self, level: usize) -> LevelLabels {
+        LevelLabels {
+            kind: self.kind.clone(),
+            level: level.to_string(),
+        }
+    }
+}
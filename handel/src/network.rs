@@ -14,9 +14,15 @@ use futures::{
 use instant::Instant;
 use linked_hash_map::{Entry, LinkedHashMap};
 use nimiq_collections::BitSet;
+use nimiq_time::now;
 use nimiq_utils::{stream::FuturesUnordered, WakerExt as _};
 
-use crate::{contribution::AggregatableContribution, identity::Identity, update::LevelUpdate};
+#[cfg(feature = "metrics")]
+use crate::metrics::AggregationMetrics;
+use crate::{
+    config::Config, contribution::AggregatableContribution, identity::Identity,
+    timeout::AdaptiveTimeout, update::LevelUpdate,
+};
 
 /// Trait defining the interface to the network. The only requirement for handel is that the network is able to send
 /// a message to a specific validator.
@@ -57,8 +63,10 @@ pub struct NetworkHelper<TNetwork: Network> {
     /// Information about the last message sent to each recipient, used to deduplicate messages.
     last_messages: HashMap<usize, LastLevelUpdate>,
 
-    /// The collection of currently pending send futures.
-    pending_sends: FuturesUnordered<BoxFuture<'static, Result<(usize, BitSet), TNetwork::Error>>>,
+    /// The collection of currently pending send futures. They resolve to the recipient, the
+    /// signers that were sent and the time it took to send them.
+    pending_sends:
+        FuturesUnordered<BoxFuture<'static, Result<(usize, BitSet, Duration), TNetwork::Error>>>,
 
     /// The collection of currently pending ban futures.
     pending_bans: FuturesUnordered<BoxFuture<'static, ()>>,
@@ -68,13 +76,20 @@ pub struct NetworkHelper<TNetwork: Network> {
 
     /// A waker option which is set whenever the future was called while there was no future pending.
     waker: Option<Waker>,
+
+    /// The adaptive level timeout which is fed the latency of sent messages.
+    adaptive_timeout: Option<AdaptiveTimeout>,
+
+    /// Metrics to count the sent messages in.
+    #[cfg(feature = "metrics")]
+    metrics: Option<AggregationMetrics>,
 }
 
 impl<TNetwork: Network> NetworkHelper<TNetwork> {
     const MAX_PARALLEL_SENDS: usize = 10;
     const ALLOW_RESEND_AFTER: Duration = Duration::from_secs(5);
 
-    pub fn new(num_nodes: usize, network: TNetwork) -> Self {
+    pub fn new(num_nodes: usize, network: TNetwork, config: &Config) -> Self {
         Self {
             num_nodes,
             message_buffer: LinkedHashMap::with_capacity(num_nodes),
@@ -83,6 +98,9 @@ impl<TNetwork: Network> NetworkHelper<TNetwork> {
             pending_bans: FuturesUnordered::new(),
             network,
             waker: None,
+            adaptive_timeout: config.adaptive_timeout.clone(),
+            #[cfg(feature = "metrics")]
+            metrics: config.metrics.clone(),
         }
     }
 
@@ -107,7 +125,8 @@ impl<TNetwork: Network> NetworkHelper<TNetwork> {
         // unnecessarily repeating the same updates.
         if let Some(last_update) = self.last_messages.get(&node_id) {
             let same_signers = last_update.signers == msg.aggregate.contributors();
-            let recently_sent = last_update.sent_at.elapsed() < Self::ALLOW_RESEND_AFTER;
+            let recently_sent =
+                now().saturating_duration_since(last_update.sent_at) < Self::ALLOW_RESEND_AFTER;
             if same_signers && recently_sent {
                 return;
             }
@@ -151,22 +170,41 @@ impl<TNetwork: Network + Unpin> Future for NetworkHelper<TNetwork> {
                     break;
                 };
 
+                #[cfg(feature = "metrics")]
+                if let Some(metrics) = &self.metrics {
+                    metrics.note_level_update_sent(update.level());
+                }
+
                 // Create the future to send the message and add it to the pending futures.
                 let signers = update.aggregate.contributors();
                 let sender = self.network.send_update(node_id as u16, update);
-                let future = async move { sender.await.map(|_| (node_id, signers)) }.boxed();
+                let started_at = now();
+                let future = async move {
+                    sender.await.map(|_| {
+                        (
+                            node_id,
+                            signers,
+                            now().saturating_duration_since(started_at),
+                        )
+                    })
+                }
+                .boxed();
                 self.pending_sends.push(future);
             }
 
             // Poll the pending level update senders.
             while let Poll::Ready(Some(result)) = self.pending_sends.poll_next_unpin(cx) {
                 match result {
-                    Ok((node_id, signers)) => {
+                    Ok((node_id, signers, latency)) => {
+                        if let Some(adaptive_timeout) = &self.adaptive_timeout {
+                            adaptive_timeout.observe(latency);
+                        }
+
                         self.last_messages.insert(
                             node_id,
                             LastLevelUpdate {
                                 signers,
-                                sent_at: Instant::now(),
+                                sent_at: now(),
                             },
                         );
                     }
@@ -206,6 +244,7 @@ mod test {
     use serde::{Deserialize, Serialize};
 
     use crate::{
+        config::Config,
         contribution::{AggregatableContribution, ContributionError},
         identity::Identity,
         network::{Network, NetworkHelper},
@@ -255,7 +294,7 @@ mod test {
     async fn it_buffers_messages() {
         let t = Arc::new(Mutex::new(vec![]));
         let nw = Net(Arc::clone(&t));
-        let mut sender = NetworkHelper::new(20, nw);
+        let mut sender = NetworkHelper::new(20, nw, &Config::default());
 
         fn send(sender: &mut NetworkHelper<Net>, i: u32) {
             // Actual values do not matter here.
@@ -0,0 +1,127 @@
+use std::{sync::Arc, time::Duration};
+
+use parking_lot::Mutex;
+
+/// Level timeout that adapts to the latency observed when sending level updates.
+///
+/// Clones share the same latency estimate, such that one instance can be handed to all
+/// aggregations of a node and each aggregation starts with what the previous ones observed.
+#[derive(Clone, Debug)]
+pub struct AdaptiveTimeout {
+    /// Lower bound for the level timeout.
+    pub min: Duration,
+
+    /// Upper bound for the level timeout.
+    pub max: Duration,
+
+    /// Factor applied to the smoothed latency to obtain the level timeout.
+    pub multiplier: u32,
+
+    /// Smoothed latency of level updates, `None` until the first latency was observed.
+    latency: Arc<Mutex<Option<Duration>>>,
+}
+
+impl AdaptiveTimeout {
+    /// Every observed latency contributes with a weight of `1 / SMOOTHING` to the estimate.
+    const SMOOTHING: u32 = 8;
+
+    pub fn new(min: Duration, max: Duration, multiplier: u32) -> Self {
+        assert!(
+            min <= max,
+            "The minimum timeout must not exceed the maximum"
+        );
+        Self {
+            min,
+            max,
+            multiplier,
+            latency: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Feeds the latency of a single level update into the estimate.
+    pub fn observe(&self, latency: Duration) {
+        let mut estimate = self.latency.lock();
+        *estimate = Some(match *estimate {
+            Some(estimate) => (estimate * (Self::SMOOTHING - 1) + latency) / Self::SMOOTHING,
+            None => latency,
+        });
+    }
+
+    /// The current latency estimate, if any latency was observed yet.
+    pub fn latency(&self) -> Option<Duration> {
+        *self.latency.lock()
+    }
+
+    /// Returns the level timeout for the current latency estimate, or `fallback` if no latency
+    /// was observed yet.
+    pub fn level_timeout(&self, fallback: Duration) -> Duration {
+        match self.latency() {
+            Some(latency) => (latency * self.multiplier).clamp(self.min, self.max),
+            None => fallback,
+        }
+    }
+}
+
+impl Default for AdaptiveTimeout {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(100), Duration::from_secs(2), 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use nimiq_test_log::test;
+
+    use super::AdaptiveTimeout;
+
+    #[test]
+    fn it_falls_back_without_observations() {
+        let timeout = AdaptiveTimeout::default();
+        assert_eq!(timeout.latency(), None);
+        assert_eq!(
+            timeout.level_timeout(Duration::from_millis(400)),
+            Duration::from_millis(400)
+        );
+    }
+
+    #[test]
+    fn it_smooths_observed_latencies() {
+        let timeout = AdaptiveTimeout::new(Duration::from_millis(10), Duration::from_secs(10), 2);
+
+        timeout.observe(Duration::from_millis(100));
+        assert_eq!(timeout.latency(), Some(Duration::from_millis(100)));
+
+        timeout.observe(Duration::from_millis(900));
+        assert_eq!(timeout.latency(), Some(Duration::from_millis(200)));
+        assert_eq!(
+            timeout.level_timeout(Duration::ZERO),
+            Duration::from_millis(400)
+        );
+
+        // Clones share the estimate.
+        timeout.clone().observe(Duration::from_millis(200));
+        assert_eq!(timeout.latency(), Some(Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn it_clamps_the_level_timeout() {
+        let timeout =
+            AdaptiveTimeout::new(Duration::from_millis(100), Duration::from_millis(500), 2);
+
+        timeout.observe(Duration::from_millis(10));
+        assert_eq!(
+            timeout.level_timeout(Duration::ZERO),
+            Duration::from_millis(100)
+        );
+
+        let timeout =
+            AdaptiveTimeout::new(Duration::from_millis(100), Duration::from_millis(500), 2);
+        timeout.observe(Duration::from_secs(1));
+        assert_eq!(
+            timeout.level_timeout(Duration::ZERO),
+            Duration::from_millis(500)
+        );
+    }
+}
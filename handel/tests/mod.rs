@@ -14,6 +14,7 @@ use nimiq_handel::{
     partitioner::BinomialPartitioner,
     protocol,
     store::ReplaceStore,
+    timeout::AdaptiveTimeout,
     update::LevelUpdate,
     verifier::{VerificationResult, Verifier},
 };
@@ -78,14 +79,24 @@ impl IdentityRegistry for Registry {
     }
 }
 
-/// A dump Verifier who is happy with everything.
-pub struct DumbVerifier {}
+/// A Verifier checking that the value of a contribution is the sum of `node_id + 1` of all its
+/// contributors, which is what honest nodes contribute.
+pub struct SumVerifier {}
 
 #[async_trait]
-impl Verifier for DumbVerifier {
+impl Verifier for SumVerifier {
     type Contribution = Contribution;
-    async fn verify(&self, _contribution: &Self::Contribution) -> VerificationResult {
-        VerificationResult::Ok
+    async fn verify(&self, contribution: &Self::Contribution) -> VerificationResult {
+        let expected: u64 = contribution
+            .contributors
+            .iter()
+            .map(|node_id| node_id as u64 + 1)
+            .sum();
+        if contribution.value == expected {
+            VerificationResult::Ok
+        } else {
+            VerificationResult::Forged
+        }
     }
 }
 
@@ -93,7 +104,7 @@ pub type Evaluator = WeightedVote<usize, Protocol>;
 
 // The test protocol combining the other types.
 pub struct Protocol {
-    verifier: Arc<DumbVerifier>,
+    verifier: Arc<SumVerifier>,
     partitioner: Arc<BinomialPartitioner>,
     evaluator: Arc<Evaluator>,
     store: Arc<RwLock<ReplaceStore<usize, Self>>>,
@@ -116,7 +127,7 @@ impl Protocol {
         ));
 
         Protocol {
-            verifier: Arc::new(DumbVerifier {}),
+            verifier: Arc::new(SumVerifier {}),
             partitioner,
             evaluator,
             store,
@@ -175,7 +186,7 @@ impl<C: AggregatableContribution + 'static> RequestCommon for Update<C> {
 
 impl protocol::Protocol<usize> for Protocol {
     type Contribution = Contribution;
-    type Verifier = DumbVerifier;
+    type Verifier = SumVerifier;
     type Registry = Registry;
     type Partitioner = BinomialPartitioner;
     type Store = ReplaceStore<usize, Self>;
@@ -333,6 +344,7 @@ async fn handel_happy_path() {
         update_interval: Duration::from_millis(100),
         level_timeout: Duration::from_millis(500),
         peer_count: 1,
+        ..Default::default()
     };
 
     let mut hub = MockHub::default();
@@ -360,6 +372,48 @@ async fn handel_happy_path() {
     wait_for_contributions(num_contributors, num_contributors, &mut rx).await;
 }
 
+#[test(tokio::test)]
+async fn handel_adaptive_timeout() {
+    let num_contributors = 20;
+    let adaptive_timeout = AdaptiveTimeout::default();
+    let mut config = Config {
+        update_interval: Duration::from_millis(100),
+        level_timeout: Duration::from_millis(500),
+        peer_count: 1,
+        ..Default::default()
+    };
+    config.adaptive_timeout = Some(adaptive_timeout.clone());
+
+    let mut hub = MockHub::default();
+    let mut networks: Vec<Arc<MockNetwork>> = vec![];
+    let (tx, mut rx) = mpsc::channel(1024);
+
+    // Spawn `num_contributors` handel instances sharing the adaptive timeout.
+    for node_id in 0..num_contributors {
+        let network = spawn_handel_instance(
+            config.clone(),
+            num_contributors,
+            node_id,
+            &mut hub,
+            tx.clone(),
+        );
+
+        // Connect the network to all already existing ones.
+        for net in &networks {
+            network.dial_mock(net);
+        }
+        networks.push(network);
+    }
+
+    // Wait for all aggregations to complete.
+    wait_for_contributions(num_contributors, num_contributors, &mut rx).await;
+
+    // The latency of the sent level updates must have been picked up.
+    assert!(adaptive_timeout.latency().is_some());
+    assert!(config.current_level_timeout() >= adaptive_timeout.min);
+    assert!(config.current_level_timeout() <= adaptive_timeout.max);
+}
+
 #[test(tokio::test)]
 async fn handel_one_node_late() {
     let num_contributors = 17;
@@ -367,6 +421,7 @@ async fn handel_one_node_late() {
         update_interval: Duration::from_millis(100),
         level_timeout: Duration::from_millis(500),
         peer_count: 1,
+        ..Default::default()
     };
 
     let mut hub = MockHub::default();
@@ -419,6 +474,7 @@ async fn handel_poor_connectivity() {
         update_interval: Duration::from_millis(100),
         level_timeout: Duration::from_millis(500),
         peer_count: 1,
+        ..Default::default()
     };
 
     let mut hub = MockHub::default();
@@ -453,6 +509,7 @@ async fn handel_netsplit() {
         update_interval: Duration::from_millis(100),
         level_timeout: Duration::from_millis(300),
         peer_count: 1,
+        ..Default::default()
     };
 
     let mut hub = MockHub::default();
@@ -495,3 +552,47 @@ async fn handel_netsplit() {
     // Wait for all aggregations to complete.
     wait_for_contributions(num_contributors, num_contributors, &mut rx).await;
 }
+
+#[test(tokio::test)]
+async fn handel_rejects_forged_contribution() {
+    let num_contributors = 2;
+    let config = Config {
+        update_interval: Duration::from_millis(100),
+        level_timeout: Duration::from_millis(500),
+        peer_count: 1,
+        ..Default::default()
+    };
+
+    let mut hub = MockHub::default();
+    let network = Arc::new(hub.new_network_with_address(0));
+    let mut handel = create_handel_instance(config, num_contributors, 0, Arc::clone(&network));
+
+    // Node 1 doesn't run handel but sends a forged contribution instead.
+    let forger = Arc::new(hub.new_network_with_address(1));
+    forger.dial_mock(&network);
+
+    let mut contributors = BitSet::new();
+    contributors.insert(1);
+    let forged = Contribution {
+        value: 100,
+        contributors,
+    };
+    let update = SerializableLevelUpdate {
+        aggregate: forged.clone(),
+        individual: Some(forged),
+        level: 1,
+    };
+    forger
+        .message(Update(update), MockPeerId(0))
+        .await
+        .expect("Sending the forged update should not fail");
+
+    // The forged contribution is not trusted, so it must be verified and never make it into an
+    // aggregate.
+    while let Ok(Some(contribution)) = timeout(Duration::from_secs(1), handel.next()).await {
+        assert!(
+            !contribution.contributors().contains(1),
+            "Forged contribution was applied: {contribution:?}"
+        );
+    }
+}
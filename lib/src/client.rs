@@ -42,13 +42,17 @@ use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
 use nimiq_wallet::WalletStore;
 use nimiq_zkp::ZKP_VERIFYING_DATA;
 #[cfg(feature = "zkp-prover")]
-use nimiq_zkp_circuits::setup::{all_files_created, load_verifying_data, setup, DEVELOPMENT_SEED};
+use nimiq_zkp_circuits::setup::{
+    all_files_created, load_verifying_data, setup, verify_keys, DEVELOPMENT_SEED,
+};
 #[cfg(feature = "database-storage")]
 use nimiq_zkp_component::proof_store::{DBProofStore, ProofStore};
 use nimiq_zkp_component::zkp_component::{
     ZKPComponent as AbstractZKPComponent, ZKPComponentProxy as AbstractZKPComponentProxy,
 };
 #[cfg(feature = "zkp-prover")]
+use nimiq_zkp_component::zkp_prover::ProverBackend;
+#[cfg(feature = "zkp-prover")]
 use nimiq_zkp_primitives::NanoZKPError;
 use parking_lot::{Mutex, RwLock};
 #[cfg(feature = "zkp-prover")]
@@ -109,14 +113,22 @@ pub(crate) struct ClientInner {
     zkp_component: ZKPComponentProxy,
 }
 
-/// This function is used to generate the services flags (provided, needed) based upon the configured sync mode
-pub fn generate_service_flags(sync_mode: SyncMode, index_history: bool) -> (Services, Services) {
+/// This function is used to generate the services flags (provided, needed) based upon the configured sync mode.
+/// History nodes that prune their history (`full_history` is false) can't serve it nor their index to other nodes.
+pub fn generate_service_flags(
+    sync_mode: SyncMode,
+    index_history: bool,
+    full_history: bool,
+) -> (Services, Services) {
     let provided_services = match sync_mode {
         // Services provided by history nodes
         SyncMode::History => {
             log::info!("Client configured as a history node");
             let mut services = Services::provided(NodeType::History);
-            if index_history {
+            if !full_history {
+                log::info!("History is pruned, not providing it to other nodes");
+                services.remove(Services::HISTORY);
+            } else if index_history {
                 services |= Services::TRANSACTION_INDEX;
             }
             services
@@ -171,7 +183,7 @@ impl ClientInner {
         }
 
         // Load the correct verifying key.
-        ZKP_VERIFYING_DATA.init_with_network_id(config.network_id);
+        ZKP_VERIFYING_DATA.init_with_network_id(config.network_id)?;
 
         #[cfg(not(feature = "zkp-prover"))]
         if config.zk_prover.is_some() {
@@ -180,8 +192,11 @@ impl ClientInner {
 
         #[cfg(feature = "zkp-prover")]
         // If the Prover is active we need to ensure that the proving keys are present.
+        // Remote prover servers use their own proving keys.
         if let Some(ref zk_prover_config) = config.zk_prover {
-            if !all_files_created(&zk_prover_config.prover_keys_path, true) {
+            if zk_prover_config.remote.is_none()
+                && !all_files_created(&zk_prover_config.prover_keys_path, true)
+            {
                 match config.network_id {
                     NetworkId::DevAlbatross => {
                         log::info!("Setting up zero-knowledge prover keys for devnet.");
@@ -220,6 +235,12 @@ impl ClientInner {
                     _ => {}
                 }
             }
+
+            // Refuse to start the prover with keys that do not match the verifying keys of the
+            // network, since all proofs generated with them would be rejected.
+            if zk_prover_config.remote.is_none() {
+                verify_keys(&zk_prover_config.prover_keys_path, &ZKP_VERIFYING_DATA)?;
+            }
         }
 
         #[cfg(feature = "full-consensus")]
@@ -234,8 +255,11 @@ impl ClientInner {
             identity_keypair.public().to_peer_id().to_base58()
         );
 
-        let (mut provided_services, required_services) =
-            generate_service_flags(config.consensus.sync_mode, config.consensus.index_history);
+        let (mut provided_services, required_services) = generate_service_flags(
+            config.consensus.sync_mode,
+            config.consensus.index_history,
+            config.consensus.history_addresses.is_none(),
+        );
 
         // We update the services flags depending on our validator configuration
         #[cfg(feature = "validator")]
@@ -252,6 +276,9 @@ impl ClientInner {
             .advertised_addresses
             .clone()
             .unwrap_or_default();
+        if let Some(quic) = &config.network.quic {
+            peer_contact_addresses.extend(quic.advertised_addresses.iter().cloned());
+        }
         peer_contact_addresses.retain(|address| {
             let mut protocols = address.iter();
             match protocols.next() {
@@ -260,6 +287,19 @@ impl ClientInner {
                 _ => true,
             }
         });
+        // We advertise to be a relay if we are configured as a relay server and can be reached
+        // directly on one of our advertised addresses.
+        if config.network.relay_server.is_some() {
+            let is_reachable = peer_contact_addresses
+                .iter()
+                .any(|address| !address.iter().any(|p| matches!(p, Protocol::P2pCircuit)));
+            if is_reachable {
+                provided_services |= Services::RELAY;
+            } else {
+                log::warn!("Relay server is enabled, but no address is advertised to reach it");
+            }
+        }
+
         let mut peer_contact = PeerContact::new(
             peer_contact_addresses,
             identity_keypair.public(),
@@ -320,6 +360,9 @@ impl ClientInner {
             seeds,
             network_info.genesis_hash().clone(),
             false,
+            config.network.quic.is_some(),
+            config.network.relay_client,
+            config.network.relay_server,
             required_services,
             tls_config,
             config.network.desired_peer_count,
@@ -329,6 +372,7 @@ impl ClientInner {
                 .network
                 .dht_quorum
                 .unwrap_or(NonZeroU8::new(3).unwrap()),
+            config.network.gossipsub,
         );
 
         log::debug!(
@@ -363,8 +407,9 @@ impl ClientInner {
         };
 
         #[cfg(feature = "database-storage")]
-        let zkp_storage: Option<Box<dyn ProofStore>> =
-            Some(Box::new(DBProofStore::new(environment.clone())));
+        let zkp_storage: Option<Box<dyn ProofStore>> = Some(Box::new(
+            DBProofStore::with_history_retention(environment.clone(), config.consensus.zkp_history),
+        ));
         #[cfg(not(feature = "database-storage"))]
         let zkp_storage = None;
 
@@ -381,6 +426,8 @@ impl ClientInner {
             SyncMode::History => {
                 blockchain_config.keep_history = true;
                 blockchain_config.index_history = config.consensus.index_history;
+                blockchain_config.secondary_indexes = config.consensus.secondary_index_kinds();
+                blockchain_config.history_pruning = config.consensus.history_pruning();
                 let blockchain = match Blockchain::new(
                     environment.clone(),
                     blockchain_config,
@@ -400,8 +447,15 @@ impl ClientInner {
                         blockchain_proxy.clone(),
                         Arc::clone(&network),
                         true,
-                        None,
+                        match zk_prover_config.remote {
+                            Some(remote) => ProverBackend::Remote(remote),
+                            None => ProverBackend::Local {
+                                prover_path: None,
+                                resources: zk_prover_config.resources,
+                            },
+                        },
                         zk_prover_config.prover_keys_path,
+                        zk_prover_config.proving_policy,
                         zkp_storage,
                     )
                     .await
@@ -446,8 +500,15 @@ impl ClientInner {
                         blockchain_proxy.clone(),
                         Arc::clone(&network),
                         true,
-                        None,
+                        match zk_prover_config.remote {
+                            Some(remote) => ProverBackend::Remote(remote),
+                            None => ProverBackend::Local {
+                                prover_path: None,
+                                resources: zk_prover_config.resources,
+                            },
+                        },
                         zk_prover_config.prover_keys_path,
+                        zk_prover_config.proving_policy,
                         zkp_storage,
                     )
                     .await
@@ -467,8 +528,9 @@ impl ClientInner {
                     zkp_component.proxy(),
                     network_events,
                     config.consensus.full_sync_threshold,
+                    config.consensus.trusted_checkpoint.clone(),
                 )
-                .await;
+                .await?;
                 (blockchain_proxy, syncer, zkp_component)
             }
             SyncMode::Light => {
@@ -538,6 +600,8 @@ impl ClientInner {
                         voting_key,
                         fee_key,
                         config.mempool.clone(),
+                        validator_config.adaptive_aggregation_timeouts,
+                        validator_config.selection_policy,
                     );
 
                     // Use the validator's mempool as TransactionVerificationCache in the blockchain.
@@ -572,7 +636,11 @@ impl ClientInner {
         }
 
         // Start network.
-        network.listen_on(config.network.listen_addresses).await;
+        let mut listen_addresses = config.network.listen_addresses;
+        if let Some(quic) = config.network.quic {
+            listen_addresses.extend(quic.listen_addresses);
+        }
+        network.listen_on(listen_addresses).await;
         network.start_connecting().await;
 
         Ok(Client {
@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use log::level_filters::{LevelFilter, ParseLevelFilterError};
+use nimiq_hash::Blake2bHash;
 use nimiq_primitives::networks::NetworkId;
 use thiserror::Error;
 
@@ -55,6 +56,15 @@ pub struct CommandLine {
     #[clap(long = "mode", value_parser)]
     pub sync_mode: Option<SyncMode>,
 
+    /// Bootstrap a full node from the trusted election block with the given hash instead of the ZKP.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client --mode full --trusted-checkpoint 2b8a1d...`
+    ///
+    #[clap(long, value_parser)]
+    pub trusted_checkpoint: Option<Blake2bHash>,
+
     /// Configure the network to connect to, one of main-albatross, test-albatross (default), dev-albatross.
     ///
     /// # Examples
@@ -64,9 +74,81 @@ pub struct CommandLine {
     #[clap(long)]
     pub network: Option<NetworkId>,
 
+    /// Write a consistent snapshot of the database to the given directory and exit.
+    /// This can be used while another client is running on the same database.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client --snapshot ~/nimiq-snapshot`
+    ///
+    #[clap(long, value_name = "DIR")]
+    pub snapshot: Option<PathBuf>,
+
+    /// Preallocate the space used by the database for the snapshot written by `--snapshot`.
+    /// By default, the snapshot is sized to its content.
+    #[clap(long, action, requires = "snapshot")]
+    pub snapshot_keep_size: bool,
+
+    /// Replace the database with the snapshot in the given directory before starting.
+    /// The snapshot is validated first, the client must not be running.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client --restore-snapshot ~/nimiq-snapshot`
+    ///
+    #[clap(long, value_name = "DIR", conflicts_with = "snapshot")]
+    pub restore_snapshot: Option<PathBuf>,
+
     /// Internally used flag to start a zero-knowledge prover process.
     #[clap(long, action)]
     pub prove: bool,
+
+    /// Run a maintenance command instead of starting the client.
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Maintenance commands that operate on the client's database and exit.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Export a range of epochs (macro blocks, history and the micro blocks of the current batch)
+    /// to a file.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client export-chain --file chain.bin --from-epoch 1 --to-epoch 100`
+    ///
+    ExportChain {
+        /// The file to write the export to.
+        #[clap(long)]
+        file: PathBuf,
+        /// The first epoch to export.
+        #[clap(long, default_value_t = 1)]
+        from_epoch: u32,
+        /// The last epoch to export (default: the current epoch).
+        #[clap(long)]
+        to_epoch: Option<u32>,
+    },
+    /// Import a file created by `export-chain` into the database of a history node.
+    /// All imported blocks and history are verified.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client import-chain --file chain.bin`
+    ///
+    ImportChain {
+        /// The file to import.
+        #[clap(long)]
+        file: PathBuf,
+    },
+    /// Run a prover server that generates zero-knowledge proofs for remote nodes instead of
+    /// starting the client. The server is configured in the `[zk-prover]` section.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client prover-server`
+    ///
+    ProverServer,
 }
 
 impl CommandLine {
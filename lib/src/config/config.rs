@@ -1,3 +1,5 @@
+#[cfg(feature = "full-consensus")]
+use std::collections::BTreeSet;
 #[cfg(any(feature = "rpc-server", feature = "metrics-server"))]
 use std::net::IpAddr;
 #[cfg(feature = "metrics-server")]
@@ -7,26 +9,41 @@ use std::{
     num::NonZeroU8,
     path::{Path, PathBuf},
     string::ToString,
+    time::Duration,
 };
 
 use derive_builder::Builder;
+#[cfg(feature = "full-consensus")]
+use nimiq_blockchain::{HistoryPruning, SecondaryIndexKind};
 #[cfg(feature = "validator")]
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
+use nimiq_consensus::messages::BlockHeaderTopic;
 #[cfg(feature = "database-storage")]
 use nimiq_database::mdbx::MdbxDatabase;
 use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
 #[cfg(feature = "validator")]
-use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_keys::{KeyPair, PrivateKey};
+#[cfg(feature = "validator")]
+use nimiq_mempool::selection::SelectionPolicy;
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::{config::MempoolConfig, filter::MempoolRules};
-use nimiq_network_interface::Multiaddr;
-use nimiq_network_libp2p::{Keypair as IdentityKeypair, Libp2pKeyPair};
+use nimiq_network_interface::{network::Topic, Multiaddr, Protocol};
+use nimiq_network_libp2p::{
+    libp2p::gossipsub, GossipsubConfig, Keypair as IdentityKeypair, Libp2pKeyPair,
+    RelayServerConfig,
+};
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_serde::Deserialize;
+use nimiq_transaction::TransactionTopic;
 #[cfg(feature = "validator")]
 use nimiq_utils::key_rng::SecureGenerate;
 use nimiq_utils::{file_store::FileStore, Sensitive};
 use nimiq_zkp_circuits::DEFAULT_PROVER_KEYS_PATH;
+use nimiq_zkp_component::{
+    proof_store::ProofHistoryRetention,
+    types::{ProverResources, ProverServerConfig, ProvingPolicy, RemoteProverConfig, ZKProofTopic},
+};
 use subtle::ConstantTimeEq;
 
 #[cfg(feature = "database-storage")]
@@ -38,7 +55,10 @@ use crate::config::consts::default_bind;
 use crate::{
     config::{
         command_line::CommandLine,
-        config_file::{ConfigFile, Seed, TlsSettings},
+        config_file::{
+            ConfigFile, GossipsubSettings, QuicSettings, RelaySettings, SecondaryIndex, Seed,
+            TlsSettings, TopicScoreSettings,
+        },
         paths,
         user_agent::UserAgent,
     },
@@ -89,9 +109,23 @@ pub struct ConsensusConfig {
     #[builder(default = "10800")]
     /// Minimum distance away, in number of blocks, from the head to switch from state sync to live sync
     pub full_sync_threshold: u32,
+    #[builder(default)]
+    /// Hash of a trusted election block to bootstrap from instead of the ZKP. Only effective for full nodes
+    pub trusted_checkpoint: Option<Blake2bHash>,
     #[builder(default = "true")]
     /// History indices enabled. Only effective for history nodes (default: `true`)
     pub index_history: bool,
+    #[builder(default)]
+    /// Addresses for which the transaction history of finalized epochs is kept. If set, the
+    /// history of all other transactions is pruned. Only effective for history nodes
+    pub history_addresses: Option<Vec<Address>>,
+    #[builder(default)]
+    /// Secondary indexes maintained over the transaction history. Only effective for history nodes
+    /// with `index_history` enabled
+    pub secondary_indexes: Vec<SecondaryIndex>,
+    #[builder(default)]
+    /// Election blocks for which the proofs are kept in addition to the latest one
+    pub zkp_history: ProofHistoryRetention,
 }
 
 impl Default for ConsensusConfig {
@@ -101,11 +135,34 @@ impl Default for ConsensusConfig {
             min_peers: 3,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
             full_sync_threshold: 10800,
+            trusted_checkpoint: None,
             index_history: true,
+            history_addresses: None,
+            secondary_indexes: vec![],
+            zkp_history: ProofHistoryRetention::default(),
         }
     }
 }
 
+#[cfg(feature = "full-consensus")]
+impl ConsensusConfig {
+    /// Returns the history pruning policy for the blockchain.
+    pub fn history_pruning(&self) -> HistoryPruning {
+        match &self.history_addresses {
+            Some(addresses) => HistoryPruning::KeepAddresses(addresses.iter().cloned().collect()),
+            None => HistoryPruning::KeepAll,
+        }
+    }
+
+    /// Returns the secondary indexes for the history store.
+    pub fn secondary_index_kinds(&self) -> BTreeSet<SecondaryIndexKind> {
+        self.secondary_indexes
+            .iter()
+            .map(|&secondary_index| secondary_index.into())
+            .collect()
+    }
+}
+
 /// Network config
 #[derive(Debug, Clone, Builder, Default)]
 #[builder(setter(into))]
@@ -135,6 +192,18 @@ pub struct NetworkConfig {
     #[builder(default)]
     pub tls: Option<TlsConfig>,
 
+    /// Optional QUIC transport configuration
+    #[builder(default)]
+    pub quic: Option<QuicConfig>,
+
+    /// Reserve a slot on a relay peer if this node is not publicly reachable
+    #[builder(default)]
+    pub relay_client: bool,
+
+    /// Optional relay server configuration. If set, this node acts as a relay for other peers.
+    #[builder(default)]
+    pub relay_server: Option<RelayServerConfig>,
+
     /// Optional desired number of peers for the network to connect to.
     /// The network will always try to maintain this number of connections.
     #[builder(default = "12")]
@@ -151,6 +220,10 @@ pub struct NetworkConfig {
     /// Optional quorum value for the network DHT
     #[builder(default)]
     pub dht_quorum: Option<NonZeroU8>,
+
+    /// Gossipsub mesh and topic score settings
+    #[builder(default)]
+    pub gossipsub: GossipsubConfig,
 }
 
 /// Configuration for setting TLS for secure WebSocket
@@ -172,6 +245,151 @@ impl From<TlsSettings> for TlsConfig {
     }
 }
 
+/// Configuration of the QUIC transport
+#[derive(Debug, Clone, Default)]
+pub struct QuicConfig {
+    /// List of QUIC addresses this node is going to listen to
+    pub listen_addresses: Vec<Multiaddr>,
+    /// List of QUIC addresses that are advertised to other peers
+    pub advertised_addresses: Vec<Multiaddr>,
+}
+
+impl TryFrom<&QuicSettings> for QuicConfig {
+    type Error = Error;
+
+    fn try_from(value: &QuicSettings) -> Result<Self, Self::Error> {
+        if cfg!(not(feature = "tokio-quic")) {
+            return Err(Error::config_error(
+                "The QUIC transport requires the `tokio-quic` feature",
+            ));
+        }
+
+        let parse_quic_addresses = |addresses: &[String]| {
+            addresses
+                .iter()
+                .map(|address| -> Result<Multiaddr, Error> {
+                    let address: Multiaddr = address.parse()?;
+                    if !address.iter().any(|p| matches!(p, Protocol::QuicV1)) {
+                        return Err(Error::config_error(format!(
+                            "Not a QUIC address: {address}"
+                        )));
+                    }
+                    Ok(address)
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            listen_addresses: parse_quic_addresses(&value.listen_addresses)?,
+            advertised_addresses: parse_quic_addresses(&value.advertised_addresses)?,
+        })
+    }
+}
+
+impl From<&RelaySettings> for RelayServerConfig {
+    fn from(value: &RelaySettings) -> Self {
+        let mut config = RelayServerConfig::default();
+        if let Some(max_reservations) = value.max_reservations {
+            config.max_reservations = max_reservations;
+        }
+        if let Some(max_reservations_per_peer) = value.max_reservations_per_peer {
+            config.max_reservations_per_peer = max_reservations_per_peer;
+        }
+        if let Some(reservation_duration_secs) = value.reservation_duration_secs {
+            config.reservation_duration = Duration::from_secs(reservation_duration_secs);
+        }
+        if let Some(max_circuits) = value.max_circuits {
+            config.max_circuits = max_circuits;
+        }
+        if let Some(max_circuits_per_peer) = value.max_circuits_per_peer {
+            config.max_circuits_per_peer = max_circuits_per_peer;
+        }
+        if let Some(max_circuit_duration_secs) = value.max_circuit_duration_secs {
+            config.max_circuit_duration = Duration::from_secs(max_circuit_duration_secs);
+        }
+        if let Some(max_circuit_bytes) = value.max_circuit_bytes {
+            config.max_circuit_bytes = max_circuit_bytes;
+        }
+        config
+    }
+}
+
+impl From<TopicScoreSettings> for gossipsub::TopicScoreParams {
+    fn from(value: TopicScoreSettings) -> Self {
+        let mut params = gossipsub::TopicScoreParams::default();
+        if let Some(topic_weight) = value.topic_weight {
+            params.topic_weight = topic_weight;
+        }
+        if let Some(time_in_mesh_weight) = value.time_in_mesh_weight {
+            params.time_in_mesh_weight = time_in_mesh_weight;
+        }
+        if let Some(time_in_mesh_quantum_ms) = value.time_in_mesh_quantum_ms {
+            params.time_in_mesh_quantum = Duration::from_millis(time_in_mesh_quantum_ms);
+        }
+        if let Some(time_in_mesh_cap) = value.time_in_mesh_cap {
+            params.time_in_mesh_cap = time_in_mesh_cap;
+        }
+        if let Some(weight) = value.first_message_deliveries_weight {
+            params.first_message_deliveries_weight = weight;
+        }
+        if let Some(decay) = value.first_message_deliveries_decay {
+            params.first_message_deliveries_decay = decay;
+        }
+        if let Some(cap) = value.first_message_deliveries_cap {
+            params.first_message_deliveries_cap = cap;
+        }
+        if let Some(weight) = value.invalid_message_deliveries_weight {
+            params.invalid_message_deliveries_weight = weight;
+        }
+        if let Some(decay) = value.invalid_message_deliveries_decay {
+            params.invalid_message_deliveries_decay = decay;
+        }
+        params
+    }
+}
+
+impl TryFrom<&GossipsubSettings> for GossipsubConfig {
+    type Error = Error;
+
+    fn try_from(value: &GossipsubSettings) -> Result<Self, Self::Error> {
+        let mut config = GossipsubConfig::default();
+        if let Some(mesh_n) = value.mesh_n {
+            config.mesh_n = mesh_n;
+        }
+        if let Some(mesh_n_low) = value.mesh_n_low {
+            config.mesh_n_low = mesh_n_low;
+        }
+        if let Some(mesh_n_high) = value.mesh_n_high {
+            config.mesh_n_high = mesh_n_high;
+        }
+        if let Some(mesh_outbound_min) = value.mesh_outbound_min {
+            config.mesh_outbound_min = mesh_outbound_min;
+        }
+        if let Some(heartbeat_interval_ms) = value.heartbeat_interval_ms {
+            config.heartbeat_interval = Duration::from_millis(heartbeat_interval_ms);
+        }
+        if let Some(max_transmit_size) = value.max_transmit_size {
+            config.max_transmit_size = max_transmit_size;
+        }
+
+        let topics = [
+            (BlockHeaderTopic::NAME, &value.block_header),
+            (TransactionTopic::NAME, &value.transaction),
+            (ZKProofTopic::NAME, &value.zk_proof),
+        ];
+        for (topic_name, settings) in topics {
+            if let Some(settings) = settings {
+                config
+                    .topic_score_params
+                    .insert(topic_name.to_string(), settings.clone().into());
+            }
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct FileStorageConfig {
     /// The parent directory where the database will be stored. The database directory name
@@ -261,8 +479,8 @@ pub struct DatabaseConfig {
     #[builder(default = "1024 * 1024 * 1024 * 1024")]
     size: usize,
 
-    /// Max number of DBs. Recommended: 20
-    #[builder(default = "20")]
+    /// Max number of DBs. Recommended: 32
+    #[builder(default = "nimiq_database::mdbx::DEFAULT_MAX_TABLES")]
     max_dbs: u32,
 
     /// Max number of threads that can open read transactions.
@@ -278,12 +496,25 @@ impl Default for DatabaseConfig {
         Self {
             // 1 TB
             size: 1024 * 1024 * 1024 * 1024,
-            max_dbs: 20,
+            max_dbs: nimiq_database::mdbx::DEFAULT_MAX_TABLES,
             max_readers: 600,
         }
     }
 }
 #[cfg(feature = "database-storage")]
+impl DatabaseConfig {
+    /// Returns the options to open the database with. Tools that open the node's database should
+    /// use these as well, so that all tables of the node can be opened.
+    pub fn mdbx_config(&self) -> nimiq_database::mdbx::DatabaseConfig {
+        nimiq_database::mdbx::DatabaseConfig {
+            max_tables: Some(self.max_dbs as u64),
+            size: Some(-1..self.size as isize),
+            max_readers: Some(self.max_readers),
+            ..Default::default()
+        }
+    }
+}
+#[cfg(feature = "database-storage")]
 impl From<Option<DatabaseSettings>> for DatabaseConfig {
     fn from(db_settings: Option<DatabaseSettings>) -> Self {
         let default = DatabaseConfig::default();
@@ -340,22 +571,14 @@ impl StorageConfig {
         sync_mode: SyncMode,
         db_config: DatabaseConfig,
     ) -> Result<MdbxDatabase, Error> {
-        use nimiq_database::mdbx;
-
         let db_name = format!("{network_id}-{sync_mode}-consensus").to_lowercase();
         log::info!("Opening database: {}", db_name);
 
-        let config = mdbx::DatabaseConfig {
-            max_tables: Some(db_config.max_dbs as u64),
-            size: Some(-1..db_config.size as isize),
-            max_readers: Some(db_config.max_readers),
-            ..Default::default()
-        };
+        let config = db_config.mdbx_config();
 
-        Ok(match self {
-            StorageConfig::Volatile => MdbxDatabase::new_volatile(config)?,
-            StorageConfig::Filesystem(file_storage) => {
-                let db_path = file_storage.database_parent.join(db_name);
+        Ok(match self.database_path(network_id, sync_mode) {
+            None => MdbxDatabase::new_volatile(config)?,
+            Some(db_path) => {
                 let db_path = db_path
                     .to_str()
                     .ok_or_else(|| {
@@ -370,6 +593,19 @@ impl StorageConfig {
         })
     }
 
+    /// Returns the directory of the database for the given network ID and consensus type, or
+    /// `None` for volatile storage.
+    #[cfg(feature = "database-storage")]
+    pub fn database_path(&self, network_id: NetworkId, sync_mode: SyncMode) -> Option<PathBuf> {
+        match self {
+            StorageConfig::Volatile => None,
+            StorageConfig::Filesystem(file_storage) => {
+                let db_name = format!("{network_id}-{sync_mode}-consensus").to_lowercase();
+                Some(file_storage.database_parent.join(db_name))
+            }
+        }
+    }
+
     #[cfg(feature = "validator")]
     pub(crate) fn voting_keypair(&self) -> Result<BlsKeyPair, Error> {
         Ok(match self {
@@ -508,6 +744,12 @@ pub struct ValidatorConfig {
 
     /// Config if the validator automatically reactivates itself.
     pub automatic_reactivate: bool,
+
+    /// Config if the level timeouts of the signature aggregations adapt to the observed latency.
+    pub adaptive_aggregation_timeouts: bool,
+
+    /// The policy used to select the transactions included in produced blocks.
+    pub selection_policy: SelectionPolicy,
 }
 
 /// Credentials for JSON RPC server, metrics server or websocket RPC server
@@ -569,6 +811,10 @@ pub struct RpcServerConfig {
     /// If specified, require HTTP basic auth with these credentials
     #[builder(setter(strip_option))]
     pub credentials: Option<Credentials>,
+
+    /// If specified, database snapshots can be created via RPC in this directory
+    #[builder(setter(strip_option))]
+    pub snapshot_dir: Option<PathBuf>,
 }
 
 #[cfg(feature = "metrics-server")]
@@ -669,10 +915,22 @@ impl ClientConfigBuilder {
     pub fn build(&self) -> Result<ClientConfig, Error> {
         // NOTE: We rename the generated builder and make it private to map the error from a plain
         // `String` to an actual Error.
-        // We could also put some validation here.
+        let config = self
+            .build_internal()
+            .map_err(|e| Error::config_error(e.to_string()))?;
+
+        // Only full nodes can bootstrap from a trusted checkpoint, history nodes sync the whole
+        // history and light nodes don't have an accounts tree to sync the state into.
+        if config.consensus.trusted_checkpoint.is_some()
+            && config.consensus.sync_mode != SyncMode::Full
+        {
+            return Err(Error::config_error(format!(
+                "A trusted checkpoint is only supported in full sync mode, not in {} sync mode",
+                config.consensus.sync_mode
+            )));
+        }
 
-        self.build_internal()
-            .map_err(|e| Error::config_error(e.to_string()))
+        Ok(config)
     }
 
     /// Sets the network ID to the Albatross DevNet
@@ -754,15 +1012,34 @@ impl ClientConfigBuilder {
             desired_peer_count: config_file.network.desired_peer_count,
 
             tls: config_file.network.tls.as_ref().map(|s| s.clone().into()),
+            quic: config_file
+                .network
+                .quic
+                .as_ref()
+                .map(QuicConfig::try_from)
+                .transpose()?,
+            relay_client: config_file
+                .network
+                .relay
+                .as_ref()
+                .is_some_and(|relay| relay.client),
+            relay_server: config_file
+                .network
+                .relay
+                .as_ref()
+                .filter(|relay| relay.server)
+                .map(RelayServerConfig::from),
             only_secure_ws_connections: false,
             allow_loopback_addresses: config_file.network.allow_loopback_addresses,
             dht_quorum: config_file.network.dht_quorum,
+            gossipsub: GossipsubConfig::try_from(&config_file.network.gossipsub)?,
         });
 
         // Configure consensus
         let mut consensus = ConsensusConfigBuilder::default()
             .sync_mode(config_file.consensus.sync_mode)
             .index_history(config_file.consensus.index_history)
+            .secondary_indexes(config_file.consensus.secondary_indexes.clone())
             .build()
             .unwrap();
         if let Some(min_peers) = config_file.consensus.min_peers {
@@ -771,6 +1048,32 @@ impl ClientConfigBuilder {
         if let Some(full_sync_threshold) = config_file.consensus.full_sync_threshold {
             consensus.full_sync_threshold = full_sync_threshold;
         }
+        if config_file.consensus.max_epochs_stored > 0 {
+            consensus.max_epochs_stored = config_file.consensus.max_epochs_stored as u32;
+        }
+        if let Some(history_addresses) = &config_file.consensus.history_addresses {
+            consensus.history_addresses = Some(
+                history_addresses
+                    .iter()
+                    .map(|address| {
+                        Address::from_any_str(address).map_err(|_| {
+                            Error::config_error(format!("Invalid history address: {address}"))
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        if config_file.consensus.zkp_history {
+            consensus.zkp_history = match config_file.consensus.zkp_history_epochs {
+                Some(num_epochs) => ProofHistoryRetention::Epochs(num_epochs),
+                None => ProofHistoryRetention::All,
+            };
+        }
+        if let Some(trusted_checkpoint) = &config_file.consensus.trusted_checkpoint {
+            consensus.trusted_checkpoint = Some(trusted_checkpoint.parse().map_err(|_| {
+                Error::config_error(format!("Invalid trusted checkpoint: {trusted_checkpoint}"))
+            })?);
+        }
         self.consensus(consensus);
 
         // Configure network
@@ -793,9 +1096,24 @@ impl ClientConfigBuilder {
         }
         #[cfg(feature = "validator")]
         if let Some(validator_config) = config_file.validator.as_ref() {
+            let selection_policy = match &validator_config.block_production {
+                Some(settings) => {
+                    let min_fee_per_byte = settings.min_fee_per_byte;
+                    if !min_fee_per_byte.is_finite() || min_fee_per_byte < 0.0 {
+                        return Err(Error::config_error(format!(
+                            "Invalid min_fee_per_byte setting: {min_fee_per_byte}"
+                        )));
+                    }
+                    SelectionPolicy::from(settings.clone())
+                }
+                None => SelectionPolicy::default(),
+            };
+
             self.validator(ValidatorConfig {
                 validator_address: Address::from_any_str(&validator_config.validator_address)?,
                 automatic_reactivate: validator_config.automatic_reactivate,
+                adaptive_aggregation_timeouts: validator_config.adaptive_aggregation_timeouts,
+                selection_policy,
             });
 
             if let Some(key_path) = &validator_config.voting_key_file {
@@ -830,7 +1148,71 @@ impl ClientConfigBuilder {
                 .as_ref()
                 .map_or(PathBuf::from(DEFAULT_PROVER_KEYS_PATH), PathBuf::from);
 
-            self.zk_prover = Some(Some(ZKProverConfig { prover_keys_path }));
+            let remote = if zkp_settings.remote_servers.is_empty() {
+                None
+            } else {
+                Some(RemoteProverConfig {
+                    servers: zkp_settings.remote_servers.clone(),
+                    auth_token: zkp_settings.auth_token.clone().ok_or_else(|| {
+                        Error::config_error("No auth token for the remote prover servers specified")
+                    })?,
+                    connect_timeout: Duration::from_secs(
+                        zkp_settings.remote_connect_timeout.unwrap_or(10),
+                    ),
+                    proof_timeout: Duration::from_secs(zkp_settings.remote_timeout.unwrap_or(7200)),
+                    retries: zkp_settings.remote_retries.unwrap_or(3),
+                    retry_delay: Duration::from_secs(zkp_settings.remote_retry_delay.unwrap_or(30)),
+                })
+            };
+
+            let resources = ProverResources {
+                max_threads: zkp_settings.max_threads,
+                niceness: zkp_settings.niceness,
+                cpu_affinity: zkp_settings.cpu_affinity.clone(),
+                max_memory: zkp_settings.max_memory,
+            };
+
+            let proving_policy = ProvingPolicy {
+                every_nth_epoch: match zkp_settings.prove_every_nth_epoch {
+                    Some(0) => {
+                        return Err(Error::config_error(
+                            "The prove_every_nth_epoch setting must be at least 1",
+                        ))
+                    }
+                    Some(n) => n,
+                    None => 1,
+                },
+                peer_proof_wait: Duration::from_secs(zkp_settings.peer_proof_wait.unwrap_or(0)),
+            };
+
+            let server = if let Some(listen_address) = &zkp_settings.server_listen_address {
+                Some(ProverServerConfig {
+                    listen_address: listen_address.parse().map_err(|e| {
+                        Error::config_error(format!(
+                            "Invalid prover server listen address {}: {}",
+                            listen_address, e
+                        ))
+                    })?,
+                    auth_token: zkp_settings.auth_token.clone().ok_or_else(|| {
+                        Error::config_error("No auth token for the prover server specified")
+                    })?,
+                    prover_keys_path: prover_keys_path.clone(),
+                    prover_path: None,
+                    resources: resources.clone(),
+                    max_queued_jobs: zkp_settings.server_max_queued_jobs.unwrap_or(4),
+                    max_connections: zkp_settings.server_max_connections.unwrap_or(16),
+                })
+            } else {
+                None
+            };
+
+            self.zk_prover = Some(Some(ZKProverConfig {
+                prover_keys_path,
+                remote,
+                server,
+                resources,
+                proving_policy,
+            }));
         }
 
         // Configure RPC server
@@ -880,6 +1262,7 @@ impl ClientConfigBuilder {
                     allow_ips,
                     allowed_methods: Some(rpc_config.methods.clone()),
                     credentials,
+                    snapshot_dir: rpc_config.snapshot_dir.as_ref().map(PathBuf::from),
                 }));
             }
         }
@@ -930,6 +1313,13 @@ impl ClientConfigBuilder {
                 .sync_mode = sync_mode.into()
         }
 
+        // Set trusted checkpoint
+        if let Some(trusted_checkpoint) = &command_line.trusted_checkpoint {
+            self.consensus
+                .get_or_insert_with(ConsensusConfig::default)
+                .trusted_checkpoint = Some(trusted_checkpoint.clone());
+        }
+
         // Set network ID
         if let Some(network_id) = command_line.network {
             self.network_id(network_id);
@@ -945,4 +1335,20 @@ impl ClientConfigBuilder {
 pub struct ZKProverConfig {
     /// Prover keys path for the zkp prover.
     pub prover_keys_path: PathBuf,
+
+    /// Remote prover servers that generate the proofs instead of a local prover process.
+    #[builder(default)]
+    pub remote: Option<RemoteProverConfig>,
+
+    /// The prover server started by the `prover-server` command.
+    #[builder(default)]
+    pub server: Option<ProverServerConfig>,
+
+    /// The resources the local prover process may use.
+    #[builder(default)]
+    pub resources: ProverResources,
+
+    /// Defines for which election blocks proofs are generated.
+    #[builder(default)]
+    pub proving_policy: ProvingPolicy,
 }
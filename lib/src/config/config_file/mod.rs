@@ -4,11 +4,14 @@ use std::{
 };
 
 use log::level_filters::LevelFilter;
+#[cfg(feature = "full-consensus")]
+use nimiq_blockchain::SecondaryIndexKind;
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::{
     config::MempoolConfig,
     filter::{MempoolFilter, MempoolRules},
     mempool::Mempool,
+    selection::{SelectionPolicy, SelectionStrategy},
 };
 use nimiq_network_interface::Multiaddr;
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
@@ -173,6 +176,8 @@ pub struct NetworkSettings {
     pub user_agent: Option<String>,
 
     pub tls: Option<TlsSettings>,
+    pub quic: Option<QuicSettings>,
+    pub relay: Option<RelaySettings>,
     pub instant_inbound: Option<bool>,
     #[serde(default = "NetworkSettings::default_desired_peer_count")]
     pub desired_peer_count: usize,
@@ -180,6 +185,8 @@ pub struct NetworkSettings {
     pub allow_loopback_addresses: bool,
     #[serde(default)]
     pub dht_quorum: Option<NonZeroU8>,
+    #[serde(default)]
+    pub gossipsub: GossipsubSettings,
 }
 
 impl NetworkSettings {
@@ -193,6 +200,92 @@ pub struct Seed {
     pub address: Multiaddr,
 }
 
+/// Settings for the QUIC transport. The transport is enabled if these settings are present.
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct QuicSettings {
+    /// The local QUIC multiaddresses this node should bind to
+    #[serde(default)]
+    pub listen_addresses: Vec<String>,
+    /// QUIC multiaddresses that will be advertised to peers in addition to `advertised_addresses`
+    #[serde(default)]
+    pub advertised_addresses: Vec<String>,
+}
+
+/// Settings for the circuit relay. Relaying is disabled if these settings are not present.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelaySettings {
+    /// Reserve a slot on a relay peer if this node is not publicly reachable (default: `true`)
+    #[serde(default = "default_true")]
+    pub client: bool,
+    /// Act as a relay for peers that are not publicly reachable (default: `false`)
+    #[serde(default)]
+    pub server: bool,
+    /// Maximum number of reservations of the relay server
+    pub max_reservations: Option<usize>,
+    /// Maximum number of reservations per peer of the relay server
+    pub max_reservations_per_peer: Option<usize>,
+    /// Duration of a reservation in seconds
+    pub reservation_duration_secs: Option<u64>,
+    /// Maximum number of relayed connections of the relay server
+    pub max_circuits: Option<usize>,
+    /// Maximum number of relayed connections per peer of the relay server
+    pub max_circuits_per_peer: Option<usize>,
+    /// Maximum duration of a relayed connection in seconds
+    pub max_circuit_duration_secs: Option<u64>,
+    /// Maximum number of bytes transferred over a relayed connection
+    pub max_circuit_bytes: Option<u64>,
+}
+
+/// Settings for tuning the gossipsub mesh and the peer scoring of the gossiped topics
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GossipsubSettings {
+    /// Target number of peers in the mesh of a topic
+    pub mesh_n: Option<usize>,
+    /// Minimum number of peers in the mesh of a topic before adding more
+    pub mesh_n_low: Option<usize>,
+    /// Maximum number of peers in the mesh of a topic before pruning some
+    pub mesh_n_high: Option<usize>,
+    /// Minimum number of outbound peers in the mesh of a topic
+    pub mesh_outbound_min: Option<usize>,
+    /// Time between gossipsub heartbeats in milliseconds
+    pub heartbeat_interval_ms: Option<u64>,
+    /// Maximum size of a gossipsub message in bytes
+    pub max_transmit_size: Option<usize>,
+    /// Score parameters for the block header topic
+    pub block_header: Option<TopicScoreSettings>,
+    /// Score parameters for the transaction topic
+    pub transaction: Option<TopicScoreSettings>,
+    /// Score parameters for the zk proof topic
+    pub zk_proof: Option<TopicScoreSettings>,
+}
+
+/// Peer score parameters of a single gossipsub topic. Unset values use the libp2p defaults.
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TopicScoreSettings {
+    /// Weight of the topic in the overall peer score
+    pub topic_weight: Option<f64>,
+    /// Weight of the time a peer has been in the mesh of the topic
+    pub time_in_mesh_weight: Option<f64>,
+    /// Time in milliseconds a peer has to be in the mesh to count as one time quantum
+    pub time_in_mesh_quantum_ms: Option<u64>,
+    /// Maximum value of the time in mesh counter
+    pub time_in_mesh_cap: Option<f64>,
+    /// Weight of the messages first delivered by a peer
+    pub first_message_deliveries_weight: Option<f64>,
+    /// Decay of the first message deliveries counter
+    pub first_message_deliveries_decay: Option<f64>,
+    /// Maximum value of the first message deliveries counter
+    pub first_message_deliveries_cap: Option<f64>,
+    /// Weight of the invalid messages delivered by a peer. Must be negative.
+    pub invalid_message_deliveries_weight: Option<f64>,
+    /// Decay of the invalid message deliveries counter
+    pub invalid_message_deliveries_decay: Option<f64>,
+}
+
 /// Settings for configuring TLS for secure WebSocket
 #[derive(Clone, Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -220,9 +313,24 @@ pub struct ConsensusSettings {
     pub min_peers: Option<usize>,
     /// Minimum distance away, in number of blocks, from the head to switch from state sync to live sync
     pub full_sync_threshold: Option<u32>,
+    /// Hash of a trusted election block to bootstrap from instead of the ZKP. Only supported by full nodes
+    pub trusted_checkpoint: Option<String>,
     /// History indices enabled. Only effective for history nodes (default: `true`)
     #[serde(default = "default_true")]
     pub index_history: bool,
+    /// Addresses for which the transaction history of finalized epochs is kept. If set, the
+    /// history of all other transactions is pruned. Only effective for history nodes
+    pub history_addresses: Option<Vec<String>>,
+    /// Secondary indexes maintained over the transaction history. Only effective for history nodes
+    /// with `index_history` enabled
+    #[serde(default)]
+    pub secondary_indexes: Vec<SecondaryIndex>,
+    /// Keep the proofs of past election blocks in addition to the latest one (default: `false`)
+    #[serde(default)]
+    pub zkp_history: bool,
+    /// Number of most recent epochs for which the proofs are kept. Only effective with
+    /// `zkp_history` enabled (default: keep all proofs)
+    pub zkp_history_epochs: Option<u32>,
 }
 
 impl Default for ConsensusSettings {
@@ -233,7 +341,12 @@ impl Default for ConsensusSettings {
             network: None,
             min_peers: None,
             full_sync_threshold: None,
+            trusted_checkpoint: None,
             index_history: true,
+            history_addresses: None,
+            secondary_indexes: vec![],
+            zkp_history: false,
+            zkp_history_epochs: None,
         }
     }
 }
@@ -278,6 +391,32 @@ impl From<SyncMode> for config::SyncMode {
     }
 }
 
+#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+/// Secondary index that can be maintained over the transaction history of history nodes
+pub enum SecondaryIndex {
+    /// Reward, penalty and jail inherents by validator address
+    Validator,
+    /// HTLC creation and redeem transactions by hash root
+    Htlc,
+    /// Staking contract transactions by type
+    Staking,
+    /// Transactions by block timestamp
+    Timestamp,
+}
+
+#[cfg(feature = "full-consensus")]
+impl From<SecondaryIndex> for SecondaryIndexKind {
+    fn from(secondary_index: SecondaryIndex) -> Self {
+        match secondary_index {
+            SecondaryIndex::Validator => Self::Validator,
+            SecondaryIndex::Htlc => Self::HtlcHashRoot,
+            SecondaryIndex::Staking => Self::StakingType,
+            SecondaryIndex::Timestamp => Self::Timestamp,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct RpcServerSettings {
@@ -293,6 +432,7 @@ pub struct RpcServerSettings {
     pub methods: Vec<String>,
     pub username: Option<String>,
     pub password: Option<Sensitive<String>>,
+    pub snapshot_dir: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Default)]
@@ -478,6 +618,59 @@ pub struct ValidatorSettings {
     pub fee_key: Option<Sensitive<String>>,
     #[serde(default)]
     pub automatic_reactivate: bool,
+    #[serde(default)]
+    pub adaptive_aggregation_timeouts: bool,
+    pub block_production: Option<BlockProductionSettings>,
+}
+
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BlockProductionSettings {
+    /// Strategy used to fill blocks with transactions
+    #[serde(default)]
+    pub strategy: BlockProductionStrategy,
+    /// Maximum number of regular transactions of a single sender per block
+    pub max_transactions_per_sender: Option<usize>,
+    /// Bytes per block in which control transactions are preferred over regular transactions
+    pub reserved_control_bytes: Option<usize>,
+    /// Minimum fee per byte for regular transactions to be included in a block, with a precision
+    /// of 0.001 Luna
+    #[serde(default)]
+    pub min_fee_per_byte: f64,
+}
+
+#[derive(Clone, Copy, Deserialize, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+/// Strategy used to fill blocks with transactions from the mempool
+pub enum BlockProductionStrategy {
+    /// Take transactions by fee per byte until the first one doesn't fit into the block
+    #[default]
+    FeeOrder,
+    /// Take transactions by fee per byte, skipping the ones that don't fit into the block
+    MaxFeePerByte,
+}
+
+#[cfg(feature = "nimiq-mempool")]
+impl From<BlockProductionStrategy> for SelectionStrategy {
+    fn from(strategy: BlockProductionStrategy) -> Self {
+        match strategy {
+            BlockProductionStrategy::FeeOrder => Self::FeeOrder,
+            BlockProductionStrategy::MaxFeePerByte => Self::MaxFeePerByte,
+        }
+    }
+}
+
+/// Convert block production settings
+#[cfg(feature = "nimiq-mempool")]
+impl From<BlockProductionSettings> for SelectionPolicy {
+    fn from(settings: BlockProductionSettings) -> Self {
+        Self {
+            strategy: settings.strategy.into(),
+            max_transactions_per_sender: settings.max_transactions_per_sender,
+            reserved_control_bytes: settings.reserved_control_bytes,
+            min_fee_per_1000_bytes: (settings.min_fee_per_byte * 1000.0).round() as u64,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Default)]
@@ -485,4 +678,20 @@ pub struct ValidatorSettings {
 pub struct ZKProverSettings {
     #[serde(default)]
     pub prover_keys_path: Option<String>,
+    #[serde(default)]
+    pub remote_servers: Vec<String>,
+    pub auth_token: Option<Sensitive<String>>,
+    pub remote_connect_timeout: Option<u64>,
+    pub remote_timeout: Option<u64>,
+    pub remote_retries: Option<u32>,
+    pub remote_retry_delay: Option<u64>,
+    pub server_listen_address: Option<String>,
+    pub server_max_queued_jobs: Option<usize>,
+    pub server_max_connections: Option<usize>,
+    pub max_threads: Option<usize>,
+    pub niceness: Option<i32>,
+    pub cpu_affinity: Option<Vec<usize>>,
+    pub max_memory: Option<u64>,
+    pub prove_every_nth_epoch: Option<u32>,
+    pub peer_proof_wait: Option<u64>,
 }
@@ -23,6 +23,13 @@ pub enum Error {
     #[error("Consensus error: {0}")]
     Consensus(#[from] nimiq_consensus::Error),
 
+    #[error("Blockchain error: {0}")]
+    Blockchain(#[from] nimiq_blockchain_interface::BlockchainError),
+
+    #[cfg(feature = "full-consensus")]
+    #[error("Chain export error: {0}")]
+    ChainExport(#[from] nimiq_blockchain::ChainExportError),
+
     #[error("Config file parsing error: {0}")]
     Toml(#[from] toml::de::Error),
 
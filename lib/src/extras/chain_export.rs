@@ -0,0 +1,80 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+use crate::{
+    config::config::{ClientConfig, SyncMode},
+    error::Error,
+};
+
+/// Opens the blockchain stored in the client's database.
+fn open_blockchain(config: &ClientConfig) -> Result<Blockchain, Error> {
+    let env = config.storage.database(
+        config.network_id,
+        config.consensus.sync_mode,
+        config.database.clone(),
+    )?;
+    let blockchain_config = BlockchainConfig {
+        keep_history: true,
+        max_epochs_stored: config.consensus.max_epochs_stored,
+        index_history: config.consensus.index_history,
+        secondary_indexes: config.consensus.secondary_index_kinds(),
+        history_pruning: config.consensus.history_pruning(),
+    };
+    Ok(Blockchain::new(
+        env,
+        blockchain_config,
+        config.network_id,
+        Arc::new(OffsetTime::new()),
+    )?)
+}
+
+/// Exports the epochs `first_epoch..=last_epoch` of the client's chain to the given file.
+pub fn export_chain(
+    config: &ClientConfig,
+    path: &Path,
+    first_epoch: u32,
+    last_epoch: u32,
+) -> Result<(), Error> {
+    if config.consensus.sync_mode == SyncMode::Light {
+        return Err(Error::config_error(
+            "The chain can only be exported from full and history nodes",
+        ));
+    }
+
+    let blockchain = open_blockchain(config)?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    let summary = blockchain.export_chain(&mut writer, first_epoch, last_epoch)?;
+    writer.flush()?;
+
+    log::info!(
+        path = %path.display(),
+        num_macro_blocks = summary.num_macro_blocks,
+        num_micro_blocks = summary.num_micro_blocks,
+        num_history_items = summary.num_history_items,
+        "Exported chain"
+    );
+    Ok(())
+}
+
+/// Imports a chain export file into the client's database. The imported data is fully verified.
+/// This must only be called while no client is running on the database.
+pub fn import_chain(config: &ClientConfig, path: &Path) -> Result<(), Error> {
+    if config.consensus.sync_mode != SyncMode::History {
+        return Err(Error::config_error(
+            "The chain can only be imported into history nodes",
+        ));
+    }
+
+    let blockchain = RwLock::new(open_blockchain(config)?);
+    let mut reader = BufReader::new(File::open(path)?);
+    Blockchain::import_chain(&blockchain, &mut reader)?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::time::{Duration, Instant};
+
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_database::mdbx::CAPACITY_WARNING_THRESHOLD;
+
+/// The minimum time between two capacity warnings.
+const CAPACITY_WARNING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Warns when the database grows close to the maximum size configured in the `database` section.
+#[derive(Default)]
+pub struct DatabaseCapacityMonitor {
+    last_warning: Option<Instant>,
+}
+
+impl DatabaseCapacityMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks the capacity usage of the blockchain's database and logs a warning if it exceeds
+    /// `CAPACITY_WARNING_THRESHOLD`. Light blockchains don't have a database and are ignored.
+    pub fn check(&mut self, blockchain: &BlockchainProxy) {
+        let BlockchainProxy::Full(ref blockchain) = blockchain else {
+            return;
+        };
+
+        let db = blockchain.read().db().clone();
+        let usage = match db.capacity_usage() {
+            Ok(Some(usage)) => usage,
+            Ok(None) => return,
+            Err(error) => {
+                log::warn!(%error, "Failed to determine database capacity usage");
+                return;
+            }
+        };
+
+        if usage < CAPACITY_WARNING_THRESHOLD {
+            return;
+        }
+        if self
+            .last_warning
+            .is_some_and(|last_warning| last_warning.elapsed() < CAPACITY_WARNING_INTERVAL)
+        {
+            return;
+        }
+
+        log::warn!(
+            usage = %format!("{:.1}%", usage * 100.0),
+            max_size = ?db.max_size(),
+            "The database is approaching its maximum size, consider increasing `database.size`"
+        );
+        self.last_warning = Some(Instant::now());
+    }
+}
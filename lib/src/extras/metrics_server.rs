@@ -4,13 +4,14 @@ use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::mempool::Mempool;
-pub use nimiq_metrics_server::NimiqTaskMonitor;
+pub use nimiq_metrics_server::{HandelMetrics, NimiqTaskMonitor};
 use nimiq_network_interface::network::Network;
 
 pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
     blockchain_proxy: BlockchainProxy,
     #[cfg(feature = "nimiq-mempool")] mempool: Option<Arc<Mempool>>,
+    handel_metrics: Option<HandelMetrics>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
     task_monitors: &[NimiqTaskMonitor],
@@ -21,6 +22,7 @@ pub fn start_metrics_server<TNetwork: Network>(
         addr,
         blockchain_proxy,
         mempool,
+        handel_metrics,
         consensus_proxy,
         network,
         task_monitors,
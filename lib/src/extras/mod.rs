@@ -1,3 +1,7 @@
+#[cfg(feature = "full-consensus")]
+pub mod chain_export;
+#[cfg(feature = "full-consensus")]
+pub mod database_capacity;
 #[cfg(feature = "deadlock")]
 pub mod deadlock;
 #[cfg(feature = "launcher")]
@@ -8,9 +12,13 @@ pub mod logging;
 pub mod metrics_server;
 #[cfg(feature = "panic")]
 pub mod panic;
+#[cfg(feature = "zkp-prover")]
+pub mod prover_server;
 #[cfg(feature = "rpc-server")]
 pub mod rpc_server;
 #[cfg(feature = "signal-handling")]
 pub mod signal_handling;
+#[cfg(feature = "full-consensus")]
+pub mod snapshot;
 #[cfg(feature = "web-logging")]
 pub mod web_logging;
@@ -0,0 +1,35 @@
+use nimiq_zkp::ZKPVerifyingKey;
+use nimiq_zkp_circuits::setup::{all_files_created, verify_keys};
+use nimiq_zkp_component::remote_prover::ProverServer;
+
+use crate::{config::config::ClientConfig, error::Error};
+
+/// Runs a prover server that generates zero-knowledge proofs for remote nodes.
+/// The proving keys must be present, they are not generated by the server. Keys that do not match
+/// the verifying keys of the network are rejected.
+pub async fn run_prover_server(config: &ClientConfig) -> Result<(), Error> {
+    let server_config = config
+        .zk_prover
+        .as_ref()
+        .and_then(|zk_prover_config| zk_prover_config.server.clone())
+        .ok_or_else(|| {
+            Error::config_error("No prover server listen address specified in [zk-prover]")
+        })?;
+
+    if !all_files_created(&server_config.prover_keys_path, true) {
+        return Err(Error::config_error(format!(
+            "Proving keys missing, please place them in this folder: {:?}",
+            server_config.prover_keys_path
+        )));
+    }
+
+    verify_keys(
+        &server_config.prover_keys_path,
+        &ZKPVerifyingKey::embedded(config.network_id)?,
+    )?;
+
+    let server = ProverServer::bind(server_config).await?;
+    server.run().await;
+
+    Ok(())
+}
@@ -43,6 +43,11 @@ pub fn initialize_rpc_server(
 
     dispatcher.add(BlockchainDispatcher::new(client.blockchain()));
 
+    dispatcher.add(DatabaseDispatcher::new(
+        client.blockchain(),
+        config.snapshot_dir,
+    ));
+
     dispatcher.add(ConsensusDispatcher::new(
         client.consensus_proxy(),
         Some(unlocked_wallets),
@@ -0,0 +1,99 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use nimiq_blockchain::Blockchain;
+use nimiq_database::mdbx::MdbxDatabase;
+
+use crate::{
+    config::config::{ClientConfig, SyncMode},
+    error::Error,
+};
+
+/// The name of the MDBX lock file, which must not be copied along with the data.
+const LOCK_FILE_NAME: &str = "mdbx.lck";
+
+/// Writes a consistent snapshot of the client's database to the given directory.
+/// This can be used while a client is running on the same database.
+pub fn create_snapshot(config: &ClientConfig, path: &Path, compact: bool) -> Result<(), Error> {
+    let db = config.storage.database(
+        config.network_id,
+        config.consensus.sync_mode,
+        config.database.clone(),
+    )?;
+    db.snapshot(path, compact)?;
+    Ok(())
+}
+
+/// Replaces the client's database with the snapshot in the given directory. Before anything is
+/// replaced, the snapshot is validated: it must belong to the configured network and its head
+/// block must match its accounts trie.
+///
+/// This must only be called while no client is running on the database.
+pub fn restore_snapshot(config: &ClientConfig, path: &Path) -> Result<(), Error> {
+    if config.consensus.sync_mode == SyncMode::Light {
+        return Err(Error::config_error(
+            "Snapshots can only be restored for full and history nodes",
+        ));
+    }
+
+    let db_path = config
+        .storage
+        .database_path(config.network_id, config.consensus.sync_mode)
+        .ok_or_else(|| Error::config_error("Snapshots can't be restored to volatile storage"))?;
+
+    // Validate the snapshot.
+    {
+        let snapshot = MdbxDatabase::new(path, config.database.mdbx_config())?;
+        let head = Blockchain::verify_database(snapshot, config.network_id)?;
+        log::info!(
+            block_number = head.block_number(),
+            block_hash = %head.hash(),
+            "Validated database snapshot"
+        );
+    }
+
+    // Copy the snapshot next to the current database first, so that the current database is only
+    // replaced once the copy is complete.
+    let restore_path = sibling_path(&db_path, "restore");
+    if restore_path.exists() {
+        fs::remove_dir_all(&restore_path)?;
+    }
+    fs::create_dir_all(&restore_path)?;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if entry.file_name() == LOCK_FILE_NAME {
+            continue;
+        }
+        fs::copy(entry.path(), restore_path.join(entry.file_name()))?;
+    }
+
+    // Directories can't be renamed over non-empty ones, so move the current database aside first.
+    if db_path.exists() {
+        log::warn!(path = %db_path.display(), "Replacing existing database");
+        let old_path = sibling_path(&db_path, "old");
+        if old_path.exists() {
+            fs::remove_dir_all(&old_path)?;
+        }
+        fs::rename(&db_path, &old_path)?;
+        if let Err(error) = fs::rename(&restore_path, &db_path) {
+            fs::rename(&old_path, &db_path)?;
+            return Err(error.into());
+        }
+        fs::remove_dir_all(&old_path)?;
+    } else {
+        fs::rename(&restore_path, &db_path)?;
+    }
+
+    log::info!(path = %db_path.display(), "Restored database snapshot");
+    Ok(())
+}
+
+/// Returns a path in the same directory as `path` with the given suffix appended to its name.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
@@ -1,9 +1,17 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
-use nimiq_lib::config::{
-    config::{ClientConfigBuilder, DatabaseConfig, DatabaseConfigBuilder, FileStorageConfig},
-    config_file::ConfigFile,
+use nimiq_consensus::messages::BlockHeaderTopic;
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_lib::{
+    client::generate_service_flags,
+    config::{
+        config::{ClientConfigBuilder, DatabaseConfig, DatabaseConfigBuilder, FileStorageConfig},
+        config_file::ConfigFile,
+    },
 };
+use nimiq_network_interface::{network::Topic, peer_info::Services};
+use nimiq_network_libp2p::GossipsubConfig;
 use nimiq_test_log::test;
 
 #[test]
@@ -113,3 +121,213 @@ fn config_file_partial_db_entry() {
 
     assert_eq!(config.storage, db_config.into());
 }
+
+#[test]
+fn config_file_gossipsub_entry() {
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [network.gossipsub]
+    mesh_n_low = 4
+    heartbeat_interval_ms = 1000
+
+    [network.gossipsub.block_header]
+    topic_weight = 2.0
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+
+    let gossipsub = config.network.gossipsub;
+    assert_eq!(gossipsub.mesh_n_low, 4);
+    assert_eq!(gossipsub.mesh_n, GossipsubConfig::default().mesh_n);
+    assert_eq!(gossipsub.heartbeat_interval, Duration::from_millis(1000));
+    assert_eq!(gossipsub.topic_score_params.len(), 1);
+    assert_eq!(
+        gossipsub.topic_score_params[BlockHeaderTopic::NAME].topic_weight,
+        2.0
+    );
+}
+
+#[test]
+fn config_file_invalid_gossipsub_entry() {
+    // The minimum mesh size must not exceed the target mesh size
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [network.gossipsub]
+    mesh_n = 4
+    mesh_n_low = 8
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    assert!(config_builder.config_file(&config_file).is_err());
+
+    // Invalid message deliveries must be penalized
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [network.gossipsub.zk_proof]
+    invalid_message_deliveries_weight = 1.0
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    assert!(config_builder.config_file(&config_file).is_err());
+}
+
+#[test]
+fn config_file_trusted_checkpoint_entry() {
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [consensus]
+    sync_mode = "full"
+    trusted_checkpoint = "0000000000000000000000000000000000000000000000000000000000000001"
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+
+    let mut hash = [0u8; 32];
+    hash[31] = 1;
+    assert_eq!(
+        config.consensus.trusted_checkpoint,
+        Some(Blake2bHash::from(hash))
+    );
+
+    // The checkpoint must be a valid hash
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [consensus]
+    trusted_checkpoint = "not a hash"
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    assert!(config_builder.config_file(&config_file).is_err());
+
+    // Other sync modes reject the checkpoint instead of ignoring it
+    for sync_mode in ["history", "light"] {
+        let config_file: ConfigFile = toml::from_str(&format!(
+            r#"
+    [consensus]
+    sync_mode = "{sync_mode}"
+    trusted_checkpoint = "0000000000000000000000000000000000000000000000000000000000000001"
+    "#
+        ))
+        .unwrap();
+
+        let mut config_builder = ClientConfigBuilder::default();
+        config_builder.config_file(&config_file).unwrap();
+        assert!(config_builder.build().is_err());
+    }
+}
+
+#[test]
+fn config_file_history_addresses_entry() {
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [consensus]
+    sync_mode = "history"
+    max_epochs_stored = 4
+    history_addresses = ["NQ07 0000 0000 0000 0000 0000 0000 0000 0000"]
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+
+    assert_eq!(config.consensus.max_epochs_stored, 4);
+    assert_eq!(
+        config.consensus.history_addresses,
+        Some(vec![Address::burn_address()])
+    );
+
+    // A pruned history is neither advertised nor indexed for other nodes
+    let (provided_services, _) = generate_service_flags(
+        config.consensus.sync_mode,
+        config.consensus.index_history,
+        config.consensus.history_addresses.is_none(),
+    );
+    assert!(!provided_services.contains(Services::HISTORY));
+    assert!(!provided_services.contains(Services::TRANSACTION_INDEX));
+    let (provided_services, _) = generate_service_flags(config.consensus.sync_mode, true, true);
+    assert!(provided_services.contains(Services::HISTORY | Services::TRANSACTION_INDEX));
+
+    // The addresses must be valid
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [consensus]
+    history_addresses = ["not an address"]
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    assert!(config_builder.config_file(&config_file).is_err());
+}
+
+#[cfg(feature = "validator")]
+#[test]
+fn config_file_block_production_entry() {
+    use nimiq_lib::config::config_file::BlockProductionStrategy;
+
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [validator]
+    validator_address = "NQ07 0000 0000 0000 0000 0000 0000 0000 0000"
+
+    [validator.block_production]
+    strategy = "max-fee-per-byte"
+    max_transactions_per_sender = 100
+    reserved_control_bytes = 10_000
+    min_fee_per_byte = 1.5
+    "#,
+    )
+    .unwrap();
+
+    let block_production = config_file
+        .validator
+        .as_ref()
+        .and_then(|validator| validator.block_production.as_ref())
+        .unwrap();
+    assert_eq!(
+        block_production.strategy,
+        BlockProductionStrategy::MaxFeePerByte
+    );
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+
+    let selection_policy = config.validator.unwrap().selection_policy;
+    assert_eq!(selection_policy.max_transactions_per_sender, Some(100));
+    assert_eq!(selection_policy.reserved_control_bytes, Some(10_000));
+    assert_eq!(selection_policy.min_fee_per_1000_bytes, 1500);
+
+    // The minimum fee must be a non-negative number
+    for min_fee_per_byte in ["-1.0", "nan"] {
+        let config_file: ConfigFile = toml::from_str(&format!(
+            r#"
+    [validator]
+    validator_address = "NQ07 0000 0000 0000 0000 0000 0000 0000 0000"
+
+    [validator.block_production]
+    min_fee_per_byte = {min_fee_per_byte}
+    "#
+        ))
+        .unwrap();
+
+        let mut config_builder = ClientConfigBuilder::default();
+        assert!(config_builder.config_file(&config_file).is_err());
+    }
+}
@@ -52,6 +52,7 @@ pub static NIMIQ_MODULES: &[&str] = &[
     "nimiq_rpc_interface",
     "nimiq_rpc_server",
     "nimiq_serde",
+    "nimiq_simulation",
     "nimiq_spammer",
     "nimiq_subscription",
     "nimiq_tendermint",
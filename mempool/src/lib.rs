@@ -24,5 +24,7 @@ pub mod mempool;
 mod mempool_metrics;
 /// Mempool transaction module
 pub mod mempool_transactions;
+/// Block transaction selection module
+pub mod selection;
 /// Verify transaction module
 pub mod verify;
@@ -32,6 +32,7 @@ use crate::{
     filter::{MempoolFilter, MempoolRules},
     mempool_state::{EvictionReason, MempoolState},
     mempool_transactions::{MempoolTransactions, TxPriority},
+    selection::{RegularTxSelector, SelectionPolicy, SelectionStrategy, MAX_SKIPPED_TRANSACTIONS},
     verify::{verify_tx, VerifyErr},
 };
 
@@ -505,8 +506,12 @@ impl Mempool {
         max_bytes: usize,
     ) -> (Vec<Transaction>, usize) {
         let mut state = self.state.write();
-        let (txs, size) =
-            Self::get_transactions_for_block_impl(&mut state.regular_transactions, max_bytes);
+        let (txs, size) = Self::get_transactions_for_block_impl(
+            &mut state.regular_transactions,
+            max_bytes,
+            SelectionStrategy::FeeOrder,
+            None,
+        );
 
         for tx in &txs {
             state.remove(blockchain, &tx.hash(), EvictionReason::BlockBuilding);
@@ -546,8 +551,12 @@ impl Mempool {
         max_bytes: usize,
     ) -> (Vec<Transaction>, usize) {
         let mut state = self.state.write();
-        let (txs, size) =
-            Self::get_transactions_for_block_impl(&mut state.control_transactions, max_bytes);
+        let (txs, size) = Self::get_transactions_for_block_impl(
+            &mut state.control_transactions,
+            max_bytes,
+            SelectionStrategy::FeeOrder,
+            None,
+        );
 
         for tx in &txs {
             state.remove(blockchain, &tx.hash(), EvictionReason::BlockBuilding);
@@ -562,40 +571,154 @@ impl Mempool {
         (txs, size)
     }
 
+    /// Returns control and regular transactions for a block, selected according to `policy`, and
+    /// removes them from the mempool. The control transactions come first.
+    /// Note that this takes a read lock on blockchain.
+    ///
+    /// It also return the sum of the serialized size of the returned transactions.
+    pub fn select_transactions_for_block(
+        &self,
+        max_bytes: usize,
+        policy: &SelectionPolicy,
+    ) -> (Vec<Transaction>, usize) {
+        let blockchain = self.blockchain.read();
+        self.select_transactions_for_block_locked(&blockchain, max_bytes, policy)
+    }
+
+    /// Returns control and regular transactions for a block, selected according to `policy`, and
+    /// removes them from the mempool. The control transactions come first.
+    /// If the caller already holds a blockchain lock, it can be passed to this function to prevent
+    /// double-locking the blockchain.
+    ///
+    /// It also return the sum of the serialized size of the returned transactions.
+    pub fn select_transactions_for_block_locked(
+        &self,
+        blockchain: &Blockchain,
+        max_bytes: usize,
+        policy: &SelectionPolicy,
+    ) -> (Vec<Transaction>, usize) {
+        let mut state = self.state.write();
+
+        // Control transactions are preferred, but only within the reserved space if there is any.
+        let control_bytes = policy
+            .reserved_control_bytes
+            .map_or(max_bytes, |reserved| reserved.min(max_bytes));
+        let (mut txs, mut size) = Self::get_transactions_for_block_impl(
+            &mut state.control_transactions,
+            control_bytes,
+            policy.strategy,
+            None,
+        );
+
+        let mut selector = RegularTxSelector::new(policy);
+        let (mut regular_txs, regular_size) = Self::get_transactions_for_block_impl(
+            &mut state.regular_transactions,
+            max_bytes.saturating_sub(size),
+            policy.strategy,
+            Some(&mut selector),
+        );
+        size += regular_size;
+
+        // Control transactions can still use the space left over by the regular transactions.
+        if policy.reserved_control_bytes.is_some() {
+            let (mut control_txs, control_size) = Self::get_transactions_for_block_impl(
+                &mut state.control_transactions,
+                max_bytes.saturating_sub(size),
+                policy.strategy,
+                None,
+            );
+            txs.append(&mut control_txs);
+            size += control_size;
+        }
+
+        let num_control_txs = txs.len();
+        txs.append(&mut regular_txs);
+
+        for tx in &txs {
+            state.remove(blockchain, &tx.hash(), EvictionReason::BlockBuilding);
+        }
+
+        debug!(
+            control_txs = num_control_txs,
+            regular_txs = txs.len() - num_control_txs,
+            remaining_control_txs = state.control_transactions.len(),
+            remaining_regular_txs = state.regular_transactions.len(),
+            "Selected transactions for block from mempool"
+        );
+
+        (txs, size)
+    }
+
     fn get_transactions_for_block_impl(
         transactions: &mut MempoolTransactions,
         max_bytes: usize,
+        strategy: SelectionStrategy,
+        mut selector: Option<&mut RegularTxSelector<'_>>,
     ) -> (Vec<Transaction>, usize) {
         let mut txs = vec![];
         let mut size = 0_usize;
 
-        loop {
-            // Get the hash of the highest paying transactions.
-            let tx_hash = match transactions.best_transactions.peek() {
-                None => break,
-                Some((tx_hash, _)) => tx_hash.clone(),
+        // Transactions that were passed over. They are put back once we are done.
+        let mut skipped = vec![];
+        // The number of passed over transactions that count towards the limit.
+        let mut num_skipped = 0;
+
+        // Remove the transactions from best_transactions so that we can advance.
+        // The caller needs to clean up the rest of the data structures.
+        // We stop once not even the smallest possible transaction fits anymore or once we passed
+        // over too many transactions.
+        while max_bytes.saturating_sub(size) > Transaction::MIN_SIZE
+            && num_skipped < MAX_SKIPPED_TRANSACTIONS
+        {
+            let Some((tx_hash, order)) = transactions.best_transactions.pop() else {
+                break;
             };
 
             // Get the transaction.
-            let tx = transactions.get(&tx_hash).unwrap().clone();
+            let tx = transactions.get(&tx_hash).unwrap();
+
+            // Skip the transaction if the selection policy doesn't allow it. The transactions of
+            // senders that already reached their limit don't count towards the skipped ones, so
+            // that a single sender flooding the mempool can't keep the others out of the block.
+            if let Some(selector) = &selector {
+                if selector.is_capped(&tx.sender) {
+                    skipped.push((tx_hash, order));
+                    continue;
+                }
+                if !selector.accepts(tx) {
+                    skipped.push((tx_hash, order));
+                    num_skipped += 1;
+                    continue;
+                }
+            }
+            let tx = tx.clone();
 
-            // Calculate size. If we can't fit the transaction in the block, then we stop here.
-            // TODO: We can optimize this. There might be a smaller transaction that still fits.
+            // Calculate size. If we can't fit the transaction in the block, then we either stop
+            // here or look for smaller transactions that still fit, depending on the strategy.
             // We need to account for one extra byte per transaction to encode its final execution status
             let next_size = size + 1 + tx.serialized_size();
             if next_size > max_bytes {
-                break;
+                skipped.push((tx_hash, order));
+                num_skipped += 1;
+                match strategy {
+                    SelectionStrategy::FeeOrder => break,
+                    SelectionStrategy::MaxFeePerByte => continue,
+                }
             }
             size = next_size;
 
-            // Remove the transaction from best_transactions so that we can advance.
-            // The caller needs to clean up the rest of the data structures.
-            transactions.best_transactions.pop();
+            if let Some(selector) = &mut selector {
+                selector.select(&tx);
+            }
 
             // Push the transaction to our output vector.
             txs.push(tx);
         }
 
+        for (tx_hash, order) in skipped {
+            transactions.best_transactions.push(tx_hash, order);
+        }
+
         (txs, size)
     }
 
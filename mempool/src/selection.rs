@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use nimiq_keys::Address;
+use nimiq_transaction::Transaction;
+
+/// Maximum number of transactions that are passed over while filling a block, either because they
+/// don't fit or because the selection policy rejects them. This bounds the time spent on a
+/// mempool full of transactions that can't be included. The transactions of senders that reached
+/// [`SelectionPolicy::max_transactions_per_sender`] are passed over without being counted.
+pub const MAX_SKIPPED_TRANSACTIONS: usize = 1_000;
+
+/// Strategy used to fill a block with transactions from the mempool.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SelectionStrategy {
+    /// Transactions are taken in fee per byte order until the first one doesn't fit into the block.
+    #[default]
+    FeeOrder,
+    /// Transactions are taken in fee per byte order, skipping the ones that don't fit into the
+    /// block. This fills the remaining space with smaller transactions, at the cost of looking at
+    /// up to [`MAX_SKIPPED_TRANSACTIONS`] more transactions of the mempool.
+    MaxFeePerByte,
+}
+
+/// Policy defining which transactions of the mempool are included in a block.
+///
+/// The policy is applied on top of the mempool filter rules. Transactions that are not selected
+/// remain in the mempool and can still be included in a later block.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SelectionPolicy {
+    /// Strategy used to fill the block.
+    pub strategy: SelectionStrategy,
+    /// Maximum number of regular transactions of a single sender in a block.
+    pub max_transactions_per_sender: Option<usize>,
+    /// Number of bytes of a block in which control transactions are preferred over regular
+    /// transactions. Control transactions only get the space that regular transactions leave
+    /// over beyond that. If not set, control transactions can take up the whole block.
+    pub reserved_control_bytes: Option<usize>,
+    /// Minimum fee (in Luna) per 1000 bytes for a regular transaction to be included in a block.
+    pub min_fee_per_1000_bytes: u64,
+}
+
+/// Keeps track of the regular transactions selected for a block to enforce a [`SelectionPolicy`].
+pub(crate) struct RegularTxSelector<'a> {
+    policy: &'a SelectionPolicy,
+    selected_per_sender: HashMap<Address, usize>,
+}
+
+impl<'a> RegularTxSelector<'a> {
+    pub(crate) fn new(policy: &'a SelectionPolicy) -> Self {
+        Self {
+            policy,
+            selected_per_sender: HashMap::new(),
+        }
+    }
+
+    /// Checks whether the policy allows adding the transaction to the ones selected so far.
+    pub(crate) fn accepts(&self, tx: &Transaction) -> bool {
+        // Compare `fee / size` with `min_fee_per_1000_bytes / 1000` without rounding errors.
+        let fee = u64::from(tx.fee).saturating_mul(1000);
+        let min_fee = self
+            .policy
+            .min_fee_per_1000_bytes
+            .saturating_mul(tx.serialized_size() as u64);
+        if fee < min_fee {
+            return false;
+        }
+
+        !self.is_capped(&tx.sender)
+    }
+
+    /// Checks whether the sender already reached the maximum number of transactions per sender.
+    pub(crate) fn is_capped(&self, sender: &Address) -> bool {
+        let Some(max) = self.policy.max_transactions_per_sender else {
+            return false;
+        };
+        let selected = self.selected_per_sender.get(sender).copied();
+        selected.unwrap_or(0) >= max
+    }
+
+    /// Records that the transaction was selected.
+    pub(crate) fn select(&mut self, tx: &Transaction) {
+        *self
+            .selected_per_sender
+            .entry(tx.sender.clone())
+            .or_default() += 1;
+    }
+}
@@ -11,9 +11,14 @@ use nimiq_keys::{
     Address, Ed25519PublicKey as SchnorrPublicKey, KeyPair as SchnorrKeyPair,
     PrivateKey as SchnorrPrivateKey, SecureGenerate,
 };
-use nimiq_mempool::{config::MempoolConfig, mempool::Mempool, mempool_transactions::TxPriority};
+use nimiq_mempool::{
+    config::MempoolConfig,
+    mempool::Mempool,
+    mempool_transactions::TxPriority,
+    selection::{SelectionPolicy, SelectionStrategy, MAX_SKIPPED_TRANSACTIONS},
+};
 use nimiq_network_mock::{MockHub, MockId, MockNetwork, MockPeerId};
-use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_primitives::{account::AccountType, coin::Coin, networks::NetworkId, policy::Policy};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_log::test;
 use nimiq_test_utils::{
@@ -21,7 +26,7 @@ use nimiq_test_utils::{
     test_rng::test_rng,
     test_transaction::{generate_accounts, generate_transactions, TestTransaction},
 };
-use nimiq_transaction::{ExecutedTransaction, Transaction};
+use nimiq_transaction::{ExecutedTransaction, SignatureProof, Transaction};
 use nimiq_transaction_builder::TransactionBuilder;
 use nimiq_utils::time::OffsetTime;
 use nimiq_vrf::VrfSeed;
@@ -695,6 +700,310 @@ async fn multiple_transactions_multiple_senders() {
     }
 }
 
+#[test(tokio::test)]
+async fn mempool_select_txn_with_policy() {
+    let mut rng = test_rng(true);
+    let balance = 40;
+    let num_txns = 4;
+    let mut mempool_transactions = vec![];
+    let sender_balances = vec![balance + num_txns * 3; 1];
+    let recipient_balances = vec![0; num_txns as usize];
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    // Generate recipient accounts
+    let recipient_accounts =
+        generate_accounts(recipient_balances, &mut genesis_builder, false, &mut rng);
+    // Generate sender accounts
+    let sender_accounts = generate_accounts(sender_balances, &mut genesis_builder, true, &mut rng);
+
+    // Generate transactions of a single sender with increasing fees
+    for i in 0..num_txns {
+        let mempool_transaction = TestTransaction {
+            fee: (i + 1),
+            value: balance / num_txns,
+            recipient: recipient_accounts[i as usize].clone(),
+            sender: sender_accounts[0].clone(),
+        };
+        mempool_transactions.push(mempool_transaction);
+    }
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+    log::debug!("Done generating transactions and accounts");
+
+    // Create mempool and send the transactions
+    let blockchain = blockchain_with_genesis(genesis_builder, &mut rng);
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+    let mut hub = MockHub::new();
+    let mock_id = MockId::new(hub.new_address().into());
+    let mock_network = Arc::new(hub.new_network());
+
+    // The cheapest transaction is below the fee floor.
+    let policy = SelectionPolicy {
+        strategy: SelectionStrategy::MaxFeePerByte,
+        max_transactions_per_sender: Some(2),
+        reserved_control_bytes: None,
+        min_fee_per_1000_bytes: u64::from(txns[1].fee) * 1000 / txns[1].serialized_size() as u64,
+    };
+
+    send_txn_to_mempool(&mempool, mock_network, mock_id, txns).await;
+
+    // Expect only the two highest paying transactions of the sender
+    let (selected_txns, _) = mempool.select_transactions_for_block(usize::MAX, &policy);
+    let fees: Vec<_> = selected_txns.iter().map(|txn| txn.fee).collect();
+    assert_eq!(
+        fees,
+        vec![Coin::from_u64_unchecked(4), Coin::from_u64_unchecked(3)]
+    );
+
+    // The transactions that were not selected must remain in the mempool
+    assert_eq!(mempool.num_transactions(), 2);
+    let (remaining_txns, _) = mempool.get_transactions_for_block(usize::MAX);
+    let fees: Vec<_> = remaining_txns.iter().map(|txn| txn.fee).collect();
+    assert_eq!(
+        fees,
+        vec![Coin::from_u64_unchecked(2), Coin::from_u64_unchecked(1)]
+    );
+}
+
+/// Creates a blockchain with the accounts of the genesis builder and a random validator.
+fn blockchain_with_genesis(
+    mut genesis_builder: GenesisBuilder,
+    rng: &mut (impl rand::Rng + rand::CryptoRng),
+) -> Arc<RwLock<Blockchain>> {
+    let time = Arc::new(OffsetTime::new());
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = match genesis_info.block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env,
+            BlockchainConfig::default(),
+            time,
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ))
+}
+
+#[test(tokio::test)]
+async fn mempool_select_txn_with_policy_despite_flooding_sender() {
+    let mut rng = test_rng(true);
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let recipient_accounts = generate_accounts(vec![0; 1], &mut genesis_builder, false, &mut rng);
+    let sender_accounts =
+        generate_accounts(vec![10_000_000; 2], &mut genesis_builder, true, &mut rng);
+
+    // One sender floods the mempool with more transactions than can be passed over, all paying
+    // more than the transactions of the other sender.
+    let num_flooding_txns = MAX_SKIPPED_TRANSACTIONS as u64 + 10;
+    let mempool_transactions = (0..num_flooding_txns)
+        .map(|i| TestTransaction {
+            fee: 100 + i,
+            value: 1,
+            recipient: recipient_accounts[0].clone(),
+            sender: sender_accounts[0].clone(),
+        })
+        .chain((0..2).map(|i| TestTransaction {
+            fee: 1 + i,
+            value: 1,
+            recipient: recipient_accounts[0].clone(),
+            sender: sender_accounts[1].clone(),
+        }))
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    let blockchain = blockchain_with_genesis(genesis_builder, &mut rng);
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+    let mut hub = MockHub::new();
+    let mock_id = MockId::new(hub.new_address().into());
+    let mock_network = Arc::new(hub.new_network());
+    send_txn_to_mempool(&mempool, mock_network, mock_id, txns).await;
+    assert_eq!(mempool.num_transactions(), num_flooding_txns as usize + 2);
+
+    let policy = SelectionPolicy {
+        max_transactions_per_sender: Some(1),
+        ..Default::default()
+    };
+
+    // The passed over transactions of the flooding sender don't keep the other sender out
+    let (selected_txns, _) = mempool.select_transactions_for_block(usize::MAX, &policy);
+    let senders: Vec<_> = selected_txns.iter().map(|txn| &txn.sender).collect();
+    assert_eq!(
+        senders,
+        vec![&sender_accounts[0].address, &sender_accounts[1].address]
+    );
+    let fees: Vec<_> = selected_txns.iter().map(|txn| txn.fee).collect();
+    assert_eq!(
+        fees,
+        vec![
+            Coin::from_u64_unchecked(100 + num_flooding_txns - 1),
+            Coin::from_u64_unchecked(2)
+        ]
+    );
+
+    // The transactions that were not selected remain in the mempool
+    assert_eq!(mempool.num_transactions(), num_flooding_txns as usize);
+}
+
+#[test(tokio::test)]
+async fn mempool_select_txn_skips_large_txns() {
+    let mut rng = test_rng(true);
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let recipient_accounts = generate_accounts(vec![0; 2], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![1000; 3], &mut genesis_builder, true, &mut rng);
+
+    // Two small transactions with low fees
+    let mempool_transactions = (0..2)
+        .map(|i| TestTransaction {
+            fee: i + 1,
+            value: 1,
+            recipient: recipient_accounts[i as usize].clone(),
+            sender: sender_accounts[i as usize].clone(),
+        })
+        .collect();
+    let (mut txns, _) = generate_transactions(mempool_transactions, true);
+    let small_txn_len = 1 + txns[0].serialized_size();
+
+    // A larger transaction with the highest fee per byte
+    let sender = &sender_accounts[2];
+    let mut large_txn = Transaction::new_extended(
+        sender.address.clone(),
+        AccountType::Basic,
+        vec![],
+        recipient_accounts[0].address.clone(),
+        AccountType::Basic,
+        vec![0; Policy::MAX_BASIC_TX_RECIPIENT_DATA_SIZE],
+        Coin::from_u64_unchecked(1),
+        Coin::from_u64_unchecked(500),
+        1 + Policy::genesis_block_number(),
+        NetworkId::UnitAlbatross,
+    );
+    let signature = sender.keypair.sign(&large_txn.serialize_content());
+    large_txn.proof =
+        SignatureProof::from_ed25519(sender.keypair.public, signature).serialize_to_vec();
+    assert!(large_txn.serialized_size() > txns[0].serialized_size());
+    txns.push(large_txn.clone());
+
+    let blockchain = blockchain_with_genesis(genesis_builder, &mut rng);
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+    let mut hub = MockHub::new();
+    let mock_id = MockId::new(hub.new_address().into());
+    let mock_network = Arc::new(hub.new_network());
+    send_txn_to_mempool(&mempool, mock_network, mock_id, txns).await;
+    assert_eq!(mempool.num_transactions(), 3);
+
+    // The large transaction doesn't fit, so the fee order strategy stops right away
+    let mut policy = SelectionPolicy::default();
+    let (selected_txns, _) = mempool.select_transactions_for_block(small_txn_len, &policy);
+    assert!(selected_txns.is_empty());
+
+    // The max fee per byte strategy fills the block with the best small transaction instead
+    policy.strategy = SelectionStrategy::MaxFeePerByte;
+    let (selected_txns, size) = mempool.select_transactions_for_block(small_txn_len, &policy);
+    let fees: Vec<_> = selected_txns.iter().map(|txn| txn.fee).collect();
+    assert_eq!(fees, vec![Coin::from_u64_unchecked(2)]);
+    assert_eq!(size, small_txn_len);
+
+    // The skipped transactions remain in the mempool
+    assert_eq!(mempool.num_transactions(), 2);
+    assert!(mempool.contains_transaction_by_hash(&large_txn.hash()));
+}
+
+#[test(tokio::test)]
+async fn mempool_select_txn_with_reserved_control_bytes() {
+    let mut rng = test_rng(true);
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let recipient_accounts = generate_accounts(vec![0; 2], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(
+        vec![Policy::MINIMUM_STAKE + 100],
+        &mut genesis_builder,
+        true,
+        &mut rng,
+    );
+
+    let mempool_transactions = (0..2)
+        .map(|i| TestTransaction {
+            fee: i + 1,
+            value: 1,
+            recipient: recipient_accounts[i as usize].clone(),
+            sender: sender_accounts[0].clone(),
+        })
+        .collect();
+    let (txns, txns_len) = generate_transactions(mempool_transactions, true);
+
+    let control_tx = TransactionBuilder::new_create_staker(
+        &sender_accounts[0].keypair,
+        &sender_accounts[0].keypair,
+        None,
+        Policy::MINIMUM_STAKE.try_into().unwrap(),
+        1.try_into().unwrap(),
+        1 + Policy::genesis_block_number(),
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let control_tx_len = 1 + control_tx.serialized_size();
+
+    let blockchain = blockchain_with_genesis(genesis_builder, &mut rng);
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+    let mut hub = MockHub::new();
+    let mock_id = MockId::new(hub.new_address().into());
+    let mock_network = Arc::new(hub.new_network());
+    send_control_txn_to_mempool(
+        &mempool,
+        mock_network.clone(),
+        mock_id.clone(),
+        vec![control_tx.clone()],
+    )
+    .await;
+    send_txn_to_mempool(&mempool, mock_network, mock_id, txns).await;
+    assert_eq!(mempool.num_transactions(), 3);
+
+    // No space is reserved, so control transactions only get the space left by regular ones
+    let policy = SelectionPolicy {
+        reserved_control_bytes: Some(0),
+        ..Default::default()
+    };
+
+    // The regular transactions take precedence, so the control transaction doesn't fit anymore
+    let (selected_txns, size) =
+        mempool.select_transactions_for_block(txns_len + control_tx_len - 1, &policy);
+    assert_eq!(selected_txns.len(), 2);
+    assert_eq!(size, txns_len);
+
+    // The control transaction is selected in the second pass, using the space left over
+    let (selected_txns, size) = mempool.select_transactions_for_block(control_tx_len, &policy);
+    assert_eq!(selected_txns, vec![control_tx]);
+    assert_eq!(size, control_tx_len);
+    assert_eq!(mempool.num_transactions(), 0);
+}
+
 #[test(tokio::test(flavor = "current_thread"))]
 async fn mempool_tps() {
     let min_tps = tps_setting(100);
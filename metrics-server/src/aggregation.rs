@@ -0,0 +1,12 @@
+use nimiq_handel::metrics::HandelMetrics;
+use prometheus_client::registry::Registry;
+
+pub struct AggregationMetrics {}
+
+impl AggregationMetrics {
+    pub fn register(registry: &mut Registry, metrics: HandelMetrics) {
+        let sub_registry = registry.sub_registry_with_prefix("handel");
+
+        metrics.register(sub_registry);
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use log::warn;
+use nimiq_database::mdbx::{DatabaseStats, MdbxDatabase};
+use prometheus_client::{
+    encoding::EncodeLabelSet,
+    metrics::{family::Family, gauge::Gauge},
+    registry::Registry,
+};
+
+/// Collecting the database stats traverses the freelist, so they are updated less frequently.
+const DATABASE_METRICS_FREQ_SECS: u64 = 60;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct TableLabels {
+    table: String,
+}
+
+#[derive(Default)]
+pub struct DatabaseMetrics {
+    map_size: Gauge,
+    max_size: Gauge,
+    allocated_size: Gauge,
+    used_size: Gauge,
+    freelist_pages: Gauge,
+    num_readers: Gauge,
+    max_readers: Gauge,
+    table_entries: Family<TableLabels, Gauge>,
+    table_pages: Family<TableLabels, Gauge>,
+    table_size: Family<TableLabels, Gauge>,
+}
+
+impl DatabaseMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        let sub_registry = registry.sub_registry_with_prefix("database");
+
+        sub_registry.register(
+            "map_size_bytes",
+            "Current size of the memory map",
+            self.map_size.clone(),
+        );
+        sub_registry.register(
+            "max_size_bytes",
+            "Configured maximum size of the database",
+            self.max_size.clone(),
+        );
+        sub_registry.register(
+            "allocated_bytes",
+            "Number of bytes allocated in the database file",
+            self.allocated_size.clone(),
+        );
+        sub_registry.register(
+            "used_bytes",
+            "Number of allocated bytes that are not on the freelist",
+            self.used_size.clone(),
+        );
+        sub_registry.register(
+            "freelist_pages",
+            "Number of pages on the freelist",
+            self.freelist_pages.clone(),
+        );
+        sub_registry.register(
+            "readers",
+            "Number of reader slots in use",
+            self.num_readers.clone(),
+        );
+        sub_registry.register(
+            "max_readers",
+            "Maximum number of reader slots",
+            self.max_readers.clone(),
+        );
+        sub_registry.register(
+            "table_entries",
+            "Number of entries per table",
+            self.table_entries.clone(),
+        );
+        sub_registry.register(
+            "table_pages",
+            "Number of pages used per table",
+            self.table_pages.clone(),
+        );
+        sub_registry.register(
+            "table_size_bytes",
+            "Size of the pages used per table",
+            self.table_size.clone(),
+        );
+    }
+
+    pub async fn update_metric_values(self, db: MdbxDatabase) {
+        let mut interval = tokio::time::interval(Duration::from_secs(DATABASE_METRICS_FREQ_SECS));
+
+        loop {
+            interval.tick().await;
+
+            let db = db.clone();
+            match tokio::task::spawn_blocking(move || db.stats()).await {
+                Ok(Ok(stats)) => self.update(&stats),
+                Ok(Err(error)) => warn!(%error, "Failed to collect database stats"),
+                Err(error) => warn!(%error, "Database stats task failed"),
+            }
+        }
+    }
+
+    fn update(&self, stats: &DatabaseStats) {
+        self.map_size.set(stats.map_size as i64);
+        self.max_size.set(stats.max_size.unwrap_or(0) as i64);
+        self.allocated_size.set(stats.allocated_size() as i64);
+        self.used_size.set(stats.used_size() as i64);
+        self.freelist_pages.set(stats.freelist_pages as i64);
+        self.num_readers.set(stats.num_readers as i64);
+        self.max_readers.set(stats.max_readers as i64);
+
+        for table in &stats.tables {
+            let labels = TableLabels {
+                table: table.name.clone(),
+            };
+            self.table_entries
+                .get_or_create(&labels)
+                .set(table.entries as i64);
+            self.table_pages
+                .get_or_create(&labels)
+                .set(table.num_pages() as i64);
+            self.table_size
+                .get_or_create(&labels)
+                .set((table.num_pages() * stats.page_size as usize) as i64);
+        }
+    }
+}
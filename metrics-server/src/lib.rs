@@ -2,6 +2,7 @@ use std::{fmt::Debug, net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
+pub use nimiq_handel::metrics::HandelMetrics;
 use nimiq_mempool::mempool::Mempool;
 use nimiq_network_interface::network::Network;
 use nimiq_utils::spawn;
@@ -18,12 +19,15 @@ use tokio_metrics::TaskMonitor;
 #[cfg(tokio_unstable)]
 use crate::tokio_runtime::TokioRuntimeMetrics;
 use crate::{
-    chain::BlockMetrics, consensus::ConsensusMetrics, mempool::MempoolMetrics,
-    network::NetworkMetrics, server::metrics_server, tokio_task::TokioTaskMetrics,
+    aggregation::AggregationMetrics, chain::BlockMetrics, consensus::ConsensusMetrics,
+    database::DatabaseMetrics, mempool::MempoolMetrics, network::NetworkMetrics,
+    server::metrics_server, tokio_task::TokioTaskMetrics,
 };
 
+mod aggregation;
 mod chain;
 mod consensus;
+mod database;
 mod mempool;
 mod network;
 mod server;
@@ -83,6 +87,7 @@ pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
     blockchain_proxy: BlockchainProxy,
     mempool: Option<Arc<Mempool>>,
+    handel_metrics: Option<HandelMetrics>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
     task_monitors: &[NimiqTaskMonitor],
@@ -90,6 +95,13 @@ pub fn start_metrics_server<TNetwork: Network>(
     let mut registry = Registry::default();
     let nimiq_registry = registry.sub_registry_with_prefix("nimiq");
 
+    // Only full blockchains have a database.
+    if let BlockchainProxy::Full(ref blockchain) = blockchain_proxy {
+        let database_metrics = DatabaseMetrics::default();
+        database_metrics.register(nimiq_registry);
+        spawn(database_metrics.update_metric_values(blockchain.read().db().clone()));
+    }
+
     BlockMetrics::register(nimiq_registry, blockchain_proxy);
     ConsensusMetrics::register(nimiq_registry, consensus_proxy);
     NetworkMetrics::register(nimiq_registry, network);
@@ -98,6 +110,10 @@ pub fn start_metrics_server<TNetwork: Network>(
         MempoolMetrics::register(nimiq_registry, mempool);
     }
 
+    if let Some(handel_metrics) = handel_metrics {
+        AggregationMetrics::register(nimiq_registry, handel_metrics);
+    }
+
     // Setup the task metrics
     let task_metrics = Arc::new(RwLock::new(TokioTaskMetrics::new()));
     task_metrics.write().register(
@@ -41,6 +41,10 @@ bitflags! {
 
         /// This node is configured as a validator, so it is interested for other validator nodes.
         const VALIDATOR = 1 << 7;
+
+        /// The node acts as a circuit relay, so peers that are not publicly reachable can reserve a slot on it
+        /// to be reachable through this node.
+        const RELAY = 1 << 8;
     }
 }
 
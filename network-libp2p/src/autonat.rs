@@ -53,6 +53,11 @@ impl NatState {
         self.update_state();
     }
 
+    /// Returns the general NAT status of the local peer
+    pub fn status(&self) -> NatStatus {
+        self.status
+    }
+
     /// Mark the address as confirmed thus publicly reachable
     pub fn add_confirmed_address(&mut self, address: Multiaddr) {
         let address_status = self.address_status.entry(address.clone()).or_default();
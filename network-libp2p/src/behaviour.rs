@@ -2,10 +2,10 @@ use std::{iter, sync::Arc};
 
 use libp2p::{
     autonat::v2::{self as autonat, client::Config as AutonatConfig},
-    connection_limits, gossipsub,
+    connection_limits, dcutr, gossipsub,
     kad::{self, store::MemoryStore},
-    ping, request_response,
-    swarm::NetworkBehaviour,
+    ping, relay, request_response,
+    swarm::{behaviour::toggle::Toggle, NetworkBehaviour},
     Multiaddr, PeerId, StreamProtocol,
 };
 use parking_lot::RwLock;
@@ -36,6 +36,9 @@ pub struct Behaviour {
     pub discovery: discovery::Behaviour,
     pub autonat_server: autonat::server::Behaviour,
     pub autonat_client: autonat::client::Behaviour,
+    pub relay_server: Toggle<relay::Behaviour>,
+    pub relay_client: Toggle<relay::client::Behaviour>,
+    pub dcutr: Toggle<dcutr::Behaviour>,
     pub dht: kad::Behaviour<MemoryStore>,
     pub gossipsub: gossipsub::Behaviour,
     pub ping: ping::Behaviour,
@@ -48,6 +51,7 @@ impl Behaviour {
         contacts: Arc<RwLock<PeerContactBook>>,
         peer_score_params: gossipsub::PeerScoreParams,
         force_dht_server_mode: bool,
+        relay_client: Option<relay::client::Behaviour>,
     ) -> Self {
         let public_key = config.keypair.public();
         let peer_id = public_key.to_peer_id();
@@ -105,6 +109,17 @@ impl Behaviour {
         // AutoNAT client behaviour
         let autonat_client = autonat::client::Behaviour::new(OsRng, AutonatConfig::default());
 
+        // Relay server behaviour: Only enabled if configured.
+        let relay_server = config
+            .relay_server
+            .as_ref()
+            .map(|relay_config| relay::Behaviour::new(peer_id, relay_config.into()));
+
+        // Direct connection upgrade behaviour: Only useful if we can establish relayed connections.
+        let dcutr = relay_client
+            .is_some()
+            .then(|| dcutr::Behaviour::new(peer_id));
+
         // Connection limits behaviour
         let limits = connection_limits::ConnectionLimits::default()
             .with_max_pending_incoming(Some(16))
@@ -123,6 +138,9 @@ impl Behaviour {
             request_response,
             autonat_client,
             autonat_server,
+            relay_server: relay_server.into(),
+            relay_client: relay_client.into(),
+            dcutr: dcutr.into(),
             connection_limits,
         }
     }
@@ -1,13 +1,13 @@
-use std::{num::NonZeroU8, time::Duration};
+use std::{collections::HashMap, num::NonZeroU8, time::Duration};
 
-use libp2p::{gossipsub, identity::Keypair, kad, Multiaddr, StreamProtocol};
+use libp2p::{gossipsub, identity::Keypair, kad, relay, Multiaddr, StreamProtocol};
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::{network::MIN_SUPPORTED_MSG_SIZE, peer_info::Services};
 use sha2::{Digest, Sha256};
 
 use crate::{
     discovery::{self, peer_contacts::PeerContact},
-    DHT_PROTOCOL,
+    NetworkError, DHT_PROTOCOL,
 };
 
 /// TLS settings for configuring a secure WebSocket
@@ -19,6 +19,135 @@ pub struct TlsConfig {
     pub certificates: Vec<Vec<u8>>,
 }
 
+/// Gossipsub mesh and peer scoring settings
+#[derive(Clone, Debug)]
+pub struct GossipsubConfig {
+    /// Target number of peers in the mesh of a topic
+    pub mesh_n: usize,
+    /// Minimum number of peers in the mesh of a topic before adding more
+    pub mesh_n_low: usize,
+    /// Maximum number of peers in the mesh of a topic before pruning some
+    pub mesh_n_high: usize,
+    /// Minimum number of outbound peers in the mesh of a topic
+    pub mesh_outbound_min: usize,
+    /// Time between gossipsub heartbeats
+    pub heartbeat_interval: Duration,
+    /// Maximum size of a gossipsub message
+    pub max_transmit_size: usize,
+    /// Score parameters per topic name. Topics without an entry use the default parameters.
+    pub topic_score_params: HashMap<String, gossipsub::TopicScoreParams>,
+}
+
+impl GossipsubConfig {
+    /// Builds the libp2p gossipsub configuration out of these settings.
+    pub fn to_gossipsub_config(&self) -> Result<gossipsub::Config, NetworkError> {
+        gossipsub::ConfigBuilder::default()
+            .mesh_n(self.mesh_n)
+            .mesh_n_low(self.mesh_n_low)
+            .mesh_n_high(self.mesh_n_high)
+            .mesh_outbound_min(self.mesh_outbound_min)
+            .validate_messages()
+            .max_transmit_size(self.max_transmit_size)
+            .validation_mode(gossipsub::ValidationMode::Permissive)
+            .heartbeat_interval(self.heartbeat_interval)
+            // Use the message hash as the message ID instead of the default PeerId + sequence_number
+            // to avoid duplicated messages
+            .message_id_fn(|message| {
+                let mut s = Sha256::new();
+                s.update(message.topic.as_str());
+                s.update(&message.data);
+                gossipsub::MessageId::from(s.finalize().to_vec())
+            })
+            .build()
+            .map_err(NetworkError::GossipsubConfig)
+    }
+
+    /// Checks that the mesh settings and all of the topic score parameters are valid.
+    pub fn validate(&self) -> Result<(), NetworkError> {
+        if self.max_transmit_size < MIN_SUPPORTED_MSG_SIZE {
+            return Err(NetworkError::GossipsubMaxTransmitSize {
+                max_transmit_size: self.max_transmit_size,
+                min_supported: MIN_SUPPORTED_MSG_SIZE,
+            });
+        }
+
+        self.to_gossipsub_config()?;
+
+        for (topic_name, params) in self.topic_score_params.iter() {
+            params
+                .validate()
+                .map_err(|error| NetworkError::TopicScoreParams {
+                    topic_name: topic_name.clone(),
+                    error,
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for GossipsubConfig {
+    fn default() -> Self {
+        Self {
+            mesh_n: 6,
+            mesh_n_low: 3,
+            mesh_n_high: 12,
+            mesh_outbound_min: 2,
+            heartbeat_interval: Duration::from_millis(700),
+            max_transmit_size: MIN_SUPPORTED_MSG_SIZE,
+            topic_score_params: HashMap::new(),
+        }
+    }
+}
+
+/// Limits of the circuit relay server
+#[derive(Clone, Debug)]
+pub struct RelayServerConfig {
+    /// Maximum number of reservations
+    pub max_reservations: usize,
+    /// Maximum number of reservations per peer
+    pub max_reservations_per_peer: usize,
+    /// Duration of a reservation
+    pub reservation_duration: Duration,
+    /// Maximum number of relayed connections
+    pub max_circuits: usize,
+    /// Maximum number of relayed connections per peer
+    pub max_circuits_per_peer: usize,
+    /// Maximum duration of a relayed connection
+    pub max_circuit_duration: Duration,
+    /// Maximum number of bytes transferred over a relayed connection
+    pub max_circuit_bytes: u64,
+}
+
+impl Default for RelayServerConfig {
+    fn default() -> Self {
+        Self {
+            max_reservations: 128,
+            max_reservations_per_peer: 4,
+            reservation_duration: Duration::from_secs(60 * 60),
+            max_circuits: 16,
+            max_circuits_per_peer: 4,
+            max_circuit_duration: Duration::from_secs(2 * 60),
+            max_circuit_bytes: 1 << 17,
+        }
+    }
+}
+
+impl From<&RelayServerConfig> for relay::Config {
+    fn from(config: &RelayServerConfig) -> Self {
+        Self {
+            max_reservations: config.max_reservations,
+            max_reservations_per_peer: config.max_reservations_per_peer,
+            reservation_duration: config.reservation_duration,
+            max_circuits: config.max_circuits,
+            max_circuits_per_peer: config.max_circuits_per_peer,
+            max_circuit_duration: config.max_circuit_duration,
+            max_circuit_bytes: config.max_circuit_bytes,
+            ..Default::default()
+        }
+    }
+}
+
 /// LibP2P network configuration
 pub struct Config {
     pub keypair: Keypair,
@@ -27,7 +156,11 @@ pub struct Config {
     pub discovery: discovery::Config,
     pub kademlia: kad::Config,
     pub gossipsub: gossipsub::Config,
+    pub gossipsub_topic_score_params: HashMap<String, gossipsub::TopicScoreParams>,
     pub memory_transport: bool,
+    pub quic_transport: bool,
+    pub relay_client: bool,
+    pub relay_server: Option<RelayServerConfig>,
     pub required_services: Services,
     pub tls: Option<TlsConfig>,
     pub desired_peer_count: usize,
@@ -43,30 +176,19 @@ impl Config {
         seeds: Vec<Multiaddr>,
         genesis_hash: Blake2bHash,
         memory_transport: bool,
+        quic_transport: bool,
+        relay_client: bool,
+        relay_server: Option<RelayServerConfig>,
         required_services: Services,
         tls_settings: Option<TlsConfig>,
         desired_peer_count: usize,
         only_secure_ws_connections: bool,
         allow_loopback_addresses: bool,
         dht_quorum: NonZeroU8,
+        gossipsub_config: GossipsubConfig,
     ) -> Self {
-        // Hardcoding the minimum number of peers in mesh network before adding more
-        // TODO: Maybe change this to a mesh limits configuration argument of this function
-        let gossipsub = gossipsub::ConfigBuilder::default()
-            .mesh_n_low(3)
-            .validate_messages()
-            .max_transmit_size(MIN_SUPPORTED_MSG_SIZE)
-            .validation_mode(gossipsub::ValidationMode::Permissive)
-            .heartbeat_interval(Duration::from_millis(700))
-            // Use the message hash as the message ID instead of the default PeerId + sequence_number
-            // to avoid duplicated messages
-            .message_id_fn(|message| {
-                let mut s = Sha256::new();
-                s.update(message.topic.as_str());
-                s.update(&message.data);
-                gossipsub::MessageId::from(s.finalize().to_vec())
-            })
-            .build()
+        let gossipsub = gossipsub_config
+            .to_gossipsub_config()
             .expect("Invalid Gossipsub config");
 
         let mut kademlia = kad::Config::new(StreamProtocol::new(DHT_PROTOCOL));
@@ -90,7 +212,11 @@ impl Config {
             ),
             kademlia,
             gossipsub,
+            gossipsub_topic_score_params: gossipsub_config.topic_score_params,
             memory_transport,
+            quic_transport,
+            relay_client,
+            relay_server,
             required_services,
             tls: tls_settings,
             desired_peer_count,
@@ -314,6 +314,10 @@ pub struct PeerContactBook {
     allow_loopback_addresses: bool,
     /// Flag to indicate whether to support memory transport addresses
     memory_transport: bool,
+    /// Flag to indicate whether to support QUIC addresses
+    quic_transport: bool,
+    /// Flag to indicate whether to support relayed (`P2pCircuit`) addresses
+    relay_transport: bool,
 }
 
 impl PeerContactBook {
@@ -326,6 +330,8 @@ impl PeerContactBook {
         only_secure_addresses: bool,
         allow_loopback_addresses: bool,
         memory_transport: bool,
+        quic_transport: bool,
+        relay_transport: bool,
     ) -> Self {
         let own_peer_id = own_peer_contact.inner.peer_id();
         Self {
@@ -335,6 +341,8 @@ impl PeerContactBook {
             only_secure_addresses,
             allow_loopback_addresses,
             memory_transport,
+            quic_transport,
+            relay_transport,
         }
     }
 
@@ -597,6 +605,22 @@ impl PeerContactBook {
         if self.memory_transport {
             return true;
         }
+        // Relayed addresses are dialable if the relay transport is enabled and the relay is dialable
+        if let Some(relay_address) = utils::relay_address(address) {
+            return self.relay_transport && self.is_address_dialable(&relay_address);
+        }
+        // QUIC addresses are only dialable if the QUIC transport is enabled
+        if utils::is_address_quic(address) {
+            if !self.quic_transport || self.only_secure_addresses {
+                return false;
+            }
+            return match address.iter().next() {
+                Some(Protocol::Ip4(ip)) => self.allow_loopback_addresses || !ip.is_loopback(),
+                Some(Protocol::Ip6(ip)) => self.allow_loopback_addresses || !ip.is_loopback(),
+                Some(Protocol::Dns(_)) | Some(Protocol::Dns4(_)) | Some(Protocol::Dns6(_)) => true,
+                _ => false,
+            };
+        }
         // Otherwise check for an appropriate WS address
         let mut protocols = address.iter();
         let mut ip = protocols.next();
@@ -43,11 +43,21 @@ pub enum NetworkError {
     #[error("Unknown Request ID")]
     UnknownRequestId,
 
-    #[error("Couldn't set topic score parameters")]
+    #[error("Couldn't set topic score parameters for {topic_name}: {error}")]
     TopicScoreParams {
         topic_name: String,
         error: &'static str,
     },
+
+    #[error("Invalid gossipsub config: {0}")]
+    GossipsubConfig(libp2p::gossipsub::ConfigBuilderError),
+
+    #[error("Gossipsub max transmit size {max_transmit_size} is below the minimum supported message size {min_supported}")]
+    GossipsubMaxTransmitSize {
+        max_transmit_size: usize,
+        min_supported: usize,
+    },
+
     #[error("Response channel closed: {0:?}")]
     ResponseChannelClosed(<MessageCodec as libp2p::request_response::Codec>::Response),
 
@@ -22,7 +22,7 @@ pub const DHT_PROTOCOL: &str = "/nimiq/kad/0.0.1";
 pub const AUTONAT_DIAL_REQUEST_PROTOCOL: &str = "/libp2p/autonat/2/dial-request";
 pub const AUTONAT_DIAL_BACK_PROTOCOL: &str = "/libp2p/autonat/2/dial-back";
 
-pub use config::{Config, TlsConfig};
+pub use config::{Config, GossipsubConfig, RelayServerConfig, TlsConfig};
 pub use error::NetworkError;
 pub use libp2p::{
     self,
@@ -84,12 +84,15 @@ impl Network {
             config.only_secure_ws_connections,
             config.allow_loopback_addresses,
             config.memory_transport,
+            config.quic_transport,
+            config.relay_client && !config.only_secure_ws_connections,
         )));
         let params = gossipsub::PeerScoreParams {
             ip_colocation_factor_threshold: 20.0,
             ..Default::default()
         };
         let dht_quorum = config.dht_quorum;
+        let gossip_topic_score_params = config.gossipsub_topic_score_params.clone();
         // Only force the server mode if we are doing a memory transport.
         // Otherwise expect the regular flow: DHT will get in server mode once a confirmed address is obtained using Autonat.
         // In memory transport we don't have a mechanism that sets the DHT in server mode such as confirming an address
@@ -124,6 +127,7 @@ impl Network {
             Arc::clone(&contacts),
             force_dht_server_mode,
             dht_quorum,
+            gossip_topic_score_params,
             #[cfg(feature = "metrics")]
             metrics.clone(),
         )));
@@ -4,6 +4,7 @@ use bytes::Bytes;
 #[cfg(feature = "metrics")]
 use instant::Instant;
 use libp2p::{
+    core::transport::ListenerId,
     gossipsub,
     kad::{QueryId, Record},
     request_response::{InboundRequestId, OutboundRequestId, ResponseChannel},
@@ -259,6 +260,10 @@ pub(crate) struct TaskState {
     >,
     /// DHT quorum value
     pub(crate) dht_quorum: u8,
+    /// The relay peer and the listener of our relay reservation, if any
+    pub(crate) relay_listener: Option<(PeerId, ListenerId)>,
+    /// Gossipsub score parameters per topic name, applied when subscribing to a topic
+    pub(crate) gossip_topic_score_params: HashMap<String, gossipsub::TopicScoreParams>,
 }
 
 #[derive(Clone, Debug)]
@@ -1,8 +1,10 @@
 use std::{collections::HashMap, num::NonZeroU8, sync::Arc};
 
-use futures::StreamExt;
+use futures::{future::Either, StreamExt};
 #[cfg(feature = "metrics")]
 use instant::Instant;
+#[cfg(feature = "tokio-quic")]
+use libp2p::quic;
 #[cfg(all(target_family = "wasm", not(feature = "tokio-websocket")))]
 use libp2p::websocket_websys;
 use libp2p::{
@@ -15,7 +17,8 @@ use libp2p::{
     gossipsub,
     identity::Keypair,
     kad::{self, store::RecordStore, GetRecordOk, InboundRequest, QueryResult, Quorum, Record},
-    noise,
+    multiaddr::Protocol,
+    noise, relay,
     request_response::{self},
     swarm::{
         dial_opts::{DialOpts, PeerCondition},
@@ -29,7 +32,7 @@ use log::Instrument;
 use nimiq_bls::{CompressedPublicKey, KeyPair};
 use nimiq_network_interface::{
     network::{CloseReason, NetworkEvent},
-    peer_info::PeerInfo,
+    peer_info::{PeerInfo, Services},
     request::{peek_type, InboundRequestError, OutboundRequestError, RequestError},
 };
 use nimiq_serde::{Deserialize, Serialize};
@@ -49,7 +52,7 @@ use crate::{
         DhtBootStrapState, DhtRecord, DhtResults, NetworkAction, TaskState, ValidateMessage,
     },
     rate_limiting::RateLimits,
-    Config, NetworkError, TlsConfig,
+    utils, Config, NetworkError, TlsConfig,
 };
 
 type NimiqSwarm = Swarm<behaviour::Behaviour>;
@@ -61,16 +64,33 @@ pub(crate) fn new_swarm(
     force_dht_server_mode: bool,
 ) -> Swarm<behaviour::Behaviour> {
     let keypair = config.keypair.clone();
+
+    // The relay client is neither used with the memory transport nor for secure WebSocket only nodes.
+    let (relay_transport, relay_client) =
+        if config.relay_client && !config.memory_transport && !config.only_secure_ws_connections {
+            let (relay_transport, relay_client) = relay::client::new(keypair.public().to_peer_id());
+            (Some(relay_transport), Some(relay_client))
+        } else {
+            (None, None)
+        };
+
     let transport = new_transport(
         &keypair,
         config.memory_transport,
+        config.quic_transport,
+        relay_transport,
         config.only_secure_ws_connections,
         config.tls.as_ref(),
     )
     .unwrap();
 
-    let behaviour =
-        behaviour::Behaviour::new(config, contacts, peer_score_params, force_dht_server_mode);
+    let behaviour = behaviour::Behaviour::new(
+        config,
+        contacts,
+        peer_score_params,
+        force_dht_server_mode,
+        relay_client,
+    );
 
     // TODO add proper config
     #[cfg(not(target_family = "wasm"))]
@@ -102,11 +122,13 @@ pub(crate) async fn swarm_task(
     contacts: Arc<RwLock<PeerContactBook>>,
     force_dht_server_mode: bool,
     dht_quorum: NonZeroU8,
+    gossip_topic_score_params: HashMap<String, gossipsub::TopicScoreParams>,
     #[cfg(feature = "metrics")] metrics: Arc<NetworkMetrics>,
 ) {
     let mut task_state = TaskState {
         dht_server_mode: force_dht_server_mode,
         dht_quorum: dht_quorum.into(),
+        gossip_topic_score_params,
         ..Default::default()
     };
     let mut rate_limiting = RateLimits::default();
@@ -138,7 +160,7 @@ pub(crate) async fn swarm_task(
                 },
                 event = swarm.next() => {
                     if let Some(event) = event {
-                        handle_event(event, &events_tx, &mut swarm, &mut task_state, &connected_peers, &mut rate_limiting, #[cfg( feature = "metrics")] &metrics);
+                        handle_event(event, &events_tx, &mut swarm, &mut task_state, &connected_peers, &contacts, &mut rate_limiting, #[cfg( feature = "metrics")] &metrics);
                     }
                 },
                 action = action_rx.recv() => {
@@ -163,6 +185,8 @@ pub(crate) async fn swarm_task(
 fn new_transport(
     keypair: &Keypair,
     memory_transport: bool,
+    quic_transport: bool,
+    relay_transport: Option<relay::client::Transport>,
     only_secure_ws_connections: bool,
     tls: Option<&TlsConfig>,
 ) -> std::io::Result<Boxed<(PeerId, StreamMuxerBox)>> {
@@ -248,22 +272,147 @@ fn new_transport(
                 .timeout(std::time::Duration::from_secs(20))
                 .boxed())
         } else {
-            Ok(transport
+            let transport = transport
                 .upgrade(core::upgrade::Version::V1)
                 .authenticate(noise::Config::new(keypair).unwrap())
                 .multiplex(yamux)
                 .timeout(std::time::Duration::from_secs(20))
-                .boxed())
+                .boxed();
+
+            // QUIC brings its own encryption and multiplexing, so it is combined with the
+            // already upgraded transport.
+            #[cfg(feature = "tokio-quic")]
+            let transport = if quic_transport {
+                with_quic_transport(keypair, transport)
+            } else {
+                transport
+            };
+
+            #[cfg(not(feature = "tokio-quic"))]
+            if quic_transport {
+                warn!("QUIC transport requested but the `tokio-quic` feature is not enabled");
+            }
+
+            match relay_transport {
+                Some(relay_transport) => {
+                    Ok(with_relay_transport(keypair, relay_transport, transport))
+                }
+                None => Ok(transport),
+            }
         }
     }
 }
 
+/// Adds the relay client transport to the given transport. Dialing and listening on relayed
+/// (`P2pCircuit`) addresses uses the relay transport, all other addresses use the given transport.
+fn with_relay_transport(
+    keypair: &Keypair,
+    relay_transport: relay::client::Transport,
+    transport: Boxed<(PeerId, StreamMuxerBox)>,
+) -> Boxed<(PeerId, StreamMuxerBox)> {
+    relay_transport
+        .upgrade(core::upgrade::Version::V1)
+        .authenticate(noise::Config::new(keypair).unwrap())
+        .multiplex(yamux::Config::default())
+        .or_transport(transport)
+        .map(|output, _| match output {
+            Either::Left((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+            Either::Right(output) => output,
+        })
+        .boxed()
+}
+
+/// Adds a QUIC transport to the given transport. Dialing QUIC addresses uses the QUIC transport,
+/// all other addresses are dialed using the given transport.
+#[cfg(feature = "tokio-quic")]
+fn with_quic_transport(
+    keypair: &Keypair,
+    transport: Boxed<(PeerId, StreamMuxerBox)>,
+) -> Boxed<(PeerId, StreamMuxerBox)> {
+    let quic_transport = quic::tokio::Transport::new(quic::Config::new(keypair));
+
+    quic_transport
+        .or_transport(transport)
+        .map(|output, _| match output {
+            Either::Left((peer_id, connection)) => (peer_id, StreamMuxerBox::new(connection)),
+            Either::Right(output) => output,
+        })
+        .boxed()
+}
+
+/// Reserves a slot on a connected relay peer if the local peer is not publicly reachable and
+/// there is no reservation yet. The reservation is removed once the local peer is publicly
+/// reachable.
+fn update_relay_reservation(
+    swarm: &mut NimiqSwarm,
+    state: &mut TaskState,
+    connected_peers: &RwLock<HashMap<PeerId, PeerInfo>>,
+    contacts: &RwLock<PeerContactBook>,
+) {
+    if !swarm.behaviour().relay_client.is_enabled() {
+        return;
+    }
+
+    match state.nat_status.status() {
+        NatStatus::Public => {
+            if let Some((relay_peer_id, listener_id)) = state.relay_listener.take() {
+                debug!(%relay_peer_id, "Publicly reachable, removing relay reservation");
+                swarm.remove_listener(listener_id);
+            }
+        }
+        NatStatus::Private => {
+            if state.relay_listener.is_some() {
+                return;
+            }
+
+            // The address of a connection is not necessarily a listen address of the relay (e.g.
+            // for inbound connections), so one of the addresses the relay advertises is used.
+            let relay_peers: Vec<PeerId> = connected_peers
+                .read()
+                .iter()
+                .filter(|(_, peer_info)| peer_info.get_services().contains(Services::RELAY))
+                .map(|(peer_id, _)| *peer_id)
+                .collect();
+            let relay = {
+                let contacts = contacts.read();
+                relay_peers.into_iter().find_map(|peer_id| {
+                    contacts
+                        .get_addresses(&peer_id)?
+                        .into_iter()
+                        .find(|address| !utils::is_address_relayed(address))
+                        .map(|address| (peer_id, address))
+                })
+            };
+            let Some((relay_peer_id, relay_address)) = relay else {
+                debug!("Not publicly reachable, but no relay peer is connected");
+                return;
+            };
+            let Ok(relay_address) = relay_address.with_p2p(relay_peer_id) else {
+                return;
+            };
+            let relay_address = relay_address.with(Protocol::P2pCircuit);
+
+            match swarm.listen_on(relay_address.clone()) {
+                Ok(listener_id) => {
+                    info!(%relay_peer_id, address = %relay_address, "Reserving relay slot");
+                    state.relay_listener = Some((relay_peer_id, listener_id));
+                }
+                Err(error) => {
+                    warn!(%relay_peer_id, %error, "Failed to reserve relay slot");
+                }
+            }
+        }
+        NatStatus::Unknown => {}
+    }
+}
+
 fn handle_event(
     event: SwarmEvent<behaviour::BehaviourEvent>,
     events_tx: &broadcast::Sender<NetworkEvent<PeerId>>,
     swarm: &mut NimiqSwarm,
     state: &mut TaskState,
     connected_peers: &RwLock<HashMap<PeerId, PeerInfo>>,
+    contacts: &RwLock<PeerContactBook>,
     rate_limiting: &mut RateLimits,
     #[cfg(feature = "metrics")] metrics: &Arc<NetworkMetrics>,
 ) {
@@ -397,29 +546,42 @@ fn handle_event(
                 .behaviour_mut()
                 .discovery
                 .add_own_addresses([address.clone()].to_vec());
-            if swarm.behaviour().is_address_dialable(&address) {
+            // Relayed addresses are reachable by definition, so their NAT status is not tracked
+            if swarm.behaviour().is_address_dialable(&address)
+                && !utils::is_address_relayed(&address)
+            {
                 state.nat_status.add_address(address);
             }
         }
 
         SwarmEvent::ListenerClosed {
-            listener_id: _,
+            listener_id,
             addresses,
             reason: _,
         } => {
             addresses.iter().for_each(|address| {
                 state.nat_status.remove_address(address);
             });
+
+            if let Some((relay_peer_id, relay_listener_id)) = state.relay_listener {
+                if relay_listener_id == listener_id {
+                    debug!(%relay_peer_id, "Relay reservation closed");
+                    state.relay_listener = None;
+                }
+            }
+            update_relay_reservation(swarm, state, connected_peers, contacts);
         }
 
         SwarmEvent::ExternalAddrConfirmed { address } => {
             log::trace!(%address, "Address is confirmed and externally reachable");
             state.nat_status.add_confirmed_address(address);
+            update_relay_reservation(swarm, state, connected_peers, contacts);
         }
 
         SwarmEvent::ExternalAddrExpired { address } => {
             log::trace!(%address, "External address is expired and no longer externally reachable");
             state.nat_status.remove_confirmed_address(&address);
+            update_relay_reservation(swarm, state, connected_peers, contacts);
         }
 
         SwarmEvent::Behaviour(event) => {
@@ -434,10 +596,42 @@ fn handle_event(
                             .nat_status
                             .set_address_nat(event.tested_addr, NatStatus::Private),
                     }
+                    update_relay_reservation(swarm, state, connected_peers, contacts);
                 }
                 behaviour::BehaviourEvent::AutonatServer(event) => {
                     log::trace!(?event, "AutoNAT inbound probe");
                 }
+                behaviour::BehaviourEvent::RelayServer(event) => {
+                    log::trace!(?event, "Relay server event");
+                }
+                behaviour::BehaviourEvent::RelayClient(event) => match event {
+                    relay::client::Event::ReservationReqAccepted {
+                        relay_peer_id,
+                        renewal,
+                        ..
+                    } => {
+                        debug!(%relay_peer_id, renewal, "Relay reservation accepted");
+                    }
+                    event => {
+                        log::trace!(?event, "Relay client event");
+                    }
+                },
+                behaviour::BehaviourEvent::Dcutr(event) => match event.result {
+                    Ok(connection_id) => {
+                        debug!(
+                            peer_id = %event.remote_peer_id,
+                            %connection_id,
+                            "Upgraded relayed connection to a direct connection",
+                        );
+                    }
+                    Err(error) => {
+                        debug!(
+                            peer_id = %event.remote_peer_id,
+                            %error,
+                            "Failed to upgrade relayed connection to a direct connection",
+                        );
+                    }
+                },
                 behaviour::BehaviourEvent::ConnectionLimits(_) => {}
                 behaviour::BehaviourEvent::Dht(event) => {
                     match event {
@@ -638,6 +832,15 @@ fn handle_event(
                                 let _ =
                                     events_tx.send(NetworkEvent::PeerJoined(peer_id, peer_info));
 
+                                if peer_contact.services.contains(Services::RELAY) {
+                                    update_relay_reservation(
+                                        swarm,
+                                        state,
+                                        connected_peers,
+                                        contacts,
+                                    );
+                                }
+
                                 if swarm.behaviour().is_address_dialable(&peer_address) {
                                     swarm
                                         .behaviour_mut()
@@ -974,10 +1177,15 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
 
                     state.gossip_topics.insert(topic.hash(), (tx, validate));
 
+                    let topic_score_params = state
+                        .gossip_topic_score_params
+                        .get(&topic_name)
+                        .cloned()
+                        .unwrap_or_default();
                     let result = swarm
                         .behaviour_mut()
                         .gossipsub
-                        .set_topic_params(topic, gossipsub::TopicScoreParams::default());
+                        .set_topic_params(topic, topic_score_params);
                     match result {
                         Ok(_) => output.send(Ok(rx)).ok(),
                         Err(e) => {
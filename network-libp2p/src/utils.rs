@@ -5,3 +5,27 @@ use libp2p::{multiaddr::Protocol, Multiaddr};
 pub fn is_address_ws_secure(address: &Multiaddr) -> bool {
     address.into_iter().any(|p| matches!(p, Protocol::Wss(_)))
 }
+
+/// Returns true if an address is a QUIC address.
+pub fn is_address_quic(address: &Multiaddr) -> bool {
+    address.into_iter().any(|p| matches!(p, Protocol::QuicV1))
+}
+
+/// Returns true if an address is reached through a circuit relay.
+pub fn is_address_relayed(address: &Multiaddr) -> bool {
+    address.into_iter().any(|p| matches!(p, Protocol::P2pCircuit))
+}
+
+/// Returns the address of the relay of a relayed address, i.e. the part of the address before
+/// the `P2pCircuit` protocol. Returns `None` if the address is not a relayed address.
+pub fn relay_address(address: &Multiaddr) -> Option<Multiaddr> {
+    if !is_address_relayed(address) {
+        return None;
+    }
+    Some(
+        address
+            .into_iter()
+            .take_while(|p| !matches!(p, Protocol::P2pCircuit))
+            .collect(),
+    )
+}
@@ -75,6 +75,8 @@ impl TestNode {
             false,
             true,
             true,
+            false,
+            false,
         )));
 
         let behaviour =
@@ -247,6 +249,8 @@ fn test_housekeeping() {
         false,
         true,
         true,
+        false,
+        false,
     );
 
     let fresh_contact = random_peer_contact(1, Services::FULL_BLOCKS);
@@ -298,3 +302,66 @@ fn test_housekeeping() {
         .get(&old_contact.public_key().clone().to_peer_id())
         .is_none());
 }
+
+#[test]
+fn test_quic_addresses_dialable() {
+    let quic_address: Multiaddr = "/ip4/1.2.3.4/udp/8443/quic-v1".parse().unwrap();
+    let loopback_quic_address: Multiaddr = "/ip4/127.0.0.1/udp/8443/quic-v1".parse().unwrap();
+    let ws_address: Multiaddr = "/ip4/1.2.3.4/tcp/8443/ws".parse().unwrap();
+
+    // QUIC transport enabled
+    let peer_contact_book = PeerContactBook::new(
+        random_peer_contact(1, Services::FULL_BLOCKS),
+        false,
+        false,
+        false,
+        true,
+        false,
+    );
+    assert!(peer_contact_book.is_address_dialable(&quic_address));
+    assert!(!peer_contact_book.is_address_dialable(&loopback_quic_address));
+    assert!(peer_contact_book.is_address_dialable(&ws_address));
+
+    // QUIC transport disabled
+    let peer_contact_book = PeerContactBook::new(
+        random_peer_contact(1, Services::FULL_BLOCKS),
+        false,
+        false,
+        false,
+        false,
+        false,
+    );
+    assert!(!peer_contact_book.is_address_dialable(&quic_address));
+    assert!(peer_contact_book.is_address_dialable(&ws_address));
+}
+
+#[test]
+fn test_relayed_addresses_dialable() {
+    let relay_peer_id = Keypair::generate_ed25519().public().to_peer_id();
+    let relayed_address: Multiaddr =
+        format!("/ip4/1.2.3.4/tcp/8443/ws/p2p/{relay_peer_id}/p2p-circuit")
+            .parse()
+            .unwrap();
+
+    // Relay transport enabled
+    let peer_contact_book = PeerContactBook::new(
+        random_peer_contact(1, Services::FULL_BLOCKS),
+        false,
+        false,
+        false,
+        false,
+        true,
+    );
+    assert!(peer_contact_book.is_address_dialable(&relayed_address));
+
+    // Relay transport disabled
+    let peer_contact_book = PeerContactBook::new(
+        random_peer_contact(1, Services::FULL_BLOCKS),
+        false,
+        false,
+        false,
+        false,
+        false,
+    );
+    assert!(!peer_contact_book.is_address_dialable(&relayed_address));
+}
@@ -1,4 +1,4 @@
-use std::{num::NonZeroU8, time::Duration};
+use std::{collections::HashMap, num::NonZeroU8, time::Duration};
 
 use futures::{Stream, StreamExt};
 use libp2p::{
@@ -59,7 +59,11 @@ fn network_config(address: Multiaddr) -> Config {
         },
         kademlia: Default::default(),
         gossipsub,
+        gossipsub_topic_score_params: HashMap::new(),
         memory_transport: true,
+        quic_transport: false,
+        relay_client: false,
+        relay_server: None,
         required_services: Services::all(),
         tls: None,
         desired_peer_count: 3,
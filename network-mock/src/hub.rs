@@ -1,7 +1,9 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt::Debug,
     hash::Hash,
     sync::{atomic::AtomicBool, Arc},
+    time::Duration,
 };
 
 use nimiq_network_interface::{peer_info::PeerInfo, request::RequestType};
@@ -13,6 +15,27 @@ use crate::{
     MockAddress, MockPeerId, ObservableHashMap,
 };
 
+/// How a message sent over a link between two mock networks is delivered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Delivery {
+    /// The message is delivered immediately.
+    Deliver,
+    /// The message is delivered after the given delay.
+    Delay(Duration),
+    /// The message is lost.
+    Drop,
+}
+
+/// Decides the fate of every message sent between mock networks, e.g. to simulate latency, message
+/// loss or network partitions.
+///
+/// The conditioner is consulted for direct messages, requests and gossipsub messages. Responses to
+/// requests are not affected, so a request that was delivered is always answered. Delays of
+/// gossipsub messages are applied in order per subscriber.
+pub trait LinkConditioner: Debug + Send + Sync {
+    fn deliver(&self, from: MockPeerId, to: MockPeerId) -> Delivery;
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub(crate) struct SenderKey {
     pub network_recipient: MockAddress,
@@ -68,6 +91,9 @@ pub(crate) struct MockHubInner {
 
     /// Arcs to `AtomicBool`s for each network if they're connected.
     pub is_connected: HashMap<MockAddress, Arc<AtomicBool>>,
+
+    /// Conditions applied to the messages sent between the networks.
+    pub link_conditioner: Option<Arc<dyn LinkConditioner>>,
 }
 
 impl MockHubInner {
@@ -76,6 +102,15 @@ impl MockHubInner {
         self.gossipsub_topics.get(topic_name)
     }
 
+    /// Returns how a message from `from` to `to` is delivered.
+    pub fn delivery(&self, from: MockPeerId, to: MockPeerId) -> Delivery {
+        self.link_conditioner
+            .as_ref()
+            .map_or(Delivery::Deliver, |conditioner| {
+                conditioner.deliver(from, to)
+            })
+    }
+
     /// Subscribe to a MockTopic; if the topic doesn't exist yet, this function creates it.
     /// Return the MockTopic when a new address is inserted into the subscribed peer list.
     pub fn subscribe(
@@ -148,4 +183,15 @@ impl MockHub {
         log::trace!("New mock network with address={}", address);
         MockNetwork::new(address, Arc::clone(&self.inner))
     }
+
+    /// Sets the conditions applied to all messages sent between the networks of this hub, replacing
+    /// previously set ones.
+    pub fn set_link_conditioner<C: LinkConditioner + 'static>(&self, conditioner: C) {
+        self.inner.lock().link_conditioner = Some(Arc::new(conditioner));
+    }
+
+    /// Removes the link conditions, such that all messages are delivered immediately again.
+    pub fn clear_link_conditioner(&self) {
+        self.inner.lock().link_conditioner = None;
+    }
 }
@@ -3,7 +3,7 @@ mod network;
 mod observable_hash_map;
 
 use derive_more::{Display, From, Into};
-pub use hub::MockHub;
+pub use hub::{Delivery, LinkConditioner, MockHub};
 pub use network::{MockId, MockNetwork};
 use nimiq_network_interface::{multiaddr, Multiaddr};
 pub use observable_hash_map::ObservableHashMap;
@@ -72,13 +72,15 @@ pub async fn create_mock_validator_network(n: usize, dial: bool) -> Vec<MockNetw
 pub mod tests {
     use futures::{Stream, StreamExt};
     use nimiq_keys::{KeyPair, SecureGenerate};
-    use nimiq_network_interface::network::{Network, NetworkEvent, SubscribeEvents, Topic};
+    use nimiq_network_interface::network::{
+        Network, NetworkEvent, PubsubId, SubscribeEvents, Topic,
+    };
     use nimiq_test_log::test;
     use nimiq_test_utils::test_rng::test_rng;
     use nimiq_utils::{spawn, tagged_signing::TaggedSignable};
     use serde::{Deserialize, Serialize};
 
-    use super::{network::MockNetworkError, MockHub, MockPeerId};
+    use super::{network::MockNetworkError, Delivery, LinkConditioner, MockHub, MockPeerId};
 
     pub async fn assert_peer_joined(
         events: &mut SubscribeEvents<MockPeerId>,
@@ -218,4 +220,55 @@ pub mod tests {
             net1.unsubscribe::<TestTopic>().await
         );
     }
+
+    /// Drops all messages sent by the given peer.
+    #[derive(Debug)]
+    struct DropFrom(MockPeerId);
+
+    impl LinkConditioner for DropFrom {
+        fn deliver(&self, from: MockPeerId, _to: MockPeerId) -> Delivery {
+            if from == self.0 {
+                Delivery::Drop
+            } else {
+                Delivery::Delay(std::time::Duration::from_millis(100))
+            }
+        }
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn test_gossipsub_link_conditions() {
+        let mut hub = MockHub::new();
+        let net1 = hub.new_network();
+        let net2 = hub.new_network();
+        let net3 = hub.new_network();
+        net1.dial_mock(&net2);
+        net1.dial_mock(&net3);
+        hub.set_link_conditioner(DropFrom(net2.peer_id()));
+
+        let mut messages = net1.subscribe::<TestTopic>().await.unwrap();
+        consume_stream(net2.subscribe::<TestTopic>().await.unwrap());
+        consume_stream(net3.subscribe::<TestTopic>().await.unwrap());
+
+        net2.publish::<TestTopic>(TestRecord { x: 1 })
+            .await
+            .unwrap();
+        net3.publish::<TestTopic>(TestRecord { x: 2 })
+            .await
+            .unwrap();
+
+        // The message of net2 is lost, the one of net3 is delayed.
+        let start = tokio::time::Instant::now();
+        let (received_message, peer) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 2 });
+        assert_eq!(peer.propagation_source(), net3.peer_id());
+        assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+
+        // Without conditions, messages are delivered again.
+        hub.clear_link_conditioner();
+        net2.publish::<TestTopic>(TestRecord { x: 3 })
+            .await
+            .unwrap();
+        let (received_message, _peer) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 3 });
+    }
 }
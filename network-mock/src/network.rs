@@ -19,15 +19,18 @@ use nimiq_network_interface::{
     },
 };
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
-use nimiq_time::timeout;
-use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSignable};
+use nimiq_time::{sleep, timeout};
+use nimiq_utils::{
+    spawn,
+    tagged_signing::{TaggedKeyPair, TaggedSignable},
+};
 use parking_lot::{Mutex, RwLock};
 use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
 
 use crate::{
-    hub::{MockHubInner, RequestKey, ResponseSender},
+    hub::{Delivery, MockHubInner, RequestKey, ResponseSender},
     observable_hash_map, MockAddress, MockPeerId, ObservableHashMap,
 };
 
@@ -190,7 +193,7 @@ impl MockNetwork {
         let sender_id = MockPeerId::from(self.address);
         let (tx, rx) = oneshot::channel::<Vec<u8>>();
 
-        let (sender, request_id) = {
+        let (sender, request_id, delivery) = {
             let mut hub = self.hub.lock();
 
             let key = RequestKey {
@@ -221,13 +224,34 @@ impl MockNetwork {
             }
             hub.next_request_id += 1;
 
-            (sender, request_id)
+            (sender, request_id, hub.delivery(sender_id, peer_id))
         };
 
         let data = request.serialize_request();
 
         let request = (data, request_id, sender_id);
-        if let Err(e) = sender.send(request).await {
+        // Lost requests run into the timeout below, like they would on a real network.
+        let send_result = match delivery {
+            Delivery::Deliver => sender.send(request).await,
+            Delivery::Delay(delay) => {
+                // Deliver in the background, such that delayed messages don't hold back the sender.
+                spawn(async move {
+                    sleep(delay).await;
+                    let _ = sender.send(request).await;
+                });
+                Ok(())
+            }
+            Delivery::Drop => {
+                log::trace!(
+                    "Dropping request {} from {} to {}",
+                    std::any::type_name::<Req>(),
+                    self.address,
+                    peer_id,
+                );
+                Ok(())
+            }
+        };
+        if let Err(e) = send_result {
             log::warn!(
                 "Cannot send request {} from {} to {} - {:?}",
                 std::any::type_name::<Req>(),
@@ -341,14 +365,33 @@ impl MockNetwork {
                 return Err(MockNetworkError::AlreadySubscribed(topic_name));
             };
 
+        let hub_ref = Arc::clone(&self.hub);
+        let recipient = self.peer_id();
         let stream = BroadcastStream::new(sender.subscribe()).filter_map(move |r| {
             let is_connected = Arc::clone(&is_connected);
+            let hub = Arc::clone(&hub_ref);
 
             async move {
                 if is_connected.load(Ordering::SeqCst) {
                     match r {
                         Ok((data, peer_id)) => match T::Item::deserialize_from_vec(&data) {
-                            Ok(item) => return Some((item, peer_id)),
+                            Ok(item) => {
+                                // Our own messages are always delivered.
+                                if peer_id == recipient {
+                                    return Some((item, peer_id));
+                                }
+                                let delivery = hub.lock().delivery(peer_id, recipient);
+                                match delivery {
+                                    Delivery::Deliver => return Some((item, peer_id)),
+                                    Delivery::Delay(delay) => {
+                                        sleep(delay).await;
+                                        return Some((item, peer_id));
+                                    }
+                                    Delivery::Drop => {
+                                        log::trace!("Dropping gossipsub message from {}", peer_id)
+                                    }
+                                }
+                            }
                             Err(e) => {
                                 log::warn!("Dropped item because deserialization failed: {}", e)
                             }
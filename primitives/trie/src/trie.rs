@@ -40,7 +40,7 @@ use crate::{
 /// It is generic over the values and makes use of Nimiq's database for storage.
 ///
 /// PITODO: Review use of unwrap/expect in the trie's methods.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct MerkleRadixTrie<T: TrieTable> {
     table: T,
 }
@@ -108,9 +108,12 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
 
         db.create_regular_table(&tree.table);
 
-        let mut txn = db.write_transaction();
-        tree.init_root(&mut (&mut txn).into(), incomplete);
-        txn.commit();
+        // A read-only database must already contain the root node.
+        if !db.is_read_only() {
+            let mut txn = db.write_transaction();
+            tree.init_root(&mut (&mut txn).into(), incomplete);
+            txn.commit();
+        }
 
         tree
     }
@@ -1585,6 +1588,37 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         chunk
     }
 
+    /// Returns the keys and values of the node with the given key and all its descendants, in
+    /// ascending key order. Returns an empty vector if there is no node with the given key.
+    pub fn get_subtree_items(&self, txn: &MdbxReadTransaction, key: &KeyNibbles) -> Vec<TrieItem> {
+        let mut items = Vec::new();
+
+        let Some(node) = self.get_node(txn, key) else {
+            return items;
+        };
+        let missing_range = self.get_missing_range(txn);
+
+        let mut stack = vec![node];
+        while let Some(item) = stack.pop() {
+            for child in item.iter_children().rev() {
+                // Stumps are not part of the subtree.
+                let combined = match child.key(&item.key, &missing_range) {
+                    Ok(key) => key,
+                    Err(MerkleRadixTrieError::ChildIsStump) => continue,
+                    Err(e) => unreachable!("Unexpected behavior when getting subtree: {}", e),
+                };
+                stack.push(self.get_node(txn, &combined)
+                    .expect("Failed to find the child of a Merkle Radix Trie node. The database must be corrupt!"));
+            }
+
+            if let Some(value) = item.value {
+                items.push(TrieItem::new(item.key, value));
+            }
+        }
+
+        items
+    }
+
     pub fn iter_nodes<'txn, D: Deserialize>(
         &self,
         txn: &'txn MdbxReadTransaction,
@@ -127,6 +127,11 @@ pub enum BlockchainCommand {
     ValidatorByAddress {
         /// The address to query by.
         address: Address,
+
+        /// Query the state after the given block instead of the current state.
+        /// Only supported by history nodes.
+        #[clap(long)]
+        block_number: Option<u32>,
     },
 
     /// Tries to fetch all validators in the staking contract.
@@ -138,17 +143,56 @@ pub enum BlockchainCommand {
     StakersByValidator {
         /// The validator address to query by.
         address: Address,
+
+        /// Query the state after the given block instead of the current state.
+        /// Only supported by history nodes.
+        #[clap(long)]
+        block_number: Option<u32>,
     },
 
     /// Tries to fetch a staker information given its address.
     Staker {
         /// The address to query by.
         address: Address,
+
+        /// Query the state after the given block instead of the current state.
+        /// Only supported by history nodes.
+        #[clap(long)]
+        block_number: Option<u32>,
+    },
+
+    /// Computes the rewards a staker earned in each epoch of the given range.
+    /// Only supported by history nodes.
+    StakerRewards {
+        /// The staker address to query by.
+        address: Address,
+
+        /// The first epoch to compute the rewards for.
+        from_epoch: u32,
+
+        /// The last epoch to compute the rewards for.
+        to_epoch: u32,
     },
 
     /// Lists the current stakes from the staking contract.
     Stakes {},
 
+    /// Returns the circulating supply and the total staked amount.
+    Supply {
+        /// Query the values after the given block instead of the head block.
+        #[clap(long)]
+        block_number: Option<u32>,
+    },
+
+    /// Returns the block reward for a finalized batch.
+    BatchReward {
+        /// The batch to retrieve the reward for.
+        batch_number: u32,
+    },
+
+    /// Projects the yearly yield of the current active stake.
+    StakingYield {},
+
     /// Follow the head of the blockchain.
     FollowHead {
         /// Show the full block instead of only the hash.
@@ -305,30 +349,87 @@ impl HandleSubcommand for BlockchainCommand {
                     )
                 }
             }
-            BlockchainCommand::ValidatorByAddress { address } => println!(
-                "{:#?}",
-                client.blockchain.get_validator_by_address(address).await?
-            ),
+            BlockchainCommand::ValidatorByAddress {
+                address,
+                block_number,
+            } => match block_number {
+                Some(block_number) => println!(
+                    "{:#?}",
+                    client
+                        .blockchain
+                        .get_validator_by_address_at(address, block_number)
+                        .await?
+                ),
+                None => println!(
+                    "{:#?}",
+                    client.blockchain.get_validator_by_address(address).await?
+                ),
+            },
 
             BlockchainCommand::Validators {} => {
                 println!("{:#?}", client.blockchain.get_validators().await?)
             }
 
-            BlockchainCommand::StakersByValidator { address } => println!(
+            BlockchainCommand::StakersByValidator {
+                address,
+                block_number,
+            } => match block_number {
+                Some(block_number) => println!(
+                    "{:#?}",
+                    client
+                        .blockchain
+                        .get_stakers_by_validator_address_at(address, block_number)
+                        .await?
+                ),
+                None => println!(
+                    "{:#?}",
+                    client
+                        .blockchain
+                        .get_stakers_by_validator_address(address)
+                        .await?
+                ),
+            },
+            BlockchainCommand::Staker {
+                address,
+                block_number,
+            } => match block_number {
+                Some(block_number) => println!(
+                    "{:#?}",
+                    client
+                        .blockchain
+                        .get_staker_by_address_at(address, block_number)
+                        .await?
+                ),
+                None => println!(
+                    "{:#?}",
+                    client.blockchain.get_staker_by_address(address).await?
+                ),
+            },
+            BlockchainCommand::StakerRewards {
+                address,
+                from_epoch,
+                to_epoch,
+            } => println!(
                 "{:#?}",
                 client
                     .blockchain
-                    .get_stakers_by_validator_address(address)
+                    .get_staker_rewards_by_address(address, from_epoch, to_epoch)
                     .await?
             ),
-            BlockchainCommand::Staker { address } => {
+            BlockchainCommand::Stakes {} => {
+                println!("{:#?}", client.blockchain.get_active_validators().await?);
+            }
+            BlockchainCommand::Supply { block_number } => {
+                println!("{:#?}", client.blockchain.get_supply(block_number).await?);
+            }
+            BlockchainCommand::BatchReward { batch_number } => {
                 println!(
                     "{:#?}",
-                    client.blockchain.get_staker_by_address(address).await?
-                )
+                    client.blockchain.get_batch_reward(batch_number).await?
+                );
             }
-            BlockchainCommand::Stakes {} => {
-                println!("{:#?}", client.blockchain.get_active_validators().await?);
+            BlockchainCommand::StakingYield {} => {
+                println!("{:#?}", client.blockchain.get_staking_yield().await?);
             }
 
             BlockchainCommand::FollowHead { block: show_block } => {
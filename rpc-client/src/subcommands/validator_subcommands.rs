@@ -1,6 +1,7 @@
 use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
+use futures::StreamExt;
 use nimiq_keys::Address;
 use nimiq_rpc_interface::{consensus::ConsensusInterface, validator::ValidatorInterface};
 
@@ -28,6 +29,12 @@ pub enum ValidatorCommand {
     /// Returns the voting key of the local validator.
     ValidatorVotingKey {},
 
+    /// Returns the Tendermint state of the latest macro block the local validator took part in.
+    TendermintState {},
+
+    /// Follow the Tendermint state updates of the macro blocks the local validator takes part in.
+    FollowTendermintState {},
+
     /// Sends a `new_validator` transaction to the network. You need to provide the address of a basic
     /// account (the sender wallet) to pay the transaction fee and the validator deposit. The sender wallet must be unlocked
     /// prior to this command.
@@ -142,6 +149,17 @@ impl HandleSubcommand for ValidatorCommand {
                 println!("{:#?}", client.validator.get_voting_key().await?);
             }
 
+            ValidatorCommand::TendermintState {} => {
+                println!("{:#?}", client.validator.get_tendermint_state().await?);
+            }
+
+            ValidatorCommand::FollowTendermintState {} => {
+                let mut stream = client.validator.subscribe_for_tendermint_state().await?;
+                while let Some(state) = stream.next().await {
+                    println!("{state:#?}");
+                }
+            }
+
             ValidatorCommand::SetAutoReactivateValidator {
                 automatic_reactivate,
             } => {
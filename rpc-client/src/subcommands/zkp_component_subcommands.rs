@@ -10,6 +10,9 @@ use crate::Client;
 pub enum ZKPComponentCommand {
     /// Returns the current zkp state.
     ZkpState {},
+
+    /// Returns the zk proof for the election block at the given block number.
+    ZkpByBlockNumber { block_number: u32 },
 }
 
 #[async_trait]
@@ -19,6 +22,15 @@ impl HandleSubcommand for ZKPComponentCommand {
             ZKPComponentCommand::ZkpState {} => {
                 println!("{:?}", client.zkp_component.get_zkp_state().await?);
             }
+            ZKPComponentCommand::ZkpByBlockNumber { block_number } => {
+                println!(
+                    "{:?}",
+                    client
+                        .zkp_component
+                        .get_zkp_by_block_number(block_number)
+                        .await?
+                );
+            }
         }
         Ok(client)
     }
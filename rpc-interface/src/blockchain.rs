@@ -4,8 +4,9 @@ use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 
 use crate::types::{
-    Account, Block, BlockLog, BlockchainState, ExecutedTransaction, Inherent, LogType,
-    PenalizedSlots, RPCData, RPCResult, Slot, Staker, Validator,
+    Account, BatchReward, Block, BlockLog, BlockchainState, ExecutedTransaction, Inherent, LogType,
+    PenalizedSlots, RPCData, RPCResult, Slot, Staker, StakerReward, StakingYield, SupplyInfo,
+    Validator,
 };
 
 #[nimiq_jsonrpc_derive::proxy(name = "BlockchainProxy", rename_all = "camelCase")]
@@ -116,6 +117,56 @@ pub trait BlockchainInterface {
         start_at: Option<Blake2bHash>,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error>;
 
+    /// Returns the hashes for the latest reward, penalty and jail inherents of the given validator.
+    /// It has an option to specify the maximum number of hashes to fetch, it defaults to 500. It has
+    /// also an option to retrieve hashes before a given transaction hash (exclusive).
+    /// The hashes are returned in descending order, meaning the latest one is the first.
+    /// Requires the `validator` secondary index.
+    async fn get_transaction_hashes_by_validator(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error>;
+
+    /// Returns the hashes for the latest HTLC creation and redeem transactions with the given hash
+    /// root, which is given as a hex string. It has an option to specify the maximum number of
+    /// hashes to fetch, it defaults to 500. It has also an option to retrieve hashes before a given
+    /// transaction hash (exclusive).
+    /// The hashes are returned in descending order, meaning the latest one is the first.
+    /// Requires the `htlc` secondary index.
+    async fn get_transaction_hashes_by_htlc_hash_root(
+        &mut self,
+        hash_root: String,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error>;
+
+    /// Returns the hashes for the latest staking contract transactions of the given type, e.g.
+    /// `createValidator` or `addStake`. It has an option to specify the maximum number of hashes to
+    /// fetch, it defaults to 500. It has also an option to retrieve hashes before a given
+    /// transaction hash (exclusive).
+    /// The hashes are returned in descending order, meaning the latest one is the first.
+    /// Requires the `staking` secondary index.
+    async fn get_transaction_hashes_by_staking_type(
+        &mut self,
+        staking_type: String,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error>;
+
+    /// Returns the hashes for the transactions (including inherents) of the blocks with a timestamp
+    /// between `from` and `to` (both inclusive, in milliseconds). It has an option to specify the
+    /// maximum number of hashes to fetch, it defaults to 500.
+    /// The hashes are returned in ascending order, meaning the earliest one is the first.
+    /// Requires the `timestamp` secondary index.
+    async fn get_transaction_hashes_by_timestamp(
+        &mut self,
+        from: u64,
+        to: u64,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error>;
+
     /// Tries to fetch the account at the given address.
     async fn get_account_by_address(
         &mut self,
@@ -169,6 +220,65 @@ pub trait BlockchainInterface {
         address: Address,
     ) -> RPCResult<Staker, BlockchainState, Self::Error>;
 
+    /// Tries to fetch a validator information given its address, as it was after the given block.
+    /// Only supported by history nodes and for blocks they processed. For blocks of epochs that
+    /// were adopted through history sync, and for blocks of pruned epochs other than their election
+    /// block, an error is returned that the staking contract history is not available.
+    async fn get_validator_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Validator, BlockchainState, Self::Error>;
+
+    /// Fetches all stakers for a given validator, as they were after the given block.
+    /// Only supported by history nodes and for blocks they processed, see
+    /// `get_validator_by_address_at`.
+    /// IMPORTANT: This operation iterates over all stakers of the staking contract
+    /// and thus is extremely computationally expensive.
+    async fn get_stakers_by_validator_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error>;
+
+    /// Tries to fetch a staker information given its address, as it was after the given block.
+    /// Only supported by history nodes and for blocks they processed, see
+    /// `get_validator_by_address_at`.
+    async fn get_staker_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Staker, BlockchainState, Self::Error>;
+
+    /// Computes the rewards a staker earned in each epoch from `from_epoch` to `to_epoch`
+    /// (inclusive). The staker's share of a validator's rewards is based on its part of the
+    /// validator's stake at the election block that started the epoch.
+    /// Only supported by history nodes. The amounts for the current epoch are incomplete.
+    /// At most 30 epochs can be queried at once.
+    async fn get_staker_rewards_by_address(
+        &mut self,
+        address: Address,
+        from_epoch: u32,
+        to_epoch: u32,
+    ) -> RPCResult<Vec<StakerReward>, BlockchainState, Self::Error>;
+
+    /// Returns the circulating supply and the total staked amount after the given block, or after
+    /// the head block if none is given. Past staked amounts are only supported by history nodes,
+    /// the staked amount is omitted for blocks whose staking history is not available.
+    async fn get_supply(
+        &mut self,
+        block_number: Option<u32>,
+    ) -> RPCResult<SupplyInfo, BlockchainState, Self::Error>;
+
+    /// Returns the block reward for a finalized batch, including the penalty for a delayed batch.
+    async fn get_batch_reward(
+        &mut self,
+        batch_number: u32,
+    ) -> RPCResult<BatchReward, BlockchainState, Self::Error>;
+
+    /// Projects the yearly yield of the current active stake from the supply curve.
+    async fn get_staking_yield(&mut self) -> RPCResult<StakingYield, BlockchainState, Self::Error>;
+
     /// Subscribes to new block events (retrieves the full block).
     #[stream]
     async fn subscribe_for_head_block(
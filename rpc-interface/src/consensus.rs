@@ -4,7 +4,7 @@ use nimiq_keys::Address;
 use nimiq_primitives::coin::Coin;
 use nimiq_transaction::account::htlc_contract::{AnyHash, PreImage};
 
-use crate::types::{RPCResult, Transaction, ValidityStartHeight};
+use crate::types::{RPCResult, SyncPeerStats, Transaction, ValidityStartHeight};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ConsensusProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -16,6 +16,10 @@ pub trait ConsensusInterface {
     #[allow(clippy::wrong_self_convention)]
     async fn is_consensus_established(&mut self) -> RPCResult<bool, (), Self::Error>;
 
+    /// Returns the request statistics (latency, throughput and error rate) of the peers that we
+    /// have requested sync data from.
+    async fn get_sync_peer_stats(&mut self) -> RPCResult<Vec<SyncPeerStats>, (), Self::Error>;
+
     /// Given a serialized transaction, it will return the corresponding transaction struct.
     async fn get_raw_transaction_info(
         &mut self,
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+use crate::types::{DatabaseSnapshot, DatabaseStats, RPCResult};
+
+/// Administrative methods for the node's database. They are not needed by regular clients and
+/// should be excluded from the allowed methods of publicly reachable RPC servers.
+#[nimiq_jsonrpc_derive::proxy(name = "DatabaseProxy", rename_all = "camelCase")]
+#[async_trait]
+pub trait DatabaseInterface {
+    type Error;
+
+    /// Writes a consistent copy of the node's database to a new directory with the given name in
+    /// the configured snapshot directory, while the node keeps running. Fails if no snapshot
+    /// directory is configured. If `compact` is set (default: true), the copy is sized to its
+    /// content, otherwise the space used by the database is preallocated.
+    async fn create_database_snapshot(
+        &mut self,
+        name: String,
+        compact: Option<bool>,
+    ) -> RPCResult<DatabaseSnapshot, (), Self::Error>;
+
+    /// Returns size and usage statistics of the node's database and each of its tables.
+    async fn get_database_stats(&mut self) -> RPCResult<DatabaseStats, (), Self::Error>;
+}
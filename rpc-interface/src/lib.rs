@@ -1,5 +1,6 @@
 pub mod blockchain;
 pub mod consensus;
+pub mod database;
 pub mod error;
 pub mod mempool;
 pub mod network;
@@ -333,6 +333,74 @@ impl From<nimiq_block::TendermintProof> for TendermintProof {
     }
 }
 
+/// The steps of a Tendermint round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TendermintStep {
+    Propose,
+    Prevote,
+    Precommit,
+}
+
+/// The Tendermint state of the validator for a macro block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TendermintState {
+    /// The block number of the macro block being produced.
+    pub block_number: u32,
+    pub round: u32,
+    pub step: TendermintStep,
+    /// The proposal the validator is locked on, if any.
+    pub locked: Option<TendermintRoundProposal>,
+    /// The latest proposal that received 2f+1 prevotes, if any.
+    pub valid: Option<TendermintRoundProposal>,
+    /// All proposals received so far.
+    pub proposals: Vec<TendermintProposal>,
+    /// The best aggregation known for every round and step.
+    pub aggregations: Vec<TendermintAggregation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TendermintRoundProposal {
+    pub round: u32,
+    pub proposal_hash: Blake2sHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TendermintProposal {
+    pub round: u32,
+    pub valid_round: Option<u32>,
+    pub proposal_hash: Blake2sHash,
+    /// The validator that signed the proposal, if the validators of its epoch are known.
+    pub proposer: Option<Address>,
+}
+
+/// The votes of a round and step, aggregated via Handel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TendermintAggregation {
+    pub round: u32,
+    pub step: TendermintStep,
+    /// Whether the validator has voted in this round and step itself.
+    pub voted: bool,
+    /// The vote of the validator. `None` if it didn't vote or voted against all proposals.
+    pub vote: Option<Blake2sHash>,
+    /// The number of slots that contributed to the aggregation.
+    pub total_weight: usize,
+    /// The number of slots that voted for each proposal.
+    pub weights: Vec<TendermintVoteWeight>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TendermintVoteWeight {
+    /// The proposal voted for. `None` for the votes against all proposals.
+    pub proposal_hash: Option<Blake2sHash>,
+    pub weight: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PolicyConstants {
@@ -922,6 +990,31 @@ impl Staker {
     }
 }
 
+/// The rewards a staker earned in an epoch, based on its share of the delegated validator's stake.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakerReward {
+    pub epoch: u32,
+    pub validator_address: Option<Address>,
+    pub stake: Coin,
+    pub validator_stake: Coin,
+    pub validator_reward: Coin,
+    pub reward: Coin,
+}
+
+impl StakerReward {
+    pub fn from_epoch_reward(reward: nimiq_blockchain::StakerEpochReward) -> Self {
+        StakerReward {
+            epoch: reward.epoch,
+            validator_address: reward.validator_address,
+            stake: reward.stake,
+            validator_stake: reward.validator_stake,
+            validator_reward: reward.validator_reward,
+            reward: reward.reward,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Validator {
@@ -1160,10 +1253,15 @@ pub fn is_of_log_type_and_related_to_addresses(
 pub struct ZKPState {
     latest_block: Block,
     latest_proof: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof_generation: Option<ProofGenerationProgress>,
 }
 
 impl ZKPState {
-    pub fn with_zkp_state(zkp_state: &nimiq_zkp_component::types::ZKPState) -> Self {
+    pub fn with_zkp_state(
+        zkp_state: &nimiq_zkp_component::types::ZKPState,
+        proof_generation: Option<nimiq_zkp_component::types::ProofGenerationProgress>,
+    ) -> Self {
         let latest_block =
             Block::from_macro_block(None, zkp_state.latest_block.clone(), true).unwrap();
         let latest_proof = zkp_state
@@ -1174,6 +1272,43 @@ impl ZKPState {
         Self {
             latest_block,
             latest_proof,
+            proof_generation: proof_generation.map(Into::into),
+        }
+    }
+}
+
+/// The proof of an election block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ZKProof {
+    pub block_number: u32,
+    pub proof: Option<String>,
+}
+
+impl From<nimiq_zkp_component::types::ZKProof> for ZKProof {
+    fn from(zk_proof: nimiq_zkp_component::types::ZKProof) -> Self {
+        Self {
+            block_number: zk_proof.block_number,
+            proof: zk_proof.proof.map(|proof| format!("{proof:?}")),
+        }
+    }
+}
+
+/// The progress of the proof that is currently being generated by the node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProofGenerationProgress {
+    pub block_number: u32,
+    pub completed_sub_proofs: usize,
+    pub total_sub_proofs: usize,
+}
+
+impl From<nimiq_zkp_component::types::ProofGenerationProgress> for ProofGenerationProgress {
+    fn from(progress: nimiq_zkp_component::types::ProofGenerationProgress) -> Self {
+        Self {
+            block_number: progress.block_number,
+            completed_sub_proofs: progress.completed_sub_proofs,
+            total_sub_proofs: progress.total_sub_proofs,
         }
     }
 }
@@ -1356,3 +1491,162 @@ impl MempoolInfo {
         info
     }
 }
+
+/// Request statistics of a peer that we sync from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPeerStats {
+    pub peer_id: String,
+    /// The number of completed requests (successful or not).
+    pub num_requests: u64,
+    /// The number of failed requests.
+    pub num_failures: u64,
+    /// The number of requests currently in flight.
+    pub num_pending: u64,
+    /// The average response latency in milliseconds.
+    pub avg_latency_ms: Option<u64>,
+    /// The average throughput in bytes per second.
+    pub avg_throughput: f64,
+    /// The fraction of recent requests that failed.
+    pub error_rate: f64,
+    /// The score used to rank the peer when selecting peers for sync requests (higher is better).
+    pub score: Option<f64>,
+}
+
+/// The circulating supply and the staked amount at a block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyInfo {
+    pub block_number: u32,
+    pub timestamp: u64,
+    /// The circulating supply at the timestamp of the block.
+    pub supply: Coin,
+    /// The total amount staked, including validator deposits. Omitted if the node doesn't have
+    /// the staking history of the block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staked: Option<Coin>,
+}
+
+impl From<nimiq_blockchain::SupplyInfo> for SupplyInfo {
+    fn from(info: nimiq_blockchain::SupplyInfo) -> Self {
+        SupplyInfo {
+            block_number: info.block_number,
+            timestamp: info.timestamp,
+            supply: info.supply,
+            staked: info.staked,
+        }
+    }
+}
+
+/// The block reward for a batch. It is paid out in the macro block of the next batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReward {
+    pub batch_number: u32,
+    /// The macro block that ends the batch.
+    pub block_number: u32,
+    /// The delay of the batch in milliseconds.
+    pub batch_delay: u64,
+    /// The share of the maximum reward that is paid out due to the delay.
+    pub delay_penalty: f64,
+    /// The reward if the batch had been produced in time.
+    pub max_reward: Coin,
+    pub reward: Coin,
+    pub tx_fees: Coin,
+}
+
+impl From<nimiq_blockchain::BatchRewardInfo> for BatchReward {
+    fn from(info: nimiq_blockchain::BatchRewardInfo) -> Self {
+        BatchReward {
+            batch_number: info.batch_number,
+            block_number: info.block_number,
+            batch_delay: info.batch_delay,
+            delay_penalty: info.delay_penalty,
+            max_reward: info.max_reward,
+            reward: info.reward,
+            tx_fees: info.tx_fees,
+        }
+    }
+}
+
+/// The projected yearly yield for the current active stake.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingYield {
+    pub block_number: u32,
+    /// The total stake of the active validators.
+    pub active_stake: Coin,
+    /// The block rewards of the next year, without transaction fees and delay penalties.
+    pub yearly_reward: Coin,
+    /// The yearly reward relative to the active stake.
+    pub apr: Option<f64>,
+}
+
+impl From<nimiq_blockchain::StakingYield> for StakingYield {
+    fn from(staking_yield: nimiq_blockchain::StakingYield) -> Self {
+        StakingYield {
+            block_number: staking_yield.block_number,
+            active_stake: staking_yield.active_stake,
+            yearly_reward: staking_yield.yearly_reward,
+            apr: staking_yield.apr,
+        }
+    }
+}
+
+/// Summary of a database snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseSnapshot {
+    /// The directory the snapshot was written to.
+    pub path: String,
+    /// The number of tables that were copied.
+    pub num_tables: usize,
+    /// The total number of entries that were copied.
+    pub num_entries: usize,
+}
+
+/// Size and page usage of a database table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseTableStats {
+    /// The name of the table.
+    pub name: String,
+    /// The number of entries (counting each duplicate).
+    pub entries: usize,
+    /// The depth of the table's B-tree.
+    pub depth: u32,
+    /// The number of internal (non-leaf) pages.
+    pub branch_pages: usize,
+    /// The number of leaf pages.
+    pub leaf_pages: usize,
+    /// The number of overflow pages, used for large values.
+    pub overflow_pages: usize,
+    /// The total size of the table's pages in bytes.
+    pub size: usize,
+}
+
+/// Statistics about the node's database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStats {
+    /// The size of a database page in bytes.
+    pub page_size: u32,
+    /// The current size of the memory map in bytes.
+    pub map_size: usize,
+    /// The configured maximum size of the database file in bytes, if any.
+    pub max_size: Option<usize>,
+    /// The number of bytes allocated in the database file.
+    pub allocated_size: usize,
+    /// The number of allocated bytes that are not on the freelist.
+    pub used_size: usize,
+    /// The number of pages on the freelist.
+    pub freelist_pages: usize,
+    /// The share of the maximum database size that is allocated.
+    pub capacity_usage: Option<f64>,
+    /// The number of reader slots in use.
+    pub num_readers: usize,
+    /// The maximum number of reader slots.
+    pub max_readers: usize,
+    /// Statistics for every table, sorted by size in descending order.
+    pub tables: Vec<DatabaseTableStats>,
+}
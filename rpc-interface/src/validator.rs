@@ -1,7 +1,8 @@
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use nimiq_keys::Address;
 
-use crate::types::RPCResult;
+use crate::types::{RPCData, RPCResult, TendermintState};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ValidatorProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -28,4 +29,13 @@ pub trait ValidatorInterface {
 
     /// Returns if our validator is currently synced.
     async fn is_validator_synced(&mut self) -> RPCResult<bool, (), Self::Error>;
+
+    /// Returns the Tendermint state of the latest macro block our validator took part in.
+    async fn get_tendermint_state(&mut self) -> RPCResult<TendermintState, (), Self::Error>;
+
+    /// Subscribes to the Tendermint state updates of the macro blocks our validator takes part in.
+    #[stream]
+    async fn subscribe_for_tendermint_state(
+        &mut self,
+    ) -> Result<BoxStream<'static, RPCData<TendermintState, ()>>, Self::Error>;
 }
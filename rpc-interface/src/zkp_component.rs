@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::types::{RPCResult, ZKPState};
+use crate::types::{RPCResult, ZKPState, ZKProof};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ZKPComponentProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -8,5 +8,13 @@ pub trait ZKPComponentInterface {
     type Error;
 
     /// Returns the current ZKP state (proof with its related block hash and block number).
+    /// If the node is generating a proof, the progress of the proof generation is included.
     async fn get_zkp_state(&mut self) -> RPCResult<ZKPState, (), Self::Error>;
+
+    /// Returns the proof for the election block at the given block number. Proofs older than the
+    /// current one are only available if the node keeps a proof history.
+    async fn get_zkp_by_block_number(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<ZKProof, (), Self::Error>;
 }
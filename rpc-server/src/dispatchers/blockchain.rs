@@ -1,18 +1,23 @@
 use async_trait::async_trait;
 use futures::{future, stream::BoxStream, StreamExt};
 use nimiq_account::{BlockLog as BBlockLog, TransactionLog};
-use nimiq_blockchain::interface::{HistoryIndexInterface, HistoryInterface};
+use nimiq_blockchain::{
+    interface::{HistoryIndexInterface, HistoryInterface},
+    HtlcHashRootIndex, SecondaryIndex, StakingTransactionType, StakingTypeIndex, TimestampIndex,
+    ValidatorIndex, MAX_STAKER_REWARD_EPOCHS,
+};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
+use nimiq_database::traits::Table;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
 use nimiq_rpc_interface::{
     blockchain::BlockchainInterface,
     types::{
-        is_of_log_type_and_related_to_addresses, Account, Block, BlockLog, BlockchainState,
-        ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, Slot, Staker,
-        Validator,
+        is_of_log_type_and_related_to_addresses, Account, BatchReward, Block, BlockLog,
+        BlockchainState, ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData,
+        RPCResult, Slot, Staker, StakerReward, StakingYield, SupplyInfo, Validator,
     },
 };
 use tokio_stream::wrappers::BroadcastStream;
@@ -46,6 +51,28 @@ fn get_block_by_hash(
         .map(|block| block.into())
 }
 
+/// Returns the hashes of the latest transactions that are indexed under the given key in the
+/// secondary index `I`.
+fn get_tx_hashes_by_secondary_key<I: SecondaryIndex>(
+    blockchain: &BlockchainProxy,
+    key: &<I::Table as Table>::Key,
+    max: Option<u16>,
+    start_at: Option<Blake2bHash>,
+) -> RPCResult<Vec<Blake2bHash>, (), Error> {
+    if let BlockchainProxy::Full(blockchain) = blockchain {
+        Ok(blockchain
+            .read()
+            .history_store
+            .history_index()
+            .ok_or(Error::RequiresHistoryIndex)?
+            .get_tx_hashes_by_secondary_key::<I>(key, max.unwrap_or(500), start_at, None)
+            .ok_or(Error::RequiresSecondaryIndex(I::KIND))?
+            .into())
+    } else {
+        Err(Error::NotSupportedForLightBlockchain)
+    }
+}
+
 /// Tries to fetch a validator information given its address.
 /// This function requires the read lock acquisition prior to its execution.
 fn get_validator_by_address(
@@ -364,6 +391,71 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_transaction_hashes_by_validator(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error> {
+        get_tx_hashes_by_secondary_key::<ValidatorIndex>(&self.blockchain, &address, max, start_at)
+    }
+
+    async fn get_transaction_hashes_by_htlc_hash_root(
+        &mut self,
+        hash_root: String,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error> {
+        let hash_root = hex::decode(hash_root)?;
+        get_tx_hashes_by_secondary_key::<HtlcHashRootIndex>(
+            &self.blockchain,
+            &hash_root,
+            max,
+            start_at,
+        )
+    }
+
+    async fn get_transaction_hashes_by_staking_type(
+        &mut self,
+        staking_type: String,
+        max: Option<u16>,
+        start_at: Option<Blake2bHash>,
+    ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error> {
+        let staking_type: StakingTransactionType = staking_type
+            .parse()
+            .map_err(|e| Error::InvalidArgument(e.to_string()))?;
+        get_tx_hashes_by_secondary_key::<StakingTypeIndex>(
+            &self.blockchain,
+            &staking_type.into(),
+            max,
+            start_at,
+        )
+    }
+
+    async fn get_transaction_hashes_by_timestamp(
+        &mut self,
+        from: u64,
+        to: u64,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<Blake2bHash>, (), Self::Error> {
+        if let BlockchainProxy::Full(blockchain) = &self.blockchain {
+            Ok(blockchain
+                .read()
+                .history_store
+                .history_index()
+                .ok_or(Error::RequiresHistoryIndex)?
+                .get_tx_hashes_by_secondary_key_range::<TimestampIndex>(
+                    from..=to,
+                    max.unwrap_or(500),
+                    None,
+                )
+                .ok_or(Error::RequiresSecondaryIndex(TimestampIndex::KIND))?
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_account_by_address(
         &mut self,
         address: Address,
@@ -551,6 +643,158 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_validator_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Validator, BlockchainState, Self::Error> {
+        let BlockchainProxy::Full(ref blockchain) = self.blockchain else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        // Don't hold the blockchain lock while reconstructing the state, the query reads from its
+        // own database snapshot. Applying the staking diffs reads from the database, so it doesn't
+        // run on the async runtime.
+        let staking_history = blockchain.read().staking_history();
+        let validator_address = address.clone();
+        let validator = tokio::task::spawn_blocking(move || {
+            staking_history.validator_at(&validator_address, block_number)
+        })
+        .await??
+        .ok_or(Error::ValidatorNotFound(address))?;
+
+        Ok(RPCData::with_blockchain(
+            Validator::from_validator(&validator),
+            &self.blockchain.read(),
+        ))
+    }
+
+    async fn get_stakers_by_validator_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error> {
+        let BlockchainProxy::Full(ref blockchain) = self.blockchain else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        let staking_history = blockchain.read().staking_history();
+        let stakers = tokio::task::spawn_blocking(move || {
+            staking_history.stakers_for_validator_at(&address, block_number)
+        })
+        .await??;
+
+        Ok(RPCData::with_blockchain(
+            stakers.iter().map(Staker::from_staker).collect(),
+            &self.blockchain.read(),
+        ))
+    }
+
+    async fn get_staker_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Staker, BlockchainState, Self::Error> {
+        let BlockchainProxy::Full(ref blockchain) = self.blockchain else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        let staking_history = blockchain.read().staking_history();
+        let staker_address = address.clone();
+        let staker = tokio::task::spawn_blocking(move || {
+            staking_history.staker_at(&staker_address, block_number)
+        })
+        .await??
+        .ok_or(Error::StakerNotFound(address))?;
+
+        Ok(RPCData::with_blockchain(
+            Staker::from_staker(&staker),
+            &self.blockchain.read(),
+        ))
+    }
+
+    async fn get_staker_rewards_by_address(
+        &mut self,
+        address: Address,
+        from_epoch: u32,
+        to_epoch: u32,
+    ) -> RPCResult<Vec<StakerReward>, BlockchainState, Self::Error> {
+        if from_epoch > to_epoch {
+            return Err(Error::InvalidArgument(
+                "from_epoch must not be greater than to_epoch".to_string(),
+            ));
+        }
+        if to_epoch - from_epoch >= MAX_STAKER_REWARD_EPOCHS {
+            return Err(Error::InvalidArgument(format!(
+                "At most {MAX_STAKER_REWARD_EPOCHS} epochs can be queried at once"
+            )));
+        }
+
+        let blockchain_proxy = self.blockchain.read();
+        let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        let rewards = blockchain.get_staker_rewards(&address, from_epoch..=to_epoch)?;
+
+        Ok(RPCData::with_blockchain(
+            rewards
+                .into_iter()
+                .map(StakerReward::from_epoch_reward)
+                .collect(),
+            &blockchain_proxy,
+        ))
+    }
+
+    async fn get_supply(
+        &mut self,
+        block_number: Option<u32>,
+    ) -> RPCResult<SupplyInfo, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        let block_number = block_number.unwrap_or_else(|| blockchain.block_number());
+        let supply_info = blockchain.get_supply_info(block_number)?;
+
+        Ok(RPCData::with_blockchain(
+            supply_info.into(),
+            &blockchain_proxy,
+        ))
+    }
+
+    async fn get_batch_reward(
+        &mut self,
+        batch_number: u32,
+    ) -> RPCResult<BatchReward, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        let batch_reward = blockchain.get_batch_reward_info(batch_number)?;
+
+        Ok(RPCData::with_blockchain(
+            batch_reward.into(),
+            &blockchain_proxy,
+        ))
+    }
+
+    async fn get_staking_yield(&mut self) -> RPCResult<StakingYield, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        let staking_yield = blockchain.get_staking_yield()?;
+
+        Ok(RPCData::with_blockchain(
+            staking_yield.into(),
+            &blockchain_proxy,
+        ))
+    }
+
     #[stream]
     async fn subscribe_for_head_block(
         &mut self,
@@ -11,7 +11,7 @@ use nimiq_network_libp2p::Network;
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_rpc_interface::{
     consensus::ConsensusInterface,
-    types::{RPCResult, Transaction as RPCTransaction, ValidityStartHeight},
+    types::{RPCResult, SyncPeerStats, Transaction as RPCTransaction, ValidityStartHeight},
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{
@@ -77,6 +77,25 @@ impl ConsensusInterface for ConsensusDispatcher {
         Ok(self.consensus.is_established().into())
     }
 
+    async fn get_sync_peer_stats(&mut self) -> RPCResult<Vec<SyncPeerStats>, (), Self::Error> {
+        Ok(self
+            .consensus
+            .sync_peer_stats()
+            .into_iter()
+            .map(|(peer_id, stats)| SyncPeerStats {
+                peer_id: peer_id.to_string(),
+                num_requests: stats.num_requests,
+                num_failures: stats.num_failures,
+                num_pending: stats.num_pending,
+                avg_latency_ms: stats.avg_latency.map(|latency| latency.as_millis() as u64),
+                avg_throughput: stats.avg_throughput,
+                error_rate: stats.error_rate,
+                score: stats.score(),
+            })
+            .collect::<Vec<_>>()
+            .into())
+    }
+
     async fn get_raw_transaction_info(
         &mut self,
         raw_tx: String,
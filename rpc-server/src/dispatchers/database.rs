@@ -0,0 +1,108 @@
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_rpc_interface::{
+    database::DatabaseInterface,
+    types::{DatabaseSnapshot, DatabaseStats, DatabaseTableStats, RPCResult},
+};
+
+use crate::error::Error;
+
+pub struct DatabaseDispatcher {
+    blockchain: BlockchainProxy,
+    /// The directory snapshots are written to. Snapshots are disabled if none is configured.
+    snapshot_dir: Option<PathBuf>,
+}
+
+impl DatabaseDispatcher {
+    pub fn new(blockchain: BlockchainProxy, snapshot_dir: Option<PathBuf>) -> Self {
+        Self {
+            blockchain,
+            snapshot_dir,
+        }
+    }
+}
+
+#[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
+#[async_trait]
+impl DatabaseInterface for DatabaseDispatcher {
+    type Error = Error;
+
+    async fn create_database_snapshot(
+        &mut self,
+        name: String,
+        compact: Option<bool>,
+    ) -> RPCResult<DatabaseSnapshot, (), Self::Error> {
+        let BlockchainProxy::Full(ref blockchain) = self.blockchain else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+        let snapshot_dir = self.snapshot_dir.as_ref().ok_or(Error::SnapshotsDisabled)?;
+
+        // Only allow plain directory names, so that snapshots can't be written elsewhere.
+        let mut components = Path::new(&name).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        ) {
+            return Err(Error::InvalidArgument(format!(
+                "Invalid snapshot name: {name}"
+            )));
+        }
+        let path = snapshot_dir.join(&name);
+
+        // Don't hold the blockchain lock while copying, the snapshot uses its own read transaction.
+        let db = blockchain.read().db().clone();
+        let snapshot_path = path.clone();
+        let snapshot_info = tokio::task::spawn_blocking(move || {
+            db.snapshot(snapshot_path, compact.unwrap_or(true))
+        })
+        .await??;
+
+        Ok(DatabaseSnapshot {
+            path: path.display().to_string(),
+            num_tables: snapshot_info.num_tables,
+            num_entries: snapshot_info.num_entries,
+        }
+        .into())
+    }
+
+    async fn get_database_stats(&mut self) -> RPCResult<DatabaseStats, (), Self::Error> {
+        let BlockchainProxy::Full(ref blockchain) = self.blockchain else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        // Collecting the stats traverses the freelist, so don't block the async runtime.
+        let db = blockchain.read().db().clone();
+        let stats = tokio::task::spawn_blocking(move || db.stats()).await??;
+
+        let mut tables: Vec<_> = stats
+            .tables
+            .iter()
+            .map(|table| DatabaseTableStats {
+                name: table.name.clone(),
+                entries: table.entries,
+                depth: table.depth,
+                branch_pages: table.branch_pages,
+                leaf_pages: table.leaf_pages,
+                overflow_pages: table.overflow_pages,
+                size: table.num_pages() * stats.page_size as usize,
+            })
+            .collect();
+        tables.sort_by(|a, b| b.size.cmp(&a.size));
+
+        Ok(DatabaseStats {
+            page_size: stats.page_size,
+            map_size: stats.map_size,
+            max_size: stats.max_size,
+            allocated_size: stats.allocated_size(),
+            used_size: stats.used_size(),
+            freelist_pages: stats.freelist_pages,
+            capacity_usage: stats.capacity_usage(),
+            num_readers: stats.num_readers,
+            max_readers: stats.max_readers,
+            tables,
+        }
+        .into())
+    }
+}
@@ -1,5 +1,6 @@
 pub use blockchain::BlockchainDispatcher;
 pub use consensus::ConsensusDispatcher;
+pub use database::DatabaseDispatcher;
 pub use mempool::MempoolDispatcher;
 pub use network::NetworkDispatcher;
 pub use policy::PolicyDispatcher;
@@ -9,6 +10,7 @@ pub use zkp_component::ZKPComponentDispatcher;
 
 mod blockchain;
 mod consensus;
+mod database;
 mod mempool;
 mod network;
 mod policy;
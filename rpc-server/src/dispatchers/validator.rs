@@ -1,12 +1,23 @@
 use std::sync::atomic::Ordering;
 
 use async_trait::async_trait;
+use futures::{future, stream::BoxStream, StreamExt};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
 use nimiq_keys::Address;
 use nimiq_network_libp2p::Network;
-use nimiq_rpc_interface::{types::RPCResult, validator::ValidatorInterface};
+use nimiq_primitives::policy::Policy;
+use nimiq_rpc_interface::{
+    types::{
+        RPCData, RPCResult, TendermintAggregation, TendermintProposal, TendermintRoundProposal,
+        TendermintState, TendermintStep, TendermintVoteWeight,
+    },
+    validator::ValidatorInterface,
+};
 use nimiq_serde::Serialize;
-use nimiq_validator::validator::ValidatorProxy;
+use nimiq_tendermint::Step;
+use nimiq_validator::{aggregation::tendermint::state::MacroState, validator::ValidatorProxy};
 
 use crate::error::Error;
 
@@ -24,6 +35,93 @@ impl ValidatorDispatcher {
     }
 }
 
+fn tendermint_step(step: Step) -> TendermintStep {
+    match step {
+        Step::Propose => TendermintStep::Propose,
+        Step::Prevote => TendermintStep::Prevote,
+        Step::Precommit => TendermintStep::Precommit,
+    }
+}
+
+/// Converts the Tendermint state of the validator into its RPC representation.
+fn tendermint_state(state: &MacroState, blockchain: &BlockchainProxy) -> TendermintState {
+    let blockchain = blockchain.read();
+
+    // The proposers are resolved using the validators of the epoch the macro block belongs to.
+    let epoch = Policy::epoch_at(state.block_number());
+    let current_epoch = Policy::epoch_at(blockchain.block_number() + 1);
+    let validators = if epoch == current_epoch {
+        blockchain.current_validators()
+    } else if epoch + 1 == current_epoch {
+        blockchain.previous_validators()
+    } else {
+        None
+    };
+
+    let proposals = state
+        .proposals()
+        .map(
+            |(round, proposal_hash, valid_round, signer)| TendermintProposal {
+                round,
+                valid_round,
+                proposal_hash: proposal_hash.clone(),
+                proposer: validators
+                    .filter(|validators| (signer as usize) < validators.num_validators())
+                    .map(|validators| {
+                        validators
+                            .get_validator_by_slot_band(signer)
+                            .address
+                            .clone()
+                    }),
+            },
+        )
+        .collect();
+
+    let aggregations = state
+        .best_votes()
+        .iter()
+        .map(|(&(round, step), contribution)| {
+            let vote = state.votes().get(&(round, step));
+            let weights: Vec<_> = contribution
+                .contributions
+                .iter()
+                .map(|(proposal_hash, signature)| TendermintVoteWeight {
+                    proposal_hash: proposal_hash.clone(),
+                    weight: signature.signers.len(),
+                })
+                .collect();
+            TendermintAggregation {
+                round,
+                step: tendermint_step(step),
+                voted: vote.is_some(),
+                vote: vote.cloned().flatten(),
+                total_weight: weights.iter().map(|weight| weight.weight).sum(),
+                weights,
+            }
+        })
+        .collect();
+
+    TendermintState {
+        block_number: state.block_number(),
+        round: state.round(),
+        step: tendermint_step(state.step()),
+        locked: state
+            .locked()
+            .map(|(round, proposal_hash)| TendermintRoundProposal {
+                round: *round,
+                proposal_hash: proposal_hash.clone(),
+            }),
+        valid: state
+            .valid()
+            .map(|(round, proposal_hash)| TendermintRoundProposal {
+                round: *round,
+                proposal_hash: proposal_hash.clone(),
+            }),
+        proposals,
+        aggregations,
+    }
+}
+
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
 #[async_trait]
 impl ValidatorInterface for ValidatorDispatcher {
@@ -69,4 +167,27 @@ impl ValidatorInterface for ValidatorDispatcher {
         let is_synced = self.consensus.is_ready_for_validation();
         Ok(is_synced.into())
     }
+
+    async fn get_tendermint_state(&mut self) -> RPCResult<TendermintState, (), Self::Error> {
+        let macro_state = self.validator.macro_state.read();
+        let state = macro_state.as_ref().ok_or(Error::TendermintStateNotFound)?;
+        Ok(tendermint_state(state, &self.consensus.blockchain).into())
+    }
+
+    #[stream]
+    async fn subscribe_for_tendermint_state(
+        &mut self,
+    ) -> Result<BoxStream<'static, RPCData<TendermintState, ()>>, Self::Error> {
+        let blockchain = self.consensus.blockchain.clone();
+        let stream = self.validator.subscribe_macro_state();
+
+        Ok(stream
+            .filter_map(move |state| {
+                let result = state
+                    .ok()
+                    .map(|state| tendermint_state(&state, &blockchain).into());
+                future::ready(result)
+            })
+            .boxed())
+    }
 }
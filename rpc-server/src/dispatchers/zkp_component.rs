@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use nimiq_network_libp2p::Network;
 use nimiq_rpc_interface::{
-    types::{RPCResult, ZKPState},
+    types::{RPCResult, ZKPState, ZKProof},
     zkp_component::ZKPComponentInterface,
 };
 use nimiq_zkp_component::zkp_component::ZKPComponentProxy;
@@ -24,6 +24,20 @@ impl ZKPComponentInterface for ZKPComponentDispatcher {
     type Error = Error;
 
     async fn get_zkp_state(&mut self) -> RPCResult<ZKPState, (), Self::Error> {
-        Ok(ZKPState::with_zkp_state(&self.zkp_component.get_zkp_state()).into())
+        Ok(ZKPState::with_zkp_state(
+            &self.zkp_component.get_zkp_state(),
+            self.zkp_component.get_proof_generation_progress(),
+        )
+        .into())
+    }
+
+    async fn get_zkp_by_block_number(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<ZKProof, (), Self::Error> {
+        self.zkp_component
+            .get_zkp_by_block_number(block_number)
+            .map(|zk_proof| ZKProof::from(zk_proof).into())
+            .ok_or(Error::ZKProofNotFound(block_number))
     }
 }
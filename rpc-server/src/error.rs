@@ -1,3 +1,4 @@
+use nimiq_blockchain::SecondaryIndexKind;
 use nimiq_hash::Blake2bHash;
 use nimiq_jsonrpc_core::RpcError;
 use nimiq_keys::Address;
@@ -21,6 +22,12 @@ pub enum Error {
     #[error("Block not found: {0}")]
     BlockNotFoundByHash(Blake2bHash),
 
+    #[error("No zk proof for block: {0}")]
+    ZKProofNotFound(u32),
+
+    #[error("No Tendermint state available")]
+    TendermintStateNotFound,
+
     #[error("Block number cannot be smaller than genesis block")]
     BlockNumberBeforeGenesis,
 
@@ -39,6 +46,9 @@ pub enum Error {
     #[error("Method requires a history index")]
     RequiresHistoryIndex,
 
+    #[error("Method requires the {0} secondary index")]
+    RequiresSecondaryIndex(SecondaryIndexKind),
+
     #[error("Invalid combination of transaction parameters")]
     InvalidTransactionParameters,
 
@@ -87,8 +97,20 @@ pub enum Error {
     #[error("No consensus")]
     NoConsensus,
 
+    #[error("{0}")]
+    Blockchain(#[from] nimiq_blockchain_interface::BlockchainError),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] nimiq_database::Error),
+
+    #[error("Database snapshots are disabled, no snapshot directory is configured")]
+    SnapshotsDisabled,
+
+    #[error("Background task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
 }
 
 impl From<Error> for RpcError {
@@ -0,0 +1,354 @@
+//! Vote aggregation of the simulated validators using Handel.
+//!
+//! BLS signatures are replaced by a keyed hash per validator which is aggregated by wrapping
+//! addition. This is cheap to create and verify, while still allowing to detect forged
+//! contributions, which is all the simulation needs.
+
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use nimiq_bls::PublicKey;
+use nimiq_collections::BitSet;
+use nimiq_handel::{
+    contribution::{AggregatableContribution, ContributionError},
+    evaluator::WeightedVote,
+    identity::{Identity, IdentityRegistry, WeightRegistry},
+    partitioner::BinomialPartitioner,
+    protocol::Protocol,
+    store::ReplaceStore,
+    update::LevelUpdate,
+    verifier::{VerificationResult, Verifier},
+};
+use nimiq_tendermint::{Aggregation, AggregationMessage, Step};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// The hash of a proposal.
+pub type ProposalHash = u64;
+
+/// Mixes the bits of `x` (the finalizer of SplitMix64).
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Hashes the given values into a single one.
+pub(crate) fn hash(values: &[u64]) -> u64 {
+    values
+        .iter()
+        .fold(0x6e69_6d69_7173_696d, |acc, value| mix(acc ^ value))
+}
+
+/// Identifies the aggregation of a round and step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VoteId {
+    pub round: u32,
+    pub step: Step,
+}
+
+impl fmt::Display for VoteId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{:?}", self.round, self.step)
+    }
+}
+
+/// The signature of `signer` for `vote` in the aggregation identified by `id`.
+pub fn sign_vote(signer: usize, id: VoteId, vote: Option<ProposalHash>) -> u64 {
+    hash(&[
+        signer as u64,
+        id.round as u64,
+        id.step as u64,
+        vote.is_some() as u64,
+        vote.unwrap_or_default(),
+    ])
+}
+
+/// An aggregated signature together with its signers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSignature {
+    pub signature: u64,
+    pub signers: BitSet,
+}
+
+impl MultiSignature {
+    /// Verifies the signature for `vote` of at most `num_validators` validators.
+    pub fn verify(
+        &self,
+        id: VoteId,
+        vote: Option<ProposalHash>,
+        num_validators: usize,
+    ) -> VerificationResult {
+        let mut expected = 0u64;
+        for signer in self.signers.iter() {
+            if signer >= num_validators {
+                return VerificationResult::UnknownSigner { signer };
+            }
+            expected = expected.wrapping_add(sign_vote(signer, id, vote));
+        }
+
+        if expected == self.signature {
+            VerificationResult::Ok
+        } else {
+            VerificationResult::Forged
+        }
+    }
+}
+
+/// The votes of a set of validators, grouped by what they voted for.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Votes {
+    pub contributions: BTreeMap<Option<ProposalHash>, MultiSignature>,
+}
+
+impl Votes {
+    /// Creates the vote of a single validator.
+    pub fn new(signer: usize, id: VoteId, vote: Option<ProposalHash>) -> Self {
+        let mut signers = BitSet::new();
+        signers.insert(signer);
+
+        let mut contributions = BTreeMap::new();
+        contributions.insert(
+            vote,
+            MultiSignature {
+                signature: sign_vote(signer, id, vote),
+                signers,
+            },
+        );
+        Self { contributions }
+    }
+
+    /// Creates the vote of a single validator with an invalid signature.
+    pub fn forged(signer: usize, id: VoteId, vote: Option<ProposalHash>) -> Self {
+        let mut votes = Self::new(signer, id, vote);
+        for multi_sig in votes.contributions.values_mut() {
+            multi_sig.signature = !multi_sig.signature;
+        }
+        votes
+    }
+
+    /// Verifies the signatures of all votes.
+    pub fn verify(&self, id: VoteId, num_validators: usize) -> VerificationResult {
+        for (vote, multi_sig) in &self.contributions {
+            let result = multi_sig.verify(id, *vote, num_validators);
+            if !result.is_ok() {
+                return result;
+            }
+        }
+        VerificationResult::Ok
+    }
+}
+
+impl AggregatableContribution for Votes {
+    fn contributors(&self) -> BitSet {
+        self.contributions
+            .values()
+            .fold(BitSet::new(), |mut contributors, multi_sig| {
+                contributors |= multi_sig.signers.clone();
+                contributors
+            })
+    }
+
+    fn combine(&mut self, other_contribution: &Self) -> Result<(), ContributionError> {
+        let overlap = &self.contributors() & &other_contribution.contributors();
+        if !overlap.is_empty() {
+            return Err(ContributionError::Overlapping(overlap));
+        }
+
+        for (vote, other) in &other_contribution.contributions {
+            let multi_sig = self.contributions.entry(*vote).or_default();
+            multi_sig.signature = multi_sig.signature.wrapping_add(other.signature);
+            multi_sig.signers = &multi_sig.signers | &other.signers;
+        }
+        Ok(())
+    }
+}
+
+impl Aggregation<ProposalHash> for Votes {
+    fn proposals(&self) -> Vec<(ProposalHash, usize)> {
+        self.contributions
+            .iter()
+            .filter_map(|(vote, multi_sig)| vote.map(|hash| (hash, multi_sig.signers.len())))
+            .collect()
+    }
+
+    fn contributors_for(&self, vote: Option<&ProposalHash>) -> BitSet {
+        self.contributions
+            .get(&vote.copied())
+            .map(|multi_sig| multi_sig.signers.clone())
+            .unwrap_or_default()
+    }
+
+    fn all_contributors(&self) -> BitSet {
+        self.contributors()
+    }
+}
+
+/// A level update received from another validator.
+#[derive(Clone, Debug)]
+pub struct AggregateMessage(pub LevelUpdate<Votes>);
+
+impl Aggregation<ProposalHash> for AggregateMessage {
+    fn proposals(&self) -> Vec<(ProposalHash, usize)> {
+        self.0.aggregate.proposals()
+    }
+
+    fn contributors_for(&self, vote: Option<&ProposalHash>) -> BitSet {
+        self.0.aggregate.contributors_for(vote)
+    }
+
+    fn all_contributors(&self) -> BitSet {
+        self.0.aggregate.all_contributors()
+    }
+}
+
+impl AggregationMessage<ProposalHash> for AggregateMessage {
+    fn sender(&self) -> u16 {
+        self.0.origin
+    }
+}
+
+/// The level update as it is sent over the network. The origin is set by the receiver.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableLevelUpdate {
+    aggregate: Votes,
+    individual: Option<Votes>,
+    level: u8,
+}
+
+impl SerializableLevelUpdate {
+    pub fn into_level_update(self, origin: u16) -> LevelUpdate<Votes> {
+        LevelUpdate {
+            aggregate: self.aggregate,
+            individual: self.individual,
+            level: self.level,
+            origin,
+        }
+    }
+}
+
+impl From<LevelUpdate<Votes>> for SerializableLevelUpdate {
+    fn from(update: LevelUpdate<Votes>) -> Self {
+        Self {
+            aggregate: update.aggregate,
+            individual: update.individual,
+            level: update.level,
+        }
+    }
+}
+
+/// Every validator has a single slot.
+#[derive(Debug)]
+pub struct ValidatorRegistry {
+    num_validators: usize,
+}
+
+impl WeightRegistry for ValidatorRegistry {
+    fn weight(&self, id: usize) -> Option<usize> {
+        (id < self.num_validators).then_some(1)
+    }
+}
+
+impl IdentityRegistry for ValidatorRegistry {
+    fn public_key(&self, _id: usize) -> Option<PublicKey> {
+        None
+    }
+
+    fn signers_identity(&self, slots: &BitSet) -> Identity {
+        Identity::new(slots.clone())
+    }
+}
+
+#[derive(Debug)]
+pub struct VoteVerifier {
+    id: VoteId,
+    num_validators: usize,
+}
+
+impl VoteVerifier {
+    pub fn new(id: VoteId, num_validators: usize) -> Self {
+        Self { id, num_validators }
+    }
+}
+
+#[async_trait]
+impl Verifier for VoteVerifier {
+    type Contribution = Votes;
+
+    async fn verify(&self, contribution: &Self::Contribution) -> VerificationResult {
+        contribution.verify(self.id, self.num_validators)
+    }
+}
+
+#[derive(Debug)]
+pub struct VoteAggregationProtocol {
+    verifier: Arc<VoteVerifier>,
+    partitioner: Arc<BinomialPartitioner>,
+    evaluator: Arc<WeightedVote<VoteId, Self>>,
+    store: Arc<RwLock<ReplaceStore<VoteId, Self>>>,
+    registry: Arc<ValidatorRegistry>,
+    node_id: usize,
+    id: VoteId,
+}
+
+impl VoteAggregationProtocol {
+    pub fn new(id: VoteId, node_id: usize, num_validators: usize) -> Self {
+        let partitioner = Arc::new(BinomialPartitioner::new(node_id, num_validators));
+        let registry = Arc::new(ValidatorRegistry { num_validators });
+        let store = Arc::new(RwLock::new(ReplaceStore::<VoteId, Self>::new(Arc::clone(
+            &partitioner,
+        ))));
+        let evaluator = Arc::new(WeightedVote::new(
+            Arc::clone(&store),
+            Arc::clone(&registry),
+            Arc::clone(&partitioner),
+        ));
+
+        Self {
+            verifier: Arc::new(VoteVerifier::new(id, num_validators)),
+            partitioner,
+            evaluator,
+            store,
+            registry,
+            node_id,
+            id,
+        }
+    }
+}
+
+impl Protocol<VoteId> for VoteAggregationProtocol {
+    type Contribution = Votes;
+    type Registry = ValidatorRegistry;
+    type Verifier = VoteVerifier;
+    type Store = ReplaceStore<VoteId, Self>;
+    type Evaluator = WeightedVote<VoteId, Self>;
+    type Partitioner = BinomialPartitioner;
+
+    fn registry(&self) -> Arc<Self::Registry> {
+        Arc::clone(&self.registry)
+    }
+
+    fn verifier(&self) -> Arc<Self::Verifier> {
+        Arc::clone(&self.verifier)
+    }
+
+    fn store(&self) -> Arc<RwLock<Self::Store>> {
+        Arc::clone(&self.store)
+    }
+
+    fn evaluator(&self) -> Arc<Self::Evaluator> {
+        Arc::clone(&self.evaluator)
+    }
+
+    fn partitioner(&self) -> Arc<Self::Partitioner> {
+        Arc::clone(&self.partitioner)
+    }
+
+    fn identify(&self) -> VoteId {
+        self.id
+    }
+
+    fn node_id(&self) -> usize {
+        self.node_id
+    }
+}
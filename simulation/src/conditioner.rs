@@ -0,0 +1,94 @@
+use std::{sync::Arc, time::Duration};
+
+use nimiq_network_mock::{Delivery, LinkConditioner, MockPeerId};
+use parking_lot::Mutex;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use tokio::time::Instant;
+
+use crate::config::NetworkConditions;
+
+/// A message sent from one validator to another and how it was delivered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageTrace {
+    /// The virtual time since the start of the run at which the message was sent.
+    pub sent_after: Duration,
+    pub from: usize,
+    pub to: usize,
+    pub delivery: Delivery,
+}
+
+/// Applies the configured network conditions to the messages between the validators.
+///
+/// The mock network of validator `i` has the address `i`. All random decisions are taken from a
+/// seeded generator, such that a run can be reproduced by using the same seed. Every message is
+/// recorded in the trace.
+#[derive(Debug)]
+pub struct SeededLinkConditioner {
+    conditions: NetworkConditions,
+    start: Instant,
+    rng: Mutex<ChaCha8Rng>,
+    trace: Arc<Mutex<Vec<MessageTrace>>>,
+}
+
+impl SeededLinkConditioner {
+    pub fn new(conditions: NetworkConditions, seed: u64) -> Self {
+        Self {
+            conditions,
+            start: Instant::now(),
+            rng: Mutex::new(ChaCha8Rng::seed_from_u64(seed)),
+            trace: Default::default(),
+        }
+    }
+
+    /// Returns the trace of the messages, which stays accessible after the conditioner was
+    /// handed to the mock hub.
+    pub fn trace(&self) -> Arc<Mutex<Vec<MessageTrace>>> {
+        Arc::clone(&self.trace)
+    }
+
+    /// Returns whether the link between the two validators is currently partitioned.
+    fn is_partitioned(&self, from: usize, to: usize) -> bool {
+        let elapsed = self.start.elapsed();
+        self.conditions.partitions.iter().any(|partition| {
+            partition.start <= elapsed && elapsed < partition.end && partition.separates(from, to)
+        })
+    }
+
+    fn delivery(&self, from: usize, to: usize) -> Delivery {
+        if self.is_partitioned(from, to) {
+            return Delivery::Drop;
+        }
+
+        let mut rng = self.rng.lock();
+        if self.conditions.loss > 0.0 && rng.gen_bool(self.conditions.loss.min(1.0)) {
+            return Delivery::Drop;
+        }
+
+        let latency = &self.conditions.latency;
+        let delay = if latency.is_empty() {
+            latency.start
+        } else {
+            rng.gen_range(latency.clone())
+        };
+        if delay.is_zero() {
+            Delivery::Deliver
+        } else {
+            Delivery::Delay(delay)
+        }
+    }
+}
+
+impl LinkConditioner for SeededLinkConditioner {
+    fn deliver(&self, from: MockPeerId, to: MockPeerId) -> Delivery {
+        let (from, to) = (from.0 as usize, to.0 as usize);
+        let delivery = self.delivery(from, to);
+        self.trace.lock().push(MessageTrace {
+            sent_after: self.start.elapsed(),
+            from,
+            to,
+            delivery,
+        });
+        delivery
+    }
+}
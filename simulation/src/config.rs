@@ -0,0 +1,113 @@
+use std::{collections::BTreeMap, ops::Range, time::Duration};
+
+use nimiq_handel::config::Config as HandelConfig;
+
+/// The behaviour of a simulated validator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Behaviour {
+    /// Follows the protocol.
+    #[default]
+    Honest,
+    /// Does not participate at all, e.g. because it crashed before the height started.
+    Offline,
+    /// Sends conflicting proposals to the two halves of the validator set whenever it is the
+    /// proposer.
+    EquivocatingProposer,
+    /// Follows the protocol, but never contributes its votes to the aggregations.
+    WithholdVotes,
+    /// Contributes votes with invalid signatures.
+    InvalidContributions,
+}
+
+impl Behaviour {
+    /// Returns whether the validator follows the protocol.
+    pub fn is_honest(&self) -> bool {
+        *self == Behaviour::Honest
+    }
+}
+
+/// A network partition which is in effect for a period of virtual time.
+#[derive(Clone, Debug)]
+pub struct Partition {
+    /// The time the partition starts at, relative to the start of the simulation.
+    pub start: Duration,
+    /// The time the partition heals at, relative to the start of the simulation.
+    pub end: Duration,
+    /// The groups of validators which can still communicate with each other.
+    /// Messages between different groups are lost. Validators that are not part of any group are
+    /// isolated.
+    pub groups: Vec<Vec<usize>>,
+}
+
+impl Partition {
+    /// Returns whether messages from `from` to `to` are lost during the partition.
+    pub fn separates(&self, from: usize, to: usize) -> bool {
+        !self
+            .groups
+            .iter()
+            .any(|group| group.contains(&from) && group.contains(&to))
+    }
+}
+
+/// Conditions of the links between all validators.
+#[derive(Clone, Debug)]
+pub struct NetworkConditions {
+    /// The range the delay of every message is sampled from.
+    pub latency: Range<Duration>,
+    /// The probability of a message being lost.
+    pub loss: f64,
+    /// The partitions happening during the simulation.
+    pub partitions: Vec<Partition>,
+}
+
+impl Default for NetworkConditions {
+    fn default() -> Self {
+        Self {
+            latency: Duration::from_millis(10)..Duration::from_millis(100),
+            loss: 0.0,
+            partitions: vec![],
+        }
+    }
+}
+
+/// Configuration of a simulation run.
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    /// The seed for all random decisions of the simulation, i.e. the message delays and losses.
+    pub seed: u64,
+    /// Conditions of the links between the validators.
+    pub conditions: NetworkConditions,
+    /// The behaviours of the validators. Validators which are not listed are honest.
+    pub behaviours: BTreeMap<usize, Behaviour>,
+    /// The virtual time after which validators that did not decide are considered stuck.
+    pub deadline: Duration,
+    /// The Handel configuration used for the vote aggregations.
+    pub handel: HandelConfig,
+}
+
+impl SimulationConfig {
+    /// Creates a configuration for a run with the given seed and otherwise default settings.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the behaviour of the given validator.
+    pub fn behaviour(&self, validator: usize) -> Behaviour {
+        self.behaviours.get(&validator).copied().unwrap_or_default()
+    }
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            conditions: NetworkConditions::default(),
+            behaviours: BTreeMap::new(),
+            deadline: Duration::from_secs(60),
+            handel: HandelConfig::default(),
+        }
+    }
+}
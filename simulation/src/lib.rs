@@ -0,0 +1,15 @@
+//! Simulation of a full validator set running Tendermint and Handel in-process.
+//!
+//! The validators communicate over `nimiq-network-mock` with configurable message delays, losses
+//! and partitions, while some of them can behave byzantine. All timers use the tokio clock, which
+//! allows to run many seeded simulations quickly with a paused, virtual clock and to check safety
+//! and liveness of every run.
+
+pub mod aggregation;
+pub mod conditioner;
+pub mod config;
+pub mod protocol;
+pub mod simulation;
+
+pub use config::{Behaviour, NetworkConditions, Partition, SimulationConfig};
+pub use simulation::{Outcome, Simulation, ValidatorOutcome, Violation};
@@ -0,0 +1,508 @@
+use std::{collections::BTreeMap, future::Future, sync::Arc, time::Duration};
+
+use futures::{
+    future::{self, BoxFuture, FutureExt},
+    stream::{BoxStream, StreamExt},
+};
+use nimiq_collections::BitSet;
+use nimiq_handel::{aggregation::Aggregation, config::Config, update::LevelUpdate};
+use nimiq_network_interface::{
+    network::{CloseReason, Network as NetworkInterface},
+    request::{MessageMarker, RequestCommon, RequestError, RequestMarker},
+};
+use nimiq_network_mock::{MockNetwork, MockPeerId};
+use nimiq_tendermint::{
+    Inherent, Proposal, ProposalError, ProposalMessage, Protocol, ProtocolError,
+    SignedProposalMessage, Step, TaggedAggregationMessage,
+};
+use nimiq_utils::spawn;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aggregation::{
+        hash, AggregateMessage, MultiSignature, ProposalHash, SerializableLevelUpdate,
+        VoteAggregationProtocol, VoteId, Votes,
+    },
+    config::Behaviour,
+};
+
+/// The proposal of a simulated validator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimProposal {
+    /// The validator which created the proposal.
+    pub proposer: u16,
+    /// The proposed value.
+    pub value: u64,
+}
+
+impl Proposal<ProposalHash, u64> for SimProposal {
+    fn hash(&self) -> ProposalHash {
+        hash(&[self.proposer as u64, self.value])
+    }
+
+    fn inherent_hash(&self) -> u64 {
+        self.value
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimInherent(pub u64);
+
+impl Inherent<u64> for SimInherent {
+    fn hash(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The value a validator proposes in a round.
+fn proposal_value(validator: u16, round: u32) -> u64 {
+    hash(&[validator as u64, round as u64])
+}
+
+/// The signature of `signer` for a proposal message.
+fn sign_proposal_message(signer: u16, message: &ProposalMessage<SimProposal>) -> u64 {
+    hash(&[
+        u64::MAX,
+        signer as u64,
+        message.proposal.hash(),
+        message.round as u64,
+        message.valid_round.map_or(0, |round| round as u64 + 1),
+    ])
+}
+
+/// A decision of Tendermint, justified by the precommits of at least 2f+1 validators.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimDecision {
+    pub round: u32,
+    pub proposal: SimProposal,
+    pub justification: MultiSignature,
+}
+
+impl SimDecision {
+    /// Verifies that the decision is justified by at least `quorum` of the `num_validators`
+    /// validators.
+    pub fn verify(&self, num_validators: usize, quorum: usize) -> bool {
+        let id = VoteId {
+            round: self.round,
+            step: Step::Precommit,
+        };
+        self.justification.signers.len() >= quorum
+            && self
+                .justification
+                .verify(id, Some(self.proposal.hash()), num_validators)
+                .is_ok()
+    }
+}
+
+/// A signed proposal as it is sent over the network.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedProposal {
+    pub proposal: SimProposal,
+    pub round: u32,
+    pub valid_round: Option<u32>,
+    pub signature: (u64, u16),
+}
+
+impl From<SignedProposalMessage<SimProposal, (u64, u16)>> for SignedProposal {
+    fn from(message: SignedProposalMessage<SimProposal, (u64, u16)>) -> Self {
+        Self {
+            proposal: message.message.proposal,
+            round: message.message.round,
+            valid_round: message.message.valid_round,
+            signature: message.signature,
+        }
+    }
+}
+
+impl From<SignedProposal> for SignedProposalMessage<SimProposal, (u64, u16)> {
+    fn from(proposal: SignedProposal) -> Self {
+        Self {
+            message: ProposalMessage {
+                proposal: proposal.proposal,
+                round: proposal.round,
+                valid_round: proposal.valid_round,
+            },
+            signature: proposal.signature,
+        }
+    }
+}
+
+impl RequestCommon for SignedProposal {
+    type Kind = MessageMarker;
+    type Response = ();
+    const TYPE_ID: u16 = 1;
+    const MAX_REQUESTS: u32 = 100;
+    const TIME_WINDOW: Duration = Duration::from_millis(500);
+}
+
+/// A Handel level update as it is sent over the network.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimUpdate(pub TaggedAggregationMessage<SerializableLevelUpdate>);
+
+impl RequestCommon for SimUpdate {
+    type Kind = MessageMarker;
+    type Response = ();
+    const TYPE_ID: u16 = 2;
+    const MAX_REQUESTS: u32 = 1000;
+    const TIME_WINDOW: Duration = Duration::from_millis(500);
+}
+
+/// Requests a proposal that was voted for, but not received.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestProposal {
+    pub round: u32,
+    pub proposal_hash: ProposalHash,
+}
+
+impl RequestCommon for RequestProposal {
+    type Kind = RequestMarker;
+    type Response = Option<SignedProposal>;
+    const TYPE_ID: u16 = 3;
+    const MAX_REQUESTS: u32 = 100;
+    const TIME_WINDOW: Duration = Duration::from_millis(500);
+}
+
+/// Announces a decision, such that validators which fell behind can adopt it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecisionMessage(pub SimDecision);
+
+impl RequestCommon for DecisionMessage {
+    type Kind = MessageMarker;
+    type Response = ();
+    const TYPE_ID: u16 = 4;
+    const MAX_REQUESTS: u32 = 100;
+    const TIME_WINDOW: Duration = Duration::from_millis(500);
+}
+
+/// The proposals a validator knows about, by round and hash.
+pub type KnownProposals = Arc<Mutex<BTreeMap<(u32, ProposalHash), SignedProposal>>>;
+
+/// Sends a message to all other validators.
+pub(crate) fn broadcast<M: RequestCommon<Kind = MessageMarker, Response = ()> + Clone>(
+    network: &Arc<MockNetwork>,
+    num_validators: usize,
+    message: M,
+) {
+    let own_peer_id = network.peer_id();
+    for validator in 0..num_validators {
+        let peer_id = MockPeerId(validator as u64);
+        if peer_id != own_peer_id {
+            send(network, peer_id, message.clone());
+        }
+    }
+}
+
+fn send<M: RequestCommon<Kind = MessageMarker, Response = ()>>(
+    network: &Arc<MockNetwork>,
+    peer_id: MockPeerId,
+    message: M,
+) {
+    let network = Arc::clone(network);
+    spawn(async move {
+        if let Err(error) = network.message(message, peer_id).await {
+            log::trace!(%peer_id, ?error, "Failed to send message");
+        }
+    });
+}
+
+struct NetworkWrapper {
+    network: Arc<MockNetwork>,
+    tag: (u32, Step),
+}
+
+impl nimiq_handel::network::Network for NetworkWrapper {
+    type Contribution = Votes;
+    type Error = RequestError;
+
+    fn send_update(
+        &self,
+        node_id: u16,
+        update: LevelUpdate<Self::Contribution>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'static {
+        let update = SimUpdate(TaggedAggregationMessage {
+            tag: self.tag,
+            aggregation: update.into(),
+        });
+        let network = Arc::clone(&self.network);
+        async move { network.message(update, MockPeerId(node_id as u64)).await }
+    }
+
+    fn ban_node(&self, node_id: u16) -> impl Future<Output = ()> + Send + 'static {
+        let network = Arc::clone(&self.network);
+        async move {
+            network
+                .disconnect_peer(MockPeerId(node_id as u64), CloseReason::MaliciousPeer)
+                .await
+        }
+    }
+}
+
+/// The Tendermint protocol of a simulated validator in a set of `N` validators with a single slot
+/// each. The proposer of round `r` is validator `r % N`.
+pub struct SimProtocol<const N: usize> {
+    validator: u16,
+    behaviour: Behaviour,
+    network: Arc<MockNetwork>,
+    handel: Config,
+    known_proposals: KnownProposals,
+}
+
+impl<const N: usize> Clone for SimProtocol<N> {
+    fn clone(&self) -> Self {
+        Self {
+            validator: self.validator,
+            behaviour: self.behaviour,
+            network: Arc::clone(&self.network),
+            handel: self.handel.clone(),
+            known_proposals: Arc::clone(&self.known_proposals),
+        }
+    }
+}
+
+impl<const N: usize> SimProtocol<N> {
+    pub fn new(
+        validator: u16,
+        behaviour: Behaviour,
+        network: Arc<MockNetwork>,
+        handel: Config,
+        known_proposals: KnownProposals,
+    ) -> Self {
+        Self {
+            validator,
+            behaviour,
+            network,
+            handel,
+            known_proposals,
+        }
+    }
+
+    fn proposer(round: u32) -> u16 {
+        (round as usize % N) as u16
+    }
+
+    fn sign(&self, message: ProposalMessage<SimProposal>) -> SignedProposal {
+        SignedProposal {
+            signature: self.sign_proposal(&message),
+            proposal: message.proposal,
+            round: message.round,
+            valid_round: message.valid_round,
+        }
+    }
+
+    fn remember(&self, proposal: &SignedProposal) {
+        self.known_proposals
+            .lock()
+            .insert((proposal.round, proposal.proposal.hash()), proposal.clone());
+    }
+
+    /// Checks the signature of a proposal, which must be signed by the proposer of its round.
+    fn is_signed_by_proposer(proposal: &SignedProposalMessage<SimProposal, (u64, u16)>) -> bool {
+        let (signature, signer) = proposal.signature;
+        signer == Self::proposer(proposal.message.round)
+            && signature == sign_proposal_message(signer, &proposal.message)
+    }
+}
+
+impl<const N: usize> Protocol for SimProtocol<N> {
+    type Decision = SimDecision;
+    type Proposal = SimProposal;
+    type ProposalHash = ProposalHash;
+    type Inherent = SimInherent;
+    type InherentHash = u64;
+    type Aggregation = Votes;
+    type AggregationMessage = AggregateMessage;
+    type ProposalSignature = (u64, u16);
+
+    const F_PLUS_ONE: usize = (N - 1) / 3 + 1;
+    const TWO_F_PLUS_ONE: usize = N - (N - 1) / 3;
+    const TIMEOUT_DELTA: u64 = 500;
+    const TIMEOUT_INIT: u64 = 1000;
+
+    fn is_proposer(&self, round: u32) -> Result<bool, ProtocolError> {
+        Ok(Self::proposer(round) == self.validator)
+    }
+
+    fn create_proposal(
+        &self,
+        round: u32,
+    ) -> Result<(ProposalMessage<Self::Proposal>, Self::Inherent), ProtocolError> {
+        let value = proposal_value(self.validator, round);
+        Ok((
+            ProposalMessage {
+                proposal: SimProposal {
+                    proposer: self.validator,
+                    value,
+                },
+                round,
+                valid_round: None,
+            },
+            SimInherent(value),
+        ))
+    }
+
+    fn sign_proposal(
+        &self,
+        proposal_message: &ProposalMessage<Self::Proposal>,
+    ) -> Self::ProposalSignature {
+        (
+            sign_proposal_message(self.validator, proposal_message),
+            self.validator,
+        )
+    }
+
+    fn verify_proposal(
+        &self,
+        proposal: &SignedProposalMessage<Self::Proposal, Self::ProposalSignature>,
+        precalculated_inherent: Option<Self::Inherent>,
+    ) -> Result<Self::Inherent, ProposalError> {
+        if !Self::is_signed_by_proposer(proposal) {
+            return Err(ProposalError::InvalidProposal);
+        }
+
+        // Keep the proposal to serve it to validators that missed it.
+        self.remember(&proposal.clone().into());
+
+        Ok(precalculated_inherent.unwrap_or(SimInherent(proposal.message.proposal.value)))
+    }
+
+    fn broadcast_proposal(
+        &self,
+        proposal: SignedProposalMessage<Self::Proposal, Self::ProposalSignature>,
+    ) {
+        let proposal = SignedProposal::from(proposal);
+        self.remember(&proposal);
+
+        if self.behaviour != Behaviour::EquivocatingProposer {
+            broadcast(&self.network, N, proposal);
+            return;
+        }
+
+        // Send a conflicting proposal to the second half of the validators.
+        let conflicting = self.sign(ProposalMessage {
+            proposal: SimProposal {
+                proposer: self.validator,
+                value: !proposal.proposal.value,
+            },
+            round: proposal.round,
+            valid_round: None,
+        });
+        self.remember(&conflicting);
+        log::debug!(
+            validator = self.validator,
+            round = proposal.round,
+            "Equivocating proposal"
+        );
+
+        for validator in (0..N as u16).filter(|validator| *validator != self.validator) {
+            let message = if (validator as usize) < N / 2 {
+                proposal.clone()
+            } else {
+                conflicting.clone()
+            };
+            send(&self.network, MockPeerId(validator as u64), message);
+        }
+    }
+
+    fn request_proposal(
+        &self,
+        proposal_hash: Self::ProposalHash,
+        round: u32,
+        candidates: BitSet,
+    ) -> BoxFuture<'static, Option<SignedProposalMessage<Self::Proposal, Self::ProposalSignature>>>
+    {
+        let this = self.clone();
+        async move {
+            let request = RequestProposal {
+                round,
+                proposal_hash,
+            };
+            for candidate in candidates.iter() {
+                if candidate == this.validator as usize {
+                    continue;
+                }
+
+                let response = this
+                    .network
+                    .request(request.clone(), MockPeerId(candidate as u64))
+                    .await;
+                if let Ok(Some(proposal)) = response {
+                    let proposal = SignedProposalMessage::from(proposal);
+                    if proposal.message.round == round
+                        && proposal.message.proposal.hash() == proposal_hash
+                        && Self::is_signed_by_proposer(&proposal)
+                    {
+                        return Some(proposal);
+                    }
+                }
+            }
+            None
+        }
+        .boxed()
+    }
+
+    fn create_decision(
+        &self,
+        proposal: Self::Proposal,
+        _inherent: Self::Inherent,
+        aggregation: Self::Aggregation,
+        round: u32,
+    ) -> Self::Decision {
+        let justification = aggregation
+            .contributions
+            .get(&Some(proposal.hash()))
+            .cloned()
+            .expect("must have the proposal hash present in the aggregate");
+
+        SimDecision {
+            round,
+            proposal,
+            justification,
+        }
+    }
+
+    fn create_aggregation(
+        &self,
+        round: u32,
+        step: Step,
+        vote: Option<Self::ProposalHash>,
+        update_stream: BoxStream<'static, Self::AggregationMessage>,
+    ) -> BoxStream<'static, Self::Aggregation> {
+        let id = VoteId { round, step };
+        let own_contribution = match self.behaviour {
+            // Keep consuming the updates, but never contribute.
+            Behaviour::WithholdVotes => {
+                return update_stream.filter_map(|_| future::ready(None)).boxed()
+            }
+            Behaviour::InvalidContributions => Votes::forged(self.validator as usize, id, vote),
+            _ => Votes::new(self.validator as usize, id, vote),
+        };
+
+        Aggregation::new(
+            VoteAggregationProtocol::new(id, self.validator as usize, N),
+            self.handel.clone(),
+            own_contribution,
+            update_stream.map(|message| message.0).boxed(),
+            NetworkWrapper {
+                network: Arc::clone(&self.network),
+                tag: (round, step),
+            },
+        )
+        .boxed()
+    }
+
+    fn verify_aggregation_message(
+        &self,
+        round: u32,
+        step: Step,
+        message: Self::AggregationMessage,
+    ) -> BoxFuture<'static, Result<(), ()>> {
+        let id = VoteId { round, step };
+        let result = if message.0.aggregate.verify(id, N).is_ok() {
+            Ok(())
+        } else {
+            Err(())
+        };
+        future::ready(result).boxed()
+    }
+}
@@ -0,0 +1,271 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use nimiq_network_interface::network::Network;
+use nimiq_network_mock::{MockHub, MockNetwork};
+use nimiq_tendermint::{
+    Proposal, Protocol, Return, SignedProposalMessage, TaggedAggregationMessage, Tendermint,
+};
+use nimiq_time::timeout;
+use nimiq_utils::stream::FuturesUnordered;
+use thiserror::Error;
+use tokio::time::Instant;
+
+use crate::{
+    aggregation::AggregateMessage,
+    conditioner::{MessageTrace, SeededLinkConditioner},
+    config::{Behaviour, SimulationConfig},
+    protocol::{
+        broadcast, DecisionMessage, KnownProposals, RequestProposal, SignedProposal, SimDecision,
+        SimProtocol, SimUpdate,
+    },
+};
+
+/// A violated property of a simulation run.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Violation {
+    #[error("Validators {0} and {1} decided on different proposals")]
+    ConflictingDecisions(usize, usize),
+    #[error("Decision of validator {0} is not justified by a quorum")]
+    UnjustifiedDecision(usize),
+    #[error("Validator {0} did not decide before the deadline")]
+    NoDecision(usize),
+}
+
+/// What happened to a single validator during a run.
+#[derive(Clone, Debug)]
+pub struct ValidatorOutcome {
+    pub behaviour: Behaviour,
+    /// The decision of the validator, if it reached or adopted one before the deadline.
+    /// Byzantine validators might not report their decision, as the run ends once all honest
+    /// validators are done.
+    pub decision: Option<SimDecision>,
+    /// Whether the decision was adopted from another validator instead of reached by Tendermint.
+    pub adopted: bool,
+    /// The virtual time it took the validator to decide.
+    pub decided_after: Option<Duration>,
+}
+
+/// The outcome of a simulation run with `N` validators.
+#[derive(Clone, Debug)]
+pub struct Outcome<const N: usize> {
+    pub seed: u64,
+    pub validators: Vec<ValidatorOutcome>,
+    /// All messages sent between the validators in the order they were sent.
+    pub messages: Vec<MessageTrace>,
+}
+
+impl<const N: usize> Outcome<N> {
+    fn honest(&self) -> impl Iterator<Item = (usize, &ValidatorOutcome)> {
+        self.validators
+            .iter()
+            .enumerate()
+            .filter(|(_, outcome)| outcome.behaviour.is_honest())
+    }
+
+    /// Checks that all honest validators decided on the same proposal and that every decision is
+    /// justified by a quorum of valid precommits.
+    pub fn check_safety(&self) -> Result<(), Violation> {
+        let mut first: Option<(usize, &SimDecision)> = None;
+        for (validator, outcome) in self.honest() {
+            let Some(decision) = &outcome.decision else {
+                continue;
+            };
+
+            if !decision.verify(N, SimProtocol::<N>::TWO_F_PLUS_ONE) {
+                return Err(Violation::UnjustifiedDecision(validator));
+            }
+            match first {
+                Some((other, other_decision))
+                    if other_decision.proposal.hash() != decision.proposal.hash() =>
+                {
+                    return Err(Violation::ConflictingDecisions(other, validator));
+                }
+                Some(_) => {}
+                None => first = Some((validator, decision)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that all honest validators decided before the deadline.
+    pub fn check_liveness(&self) -> Result<(), Violation> {
+        match self
+            .honest()
+            .find(|(_, outcome)| outcome.decision.is_none())
+        {
+            Some((validator, _)) => Err(Violation::NoDecision(validator)),
+            None => Ok(()),
+        }
+    }
+
+    /// The longest time an honest validator took to decide.
+    pub fn time_to_decision(&self) -> Option<Duration> {
+        self.honest()
+            .filter_map(|(_, outcome)| outcome.decided_after)
+            .max()
+    }
+}
+
+/// Runs a single Tendermint height with `N` in-process validators over the mock network.
+///
+/// Every validator runs a `Tendermint` instance which aggregates its votes using Handel. Messages
+/// between the validators are subject to the configured network conditions. Validators that
+/// decided announce their decision, which allows validators that fell behind to adopt it, as they
+/// would by syncing the block.
+///
+/// The simulation relies on the tokio clock, so it should be run on a current thread runtime with
+/// the clock paused (e.g. `#[tokio::test(start_paused = true)]`). Time then advances virtually
+/// whenever all validators are idle.
+pub struct Simulation<const N: usize> {
+    config: SimulationConfig,
+}
+
+impl<const N: usize> Simulation<N> {
+    pub fn new(config: SimulationConfig) -> Self {
+        Self { config }
+    }
+
+    pub async fn run(&self) -> Outcome<N> {
+        let mut hub = MockHub::new();
+        let networks: Vec<_> = (0..N)
+            .map(|validator| Arc::new(hub.new_network_with_address(validator as u64)))
+            .collect();
+
+        // Offline validators are not connected at all.
+        let is_online = |validator| self.config.behaviour(validator) != Behaviour::Offline;
+        for i in (0..N).filter(|i| is_online(*i)) {
+            for j in (i + 1..N).filter(|j| is_online(*j)) {
+                networks[i].dial_mock(&networks[j]);
+            }
+        }
+        let conditioner =
+            SeededLinkConditioner::new(self.config.conditions.clone(), self.config.seed);
+        let messages = conditioner.trace();
+        hub.set_link_conditioner(conditioner);
+
+        let mut validators: Vec<_> = (0..N)
+            .map(|validator| ValidatorOutcome {
+                behaviour: self.config.behaviour(validator),
+                decision: None,
+                adopted: false,
+                decided_after: None,
+            })
+            .collect();
+
+        // All receivers are set up before the first validator starts sending.
+        let start = Instant::now();
+        let mut running: FuturesUnordered<_> = networks
+            .iter()
+            .enumerate()
+            .filter(|(validator, _)| is_online(*validator))
+            .map(|(validator, network)| {
+                let future = self.run_validator(validator, Arc::clone(network));
+                async move {
+                    let result = timeout(self.config.deadline, future).await.ok().flatten();
+                    (validator, result, start.elapsed())
+                }
+            })
+            .collect();
+
+        let mut pending_honest = validators
+            .iter()
+            .filter(|outcome| outcome.behaviour.is_honest())
+            .count();
+        while pending_honest > 0 {
+            let Some((validator, result, elapsed)) = running.next().await else {
+                break;
+            };
+
+            let outcome = &mut validators[validator];
+            if outcome.behaviour.is_honest() {
+                pending_honest -= 1;
+            }
+            if let Some((decision, adopted)) = result {
+                log::debug!(
+                    validator,
+                    round = decision.round,
+                    adopted,
+                    ?elapsed,
+                    "Validator decided"
+                );
+                outcome.decision = Some(decision);
+                outcome.adopted = adopted;
+                outcome.decided_after = Some(elapsed);
+            }
+        }
+
+        hub.clear_link_conditioner();
+
+        let messages = std::mem::take(&mut *messages.lock());
+        Outcome {
+            seed: self.config.seed,
+            validators,
+            messages,
+        }
+    }
+
+    /// Runs a validator until it decided. Returns the decision and whether it was adopted.
+    fn run_validator(
+        &self,
+        validator: usize,
+        network: Arc<MockNetwork>,
+    ) -> impl std::future::Future<Output = Option<(SimDecision, bool)>> {
+        let known_proposals = KnownProposals::default();
+
+        let proposals = network
+            .receive_messages::<SignedProposal>()
+            .map(|(proposal, _)| SignedProposalMessage::from(proposal))
+            .boxed();
+        let updates = network
+            .receive_messages::<SimUpdate>()
+            .map(|(SimUpdate(message), peer_id)| TaggedAggregationMessage {
+                tag: message.tag,
+                // The origin is the actual sender, so it can't be spoofed.
+                aggregation: AggregateMessage(
+                    message.aggregation.into_level_update(peer_id.0 as u16),
+                ),
+            })
+            .boxed();
+        let mut decisions = network.receive_messages::<DecisionMessage>();
+        let mut requests = network.receive_requests::<RequestProposal>();
+
+        let protocol = SimProtocol::<N>::new(
+            validator as u16,
+            self.config.behaviour(validator),
+            Arc::clone(&network),
+            self.config.handel.clone(),
+            Arc::clone(&known_proposals),
+        );
+        let mut tendermint = Tendermint::new(protocol, None, proposals, updates);
+
+        async move {
+            loop {
+                tokio::select! {
+                    item = tendermint.next() => match item {
+                        Some(Return::Decision(decision)) => {
+                            broadcast(&network, N, DecisionMessage(decision.clone()));
+                            return Some((decision, false));
+                        }
+                        Some(_) => {}
+                        None => return None,
+                    },
+                    Some((DecisionMessage(decision), _)) = decisions.next() => {
+                        if decision.verify(N, SimProtocol::<N>::TWO_F_PLUS_ONE) {
+                            broadcast(&network, N, DecisionMessage(decision.clone()));
+                            return Some((decision, true));
+                        }
+                        log::debug!(validator, ?decision, "Ignoring unjustified decision");
+                    },
+                    Some((request, request_id, _)) = requests.next() => {
+                        let response = known_proposals
+                            .lock()
+                            .get(&(request.round, request.proposal_hash))
+                            .cloned();
+                        let _ = network.respond::<RequestProposal>(request_id, response).await;
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::{collections::BTreeMap, ops::Range, time::Duration};
+
+use nimiq_simulation::{
+    Behaviour, NetworkConditions, Outcome, Partition, Simulation, SimulationConfig, Violation,
+};
+use nimiq_test_log::test;
+
+const SEEDS: Range<u64> = 0..20;
+
+/// Runs the simulation for all seeds and checks safety and liveness of every run.
+async fn run_seeds<const N: usize>(
+    seeds: Range<u64>,
+    config: impl Fn(u64) -> SimulationConfig,
+) -> Vec<Outcome<N>> {
+    let mut outcomes = vec![];
+    for seed in seeds {
+        let outcome = Simulation::<N>::new(config(seed)).run().await;
+        if let Err(violation) = outcome.check_safety() {
+            panic!("Safety violated with seed {seed}: {violation}");
+        }
+        if let Err(violation) = outcome.check_liveness() {
+            panic!("Liveness violated with seed {seed}: {violation}");
+        }
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+fn with_behaviours(seed: u64, behaviours: &[(usize, Behaviour)]) -> SimulationConfig {
+    SimulationConfig {
+        behaviours: BTreeMap::from_iter(behaviours.iter().copied()),
+        ..SimulationConfig::with_seed(seed)
+    }
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn honest_validators_decide_on_the_same_proposal() {
+    run_seeds::<4>(SEEDS, SimulationConfig::with_seed).await;
+    run_seeds::<7>(SEEDS, SimulationConfig::with_seed).await;
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn runs_are_reproducible() {
+    let config = SimulationConfig {
+        conditions: NetworkConditions {
+            loss: 0.1,
+            ..Default::default()
+        },
+        ..SimulationConfig::with_seed(42)
+    };
+
+    let first = Simulation::<7>::new(config.clone()).run().await;
+    let second = Simulation::<7>::new(config).run().await;
+
+    let decisions = |outcome: &Outcome<7>| {
+        outcome
+            .validators
+            .iter()
+            .map(|validator| {
+                (
+                    validator.decision.clone(),
+                    validator.adopted,
+                    validator.decided_after,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(decisions(&first), decisions(&second));
+
+    // The same messages are sent, lost and delayed in both runs.
+    assert!(!first.messages.is_empty());
+    assert_eq!(first.messages, second.messages);
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn tolerates_message_loss() {
+    run_seeds::<7>(SEEDS, |seed| SimulationConfig {
+        conditions: NetworkConditions {
+            loss: 0.1,
+            ..Default::default()
+        },
+        ..SimulationConfig::with_seed(seed)
+    })
+    .await;
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn decides_after_partition_heals() {
+    let heal = Duration::from_secs(10);
+    let outcomes = run_seeds::<7>(SEEDS, |seed| SimulationConfig {
+        conditions: NetworkConditions {
+            partitions: vec![Partition {
+                start: Duration::ZERO,
+                end: heal,
+                groups: vec![vec![0, 1, 2, 3], vec![4, 5, 6]],
+            }],
+            ..Default::default()
+        },
+        ..SimulationConfig::with_seed(seed)
+    })
+    .await;
+
+    // Neither side of the partition has a quorum.
+    for outcome in outcomes {
+        assert!(outcome.time_to_decision().unwrap() >= heal);
+    }
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn tolerates_offline_validators() {
+    run_seeds::<4>(SEEDS, |seed| {
+        with_behaviours(seed, &[(0, Behaviour::Offline)])
+    })
+    .await;
+    run_seeds::<7>(SEEDS, |seed| {
+        with_behaviours(seed, &[(1, Behaviour::Offline), (4, Behaviour::Offline)])
+    })
+    .await;
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn tolerates_equivocating_proposer() {
+    // Validator 0 is the proposer of the first round.
+    run_seeds::<4>(SEEDS, |seed| {
+        with_behaviours(seed, &[(0, Behaviour::EquivocatingProposer)])
+    })
+    .await;
+    run_seeds::<7>(SEEDS, |seed| {
+        with_behaviours(
+            seed,
+            &[
+                (0, Behaviour::EquivocatingProposer),
+                (1, Behaviour::EquivocatingProposer),
+            ],
+        )
+    })
+    .await;
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn tolerates_withheld_votes() {
+    run_seeds::<4>(SEEDS, |seed| {
+        with_behaviours(seed, &[(2, Behaviour::WithholdVotes)])
+    })
+    .await;
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn tolerates_invalid_contributions() {
+    run_seeds::<4>(SEEDS, |seed| {
+        with_behaviours(seed, &[(0, Behaviour::InvalidContributions)])
+    })
+    .await;
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn tolerates_mixed_byzantine_validators() {
+    run_seeds::<7>(SEEDS, |seed| SimulationConfig {
+        conditions: NetworkConditions {
+            loss: 0.05,
+            ..Default::default()
+        },
+        ..with_behaviours(
+            seed,
+            &[
+                (0, Behaviour::EquivocatingProposer),
+                (3, Behaviour::InvalidContributions),
+            ],
+        )
+    })
+    .await;
+}
+
+#[test(tokio::test(start_paused = true))]
+async fn stalls_without_quorum() {
+    let config = SimulationConfig {
+        deadline: Duration::from_secs(10),
+        ..with_behaviours(0, &[(0, Behaviour::Offline), (1, Behaviour::Offline)])
+    };
+    let outcome = Simulation::<4>::new(config).run().await;
+
+    assert_eq!(outcome.check_safety(), Ok(()));
+    assert_eq!(outcome.check_liveness(), Err(Violation::NoDecision(2)));
+}
@@ -149,8 +149,13 @@ async fn main_inner() -> Result<(), Error> {
         log_tags: None,
         passive: false,
         sync_mode: None,
+        trusted_checkpoint: None,
         network: None,
+        snapshot: None,
+        snapshot_keep_size: false,
+        restore_snapshot: None,
         prove: false,
+        command: None,
     };
 
     // Parse config file - this will obey the `--config` command line option.
@@ -245,6 +250,7 @@ async fn main_inner() -> Result<(), Error> {
             metrics_config.addr,
             client.blockchain(),
             client.mempool(),
+            None,
             client.consensus_proxy(),
             client.network(),
             &[],
@@ -4,10 +4,11 @@ use nimiq_block::{
     Block, MacroBlock, MacroBody, MacroHeader, MultiSignature, SignedSkipBlockInfo, SkipBlockInfo,
     SkipBlockProof, TendermintProof,
 };
-use nimiq_blockchain::{BlockProducer, Blockchain};
+use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
 use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
 use nimiq_bls::{AggregateSignature, KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
 use nimiq_collections::BitSet;
+use nimiq_database::mdbx::MdbxDatabase;
 use nimiq_genesis::NetworkId;
 use nimiq_keys::{
     Address, KeyPair as SchnorrKeyPair, KeyPair, PrivateKey as SchnorrPrivateKey, PrivateKey,
@@ -18,6 +19,7 @@ use nimiq_primitives::{
 use nimiq_serde::Deserialize;
 use nimiq_transaction::Transaction;
 use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_utils::time::OffsetTime;
 use parking_lot::RwLock;
 use rand::{rngs::StdRng, RngCore, SeedableRng};
 
@@ -59,6 +61,34 @@ pub fn generate_transactions(
     txs
 }
 
+/// Creates a blockchain for the unit test network on a volatile database.
+pub fn new_volatile_blockchain(config: BlockchainConfig) -> Arc<RwLock<Blockchain>> {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    Arc::new(RwLock::new(
+        Blockchain::new(
+            env,
+            config,
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ))
+}
+
+/// Produces a series of macro blocks (and the corresponding batches) with one transaction per
+/// micro block, followed by a series of micro blocks without transactions.
+pub fn produce_blocks_with_txns(
+    blockchain: &Arc<RwLock<Blockchain>>,
+    num_macro_blocks: usize,
+    num_micro_blocks: usize,
+) {
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks_with_txns(&producer, blockchain, num_macro_blocks, 1, 0);
+    for _ in 0..num_micro_blocks {
+        push_micro_block(&producer, blockchain);
+    }
+}
+
 /// Produces a series of macro blocks (and the corresponding batches).
 pub fn produce_macro_blocks(
     producer: &BlockProducer,
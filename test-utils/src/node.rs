@@ -13,6 +13,8 @@ use nimiq_primitives::{networks::NetworkId, trie::TrieItem};
 use nimiq_utils::{spawn, time::OffsetTime};
 use nimiq_zkp_component::{
     proof_store::{DBProofStore, ProofStore},
+    types::{ProverResources, ProvingPolicy},
+    zkp_prover::ProverBackend,
     ZKPComponent,
 };
 use parking_lot::{Mutex, RwLock};
@@ -83,8 +85,12 @@ impl<N: NetworkInterface + TestNetwork> Node<N> {
             BlockchainProxy::from(&blockchain),
             Arc::clone(&network),
             is_prover_active,
-            prover_path,
+            ProverBackend::Local {
+                prover_path,
+                resources: ProverResources::default(),
+            },
             PathBuf::from(ZKP_TEST_KEYS_PATH),
+            ProvingPolicy::default(),
             zkp_storage,
         )
         .await;
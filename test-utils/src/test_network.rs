@@ -4,8 +4,8 @@ use async_trait::async_trait;
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::{network::Network as NetworkInterface, peer_info::Services};
 use nimiq_network_libp2p::{
-    discovery::peer_contacts::PeerContact, libp2p::core::multiaddr::multiaddr, Config, Keypair,
-    Network,
+    discovery::peer_contacts::PeerContact, libp2p::core::multiaddr::multiaddr, Config,
+    GossipsubConfig, Keypair, Network,
 };
 use nimiq_network_mock::{MockHub, MockNetwork};
 
@@ -69,12 +69,16 @@ impl TestNetwork for Network {
             Vec::new(),
             genesis_hash.clone(),
             true,
+            false,
+            false,
+            None,
             Services::all(),
             None,
             3,
             false,
             true,
             NonZeroU8::new(1).unwrap(),
+            GossipsubConfig::default(),
         );
         let network = Arc::new(Network::new(config).await);
         network.listen_on(vec![peer_address]).await;
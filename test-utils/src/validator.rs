@@ -7,7 +7,7 @@ use nimiq_consensus::{Consensus, ConsensusEvent};
 use nimiq_database::mdbx::MdbxDatabase;
 use nimiq_genesis_builder::{GenesisBuilder, GenesisInfo};
 use nimiq_keys::{Address, KeyPair as SchnorrKeyPair, SecureGenerate};
-use nimiq_mempool::config::MempoolConfig;
+use nimiq_mempool::{config::MempoolConfig, selection::SelectionPolicy};
 use nimiq_network_interface::network::Network as NetworkInterface;
 use nimiq_network_mock::MockHub;
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
@@ -56,6 +56,8 @@ where
             voting_key,
             fee_key,
             MempoolConfig::default(),
+            false,
+            SelectionPolicy::default(),
         ),
         consensus,
     )
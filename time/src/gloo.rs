@@ -46,3 +46,7 @@ pub fn sleep(duration: Duration) -> impl Future<Output = ()> {
 pub fn sleep_until(deadline: Instant) -> impl Future<Output = ()> {
     sleep(deadline.saturating_duration_since(Instant::now()))
 }
+
+pub fn now() -> Instant {
+    Instant::now()
+}
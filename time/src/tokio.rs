@@ -28,3 +28,9 @@ pub fn sleep(duration: Duration) -> Sleep {
 pub fn sleep_until(deadline: Instant) -> Sleep {
     tokio_sleep_until(TokioInstant::from_std(deadline))
 }
+
+/// Returns the current instant of the runtime's clock. Unlike `Instant::now()` this follows
+/// tokio's clock, so it stays consistent with the other timers when time is paused.
+pub fn now() -> Instant {
+    TokioInstant::now().into_std()
+}
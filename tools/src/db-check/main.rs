@@ -0,0 +1,118 @@
+use std::{path::PathBuf, process::exit, str::FromStr};
+
+use anyhow::{Context, Error};
+use clap::{crate_authors, crate_version, value_parser, Arg, ArgAction, Command};
+use nimiq_blockchain::{Blockchain, DbCheckReport};
+use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
+use nimiq_primitives::networks::NetworkId;
+
+fn run_app() -> Result<bool, Error> {
+    let matches = Command::new("nimiq-db-check")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Checks the consistency of a node's database")
+        .arg(
+            Arg::new("database")
+                .value_name("DATABASE")
+                .value_parser(value_parser!(PathBuf))
+                .required(true)
+                .help("The database directory of the node."),
+        )
+        .arg(
+            Arg::new("network_id")
+                .short('N')
+                .long("network")
+                .value_name("NETWORK")
+                .required(true)
+                .help("The network ID of the database"),
+        )
+        .arg(
+            Arg::new("repair_index")
+                .long("repair-index")
+                .help("Rebuild the history index if it is inconsistent (requires write access)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print the report as JSON")
+                .action(ArgAction::SetTrue),
+        )
+        .get_matches();
+
+    let path = matches
+        .get_one::<PathBuf>("database")
+        .expect("Database path is required");
+    let network_id = NetworkId::from_str(
+        matches
+            .get_one::<String>("network_id")
+            .expect("Network ID is required"),
+    )?;
+    let repair_index = matches.get_flag("repair_index");
+
+    let env = MdbxDatabase::new(
+        path,
+        DatabaseConfig {
+            read_only: !repair_index,
+            ..Default::default()
+        },
+    )?;
+    Blockchain::verify_database_network(env.clone(), network_id)
+        .with_context(|| format!("The database doesn't belong to {network_id}"))?;
+    let report = Blockchain::check_database(env, network_id, repair_index);
+
+    if matches.get_flag("json") {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    Ok(report.is_ok())
+}
+
+fn print_report(report: &DbCheckReport) {
+    println!("Network: {}", report.network_id);
+    match (report.head_block_number, &report.head_hash) {
+        (Some(block_number), Some(hash)) => println!("Head:    #{block_number} {hash}"),
+        _ => println!("Head:    -"),
+    }
+    println!();
+
+    for check in &report.checks {
+        if let Some(reason) = &check.skipped {
+            println!("[SKIP] {}: {reason}", check.name);
+            continue;
+        }
+
+        let status = if check.is_ok() { "OK" } else { "FAIL" };
+        println!(
+            "[{status}] {}: {} entries checked, {} errors",
+            check.name, check.num_checked, check.num_errors
+        );
+        for error in &check.errors {
+            println!("       - {error}");
+        }
+        if check.num_errors > check.errors.len() {
+            println!(
+                "       ... and {} more",
+                check.num_errors - check.errors.len()
+            );
+        }
+    }
+
+    if report.index_repaired {
+        println!();
+        println!("The history index was rebuilt.");
+    }
+}
+
+fn main() {
+    exit(match run_app() {
+        Ok(true) => 0,
+        Ok(false) => 2,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    });
+}
@@ -0,0 +1,74 @@
+use std::{collections::BTreeSet, path::PathBuf, process::exit, str::FromStr};
+
+use anyhow::{Context, Error};
+use clap::{crate_authors, crate_version, value_parser, Arg, ArgAction, Command};
+use nimiq_blockchain::{Blockchain, HistoryStoreIndex, SecondaryIndexKind};
+use nimiq_database::mdbx::MdbxDatabase;
+use nimiq_primitives::networks::NetworkId;
+
+fn run_app() -> Result<(), Error> {
+    let matches = Command::new("nimiq-rebuild-index")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Rebuilds the history index of a history node's database")
+        .arg(
+            Arg::new("database")
+                .value_name("DATABASE")
+                .value_parser(value_parser!(PathBuf))
+                .required(true)
+                .help("The database directory of the node. The node must not be running."),
+        )
+        .arg(
+            Arg::new("network_id")
+                .short('N')
+                .long("network")
+                .value_name("NETWORK")
+                .required(true)
+                .help("The network ID of the database"),
+        )
+        .arg(
+            Arg::new("secondary_index")
+                .short('s')
+                .long("secondary-index")
+                .value_name("INDEX")
+                .value_parser(value_parser!(SecondaryIndexKind))
+                .action(ArgAction::Append)
+                .help("Also build this secondary index (validator, htlc, staking, timestamp)"),
+        )
+        .get_matches();
+
+    let path = matches
+        .get_one::<PathBuf>("database")
+        .expect("Database path is required");
+    let network_id = NetworkId::from_str(
+        matches
+            .get_one::<String>("network_id")
+            .expect("Network ID is required"),
+    )?;
+    let secondary_indexes: BTreeSet<SecondaryIndexKind> = matches
+        .get_many::<SecondaryIndexKind>("secondary_index")
+        .unwrap_or_default()
+        .copied()
+        .collect();
+
+    let env = MdbxDatabase::new(path, Default::default())?;
+    Blockchain::verify_database_network(env.clone(), network_id)
+        .with_context(|| format!("The database doesn't belong to {network_id}"))?;
+    println!("Rebuilding the history index, this can take a long time.");
+    HistoryStoreIndex::rebuild_offline(env, network_id, &secondary_indexes);
+
+    if secondary_indexes.is_empty() {
+        println!("Done.");
+    } else {
+        let names: Vec<_> = secondary_indexes.iter().map(|kind| kind.name()).collect();
+        println!("Done. Secondary indexes: {}", names.join(", "));
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run_app() {
+        eprintln!("Error: {e}");
+        exit(1);
+    }
+}
@@ -0,0 +1,175 @@
+use std::{path::PathBuf, process::exit, str::FromStr, time::Instant};
+
+use anyhow::{bail, Error};
+use clap::{crate_authors, crate_version, value_parser, Arg, ArgAction, ArgMatches, Command};
+use nimiq_genesis::NetworkInfo;
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_zkp::ZKPVerifyingKey;
+use nimiq_zkp_circuits::{
+    setup::{
+        save_key_checksums, setup, verify_key_checksums, verify_keys, verify_proving_keys,
+        DEVELOPMENT_SEED, KEY_CHECKSUMS_FILE,
+    },
+    DEFAULT_PROVER_KEYS_PATH,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+fn path_arg() -> Arg {
+    Arg::new("path")
+        .value_name("PATH")
+        .value_parser(value_parser!(PathBuf))
+        .default_value(DEFAULT_PROVER_KEYS_PATH)
+        .help("The directory containing the prover keys.")
+}
+
+fn network_arg() -> Arg {
+    Arg::new("network_id")
+        .short('N')
+        .long("network")
+        .value_name("NETWORK")
+        .help("Set network ID")
+}
+
+fn network_id(matches: &ArgMatches) -> Result<NetworkId, Error> {
+    Ok(match matches.get_one::<String>("network_id") {
+        Some(s) => NetworkId::from_str(s)?,
+        None => NetworkId::MainAlbatross,
+    })
+}
+
+/// Generates the keys deterministically from the given seed and stores their checksums.
+fn generate(matches: &ArgMatches) -> Result<bool, Error> {
+    let path = matches.get_one::<PathBuf>("path").unwrap();
+    let network_id = network_id(matches)?;
+    let seed = match matches.get_one::<String>("seed") {
+        Some(s) => hex::decode(s)?
+            .try_into()
+            .map_err(|_| Error::msg("The seed must be 32 bytes"))?,
+        None => DEVELOPMENT_SEED,
+    };
+
+    // The circuits depend on the policy of the network.
+    let genesis_block_number = NetworkInfo::from_network_id(network_id)
+        .genesis_block()
+        .block_number();
+    let _ = Policy::get_or_init(Policy {
+        genesis_block_number,
+        ..Default::default()
+    });
+
+    println!("Generating keys for {network_id} in {path:?}, this might take several hours.");
+    let start = Instant::now();
+    setup(ChaCha20Rng::from_seed(seed), path, network_id, true)?;
+    save_key_checksums(path)?;
+    println!("Finished after {:?}.", start.elapsed());
+
+    Ok(true)
+}
+
+/// Computes and stores the checksums of existing keys.
+fn checksum(matches: &ArgMatches) -> Result<bool, Error> {
+    let path = matches.get_one::<PathBuf>("path").unwrap();
+
+    save_key_checksums(path)?;
+    println!("Stored checksums in {:?}.", path.join(KEY_CHECKSUMS_FILE));
+
+    Ok(true)
+}
+
+/// Verifies existing keys against the embedded verifying keys and, if present, their checksums.
+fn verify(matches: &ArgMatches) -> Result<bool, Error> {
+    let path = matches.get_one::<PathBuf>("path").unwrap();
+    let network_id = network_id(matches)?;
+
+    let mut checks = vec![(
+        "Verifying keys match network",
+        verify_keys(path, &ZKPVerifyingKey::embedded(network_id)?),
+    )];
+    if path.join(KEY_CHECKSUMS_FILE).exists() {
+        checks.push(("Checksums", verify_key_checksums(path)));
+    } else if matches.get_flag("require_checksums") {
+        bail!("No checksums found in {:?}", path.join(KEY_CHECKSUMS_FILE));
+    }
+    if matches.get_flag("deep") {
+        checks.push((
+            "Proving keys match verifying keys",
+            verify_proving_keys(path),
+        ));
+    }
+
+    let mut all_ok = true;
+    for (name, result) in checks {
+        match result {
+            Ok(()) => println!("[OK]   {name}"),
+            Err(e) => {
+                println!("[FAIL] {name}: {e}");
+                all_ok = false;
+            }
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn run_app() -> Result<bool, Error> {
+    let matches = Command::new("nimiq-zkp-keys")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Generates and verifies the keys of the ZKP prover")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("generate")
+                .about("Generates the keys deterministically from a seed (for devnets)")
+                .arg(path_arg())
+                .arg(network_arg())
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .value_name("SEED")
+                        .help("Hex encoded 32 byte seed. Defaults to the development seed."),
+                ),
+        )
+        .subcommand(
+            Command::new("checksum")
+                .about("Computes and stores the checksums of existing keys")
+                .arg(path_arg()),
+        )
+        .subcommand(
+            Command::new("verify")
+                .about("Verifies existing keys against the verifying keys of the network")
+                .arg(path_arg())
+                .arg(network_arg())
+                .arg(
+                    Arg::new("deep")
+                        .long("deep")
+                        .help("Also check that every proving key matches its verifying key (slow)")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("require_checksums")
+                        .long("require-checksums")
+                        .help("Fail if no checksums are stored for the keys")
+                        .action(ArgAction::SetTrue),
+                ),
+        )
+        .get_matches();
+
+    match matches.subcommand() {
+        Some(("generate", matches)) => generate(matches),
+        Some(("checksum", matches)) => checksum(matches),
+        Some(("verify", matches)) => verify(matches),
+        _ => unreachable!(),
+    }
+}
+
+fn main() {
+    exit(match run_app() {
+        Ok(true) => 0,
+        Ok(false) => 2,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    });
+}
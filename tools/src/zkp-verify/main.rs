@@ -0,0 +1,128 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+    str::FromStr,
+};
+
+use anyhow::{bail, Error};
+use clap::{crate_authors, crate_version, value_parser, Arg, Command};
+use nimiq_block::MacroBlock;
+use nimiq_genesis::NetworkInfo;
+use nimiq_hash::Blake2sHash;
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::Deserialize;
+use nimiq_zkp::{verify::verify_election_block, ZKPVerifyingKey};
+use nimiq_zkp_component::types::ZKProof;
+
+/// Reads a serialized value from a file. The file can either contain the raw bytes or their hex
+/// representation.
+fn read_serialized<T: Deserialize>(path: &Path) -> Result<T, Error> {
+    let bytes = fs::read(path)?;
+    let bytes = match std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|s| hex::decode(s.trim()).ok())
+    {
+        Some(decoded) => decoded,
+        None => bytes,
+    };
+    Ok(T::deserialize_from_vec(&bytes)?)
+}
+
+/// Reads a serialized `MacroBlock` and ensures that it is an election block.
+fn read_election_block(path: &Path) -> Result<MacroBlock, Error> {
+    let block: MacroBlock = read_serialized(path)?;
+    if !block.is_election() {
+        bail!("Block #{} is not an election block", block.block_number());
+    }
+    Ok(block)
+}
+
+fn run_app() -> Result<bool, Error> {
+    let matches = Command::new("nimiq-zkp-verify")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Verifies the ZK proof of an election block")
+        .arg(
+            Arg::new("block")
+                .value_name("BLOCK")
+                .value_parser(value_parser!(PathBuf))
+                .required(true)
+                .help("File containing the serialized election block (binary or hex)."),
+        )
+        .arg(
+            Arg::new("proof")
+                .value_name("PROOF")
+                .value_parser(value_parser!(PathBuf))
+                .required(true)
+                .help("File containing the serialized ZK proof (binary or hex)."),
+        )
+        .arg(
+            Arg::new("network_id")
+                .short('N')
+                .long("network")
+                .value_name("NETWORK")
+                .help("Set network ID"),
+        )
+        .arg(
+            Arg::new("genesis_hash")
+                .long("genesis-hash")
+                .value_name("HASH")
+                .help(
+                    "Blake2s header hash of the genesis block. Defaults to the one of the network.",
+                ),
+        )
+        .get_matches();
+
+    let network_id = match matches.get_one::<String>("network_id") {
+        Some(s) => NetworkId::from_str(s)?,
+        None => NetworkId::MainAlbatross,
+    };
+    let genesis_header_hash = match matches.get_one::<String>("genesis_hash") {
+        Some(s) => Blake2sHash::from_str(s).map_err(|_| Error::msg("Invalid genesis hash"))?,
+        None => NetworkInfo::from_network_id(network_id)
+            .genesis_block()
+            .unwrap_macro()
+            .hash_blake2s(),
+    };
+
+    let election_block = read_election_block(matches.get_one::<PathBuf>("block").unwrap())?;
+    let zk_proof: ZKProof = read_serialized(matches.get_one::<PathBuf>("proof").unwrap())?;
+
+    if zk_proof.block_number != election_block.block_number() {
+        bail!(
+            "Proof is for block #{}, but block #{} was given",
+            zk_proof.block_number,
+            election_block.block_number()
+        );
+    }
+    let Some(proof) = zk_proof.proof else {
+        bail!("The proof is empty");
+    };
+
+    let verifying_data = ZKPVerifyingKey::embedded(network_id)?;
+    let is_valid =
+        verify_election_block(genesis_header_hash, &election_block, proof, &verifying_data)?;
+
+    println!("Network:  {network_id}");
+    println!("Genesis:  {genesis_header_hash}");
+    println!(
+        "Block:    #{} {}",
+        election_block.block_number(),
+        election_block.hash_blake2s()
+    );
+    println!("Proof:    {}", if is_valid { "VALID" } else { "INVALID" });
+
+    Ok(is_valid)
+}
+
+fn main() {
+    exit(match run_app() {
+        Ok(true) => 0,
+        Ok(false) => 2,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    });
+}
@@ -208,6 +208,7 @@ impl SkipBlockAggregation {
         validator_id: u16,
         active_validators: Validators,
         network: Arc<N>,
+        config: Config,
     ) -> (SkipBlockInfo, SkipBlockProof) {
         // TODO expose this somewhere else so we don't need to clone here.
         let weights = Arc::new(ValidatorRegistry::new(active_validators.clone()));
@@ -263,7 +264,7 @@ impl SkipBlockAggregation {
 
         let mut aggregation = Aggregation::new(
             protocol,
-            Config::default(),
+            config,
             own_contribution,
             input_stream,
             NetworkWrapper::new(skip_block_info.clone(), Arc::clone(&network)),
@@ -110,6 +110,50 @@ impl MacroState {
         })
     }
 
+    /// The block number of the macro block this state belongs to.
+    pub fn block_number(&self) -> u32 {
+        self.block_number
+    }
+
+    /// The current round.
+    pub fn round(&self) -> u32 {
+        self.round_number
+    }
+
+    /// The current step within the round.
+    pub fn step(&self) -> Step {
+        self.step
+    }
+
+    /// The round and proposal hash this validator is locked on, if any.
+    pub fn locked(&self) -> Option<&(u32, Blake2sHash)> {
+        self.locked.as_ref()
+    }
+
+    /// The round and proposal hash of the latest valid proposal, if any.
+    pub fn valid(&self) -> Option<&(u32, Blake2sHash)> {
+        self.valid.as_ref()
+    }
+
+    /// Returns all received proposals as `(round, proposal_hash, valid_round, proposer_slot_band)`.
+    pub fn proposals(&self) -> impl Iterator<Item = (u32, &Blake2sHash, Option<u32>, u16)> {
+        self.round_proposals.iter().flat_map(|(round, proposals)| {
+            proposals
+                .iter()
+                .map(|(hash, (valid_round, signature))| (*round, hash, *valid_round, signature.1))
+        })
+    }
+
+    /// Returns the votes of this validator for every round and step it voted in.
+    pub fn votes(&self) -> &BTreeMap<(u32, Step), Option<Blake2sHash>> {
+        &self.votes
+    }
+
+    /// Returns the best aggregation known for every round and step.
+    pub fn best_votes(&self) -> &BTreeMap<(u32, Step), TendermintContribution> {
+        &self.best_votes
+    }
+
     pub fn get_proposal_for(
         &self,
         block_number: u32,
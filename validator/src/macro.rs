@@ -8,6 +8,7 @@ use std::{
 use futures::stream::{BoxStream, Stream, StreamExt};
 use nimiq_block::MacroBlock;
 use nimiq_blockchain::{BlockProducer, Blockchain};
+use nimiq_handel::config::Config as HandelConfig;
 use nimiq_keys::Ed25519Signature as SchnorrSignature;
 use nimiq_network_interface::network::Topic;
 use nimiq_primitives::{networks::NetworkId, slots_allocation::Validators};
@@ -84,6 +85,7 @@ where
             'static,
             SignedProposalMessage<Header<PubsubId<TValidatorNetwork>>, (SchnorrSignature, u16)>,
         >,
+        handel_config: HandelConfig,
     ) -> Self {
         let input = network
             .receive::<TendermintUpdate>()
@@ -107,6 +109,7 @@ where
             validator_slot_band,
             network_id,
             block_height,
+            handel_config,
         );
 
         // create the Tendermint instance, which implements Stream
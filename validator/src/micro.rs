@@ -10,7 +10,8 @@ use futures::{future::BoxFuture, ready, FutureExt, Stream};
 use nimiq_block::{Block, EquivocationProof, MicroBlock, SkipBlockInfo};
 use nimiq_blockchain::{BlockProducer, BlockProducerError, Blockchain};
 use nimiq_blockchain_interface::AbstractBlockchain;
-use nimiq_mempool::mempool::Mempool;
+use nimiq_handel::config::Config as HandelConfig;
+use nimiq_mempool::{mempool::Mempool, selection::SelectionPolicy};
 use nimiq_primitives::policy::Policy;
 use nimiq_time::sleep;
 use nimiq_utils::time::systemtime_to_timestamp;
@@ -36,6 +37,8 @@ struct NextProduceMicroBlockEvent<TValidatorNetwork> {
     block_number: u32,
     producer_timeout: Duration,
     block_separation_time: Duration,
+    handel_config: HandelConfig,
+    selection_policy: SelectionPolicy,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<TValidatorNetwork> {
@@ -53,6 +56,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
         block_number: u32,
         producer_timeout: Duration,
         block_separation_time: Duration,
+        handel_config: HandelConfig,
+        selection_policy: SelectionPolicy,
     ) -> Self {
         Self {
             blockchain,
@@ -65,6 +70,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             block_number,
             producer_timeout,
             block_separation_time,
+            handel_config,
+            selection_policy,
         }
     }
 
@@ -249,6 +256,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             self.validator_slot_band,
             active_validators.unwrap(),
             Arc::clone(&self.network),
+            self.handel_config.clone(),
         )
         .await;
 
@@ -349,20 +357,14 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             systemtime_to_timestamp(SystemTime::now()),
         );
 
-        // First we try to fill the block with control transactions
-        let mut block_available_bytes = MicroBlock::get_available_bytes(&self.equivocation_proofs);
+        // Fill the block with control and regular transactions according to the selection policy.
+        let block_available_bytes = MicroBlock::get_available_bytes(&self.equivocation_proofs);
 
-        let (mut transactions, txn_size) = self
-            .mempool
-            .get_control_transactions_for_block_locked(blockchain, block_available_bytes);
-
-        block_available_bytes = block_available_bytes.saturating_sub(txn_size);
-
-        let (mut regular_transactions, _) = self
-            .mempool
-            .get_transactions_for_block_locked(blockchain, block_available_bytes);
-
-        transactions.append(&mut regular_transactions);
+        let (transactions, _) = self.mempool.select_transactions_for_block_locked(
+            blockchain,
+            block_available_bytes,
+            &self.selection_policy,
+        );
 
         self.block_producer.next_micro_block(
             blockchain,
@@ -410,6 +412,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
         block_number: u32,
         producer_timeout: Duration,
         block_separation_time: Duration,
+        handel_config: HandelConfig,
+        selection_policy: SelectionPolicy,
     ) -> Self {
         let next_event = NextProduceMicroBlockEvent::new(
             blockchain,
@@ -422,6 +426,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
             block_number,
             producer_timeout,
             block_separation_time,
+            handel_config,
+            selection_policy,
         )
         .next()
         .boxed();
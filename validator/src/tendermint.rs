@@ -10,6 +10,7 @@ use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_collections::BitSet;
 use nimiq_handel::{
     aggregation::Aggregation,
+    config::Config as HandelConfig,
     identity::IdentityRegistry,
     protocol::Protocol as _,
     update::LevelUpdate,
@@ -129,6 +130,8 @@ pub struct TendermintProtocol<TValidatorNetwork: ValidatorNetwork> {
     blockchain: Arc<RwLock<Blockchain>>,
     // Validator registry on the heap for easy cloning into handel protocol.
     validator_registry: Arc<ValidatorRegistry>,
+    // The configuration of the handel aggregations of the voting steps.
+    handel_config: HandelConfig,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidatorNetwork> {
@@ -142,6 +145,7 @@ impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidato
             current_validators: self.current_validators.clone(),
             blockchain: Arc::clone(&self.blockchain),
             validator_registry: Arc::clone(&self.validator_registry),
+            handel_config: self.handel_config.clone(),
         }
     }
 }
@@ -158,6 +162,7 @@ where
         validator_slot_band: u16,
         network_id: NetworkId,
         block_height: u32,
+        handel_config: HandelConfig,
     ) -> Self {
         Self {
             block_producer,
@@ -168,6 +173,7 @@ where
             validator_registry: Arc::new(ValidatorRegistry::new(current_validators.clone())),
             current_validators,
             network,
+            handel_config,
         }
     }
 }
@@ -420,7 +426,7 @@ where
 
         Aggregation::new(
             protocol,
-            nimiq_handel::config::Config::default(),
+            self.handel_config.clone(),
             own_contribution,
             update_stream.map(|item| item.0).boxed(),
             network,
@@ -25,9 +25,12 @@ use nimiq_database::{
     mdbx::MdbxDatabase,
     traits::{Database, ReadTransaction, WriteTransaction},
 };
+#[cfg(feature = "metrics")]
+use nimiq_handel::metrics::HandelMetrics;
+use nimiq_handel::{config::Config as HandelConfig, timeout::AdaptiveTimeout};
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::{Address, KeyPair as SchnorrKeyPair};
-use nimiq_mempool::config::MempoolConfig;
+use nimiq_mempool::{config::MempoolConfig, selection::SelectionPolicy};
 use nimiq_mempool_task::MempoolTask;
 use nimiq_network_interface::{
     network::{MsgAcceptance, Network, NetworkEvent, SubscribeEvents},
@@ -38,6 +41,7 @@ use nimiq_transaction_builder::TransactionBuilder;
 use nimiq_utils::spawn;
 use nimiq_validator_network::{PubsubId, ValidatorNetwork};
 use parking_lot::RwLock;
+use tokio::sync::broadcast;
 #[cfg(feature = "metrics")]
 use tokio_metrics::TaskMonitor;
 use tokio_stream::wrappers::BroadcastStream;
@@ -75,6 +79,15 @@ pub struct ValidatorProxy {
     pub automatic_reactivate: Arc<AtomicBool>,
     pub slot_band: Arc<RwLock<Option<u16>>>,
     pub consensus_state: Arc<RwLock<ConsensusState>>,
+    pub macro_state: Arc<RwLock<Option<MacroState>>>,
+    macro_state_tx: broadcast::Sender<MacroState>,
+}
+
+impl ValidatorProxy {
+    /// Subscribes to the Tendermint state updates of the macro blocks this validator takes part in.
+    pub fn subscribe_macro_state(&self) -> BroadcastStream<MacroState> {
+        BroadcastStream::new(self.macro_state_tx.subscribe())
+    }
 }
 
 impl Clone for ValidatorProxy {
@@ -87,6 +100,8 @@ impl Clone for ValidatorProxy {
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
+            macro_state: Arc::clone(&self.macro_state),
+            macro_state_tx: self.macro_state_tx.clone(),
         }
     }
 }
@@ -122,9 +137,17 @@ where
 
     macro_producer: Option<ProduceMacroBlock<TValidatorNetwork>>,
     macro_state: Arc<RwLock<Option<MacroState>>>,
+    macro_state_tx: broadcast::Sender<MacroState>,
 
     micro_producer: Option<ProduceMicroBlock<TValidatorNetwork>>,
 
+    tendermint_handel_config: HandelConfig,
+    skip_block_handel_config: HandelConfig,
+    #[cfg(feature = "metrics")]
+    handel_metrics: HandelMetrics,
+
+    selection_policy: SelectionPolicy,
+
     pub mempool_task: MempoolTask<TValidatorNetwork::NetworkType>,
 }
 
@@ -144,6 +167,8 @@ where
     const BLOCK_SEPARATION_TIME: Duration = Duration::from_millis(Policy::BLOCK_SEPARATION_TIME);
     /// The maximum number of bytes that equivocation proofs are allowed to take up in a block.
     const EQUIVOCATION_PROOFS_MAX_SIZE: usize = 1_000; // bytes
+    /// The number of Tendermint state updates buffered for slow subscribers.
+    const MACRO_STATE_BUFFER_SIZE: usize = 32;
 
     pub fn new(
         env: MdbxDatabase,
@@ -156,6 +181,8 @@ where
         voting_key: BlsKeyPair,
         fee_key: SchnorrKeyPair,
         mempool_config: MempoolConfig,
+        adaptive_aggregation_timeouts: bool,
+        selection_policy: SelectionPolicy,
     ) -> Self {
         let consensus_event_rx = consensus.subscribe_events();
 
@@ -189,6 +216,28 @@ where
 
         Self::init_network_request_receivers(&consensus.network, &macro_state);
 
+        // The Tendermint and skip block aggregations share the latency estimate of the adaptive
+        // level timeout, as they are run among the same set of validators.
+        let adaptive_timeout = adaptive_aggregation_timeouts.then(AdaptiveTimeout::default);
+        #[cfg(feature = "metrics")]
+        let handel_metrics = HandelMetrics::default();
+        let tendermint_handel_config = HandelConfig {
+            adaptive_timeout: adaptive_timeout.clone(),
+            #[cfg(feature = "metrics")]
+            metrics: Some(
+                handel_metrics.aggregation("tendermint", Policy::TWO_F_PLUS_ONE as usize),
+            ),
+            ..Default::default()
+        };
+        let skip_block_handel_config = HandelConfig {
+            adaptive_timeout,
+            #[cfg(feature = "metrics")]
+            metrics: Some(
+                handel_metrics.aggregation("skip_block", Policy::TWO_F_PLUS_ONE as usize),
+            ),
+            ..Default::default()
+        };
+
         let network1 = Arc::clone(&network);
         spawn(async move {
             network1
@@ -225,9 +274,17 @@ where
 
             macro_producer: None,
             macro_state: Arc::clone(&macro_state),
+            macro_state_tx: broadcast::channel(Self::MACRO_STATE_BUFFER_SIZE).0,
 
             micro_producer: None,
 
+            tendermint_handel_config,
+            skip_block_handel_config,
+            #[cfg(feature = "metrics")]
+            handel_metrics,
+
+            selection_policy,
+
             mempool_task: mempool,
         }
     }
@@ -422,6 +479,7 @@ where
                     next_block_number,
                     self.macro_state.read().clone(),
                     proposal_stream,
+                    self.tendermint_handel_config.clone(),
                 ));
             }
             BlockType::Micro => {
@@ -443,6 +501,8 @@ where
                     next_block_number,
                     Self::compute_micro_block_producer_timeout(head, &blockchain),
                     Self::BLOCK_SEPARATION_TIME,
+                    self.skip_block_handel_config.clone(),
+                    self.selection_policy.clone(),
                 ));
             }
         }
@@ -614,7 +674,9 @@ where
                     write_transaction.put(&self.table, &(), &update);
                     write_transaction.commit();
 
-                    *self.macro_state.write() = Some(update);
+                    *self.macro_state.write() = Some(update.clone());
+                    // Notify subscribers, ignoring the error if there are none.
+                    let _ = self.macro_state_tx.send(update);
                 }
             }
         }
@@ -734,6 +796,8 @@ where
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
+            macro_state: Arc::clone(&self.macro_state),
+            macro_state_tx: self.macro_state_tx.clone(),
         }
     }
 
@@ -747,6 +811,11 @@ where
         self.mempool_task.get_control_mempool_monitor()
     }
 
+    #[cfg(feature = "metrics")]
+    pub fn get_handel_metrics(&self) -> HandelMetrics {
+        self.handel_metrics.clone()
+    }
+
     /// Publishes the given block on both the BlockHeaderTopic and BlockBodyTopic.
     pub fn publish_block(network: Arc<TValidatorNetwork>, block: Block) {
         if block.is_election() {
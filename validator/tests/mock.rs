@@ -94,6 +94,64 @@ async fn one_validator_can_create_micro_blocks() {
     assert!(consensus1.blockchain.read().block_number() >= 10 + Policy::genesis_block_number());
 }
 
+#[test(tokio::test)]
+async fn one_validator_reports_tendermint_state() {
+    let hub = MockHub::default();
+    let env =
+        MdbxDatabase::new_volatile(Default::default()).expect("Could not open a volatile database");
+
+    let voting_key = BlsKeyPair::generate(&mut seeded_rng(0));
+    let validator_key = KeyPair::generate(&mut seeded_rng(0));
+    let fee_key = KeyPair::generate(&mut seeded_rng(0));
+    let signing_key = KeyPair::generate(&mut seeded_rng(0));
+    let genesis = GenesisBuilder::default()
+        .with_network(NetworkId::UnitAlbatross)
+        .with_genesis_block_number(Policy::genesis_block_number())
+        .with_genesis_validator(
+            Address::from(&validator_key),
+            signing_key.public,
+            voting_key.public_key,
+            Address::default(),
+            None,
+            None,
+            false,
+        )
+        .generate(env)
+        .unwrap();
+
+    let (validator, mut consensus1) = build_validator::<Network>(
+        0,
+        Address::from(&validator_key),
+        false,
+        signing_key,
+        voting_key,
+        fee_key,
+        genesis.clone(),
+        &mut Some(hub),
+        false,
+    )
+    .await;
+
+    consensus1.force_established();
+    assert!(consensus1.is_established());
+
+    let proxy = validator.proxy();
+    let mut states = proxy.subscribe_macro_state();
+    spawn(validator);
+
+    let state = timeout(Duration::from_secs(60), states.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    let macro_block_number = Policy::macro_block_after(Policy::genesis_block_number());
+    assert_eq!(state.block_number(), macro_block_number);
+    assert_eq!(state.round(), 0);
+
+    let state = proxy.macro_state.read().clone().unwrap();
+    assert_eq!(state.block_number(), macro_block_number);
+}
+
 #[test(tokio::test)]
 async fn four_validators_can_create_micro_blocks() {
     let hub = MockHub::default();
@@ -1,5 +1,7 @@
 use std::{
+    collections::BTreeMap,
     fs::{self, DirBuilder, File},
+    io::{self, BufReader, BufWriter, Write},
     path::Path,
 };
 
@@ -10,6 +12,7 @@ use ark_mnt4_753::MNT4_753;
 use ark_mnt6_753::MNT6_753;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use nimiq_genesis::NetworkInfo;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
 use nimiq_primitives::networks::NetworkId;
 use nimiq_serde::Deserialize;
 use nimiq_zkp_primitives::{NanoZKPError, VerifyingData};
@@ -33,6 +36,23 @@ pub const DEVELOPMENT_SEED: [u8; 32] = [
     0, 2, 92,
 ];
 
+/// The names of the circuits, which are also the file names of their keys.
+pub const CIRCUIT_NAMES: [&str; 10] = [
+    "merger_wrapper",
+    "merger",
+    "macro_block_wrapper",
+    "macro_block",
+    "pk_tree_0",
+    "pk_tree_1",
+    "pk_tree_2",
+    "pk_tree_3",
+    "pk_tree_4",
+    "pk_tree_5",
+];
+
+/// The file in the keys directory that contains the checksums of the key files.
+pub const KEY_CHECKSUMS_FILE: &str = "checksums.json";
+
 /// This function generates the parameters (proving and verifying keys) for the entire light macro sync.
 /// It does this by generating the parameters for each circuit, "from bottom to top". The
 /// order is absolutely necessary because each circuit needs a verifying key from the circuit "below"
@@ -152,6 +172,107 @@ pub fn all_files_created(path: &Path, prover_active: bool) -> bool {
                 && proving_keys.join("merger.bin").exists()))
 }
 
+/// Verifies that the verifying keys at the given path match the verifying data, i.e. the merger wrapper
+/// verifying key and the commitment to all verifying keys. Only proofs generated with matching keys
+/// can be verified by other nodes. This check is fast, since it does not load the proving keys.
+pub fn verify_keys(path: &Path, verifying_data: &VerifyingData) -> Result<(), NanoZKPError> {
+    if !all_files_created(path, true) {
+        return Err(NanoZKPError::InvalidKeys(
+            "key files are missing".to_string(),
+        ));
+    }
+
+    let merger_wrapper_vk = load_key(&path.join("verifying_keys"), "merger_wrapper")?;
+    if merger_wrapper_vk != verifying_data.merger_wrapper_vk {
+        return Err(NanoZKPError::InvalidKeys(
+            "merger wrapper verifying key does not match".to_string(),
+        ));
+    }
+
+    if load_keys(path)?.commitment() != verifying_data.keys_commitment {
+        return Err(NanoZKPError::InvalidKeys(
+            "verifying keys do not match the commitment".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Verifies that every proving key at the given path belongs to its verifying key.
+/// This loads all proving keys, which can take a long time.
+pub fn verify_proving_keys(path: &Path) -> Result<(), NanoZKPError> {
+    verify_proving_key::<MNT6_753>(path, "merger_wrapper")?;
+    verify_proving_key::<MNT4_753>(path, "merger")?;
+    verify_proving_key::<MNT6_753>(path, "macro_block_wrapper")?;
+    verify_proving_key::<MNT4_753>(path, "macro_block")?;
+    verify_proving_key::<MNT6_753>(path, "pk_tree_0")?;
+    verify_proving_key::<MNT4_753>(path, "pk_tree_1")?;
+    verify_proving_key::<MNT6_753>(path, "pk_tree_2")?;
+    verify_proving_key::<MNT4_753>(path, "pk_tree_3")?;
+    verify_proving_key::<MNT6_753>(path, "pk_tree_4")?;
+    verify_proving_key::<MNT4_753>(path, "pk_tree_5")
+}
+
+fn verify_proving_key<E: Pairing>(path: &Path, name: &str) -> Result<(), NanoZKPError> {
+    let file = File::open(path.join("proving_keys").join(format!("{name}.bin")))?;
+    let pk = ProvingKey::<E>::deserialize_uncompressed_unchecked(&mut BufReader::new(file))?;
+
+    if pk.vk != load_key(&path.join("verifying_keys"), name)? {
+        return Err(NanoZKPError::InvalidKeys(format!(
+            "proving key {name} does not match its verifying key"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Computes the Blake2b checksums of all key files, indexed by their path relative to the keys
+/// directory.
+pub fn compute_key_checksums(path: &Path) -> Result<BTreeMap<String, Blake2bHash>, NanoZKPError> {
+    let mut checksums = BTreeMap::new();
+    for dir in ["proving_keys", "verifying_keys"] {
+        for name in CIRCUIT_NAMES {
+            let file_name = format!("{dir}/{name}.bin");
+            let mut file = File::open(path.join(&file_name))?;
+
+            let mut hasher = Blake2bHasher::new();
+            io::copy(&mut file, &mut hasher)?;
+            checksums.insert(file_name, hasher.finish());
+        }
+    }
+
+    Ok(checksums)
+}
+
+/// Computes the checksums of all key files and stores them in the keys directory.
+pub fn save_key_checksums(path: &Path) -> Result<(), NanoZKPError> {
+    let checksums = compute_key_checksums(path)?;
+
+    let file = File::create(path.join(KEY_CHECKSUMS_FILE))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, &checksums).map_err(io::Error::from)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Verifies the key files against the checksums stored in the keys directory.
+pub fn verify_key_checksums(path: &Path) -> Result<(), NanoZKPError> {
+    let file = File::open(path.join(KEY_CHECKSUMS_FILE))?;
+    let expected: BTreeMap<String, Blake2bHash> =
+        serde_json::from_reader(BufReader::new(file)).map_err(io::Error::from)?;
+
+    for (file_name, checksum) in compute_key_checksums(path)? {
+        if expected.get(&file_name) != Some(&checksum) {
+            return Err(NanoZKPError::InvalidKeys(format!(
+                "checksum of {file_name} does not match"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 fn setup_pk_tree_leaf<R: Rng + CryptoRng>(
     rng: &mut R,
     dir_path: &Path,
@@ -309,3 +430,137 @@ pub(crate) fn keys_to_file<T: Pairing>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::PrimeField;
+    use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget, fields::fp::FpVar};
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+    use nimiq_test_log::test;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    /// A trivial circuit that is used to generate small keys in place of the real ones.
+    struct TestCircuit;
+
+    impl<F: PrimeField> ConstraintSynthesizer<F> for TestCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+            let input = FpVar::new_input(cs.clone(), || Ok(F::ONE))?;
+            let witness = FpVar::new_witness(cs, || Ok(F::ONE))?;
+            input.enforce_equal(&witness)
+        }
+    }
+
+    fn setup_test_keys<E: Pairing, R: Rng + CryptoRng>(rng: &mut R, name: &str, path: &Path) {
+        let (pk, vk) = Groth16::<E>::setup(TestCircuit, rng).unwrap();
+        keys_to_file(&pk, &vk, name, path).unwrap();
+    }
+
+    /// Generates keys for all circuits. The circuits alternate between MNT6 and MNT4.
+    fn setup_all_test_keys<R: Rng + CryptoRng>(rng: &mut R, path: &Path) {
+        for (i, name) in CIRCUIT_NAMES.iter().enumerate() {
+            if i % 2 == 0 {
+                setup_test_keys::<MNT6_753, _>(rng, name, path);
+            } else {
+                setup_test_keys::<MNT4_753, _>(rng, name, path);
+            }
+        }
+    }
+
+    #[test]
+    fn it_verifies_keys_against_verifying_data() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        setup_all_test_keys(&mut rng, path);
+
+        let verifying_data = VerifyingData {
+            merger_wrapper_vk: load_key(&path.join("verifying_keys"), "merger_wrapper").unwrap(),
+            keys_commitment: load_keys(path).unwrap().commitment(),
+        };
+        verify_keys(path, &verifying_data).unwrap();
+
+        // Wrong commitment.
+        let mut wrong_commitment = verifying_data.clone();
+        wrong_commitment.keys_commitment[0] ^= 1;
+        assert!(matches!(
+            verify_keys(path, &wrong_commitment),
+            Err(NanoZKPError::InvalidKeys(_))
+        ));
+
+        // Replaced merger wrapper key.
+        setup_test_keys::<MNT6_753, _>(&mut rng, "merger_wrapper", path);
+        assert!(matches!(
+            verify_keys(path, &verifying_data),
+            Err(NanoZKPError::InvalidKeys(_))
+        ));
+
+        // Missing key file.
+        fs::remove_file(path.join("proving_keys/merger.bin")).unwrap();
+        assert!(matches!(
+            verify_keys(path, &verifying_data),
+            Err(NanoZKPError::InvalidKeys(_))
+        ));
+    }
+
+    #[test]
+    fn it_verifies_proving_keys() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        setup_all_test_keys(&mut rng, path);
+        verify_proving_keys(path).unwrap();
+
+        // Replace a verifying key with one that belongs to a different proving key.
+        let (_, vk) = Groth16::<MNT4_753>::setup(TestCircuit, &mut rng).unwrap();
+        let mut file = File::create(path.join("verifying_keys/pk_tree_5.bin")).unwrap();
+        vk.serialize_uncompressed(&mut file).unwrap();
+        assert!(matches!(
+            verify_proving_keys(path),
+            Err(NanoZKPError::InvalidKeys(_))
+        ));
+    }
+
+    #[test]
+    fn it_verifies_key_checksums() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        setup_all_test_keys(&mut rng, path);
+
+        save_key_checksums(path).unwrap();
+        verify_key_checksums(path).unwrap();
+        let checksums = compute_key_checksums(path).unwrap();
+        assert_eq!(checksums.len(), 2 * CIRCUIT_NAMES.len());
+
+        // Tampered key file.
+        let key_path = path.join("proving_keys/macro_block.bin");
+        let mut key = fs::read(&key_path).unwrap();
+        *key.last_mut().unwrap() ^= 1;
+        fs::write(&key_path, key).unwrap();
+        assert!(matches!(
+            verify_key_checksums(path),
+            Err(NanoZKPError::InvalidKeys(_))
+        ));
+
+        // Wrong checksum.
+        save_key_checksums(path).unwrap();
+        let mut checksums = compute_key_checksums(path).unwrap();
+        checksums.insert(
+            "verifying_keys/merger.bin".to_string(),
+            Blake2bHash::default(),
+        );
+        fs::write(
+            path.join(KEY_CHECKSUMS_FILE),
+            serde_json::to_vec(&checksums).unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            verify_key_checksums(path),
+            Err(NanoZKPError::InvalidKeys(_))
+        ));
+    }
+}
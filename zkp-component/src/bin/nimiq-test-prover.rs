@@ -30,5 +30,7 @@ fn initialize() {
     // Run tests with different policy values:
     // Shorter epochs and shorter batches
     let _ = Policy::get_or_init(TEST_POLICY);
-    ZKP_VERIFYING_DATA.init_with_network_id(NetworkId::UnitAlbatross);
+    ZKP_VERIFYING_DATA
+        .init_with_network_id(NetworkId::UnitAlbatross)
+        .unwrap();
 }
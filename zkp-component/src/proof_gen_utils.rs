@@ -1,5 +1,6 @@
 use std::{
     env,
+    io::{self, Write},
     path::{Path, PathBuf},
     process::Stdio,
 };
@@ -10,14 +11,18 @@ use nimiq_block::MacroBlock;
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_zkp::prove::prove;
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader},
     process::Command,
-    sync::oneshot::Receiver,
+    sync::{mpsc::UnboundedSender, oneshot::Receiver},
 };
 
 use super::types::ZKPState;
 use crate::types::*;
 
+/// The maximum size of a message of the proof generation process. The largest messages contain an
+/// election block, which is far smaller.
+const MAX_PROOF_GENERATION_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
 /// Generates the zk proof and sends it through the channel provided. Upon failure, the error is sent through the channel provided.
 pub fn generate_new_proof(
     prev_block: MacroBlock,
@@ -25,6 +30,7 @@ pub fn generate_new_proof(
     final_block: MacroBlock,
     genesis_header_hash: [u8; 32],
     prover_keys_path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<ZKPState, ZKProofGenerationError> {
     let proof = prove(
         prev_block,
@@ -33,6 +39,7 @@ pub fn generate_new_proof(
         true,
         true,
         prover_keys_path,
+        on_sub_proof,
     );
 
     match proof {
@@ -47,12 +54,15 @@ pub fn generate_new_proof(
     }
 }
 
-/// Starts the prover in a new child process.
+/// Starts the prover in a new child process, restricted to the given resources.
+/// The sub-proofs are sent through the channel provided as soon as the process generated them.
 /// Warning: The child process will continue to run if the parent process crashes.
 pub async fn launch_generate_new_proof(
     recv: Receiver<()>,
     proof_input: ProofInput,
     prover_path: Option<PathBuf>,
+    resources: ProverResources,
+    sub_proof_tx: UnboundedSender<SubProof>,
 ) -> Result<ZKPState, ZKProofGenerationError> {
     let path = match prover_path {
         Some(path) => path,
@@ -60,12 +70,14 @@ pub async fn launch_generate_new_proof(
     };
     log::debug!("Launching the prover process at path {:?}", path);
 
-    let mut child = Command::new(path)
+    let mut command = Command::new(path);
+    command
         .arg("--prove")
         .args(env::args().skip(1))
         .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()?;
+        .stdout(Stdio::piped());
+    restrict_resources(&mut command, &resources)?;
+    let mut child = command.spawn()?;
 
     child
         .stdin
@@ -74,13 +86,22 @@ pub async fn launch_generate_new_proof(
         .write_all(&proof_input.serialize_to_vec())
         .await?;
 
-    let mut output = child.stdout.take().unwrap();
-    let mut buffer = vec![];
+    let mut output = BufReader::new(child.stdout.take().unwrap());
+    let proof_generation = async {
+        loop {
+            match read_proof_generation_message(&mut output).await? {
+                ProofGenerationMessage::SubProof(sub_proof) => {
+                    let _ = sub_proof_tx.send(sub_proof);
+                }
+                ProofGenerationMessage::Result(result) => return result,
+            }
+        }
+    };
 
     tokio::select! {
-        _ = output.read_to_end(&mut buffer) => {
+        result = proof_generation => {
             let _ = child.wait().await;
-            parse_proof_generation_output(buffer)
+            result
         }
         _ = recv => {
             child.kill().await?;
@@ -89,22 +110,229 @@ pub async fn launch_generate_new_proof(
     }
 }
 
-fn parse_proof_generation_output(output: Vec<u8>) -> Result<ZKPState, ZKProofGenerationError> {
-    // We need at least two bytes to proceed.
-    if output.len() < 2 {
-        return Err(ZKProofGenerationError::ProcessError(
-            "Did not receive enough output from process".to_owned(),
-        ));
+/// Restricts the resources the prover process may use.
+fn restrict_resources(
+    command: &mut Command,
+    resources: &ProverResources,
+) -> Result<(), ZKProofGenerationError> {
+    // Arkworks parallelizes the proof generation with the global rayon thread pool.
+    if let Some(max_threads) = resources.max_threads {
+        command.env("RAYON_NUM_THREADS", max_threads.to_string());
+    }
+
+    #[cfg(target_os = "linux")]
+    let cpu_set = match resources.cpu_affinity {
+        Some(ref cpus) => {
+            if let Some(cpu) = cpus.iter().find(|&&cpu| cpu >= libc::CPU_SETSIZE as usize) {
+                return Err(ZKProofGenerationError::ProcessError(format!(
+                    "Invalid CPU for the prover affinity: {cpu}"
+                )));
+            }
+            // SAFETY: An all-zero `cpu_set_t` is a valid empty set and all CPUs are within its size.
+            unsafe {
+                let mut cpu_set: libc::cpu_set_t = std::mem::zeroed();
+                for &cpu in cpus {
+                    libc::CPU_SET(cpu, &mut cpu_set);
+                }
+                Some(cpu_set)
+            }
+        }
+        None => None,
+    };
+    #[cfg(not(target_os = "linux"))]
+    if resources.cpu_affinity.is_some() {
+        log::warn!("Setting the CPU affinity of the prover is only supported on Linux");
     }
 
+    #[cfg(unix)]
+    {
+        let niceness = resources.niceness;
+        let max_memory = resources.max_memory;
+        // SAFETY: The closure only calls async-signal-safe functions and doesn't allocate.
+        unsafe {
+            command.pre_exec(move || {
+                if let Some(niceness) = niceness {
+                    if libc::setpriority(libc::PRIO_PROCESS, 0, niceness) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                if let Some(max_memory) = max_memory {
+                    let limit = libc::rlimit {
+                        rlim_cur: max_memory as libc::rlim_t,
+                        rlim_max: max_memory as libc::rlim_t,
+                    };
+                    if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                #[cfg(target_os = "linux")]
+                if let Some(ref cpu_set) = cpu_set {
+                    if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), cpu_set)
+                        != 0
+                    {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                Ok(())
+            });
+        }
+    }
+    #[cfg(not(unix))]
+    if resources.niceness.is_some() {
+        log::warn!("Setting the niceness of the prover is only supported on Unix");
+    }
+    #[cfg(not(unix))]
+    if resources.max_memory.is_some() {
+        log::warn!("Limiting the memory of the prover is only supported on Unix");
+    }
+
+    Ok(())
+}
+
+/// Writes a message of the proof generation process.
+/// Every message is preceded by the delimiter and its length, so that it can be told apart from
+/// other output of the process, e.g. logs.
+pub(crate) fn write_proof_generation_message<W: Write>(
+    writer: &mut W,
+    message: &ProofGenerationMessage,
+) -> io::Result<()> {
+    let data = message.serialize_to_vec();
+
+    // Write the message at once, so that it is not interleaved with other output.
+    let mut buffer = Vec::with_capacity(PROOF_GENERATION_OUTPUT_DELIMITER.len() + 4 + data.len());
+    buffer.extend_from_slice(&PROOF_GENERATION_OUTPUT_DELIMITER);
+    buffer.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    buffer.extend_from_slice(&data);
+
+    writer.write_all(&buffer)?;
+    writer.flush()
+}
+
+/// Reads the next message of the proof generation process, skipping any other output.
+async fn read_proof_generation_message<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<ProofGenerationMessage, ZKProofGenerationError> {
     // We read until the two delimiter bytes.
-    let mut mid = 0;
-    for i in 0..output.len() - 2 {
-        if output[i..i + 2] == PROOF_GENERATION_OUTPUT_DELIMITER {
-            mid = i + 2;
+    let mut previous_byte = None;
+    loop {
+        let byte = reader.read_u8().await.map_err(|_| {
+            ZKProofGenerationError::ProcessError(
+                "Did not receive enough output from process".to_owned(),
+            )
+        })?;
+        if previous_byte == Some(PROOF_GENERATION_OUTPUT_DELIMITER[0])
+            && byte == PROOF_GENERATION_OUTPUT_DELIMITER[1]
+        {
             break;
         }
+        previous_byte = Some(byte);
     }
 
-    <Result<ZKPState, ZKProofGenerationError>>::deserialize_from_vec(&output[mid..])?
+    let len = reader.read_u32().await? as usize;
+    if len > MAX_PROOF_GENERATION_MESSAGE_SIZE {
+        return Err(ZKProofGenerationError::ProcessError(format!(
+            "Message of the process is too large: {len} bytes"
+        )));
+    }
+    let mut buffer = vec![0; len];
+    reader.read_exact(&mut buffer).await?;
+
+    Ok(ProofGenerationMessage::deserialize_from_vec(&buffer)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_test_log::test;
+
+    use super::*;
+
+    fn sub_proof_message(name: &str) -> ProofGenerationMessage {
+        ProofGenerationMessage::SubProof(SubProof {
+            final_header_hash: [1; 32],
+            name: name.to_owned(),
+            proof: vec![1, 2, 3],
+        })
+    }
+
+    #[test(tokio::test)]
+    async fn it_reads_messages_between_other_output() {
+        let mut output = b"some log output\n".to_vec();
+        write_proof_generation_message(&mut output, &sub_proof_message("merger")).unwrap();
+        output.extend_from_slice(b"more log output\n");
+        write_proof_generation_message(&mut output, &sub_proof_message("pk_tree_0_0")).unwrap();
+
+        let mut reader = output.as_slice();
+        for name in ["merger", "pk_tree_0_0"] {
+            match read_proof_generation_message(&mut reader).await.unwrap() {
+                ProofGenerationMessage::SubProof(sub_proof) => assert_eq!(sub_proof.name, name),
+                message => panic!("Unexpected message: {message:?}"),
+            }
+        }
+        assert!(read_proof_generation_message(&mut reader).await.is_err());
+    }
+
+    #[test(tokio::test)]
+    async fn it_rejects_oversized_messages() {
+        let mut output = PROOF_GENERATION_OUTPUT_DELIMITER.to_vec();
+        output.extend_from_slice(&(MAX_PROOF_GENERATION_MESSAGE_SIZE as u32 + 1).to_be_bytes());
+
+        assert!(matches!(
+            read_proof_generation_message(&mut output.as_slice()).await,
+            Err(ZKProofGenerationError::ProcessError(_))
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test(tokio::test)]
+    async fn it_restricts_the_resources_of_the_process() {
+        // Pin the process to one of the CPUs we may run on.
+        // SAFETY: An all-zero `cpu_set_t` is a valid empty set.
+        let cpu = unsafe {
+            let mut cpu_set: libc::cpu_set_t = std::mem::zeroed();
+            assert_eq!(
+                libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut cpu_set),
+                0
+            );
+            (0..libc::CPU_SETSIZE as usize)
+                .find(|&cpu| libc::CPU_ISSET(cpu, &cpu_set))
+                .unwrap()
+        };
+        let resources = ProverResources {
+            max_threads: Some(1),
+            niceness: Some(19),
+            cpu_affinity: Some(vec![cpu]),
+            max_memory: Some(1 << 30),
+        };
+
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(
+            "echo \"$RAYON_NUM_THREADS\"; \
+             cut -d ' ' -f 19 /proc/self/stat; \
+             grep Cpus_allowed_list /proc/self/status; \
+             grep 'Max address space' /proc/self/limits",
+        );
+        restrict_resources(&mut command, &resources).unwrap();
+        let output = command.output().await.unwrap();
+        assert!(output.status.success());
+
+        let output = String::from_utf8(output.stdout).unwrap();
+        let lines: Vec<Vec<&str>> = output
+            .lines()
+            .map(|line| line.split_whitespace().collect())
+            .collect();
+        assert_eq!(lines[0], ["1"]);
+        assert_eq!(lines[1], ["19"]);
+        assert_eq!(lines[2], ["Cpus_allowed_list:", &cpu.to_string()]);
+        assert_eq!(
+            lines[3],
+            [
+                "Max",
+                "address",
+                "space",
+                "1073741824",
+                "1073741824",
+                "bytes"
+            ]
+        );
+    }
 }
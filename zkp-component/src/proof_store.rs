@@ -2,42 +2,92 @@
 use nimiq_database::{
     declare_table,
     mdbx::MdbxDatabase,
-    traits::{Database, ReadTransaction, WriteTransaction},
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
 };
+#[cfg(feature = "database-storage")]
+use nimiq_primitives::policy::Policy;
 
 use crate::types::*;
 
 /// Defines an interface for storing and retrieving ZK proofs.
-pub trait ProofStore: Send {
+pub trait ProofStore: Send + Sync {
     /// Gets a ZK proof.
     fn get_zkp(&self) -> Option<ZKProof>;
 
     /// Sets or stores a ZK proof.
     fn set_zkp(&self, zk_proof: &ZKProof);
+
+    /// Gets the ZK proof for the election block with the given block number from the proof
+    /// history, if it is kept.
+    fn get_zkp_at(&self, _block_number: u32) -> Option<ZKProof> {
+        None
+    }
+
+    /// Gets the stored sub-proofs of the proof generation for the election block with the given
+    /// header hash.
+    fn get_sub_proofs(&self, _final_header_hash: &[u8; 32]) -> Vec<SubProof> {
+        vec![]
+    }
+
+    /// Stores a sub-proof. Only the sub-proofs of a single proof generation are kept, so the
+    /// sub-proofs for other election blocks are removed.
+    fn add_sub_proof(&self, _sub_proof: &SubProof) {}
+
+    /// Removes all stored sub-proofs.
+    fn clear_sub_proofs(&self) {}
 }
 
 #[cfg(feature = "database-storage")]
 declare_table!(ZKProofTable, "ZKPState", () => ZKProof);
+#[cfg(feature = "database-storage")]
+declare_table!(SubProofTable, "ZKPSubProofs", String => SubProof);
+#[cfg(feature = "database-storage")]
+declare_table!(ZKProofHistoryTable, "ZKPHistory", u32 => ZKProof);
+
+/// Defines for which election blocks the proofs are kept in the proof history.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProofHistoryRetention {
+    /// Only the latest proof is kept.
+    #[default]
+    Disabled,
+    /// The proofs of all election blocks are kept.
+    All,
+    /// The proofs of the election blocks of the given number of most recent epochs are kept.
+    Epochs(u32),
+}
 
 #[cfg(feature = "database-storage")]
-/// DB implementation of a ProofStore meant for persistent storage
+/// DB implementation of a ProofStore meant for persistent storage.
+/// It works on any database backend, the node uses mdbx.
 #[derive(Debug)]
-pub struct DBProofStore {
+pub struct DBProofStore<D = MdbxDatabase> {
     /// Environment for the DB creation and transaction handling.
-    env: MdbxDatabase,
+    env: D,
+    /// The retention of the proof history.
+    history_retention: ProofHistoryRetention,
 }
 
 #[cfg(feature = "database-storage")]
-impl DBProofStore {
-    pub fn new(env: MdbxDatabase) -> Self {
+impl<D: Database> DBProofStore<D> {
+    pub fn new(env: D) -> Self {
+        Self::with_history_retention(env, ProofHistoryRetention::default())
+    }
+
+    /// Creates a proof store that additionally keeps the proofs of past election blocks.
+    pub fn with_history_retention(env: D, history_retention: ProofHistoryRetention) -> Self {
         env.create_regular_table(&ZKProofTable);
+        env.create_regular_table(&SubProofTable);
+        env.create_regular_table(&ZKProofHistoryTable);
 
-        Self { env }
+        Self {
+            env,
+            history_retention,
+        }
     }
 }
 
 #[cfg(feature = "database-storage")]
-impl ProofStore for DBProofStore {
+impl<D: Database + Send + Sync> ProofStore for DBProofStore<D> {
     fn get_zkp(&self) -> Option<ZKProof> {
         self.env.read_transaction().get(&ZKProofTable, &())
     }
@@ -45,6 +95,63 @@ impl ProofStore for DBProofStore {
     fn set_zkp(&self, zk_proof: &ZKProof) {
         let mut tx = self.env.write_transaction();
         tx.put(&ZKProofTable, &(), zk_proof);
+
+        // The genesis block has no proof, so there is nothing to keep in the history.
+        if self.history_retention != ProofHistoryRetention::Disabled && zk_proof.proof.is_some() {
+            tx.put(&ZKProofHistoryTable, &zk_proof.block_number, zk_proof);
+        }
+
+        // Prunes the proofs of the election blocks that are out of the retention window.
+        if let ProofHistoryRetention::Epochs(num_epochs) = self.history_retention {
+            let cutoff = zk_proof
+                .block_number
+                .saturating_sub(num_epochs.saturating_mul(Policy::blocks_per_epoch()));
+            let outdated: Vec<u32> = ReadTransaction::cursor(&tx, &ZKProofHistoryTable)
+                .into_iter_start()
+                .map(|(block_number, _)| block_number)
+                .take_while(|&block_number| block_number <= cutoff)
+                .collect();
+            for block_number in outdated {
+                tx.remove(&ZKProofHistoryTable, &block_number);
+            }
+        }
+
         tx.commit();
     }
+
+    fn get_zkp_at(&self, block_number: u32) -> Option<ZKProof> {
+        self.env
+            .read_transaction()
+            .get(&ZKProofHistoryTable, &block_number)
+    }
+
+    fn get_sub_proofs(&self, final_header_hash: &[u8; 32]) -> Vec<SubProof> {
+        let txn = self.env.read_transaction();
+        let cursor = txn.cursor(&SubProofTable);
+        cursor
+            .into_iter_start()
+            .map(|(_, sub_proof)| sub_proof)
+            .filter(|sub_proof| &sub_proof.final_header_hash == final_header_hash)
+            .collect()
+    }
+
+    fn add_sub_proof(&self, sub_proof: &SubProof) {
+        let mut txn = self.env.write_transaction();
+
+        let is_outdated = ReadTransaction::cursor(&txn, &SubProofTable)
+            .first()
+            .is_some_and(|(_, other)| other.final_header_hash != sub_proof.final_header_hash);
+        if is_outdated {
+            txn.clear_table(&SubProofTable);
+        }
+
+        txn.put_reserve(&SubProofTable, &sub_proof.name, sub_proof);
+        txn.commit();
+    }
+
+    fn clear_sub_proofs(&self) {
+        let mut txn = self.env.write_transaction();
+        txn.clear_table(&SubProofTable);
+        txn.commit();
+    }
 }
@@ -7,9 +7,26 @@ use nimiq_genesis::NetworkInfo;
 use nimiq_primitives::policy::Policy;
 use nimiq_zkp::{verify::verify, ZKP_VERIFYING_DATA};
 use nimiq_zkp_primitives::NanoZKPError;
+use parking_lot::RwLock;
 
 use super::types::ZKPState;
-use crate::types::*;
+use crate::{proof_store::ProofStore, types::*};
+
+/// Gets the proof for the election block at the given block number. The current proof is taken
+/// from the zkp state, older ones from the proof history of the storage.
+pub(crate) fn get_zkp_at(
+    zkp_state: &RwLock<ZKPState>,
+    proof_storage: Option<&dyn ProofStore>,
+    block_number: u32,
+) -> Option<ZKProof> {
+    let zkp_state = zkp_state.read();
+    if zkp_state.latest_block.block_number() == block_number {
+        return Some(zkp_state.clone().into());
+    }
+    drop(zkp_state);
+
+    proof_storage.and_then(|proof_storage| proof_storage.get_zkp_at(block_number))
+}
 
 /// Fully validates the proof by verifying both the zk proof and the blocks existence on the blockchain.
 pub fn validate_proof(
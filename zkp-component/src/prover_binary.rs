@@ -1,11 +1,12 @@
-use std::io::{self, BufReader, BufWriter, Error};
+use std::io::{self, BufReader, Error};
 
-use ark_serialize::{Read, Write};
-use nimiq_serde::{Deserialize, Serialize};
+use ark_serialize::Read;
+use nimiq_serde::Deserialize;
+use nimiq_zkp::prove::restore_sub_proofs;
 
 use crate::{
-    proof_gen_utils::generate_new_proof,
-    types::{ProofInput, ZKProofGenerationError, PROOF_GENERATION_OUTPUT_DELIMITER},
+    proof_gen_utils::{generate_new_proof, write_proof_generation_message},
+    types::{ProofGenerationMessage, ProofInput, SubProof, ZKPState, ZKProofGenerationError},
 };
 
 pub async fn prover_main() -> Result<(), Error> {
@@ -21,25 +22,59 @@ pub async fn prover_main() -> Result<(), Error> {
         proof_input.as_ref().map(|input| &input.final_block)
     );
 
-    // Then generate proof.
+    // Then generate proof, resuming from the sub-proofs of previous attempts.
     let result = match proof_input {
-        Ok(proof_input) => generate_new_proof(
-            proof_input.previous_block,
-            proof_input.previous_proof,
-            proof_input.final_block,
-            proof_input.genesis_header_hash,
-            &proof_input.prover_keys_path,
-        ),
+        Ok(proof_input) => generate_proof(proof_input),
         Err(e) => Err(ZKProofGenerationError::from(e)),
     };
     log::info!("Finished proof generation with result {:?}", result);
 
-    // Then print delimiter followed by the serialized result.
-    let mut stdout = BufWriter::new(io::stdout());
-    stdout.write_all(&PROOF_GENERATION_OUTPUT_DELIMITER)?;
-    Serialize::serialize_to_writer(&result, &mut stdout)?;
-
-    stdout.flush()?;
+    // Then print the result.
+    write_proof_generation_message(
+        &mut io::stdout().lock(),
+        &ProofGenerationMessage::Result(result),
+    )?;
 
     Ok(())
 }
+
+fn generate_proof(proof_input: ProofInput) -> Result<ZKPState, ZKProofGenerationError> {
+    let final_header_hash = proof_input.final_block.hash_blake2s().0;
+
+    if !proof_input.sub_proofs.is_empty() {
+        log::info!(
+            num_sub_proofs = proof_input.sub_proofs.len(),
+            "Resuming proof generation"
+        );
+    }
+    restore_sub_proofs(
+        &proof_input.prover_keys_path,
+        &final_header_hash,
+        proof_input
+            .sub_proofs
+            .iter()
+            .filter(|sub_proof| sub_proof.final_header_hash == final_header_hash)
+            .map(|sub_proof| (sub_proof.name.as_str(), sub_proof.proof.as_slice())),
+    )?;
+
+    // Print every sub-proof as soon as it is generated, so that it can be persisted.
+    let on_sub_proof = |name: &str, proof: &[u8]| {
+        let message = ProofGenerationMessage::SubProof(SubProof {
+            final_header_hash,
+            name: name.to_owned(),
+            proof: proof.to_vec(),
+        });
+        if let Err(e) = write_proof_generation_message(&mut io::stdout().lock(), &message) {
+            log::warn!(error = %e, name, "Failed to output sub-proof");
+        }
+    };
+
+    generate_new_proof(
+        proof_input.previous_block,
+        proof_input.previous_proof,
+        proof_input.final_block,
+        proof_input.genesis_header_hash,
+        &proof_input.prover_keys_path,
+        &on_sub_proof,
+    )
+}
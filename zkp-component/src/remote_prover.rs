@@ -0,0 +1,321 @@
+//! Proof generation on remote prover servers.
+//!
+//! A prover server accepts proof generation jobs over TCP and processes them one after the other
+//! with its local prover process. This allows nodes to delegate the proof generation instead of
+//! hosting the proving keys themselves.
+//!
+//! The protocol consists of length-prefixed frames:
+//! 1. The client sends the authentication token.
+//! 2. The client sends the serialized `ProofInput`.
+//! 3. The server replies with a serialized `ProofGenerationMessage` for every sub-proof and a
+//!    final one with the result.
+//!
+//! If the client closes the connection before receiving the result, the job is aborted.
+//! The `ProofInput` is deserialized without checks, so servers must only be reachable by trusted
+//! clients and should use a strong authentication token.
+//!
+//! The connection is not encrypted, so the authentication token and all data travel in cleartext.
+//! Servers that are not on a trusted network must be reached through a TLS tunnel or VPN.
+
+use std::{io, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
+
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_utils::spawn;
+use subtle::ConstantTimeEq;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, UnboundedSender},
+        oneshot, Semaphore,
+    },
+    time::{sleep, timeout},
+};
+
+use crate::{proof_gen_utils::launch_generate_new_proof, types::*};
+
+/// The maximum size of a frame. Proof inputs consist of two macro blocks and a proof.
+const MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+/// The maximum size of the authentication token sent by clients.
+const MAX_AUTH_TOKEN_SIZE: usize = 256;
+
+/// The time a client has to authenticate after connecting.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A proof generation job waiting in the queue of the server.
+struct ProofJob {
+    proof_input: ProofInput,
+    abort: oneshot::Receiver<()>,
+    sub_proofs: UnboundedSender<SubProof>,
+    result: oneshot::Sender<Result<ZKPState, ZKProofGenerationError>>,
+}
+
+/// A server that generates proofs for remote nodes.
+pub struct ProverServer {
+    listener: TcpListener,
+    config: ProverServerConfig,
+}
+
+impl ProverServer {
+    /// Binds the server to the configured listen address.
+    pub async fn bind(config: ProverServerConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.listen_address).await?;
+        Ok(Self { listener, config })
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections and processes the received jobs. This never returns.
+    pub async fn run(self) {
+        log::info!(
+            address = %self.config.listen_address,
+            "Prover server listening for proof generation jobs"
+        );
+
+        let (jobs_tx, jobs_rx) = mpsc::channel(self.config.max_queued_jobs.max(1));
+        spawn(process_jobs(
+            jobs_rx,
+            self.config.prover_path.clone(),
+            self.config.resources.clone(),
+        ));
+
+        let auth_token = Arc::new(self.config.auth_token.0.clone());
+        let connections = Arc::new(Semaphore::new(self.config.max_connections.max(1)));
+        loop {
+            match self.listener.accept().await {
+                Ok((stream, address)) => {
+                    // The connection is closed when the permit is not available.
+                    let Ok(permit) = Arc::clone(&connections).try_acquire_owned() else {
+                        log::debug!(%address, "Too many prover server connections, closing connection");
+                        continue;
+                    };
+                    let auth_token = Arc::clone(&auth_token);
+                    let prover_keys_path = self.config.prover_keys_path.clone();
+                    let jobs_tx = jobs_tx.clone();
+                    spawn(async move {
+                        if let Err(error) =
+                            handle_connection(stream, &auth_token, prover_keys_path, jobs_tx).await
+                        {
+                            log::debug!(%address, %error, "Prover server connection failed");
+                        }
+                        drop(permit);
+                    });
+                }
+                Err(error) => {
+                    log::warn!(%error, "Prover server failed to accept connection");
+                }
+            }
+        }
+    }
+}
+
+/// Runs the queued jobs sequentially, since every proof generation uses all available resources.
+async fn process_jobs(
+    mut jobs: mpsc::Receiver<ProofJob>,
+    prover_path: Option<PathBuf>,
+    resources: ProverResources,
+) {
+    while let Some(job) = jobs.recv().await {
+        // Skip jobs whose client disconnected while waiting.
+        if job.result.is_closed() {
+            continue;
+        }
+
+        log::info!(
+            block_number = job.proof_input.final_block.block_number(),
+            "Starting remote proof generation job"
+        );
+        let result = launch_generate_new_proof(
+            job.abort,
+            job.proof_input,
+            prover_path.clone(),
+            resources.clone(),
+            job.sub_proofs,
+        )
+        .await;
+        let _ = job.result.send(result);
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    auth_token: &str,
+    prover_keys_path: PathBuf,
+    jobs: mpsc::Sender<ProofJob>,
+) -> Result<(), ZKProofGenerationError> {
+    // Unauthenticated clients may only send a small token and must do so in time.
+    let token = timeout(AUTH_TIMEOUT, read_frame(&mut stream, MAX_AUTH_TOKEN_SIZE))
+        .await
+        .map_err(|_| {
+            ZKProofGenerationError::RemoteProverError("Authentication timed out".to_owned())
+        })??;
+    if !bool::from(token.ct_eq(auth_token.as_bytes())) {
+        let result = Err(ZKProofGenerationError::RemoteProverError(
+            "Authentication failed".to_owned(),
+        ));
+        return write_message(&mut stream, &ProofGenerationMessage::Result(result)).await;
+    }
+
+    let mut proof_input =
+        ProofInput::deserialize_from_vec(&read_frame(&mut stream, MAX_FRAME_SIZE).await?)?;
+    // The proving keys are located on this server.
+    proof_input.prover_keys_path = prover_keys_path;
+
+    // The job is aborted when the abort sender is dropped, i.e. when this function returns.
+    let (_abort_tx, abort_rx) = oneshot::channel();
+    let (sub_proof_tx, mut sub_proof_rx) = mpsc::unbounded_channel();
+    let (result_tx, mut result_rx) = oneshot::channel();
+    let job = ProofJob {
+        proof_input,
+        abort: abort_rx,
+        sub_proofs: sub_proof_tx,
+        result: result_tx,
+    };
+    if jobs.try_send(job).is_err() {
+        let result = Err(ZKProofGenerationError::RemoteProverError(
+            "Job queue is full".to_owned(),
+        ));
+        return write_message(&mut stream, &ProofGenerationMessage::Result(result)).await;
+    }
+
+    let (mut reader, mut writer) = stream.split();
+    let mut buffer = [0u8; 1];
+    loop {
+        tokio::select! {
+            // Forward all sub-proofs before the result.
+            biased;
+            Some(sub_proof) = sub_proof_rx.recv() => {
+                write_message(&mut writer, &ProofGenerationMessage::SubProof(sub_proof)).await?;
+            }
+            result = &mut result_rx => {
+                let result = result.unwrap_or(Err(ZKProofGenerationError::ChannelError));
+                return write_message(&mut writer, &ProofGenerationMessage::Result(result)).await;
+            }
+            // The client is not supposed to send anything else, so this only completes if the
+            // connection is closed.
+            _ = reader.read(&mut buffer) => {
+                log::debug!("Client disconnected, aborting proof generation job");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Generates the proof on the configured remote prover servers.
+///
+/// The servers are tried in order. If all of them fail, the servers are retried up to the configured
+/// number of times. Errors of the proof generation itself are returned without retrying.
+/// The sub-proofs are sent through the channel provided as soon as the server generated them.
+pub async fn launch_remote_proof_generation(
+    recv: oneshot::Receiver<()>,
+    proof_input: ProofInput,
+    config: RemoteProverConfig,
+    sub_proof_tx: UnboundedSender<SubProof>,
+) -> Result<ZKPState, ZKProofGenerationError> {
+    let request = proof_input.serialize_to_vec();
+
+    let attempts = async {
+        let mut last_error =
+            ZKProofGenerationError::RemoteProverError("No prover server configured".to_owned());
+        for attempt in 0..=config.retries {
+            if attempt > 0 {
+                sleep(config.retry_delay).await;
+            }
+
+            for server in &config.servers {
+                log::debug!(server, attempt, "Requesting proof from prover server");
+                match request_proof(server, &request, &config, &sub_proof_tx).await {
+                    Err(
+                        error @ (ZKProofGenerationError::RemoteProverError(_)
+                        | ZKProofGenerationError::ProcessError(_)),
+                    ) => {
+                        log::warn!(server, attempt, %error, "Prover server failed");
+                        last_error = error;
+                    }
+                    result => return result,
+                }
+            }
+        }
+        Err(last_error)
+    };
+
+    tokio::select! {
+        result = attempts => result,
+        _ = recv => Err(ZKProofGenerationError::ChannelError),
+    }
+}
+
+async fn request_proof(
+    server: &str,
+    request: &[u8],
+    config: &RemoteProverConfig,
+    sub_proof_tx: &UnboundedSender<SubProof>,
+) -> Result<ZKPState, ZKProofGenerationError> {
+    let mut stream = timeout(config.connect_timeout, TcpStream::connect(server))
+        .await
+        .map_err(|_| {
+            ZKProofGenerationError::RemoteProverError("Connection timed out".to_owned())
+        })??;
+
+    write_frame(&mut stream, config.auth_token.as_bytes()).await?;
+    write_frame(&mut stream, request).await?;
+
+    let proof_generation = async {
+        loop {
+            let message = ProofGenerationMessage::deserialize_from_vec(
+                &read_frame(&mut stream, MAX_FRAME_SIZE).await?,
+            )?;
+            match message {
+                ProofGenerationMessage::SubProof(sub_proof) => {
+                    let _ = sub_proof_tx.send(sub_proof);
+                }
+                ProofGenerationMessage::Result(result) => return result,
+            }
+        }
+    };
+
+    timeout(config.proof_timeout, proof_generation)
+        .await
+        .map_err(|_| {
+            ZKProofGenerationError::RemoteProverError("Proof generation timed out".to_owned())
+        })?
+}
+
+/// Reads a length-prefixed frame, rejecting frames larger than `max_len` before allocating.
+async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    max_len: usize,
+) -> Result<Vec<u8>, ZKProofGenerationError> {
+    let len = reader.read_u32().await? as usize;
+    if len > max_len {
+        return Err(ZKProofGenerationError::RemoteProverError(format!(
+            "Frame too large: {} bytes",
+            len
+        )));
+    }
+
+    let mut buffer = vec![0; len];
+    reader.read_exact(&mut buffer).await?;
+    Ok(buffer)
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &ProofGenerationMessage,
+) -> Result<(), ZKProofGenerationError> {
+    write_frame(writer, &message.serialize_to_vec()).await
+}
+
+async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    data: &[u8],
+) -> Result<(), ZKProofGenerationError> {
+    writer.write_u32(data.len() as u32).await?;
+    writer.write_all(data).await?;
+    writer.flush().await?;
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use std::{io, path::PathBuf, sync::Arc};
+use std::{io, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
 use ark_groth16::Proof;
 use ark_mnt6_753::MNT6_753;
@@ -11,12 +11,14 @@ use nimiq_network_interface::{
     network::{Network, Topic},
     request::{Handle, RequestCommon, RequestError, RequestMarker},
 };
+use nimiq_primitives::policy::Policy;
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_utils::Sensitive;
 use nimiq_zkp_primitives::NanoZKPError;
 use parking_lot::RwLock;
 use thiserror::Error;
 
-use crate::ZKPComponent;
+use crate::{proof_store::ProofStore, proof_utils::get_zkp_at, ZKPComponent};
 
 pub const PROOF_GENERATION_OUTPUT_DELIMITER: [u8; 2] = [242, 208];
 
@@ -132,6 +134,117 @@ pub struct ProofInput {
     pub final_block: MacroBlock,
     pub genesis_header_hash: [u8; 32],
     pub prover_keys_path: PathBuf,
+    /// Sub-proofs of a previous, interrupted attempt to generate this proof.
+    pub sub_proofs: Vec<SubProof>,
+}
+
+/// A sub-proof that was generated while proving an election block.
+/// Sub-proofs are persisted so that an interrupted proof generation can resume from them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DbSerializable)]
+pub struct SubProof {
+    /// The header hash of the election block the proof is generated for.
+    pub final_header_hash: [u8; 32],
+    /// The name of the sub-proof, e.g. `pk_tree_5_3` or `merger`.
+    pub name: String,
+    /// The serialized sub-proof.
+    pub proof: Vec<u8>,
+}
+
+/// A message sent by the proof generation process.
+#[derive(Debug, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ProofGenerationMessage {
+    /// A sub-proof was generated.
+    SubProof(SubProof),
+    /// The proof generation finished.
+    Result(Result<ZKPState, ZKProofGenerationError>),
+}
+
+/// The progress of the proof generation for an election block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofGenerationProgress {
+    /// The election block the proof is generated for.
+    pub block_number: u32,
+    /// The number of sub-proofs that have been generated, including the ones of previous attempts.
+    pub completed_sub_proofs: usize,
+    /// The total number of sub-proofs.
+    pub total_sub_proofs: usize,
+}
+
+/// The configuration for delegating the proof generation to remote prover servers.
+#[derive(Clone, Debug)]
+pub struct RemoteProverConfig {
+    /// The addresses (`host:port`) of the prover servers, in the order in which they are tried.
+    pub servers: Vec<String>,
+    /// The token to authenticate with the prover servers.
+    pub auth_token: Sensitive<String>,
+    /// The maximum time to establish a connection to a prover server.
+    pub connect_timeout: Duration,
+    /// The maximum time a prover server may take to return a proof, including queueing time.
+    pub proof_timeout: Duration,
+    /// The number of times all servers are retried after the first round of attempts failed.
+    pub retries: u32,
+    /// The delay between two rounds of attempts.
+    pub retry_delay: Duration,
+}
+
+/// The configuration of a prover server that generates proofs for remote nodes.
+#[derive(Clone, Debug)]
+pub struct ProverServerConfig {
+    /// The address the server listens on.
+    pub listen_address: SocketAddr,
+    /// The token clients need to present.
+    pub auth_token: Sensitive<String>,
+    /// The path of the proving keys directory on the server.
+    pub prover_keys_path: PathBuf,
+    /// The path of the prover binary, defaults to the current executable.
+    pub prover_path: Option<PathBuf>,
+    /// The resources the prover process may use.
+    pub resources: ProverResources,
+    /// The maximum number of jobs waiting for the prover. Further jobs are rejected.
+    pub max_queued_jobs: usize,
+    /// The maximum number of concurrent client connections. Further connections are closed.
+    pub max_connections: usize,
+}
+
+/// The resources a local prover process may use.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProverResources {
+    /// The maximum number of threads used for the proof generation. Defaults to the number of CPUs.
+    pub max_threads: Option<usize>,
+    /// The niceness of the prover process. Only supported on Unix.
+    pub niceness: Option<i32>,
+    /// The CPUs the prover process is pinned to. Only supported on Linux.
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// The maximum size of the address space of the prover process in bytes. Only supported on Unix.
+    pub max_memory: Option<u64>,
+}
+
+/// Defines for which election blocks the prover generates proofs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvingPolicy {
+    /// Proofs are only generated for the election blocks of every nth epoch, the other proofs are
+    /// expected from peers. If a proof is missing when it is needed for a later epoch, it is
+    /// generated anyway.
+    pub every_nth_epoch: u32,
+    /// The time to wait for a proof from peers after an election block before generating it.
+    pub peer_proof_wait: Duration,
+}
+
+impl Default for ProvingPolicy {
+    fn default() -> Self {
+        Self {
+            every_nth_epoch: 1,
+            peer_proof_wait: Duration::ZERO,
+        }
+    }
+}
+
+impl ProvingPolicy {
+    /// Returns whether the proof for the given election block should be generated by this node.
+    pub fn is_due(&self, block_number: u32) -> bool {
+        Policy::epoch_at(block_number) % self.every_nth_epoch.max(1) == 0
+    }
 }
 
 /// The topic for zkp gossiping.
@@ -181,6 +294,9 @@ pub enum ZKProofGenerationError {
 
     #[error("Process launching error: {0}")]
     ProcessError(String),
+
+    #[error("Remote prover error: {0}")]
+    RemoteProverError(String),
 }
 
 impl From<DeserializeError> for ZKProofGenerationError {
@@ -228,10 +344,52 @@ pub enum RequestZKPResponse {
     Outdated(u32),
 }
 
+/// The request of the zkp for a specific election block. This allows to fetch older proofs from
+/// peers that keep a proof history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestZKPAtBlock {
+    pub(crate) block_number: u32,
+    pub(crate) request_election_block: bool,
+}
+
+impl RequestCommon for RequestZKPAtBlock {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 219;
+    type Response = RequestZKPAtBlockResponse;
+
+    const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_ZKP;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum RequestZKPAtBlockResponse {
+    Proof(ZKProof, Option<MacroBlock>),
+    NotFound,
+}
+
 #[derive(Clone)]
 pub(crate) struct ZKPStateEnvironment {
     pub(crate) zkp_state: Arc<RwLock<ZKPState>>,
     pub(crate) blockchain: BlockchainProxy,
+    pub(crate) proof_storage: Option<Arc<dyn ProofStore>>,
+}
+
+impl ZKPStateEnvironment {
+    /// Gets the election block at the given block number, if requested.
+    fn get_election_block(
+        &self,
+        block_number: u32,
+        request_election_block: bool,
+    ) -> Option<MacroBlock> {
+        if !request_election_block {
+            return None;
+        }
+        self.blockchain
+            .read()
+            .get_block_at(block_number, false)
+            .ok()
+            .map(|block| block.unwrap_macro())
+    }
 }
 
 impl<N: Network> From<&ZKPComponent<N>> for ZKPStateEnvironment {
@@ -239,6 +397,7 @@ impl<N: Network> From<&ZKPComponent<N>> for ZKPStateEnvironment {
         ZKPStateEnvironment {
             zkp_state: Arc::clone(&component.zkp_state),
             blockchain: component.blockchain.clone(),
+            proof_storage: component.proof_storage.clone(),
         }
     }
 }
@@ -255,19 +414,30 @@ impl<N: Network> Handle<N, Arc<ZKPStateEnvironment>> for RequestZKP {
         drop(zkp_state);
 
         // Then get the corresponding block if necessary.
-        let block = if self.request_election_block {
-            env.blockchain
-                .read()
-                .get_block_at(latest_block_number, false)
-                .ok()
-                .map(|block| block.unwrap_macro())
-        } else {
-            None
-        };
+        let block = env.get_election_block(latest_block_number, self.request_election_block);
         RequestZKPResponse::Proof(zkp_proof, block)
     }
 }
 
+impl<N: Network> Handle<N, Arc<ZKPStateEnvironment>> for RequestZKPAtBlock {
+    fn handle(
+        &self,
+        _peer_id: N::PeerId,
+        env: &Arc<ZKPStateEnvironment>,
+    ) -> RequestZKPAtBlockResponse {
+        let Some(zkp_proof) = get_zkp_at(
+            &env.zkp_state,
+            env.proof_storage.as_deref(),
+            self.block_number,
+        ) else {
+            return RequestZKPAtBlockResponse::NotFound;
+        };
+
+        let block = env.get_election_block(self.block_number, self.request_election_block);
+        RequestZKPAtBlockResponse::Proof(zkp_proof, block)
+    }
+}
+
 mod serde_derive {
 
     use std::fmt;
@@ -288,6 +458,7 @@ mod serde_derive {
         "final_block",
         "genesis_header_hash",
         "prover_keys_path",
+        "sub_proofs",
     ];
 
     struct ZKProofVisitor;
@@ -463,6 +634,9 @@ mod serde_derive {
             let path_buf: String = seq
                 .next_element()?
                 .ok_or_else(|| A::Error::invalid_length(4, &self))?;
+            let sub_proofs: Vec<SubProof> = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(5, &self))?;
 
             let previous_proof = if let Some(ser_proof) = ser_previous_proof {
                 Some(
@@ -480,6 +654,7 @@ mod serde_derive {
                 final_block,
                 genesis_header_hash,
                 prover_keys_path: PathBuf::from(path_buf),
+                sub_proofs,
             })
         }
     }
@@ -512,6 +687,7 @@ mod serde_derive {
                 PROOF_INPUT_FIELDS[4],
                 &self.prover_keys_path.to_string_lossy().to_string(),
             )?;
+            state.serialize_field(PROOF_INPUT_FIELDS[5], &self.sub_proofs)?;
             state.end()
         }
     }
@@ -525,3 +701,36 @@ mod serde_derive {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use nimiq_test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn proving_policy_is_due_every_nth_epoch() {
+        let election_block = |epoch| Policy::election_block_of(epoch).unwrap();
+
+        let proving_policy = ProvingPolicy::default();
+        for epoch in 1..=4 {
+            assert!(proving_policy.is_due(election_block(epoch)));
+        }
+
+        let proving_policy = ProvingPolicy {
+            every_nth_epoch: 3,
+            peer_proof_wait: Duration::from_secs(60),
+        };
+        let due_epochs: Vec<u32> = (1..=7)
+            .filter(|&epoch| proving_policy.is_due(election_block(epoch)))
+            .collect();
+        assert_eq!(due_epochs, [3, 6]);
+
+        // An invalid policy proves every epoch.
+        let proving_policy = ProvingPolicy {
+            every_nth_epoch: 0,
+            ..Default::default()
+        };
+        assert!(proving_policy.is_due(election_block(1)));
+    }
+}
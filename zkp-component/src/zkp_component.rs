@@ -24,7 +24,7 @@ use tokio::sync::{
 use tokio_stream::wrappers::BroadcastStream;
 
 #[cfg(feature = "zkp-prover")]
-use crate::zkp_prover::ZKProver;
+use crate::zkp_prover::{ProverBackend, ZKProver};
 use crate::{proof_store::ProofStore, proof_utils::*, types::*, zkp_requests::ZKPRequests};
 
 pub type ZKProofsStream<N> = BoxStream<'static, (ZKProof, <N as Network>::PubsubId)>;
@@ -32,8 +32,11 @@ pub type ZKProofsStream<N> = BoxStream<'static, (ZKProof, <N as Network>::Pubsub
 pub(crate) const BROADCAST_MAX_CAPACITY: usize = 256;
 
 pub struct ZKPComponentProxy<N: Network> {
+    blockchain: BlockchainProxy,
     network: Arc<N>,
     zkp_state: Arc<RwLock<ZKPState>>,
+    proof_storage: Option<Arc<dyn ProofStore>>,
+    proof_generation_progress: Arc<RwLock<Option<ProofGenerationProgress>>>,
     zkp_requests: Arc<Mutex<ZKPRequests<N>>>,
     pub(crate) zkp_events_notifier: BroadcastSender<ZKPEvent<N>>,
 }
@@ -41,8 +44,11 @@ pub struct ZKPComponentProxy<N: Network> {
 impl<N: Network> Clone for ZKPComponentProxy<N> {
     fn clone(&self) -> Self {
         Self {
+            blockchain: self.blockchain.clone(),
             network: Arc::clone(&self.network),
             zkp_state: Arc::clone(&self.zkp_state),
+            proof_storage: self.proof_storage.clone(),
+            proof_generation_progress: Arc::clone(&self.proof_generation_progress),
             zkp_requests: Arc::clone(&self.zkp_requests),
             zkp_events_notifier: self.zkp_events_notifier.clone(),
         }
@@ -55,6 +61,17 @@ impl<N: Network> ZKPComponentProxy<N> {
         self.zkp_state.read().clone()
     }
 
+    /// Gets the proof for the election block at the given block number. Proofs older than the
+    /// current one are only available if the proof history is enabled.
+    pub fn get_zkp_by_block_number(&self, block_number: u32) -> Option<ZKProof> {
+        get_zkp_at(&self.zkp_state, self.proof_storage.as_deref(), block_number)
+    }
+
+    /// Gets the progress of the proof that is currently being generated by us, if any.
+    pub fn get_proof_generation_progress(&self) -> Option<ProofGenerationProgress> {
+        self.proof_generation_progress.read().clone()
+    }
+
     /// Sends zkp request to all given peers. If no requests are ongoing, we request and return true,
     /// otherwise no requests will be sent.
     pub fn request_zkp_from_peers(
@@ -87,6 +104,37 @@ impl<N: Network> ZKPComponentProxy<N> {
         (request.await, peer_id)
     }
 
+    /// Requests the proof for the election block at the given block number from a single peer and
+    /// verifies it. The proof is not pushed into our zkp state.
+    pub async fn request_zkp_at_block_from_peer(
+        &self,
+        peer_id: N::PeerId,
+        block_number: u32,
+        request_election_block: bool,
+    ) -> Result<Option<ZKPState>, Error> {
+        let request = RequestZKPAtBlock {
+            block_number,
+            request_election_block,
+        };
+        let (proof, election_block) = match self.network.request(request, peer_id).await? {
+            RequestZKPAtBlockResponse::Proof(proof, election_block) => (proof, election_block),
+            RequestZKPAtBlockResponse::NotFound => return Ok(None),
+        };
+
+        if proof.block_number != block_number {
+            return Err(Error::InvalidProof);
+        }
+        if let Some(ref block) = election_block {
+            if block.block_number() != block_number || !block.is_election() {
+                return Err(Error::InvalidBlock);
+            }
+        }
+
+        let (block, genesis_block, proof) =
+            get_proof_macro_blocks(&self.blockchain, &proof, election_block)?;
+        validate_proof_get_new_state(proof, block, genesis_block).map(Some)
+    }
+
     pub fn subscribe_zkps(&self) -> BroadcastStream<ZKPEvent<N>> {
         BroadcastStream::new(self.zkp_events_notifier.subscribe())
     }
@@ -101,8 +149,9 @@ impl<N: Network> ZKPComponentProxy<N> {
 /// - The network
 /// - The current zkp state
 /// - The proof generating component that can be activated by a client configuration
+/// - The progress of the proof generation
 /// - The zkp gossip stream
-/// - The db storage for the current proof
+/// - The db storage for the current proof, the proof history and the sub-proofs of the proof generation
 /// - The zkp requests component to fetch an up to date proof from our peers
 /// - The zkp events notifies newly stored proofs.
 ///
@@ -113,8 +162,9 @@ pub struct ZKPComponent<N: Network> {
     pub(crate) zkp_state: Arc<RwLock<ZKPState>>,
     #[cfg(feature = "zkp-prover")]
     zk_prover: Option<ZKProver<N>>,
+    proof_generation_progress: Arc<RwLock<Option<ProofGenerationProgress>>>,
     zk_proofs_stream: ZKProofsStream<N>,
-    proof_storage: Option<Box<dyn ProofStore>>,
+    pub(crate) proof_storage: Option<Arc<dyn ProofStore>>,
     zkp_requests: Arc<Mutex<ZKPRequests<N>>>,
     zkp_events_notifier: BroadcastSender<ZKPEvent<N>>,
 }
@@ -144,8 +194,9 @@ impl<N: Network> ZKPComponent<N> {
             zkp_state,
             #[cfg(feature = "zkp-prover")]
             zk_prover: None,
+            proof_generation_progress: Arc::new(RwLock::new(None)),
             zk_proofs_stream,
-            proof_storage,
+            proof_storage: proof_storage.map(Arc::from),
             zkp_requests: Arc::new(Mutex::new(ZKPRequests::new(network))),
             zkp_events_notifier,
         };
@@ -163,8 +214,9 @@ impl<N: Network> ZKPComponent<N> {
         blockchain: BlockchainProxy,
         network: Arc<N>,
         is_prover_active: bool,
-        prover_path: Option<PathBuf>,
+        prover_backend: ProverBackend,
         prover_keys_path: PathBuf,
+        proving_policy: ProvingPolicy,
         proof_storage: Option<Box<dyn ProofStore>>,
     ) -> Self {
         let mut zkp_component = Self::new(blockchain, network, proof_storage).await;
@@ -176,8 +228,11 @@ impl<N: Network> ZKPComponent<N> {
                     Arc::clone(blockchain),
                     Arc::clone(&zkp_component.network),
                     Arc::clone(&zkp_component.zkp_state),
-                    prover_path,
+                    Arc::clone(&zkp_component.proof_generation_progress),
+                    zkp_component.proof_storage.clone(),
+                    prover_backend,
                     prover_keys_path,
+                    proving_policy,
                 )
                 .await,
             ),
@@ -191,18 +246,25 @@ impl<N: Network> ZKPComponent<N> {
         zkp_component
     }
 
-    /// Launches thread that processes the zkp requests and replies to them.
+    /// Launches threads that process the zkp requests and reply to them.
     fn launch_request_handler(&self) {
-        let stream = self.network.receive_requests::<RequestZKP>();
         let env = Arc::new(ZKPStateEnvironment::from(self));
+
+        let stream = self.network.receive_requests::<RequestZKP>();
+        spawn(Box::pin(request_handler(&self.network, stream, &env)));
+
+        let stream = self.network.receive_requests::<RequestZKPAtBlock>();
         spawn(Box::pin(request_handler(&self.network, stream, &env)));
     }
 
     /// Gets a proxy for the current ZKP Component.
     pub fn proxy(&self) -> ZKPComponentProxy<N> {
         ZKPComponentProxy {
+            blockchain: self.blockchain.clone(),
             network: Arc::clone(&self.network),
             zkp_state: Arc::clone(&self.zkp_state),
+            proof_storage: self.proof_storage.clone(),
+            proof_generation_progress: Arc::clone(&self.proof_generation_progress),
             zkp_requests: Arc::clone(&self.zkp_requests),
             zkp_events_notifier: self.zkp_events_notifier.clone(),
         }
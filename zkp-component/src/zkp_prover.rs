@@ -16,34 +16,81 @@ use nimiq_genesis::NetworkInfo;
 use nimiq_network_interface::network::Network;
 use nimiq_primitives::policy::Policy;
 use nimiq_utils::spawn;
+use nimiq_zkp::prove::NUM_SUB_PROOFS;
 use parking_lot::{lock_api::RwLockUpgradableReadGuard, RwLock, RwLockWriteGuard};
-use tokio::sync::oneshot::{channel, Sender};
+use tokio::{
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver},
+        oneshot::{channel, Sender},
+    },
+    time::{sleep_until, Instant, Sleep},
+};
+
+use crate::{
+    proof_gen_utils::*, proof_store::ProofStore, remote_prover::launch_remote_proof_generation,
+    types::*,
+};
+
+/// Where the proofs are generated.
+#[derive(Clone, Debug)]
+pub enum ProverBackend {
+    /// In a local child process running the given prover binary, defaulting to the current executable.
+    Local {
+        prover_path: Option<PathBuf>,
+        resources: ProverResources,
+    },
+    /// On remote prover servers.
+    Remote(RemoteProverConfig),
+}
 
-use crate::{proof_gen_utils::*, types::*};
+/// Returns when the next proof generation should be launched according to the proving policy.
+/// This is the earliest time a pending election block is due, after waiting for a proof from
+/// our peers.
+fn next_proving_time(
+    proving_policy: &ProvingPolicy,
+    pending_election_blocks: &VecDeque<(MacroBlock, Instant)>,
+) -> Option<Instant> {
+    pending_election_blocks
+        .iter()
+        .filter(|(block, _)| proving_policy.is_due(block.block_number()))
+        .map(|(_, received)| *received + proving_policy.peer_proof_wait)
+        .min()
+}
 
 /// ZK Prover generates the zk proof for an election block. It has:
 ///
 /// - The network
 /// - The current zkp state
+/// - The pending election blocks and when they were received
 /// - The election blocks stream
 /// - The genesis state
 /// - The current proof generation future if a proof is being generated
 /// - The channel to kill the current process generating the proof
+/// - The channel receiving the sub-proofs of the current proof generation
+/// - The progress of the current proof generation
+/// - The db storage for the sub-proofs, allowing to resume an interrupted proof generation
 /// - The path of the proving keys directory
-/// - The path of the prover binary
+/// - The backend generating the proofs
+/// - The policy defining for which election blocks proofs are generated
+/// - The timer waiting for the next election block to become due for proving
 ///
 /// The proofs are returned by polling the components.
 pub struct ZKProver<N: Network> {
     network: Arc<N>,
     zkp_state: Arc<RwLock<ZKPState>>,
-    pending_election_blocks: VecDeque<MacroBlock>,
+    pending_election_blocks: VecDeque<(MacroBlock, Instant)>,
     election_stream: BoxStream<'static, MacroBlock>,
     genesis_header_hash: [u8; 32],
     proof_future:
         Option<BoxFuture<'static, Result<(ZKPState, MacroBlock), ZKProofGenerationError>>>,
     proof_future_abort: Option<Sender<()>>,
+    sub_proof_rx: Option<UnboundedReceiver<SubProof>>,
+    proof_generation_progress: Arc<RwLock<Option<ProofGenerationProgress>>>,
+    proof_storage: Option<Arc<dyn ProofStore>>,
     prover_keys_path: PathBuf,
-    prover_path: Option<PathBuf>,
+    prover_backend: ProverBackend,
+    proving_policy: ProvingPolicy,
+    proving_delay: Option<Pin<Box<Sleep>>>,
 }
 
 impl<N: Network> ZKProver<N> {
@@ -51,8 +98,11 @@ impl<N: Network> ZKProver<N> {
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<N>,
         zkp_state: Arc<RwLock<ZKPState>>,
-        prover_path: Option<PathBuf>,
+        proof_generation_progress: Arc<RwLock<Option<ProofGenerationProgress>>>,
+        proof_storage: Option<Arc<dyn ProofStore>>,
+        prover_backend: ProverBackend,
         prover_keys_path: PathBuf,
+        proving_policy: ProvingPolicy,
     ) -> Self {
         let network_info = NetworkInfo::from_network_id(blockchain.read().network_id());
         let genesis_block = network_info.genesis_block().unwrap_macro();
@@ -79,7 +129,7 @@ impl<N: Network> ZKProver<N> {
                 )
                 .expect("Fetching election blocks for zkp prover initialization failed")
                 .drain(..)
-                .map(|block| block.unwrap_macro())
+                .map(|block| (block.unwrap_macro(), Instant::now()))
                 .collect()
         } else {
             VecDeque::new()
@@ -112,8 +162,13 @@ impl<N: Network> ZKProver<N> {
             election_stream: Box::pin(blockchain_election_rx),
             proof_future: None,
             proof_future_abort: None,
+            sub_proof_rx: None,
+            proof_generation_progress,
+            proof_storage,
             prover_keys_path,
-            prover_path,
+            prover_backend,
+            proving_policy,
+            proving_delay: None,
         }
     }
 
@@ -124,6 +179,23 @@ impl<N: Network> ZKProver<N> {
         }
     }
 
+    /// Persists the sub-proofs received from the proof generation process and updates the progress.
+    fn store_sub_proofs(&mut self, cx: &mut Context) {
+        let Some(ref mut sub_proof_rx) = self.sub_proof_rx else {
+            return;
+        };
+
+        while let Poll::Ready(Some(sub_proof)) = sub_proof_rx.poll_recv(cx) {
+            log::debug!(name = sub_proof.name, "Generated sub-proof");
+            if let Some(ref proof_storage) = self.proof_storage {
+                proof_storage.add_sub_proof(&sub_proof);
+            }
+            if let Some(ref mut progress) = *self.proof_generation_progress.write() {
+                progress.completed_sub_proofs += 1;
+            }
+        }
+    }
+
     /// The broadcasting of the generated zk proof.
     fn broadcast_zk_proof(network: &Arc<N>, zk_proof: ZKProof) {
         let network = Arc::clone(network);
@@ -152,23 +224,62 @@ impl<N: Network> ZKProver<N> {
         if zkp_state.latest_block.block_number()
             == block.block_number() - Policy::blocks_per_epoch()
         {
+            // Resume from the sub-proofs of a previous attempt, if any.
+            let sub_proofs = self
+                .proof_storage
+                .as_ref()
+                .map(|proof_storage| proof_storage.get_sub_proofs(&block.hash_blake2s().0))
+                .unwrap_or_default();
+            if !sub_proofs.is_empty() {
+                log::info!(
+                    block_number = block.block_number(),
+                    num_sub_proofs = sub_proofs.len(),
+                    "Resuming proof generation from stored sub-proofs"
+                );
+            }
+            *self.proof_generation_progress.write() = Some(ProofGenerationProgress {
+                block_number: block.block_number(),
+                completed_sub_proofs: sub_proofs.len(),
+                total_sub_proofs: NUM_SUB_PROOFS,
+            });
+
             let (abort_sender, abort_receiver) = channel();
-            self.proof_future = Some(
-                launch_generate_new_proof(
+            let (sub_proof_tx, sub_proof_rx) = unbounded_channel();
+            let proof_input = ProofInput {
+                previous_block: zkp_state.latest_block.clone(),
+                previous_proof: zkp_state.latest_proof.clone(),
+                final_block: block.clone(),
+                genesis_header_hash: self.genesis_header_hash,
+                prover_keys_path: self.prover_keys_path.clone(),
+                sub_proofs,
+            };
+            let proof_future = match self.prover_backend {
+                ProverBackend::Local {
+                    ref prover_path,
+                    ref resources,
+                } => launch_generate_new_proof(
                     abort_receiver,
-                    ProofInput {
-                        previous_block: zkp_state.latest_block.clone(),
-                        previous_proof: zkp_state.latest_proof.clone(),
-                        final_block: block.clone(),
-                        genesis_header_hash: self.genesis_header_hash,
-                        prover_keys_path: self.prover_keys_path.clone(),
-                    },
-                    self.prover_path.clone(),
+                    proof_input,
+                    prover_path.clone(),
+                    resources.clone(),
+                    sub_proof_tx,
                 )
-                .map(|res| res.map(|state| (state, block)))
                 .boxed(),
+                ProverBackend::Remote(ref config) => launch_remote_proof_generation(
+                    abort_receiver,
+                    proof_input,
+                    config.clone(),
+                    sub_proof_tx,
+                )
+                .boxed(),
+            };
+            self.proof_future = Some(
+                proof_future
+                    .map(|res| res.map(|state| (state, block)))
+                    .boxed(),
             );
             self.proof_future_abort = Some(abort_sender);
+            self.sub_proof_rx = Some(sub_proof_rx);
         } else {
             log::debug!(
                 block_height = zkp_state.latest_block.block_number(),
@@ -185,26 +296,58 @@ impl<N: Network> Stream for ZKProver<N> {
     fn poll_next(mut self: Pin<&mut ZKProver<N>>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         // Fetch and store the new election blocks.
         while let Poll::Ready(Some(block)) = self.election_stream.poll_next_unpin(cx) {
-            self.pending_election_blocks.push_back(block);
+            self.pending_election_blocks
+                .push_back((block, Instant::now()));
         }
 
-        // Launches new proof generation, if we have a pending election block are and no proof generation is launched yet.
-        // We need to loop in case we already received a zkp for one of the blocks in the list.
+        // Drops the pending election blocks for which we already received a proof from our peers.
+        let latest_block_number = self.zkp_state.read().latest_block.block_number();
+        self.pending_election_blocks
+            .retain(|(block, _)| block.block_number() > latest_block_number);
+
+        // Launches new proof generation, if a pending election block is due and no proof generation is launched yet.
         while self.proof_future.is_none() {
-            if let Some(block) = self.pending_election_blocks.pop_front() {
-                self.launch_proof_generation(block);
-            } else {
-                break;
+            match next_proving_time(&self.proving_policy, &self.pending_election_blocks) {
+                Some(time) if time <= Instant::now() => {
+                    // The proofs build on each other, so the oldest pending block is proven first.
+                    self.proving_delay = None;
+                    let (block, _) = self.pending_election_blocks.pop_front().unwrap();
+                    self.launch_proof_generation(block);
+                }
+                Some(time) => {
+                    let proving_delay = self
+                        .proving_delay
+                        .get_or_insert_with(|| Box::pin(sleep_until(time)));
+                    proving_delay.as_mut().reset(time);
+                    if proving_delay.poll_unpin(cx).is_pending() {
+                        break;
+                    }
+                }
+                None => {
+                    self.proving_delay = None;
+                    break;
+                }
             }
         }
 
+        // Persists the sub-proofs generated so far.
+        self.store_sub_proofs(cx);
+
         // If a new proof was generated it sets the state and broadcasts the new proof.
         if let Some(proof_future) = &mut self.proof_future {
             if let Poll::Ready(proof) = proof_future.poll_unpin(cx) {
                 self.proof_future = None;
                 self.proof_future_abort = None;
+                self.store_sub_proofs(cx);
+                self.sub_proof_rx = None;
+                *self.proof_generation_progress.write() = None;
                 match proof {
                     Ok((new_zkp_state, block)) => {
+                        // The sub-proofs are no longer needed once the proof is complete.
+                        if let Some(ref proof_storage) = self.proof_storage {
+                            proof_storage.clear_sub_proofs();
+                        }
+
                         assert!(
                             new_zkp_state.latest_proof.is_some(),
                             "The generate new proof should never produces a empty proof"
@@ -236,3 +379,59 @@ impl<N: Network> Stream for ZKProver<N> {
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nimiq_test_log::test;
+
+    use super::*;
+
+    fn election_block(epoch: u32) -> MacroBlock {
+        let mut block = MacroBlock::default();
+        block.header.block_number = Policy::election_block_of(epoch).unwrap();
+        block
+    }
+
+    #[test]
+    fn it_proves_due_election_blocks_after_the_peer_proof_wait() {
+        let proving_policy = ProvingPolicy {
+            every_nth_epoch: 2,
+            peer_proof_wait: Duration::from_secs(10),
+        };
+        let now = Instant::now();
+
+        // Nothing to prove.
+        assert_eq!(next_proving_time(&proving_policy, &VecDeque::new()), None);
+
+        // The election block of an odd epoch is not due.
+        let mut pending_election_blocks = VecDeque::from([(election_block(1), now)]);
+        assert_eq!(
+            next_proving_time(&proving_policy, &pending_election_blocks),
+            None
+        );
+
+        // The next even epoch is due once we waited for a proof from our peers.
+        pending_election_blocks.push_back((election_block(2), now + Duration::from_secs(5)));
+        pending_election_blocks.push_back((election_block(4), now + Duration::from_secs(20)));
+        assert_eq!(
+            next_proving_time(&proving_policy, &pending_election_blocks),
+            Some(now + Duration::from_secs(15))
+        );
+    }
+
+    #[test]
+    fn it_proves_every_election_block_immediately_by_default() {
+        let now = Instant::now();
+        let pending_election_blocks = VecDeque::from([
+            (election_block(1), now),
+            (election_block(2), now + Duration::from_secs(5)),
+        ]);
+
+        assert_eq!(
+            next_proving_time(&ProvingPolicy::default(), &pending_election_blocks),
+            Some(now)
+        );
+    }
+}
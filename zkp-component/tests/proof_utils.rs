@@ -3,7 +3,7 @@ use std::{path::Path, sync::Arc};
 use ark_groth16::Proof;
 use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
 use nimiq_blockchain_proxy::BlockchainProxy;
-use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
+use nimiq_database::{mdbx::MdbxDatabase, memory::MemoryDatabase};
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_test_log::test;
 use nimiq_test_utils::{
@@ -16,7 +16,7 @@ use nimiq_zkp::ZKP_VERIFYING_DATA;
 use nimiq_zkp_component::{
     proof_store::{DBProofStore, ProofStore},
     proof_utils::validate_proof,
-    types::ZKProof,
+    types::{SubProof, ZKProof},
 };
 use parking_lot::RwLock;
 
@@ -151,13 +151,7 @@ async fn can_detect_valid_proof_none_genesis_blocks() {
 
 #[test(tokio::test)]
 async fn can_store_and_load_zkp_state_from_db() {
-    let env = MdbxDatabase::new_volatile(DatabaseConfig {
-        max_tables: Some(2),
-        ..Default::default()
-    })
-    .unwrap();
-
-    let proof_store = DBProofStore::new(env);
+    let proof_store = DBProofStore::new(MemoryDatabase::new());
     let new_proof = ZKProof {
         block_number: Policy::blocks_per_epoch(),
         proof: Some(Proof::default()),
@@ -182,3 +176,45 @@ async fn can_store_and_load_zkp_state_from_db() {
         "Load from db was not successful"
     );
 }
+
+fn sub_proof(final_header_hash: [u8; 32], name: &str) -> SubProof {
+    SubProof {
+        final_header_hash,
+        name: name.to_owned(),
+        proof: name.as_bytes().to_vec(),
+    }
+}
+
+#[test]
+fn can_store_and_clear_sub_proofs() {
+    let proof_store = DBProofStore::new(MemoryDatabase::new());
+    let (block1, block2) = ([1; 32], [2; 32]);
+    assert!(proof_store.get_sub_proofs(&block1).is_empty());
+
+    let mut sub_proofs = vec![
+        sub_proof(block1, "pk_tree_0_0"),
+        sub_proof(block1, "merger"),
+    ];
+    for sub_proof in sub_proofs.iter() {
+        proof_store.add_sub_proof(sub_proof);
+    }
+    // Storing a sub-proof again replaces it.
+    proof_store.add_sub_proof(&sub_proofs[0]);
+
+    let mut stored = proof_store.get_sub_proofs(&block1);
+    stored.sort_by(|a, b| a.name.cmp(&b.name));
+    sub_proofs.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(stored, sub_proofs);
+    assert!(proof_store.get_sub_proofs(&block2).is_empty());
+
+    // Only the sub-proofs of the latest election block are kept.
+    proof_store.add_sub_proof(&sub_proof(block2, "merger"));
+    assert!(proof_store.get_sub_proofs(&block1).is_empty());
+    assert_eq!(
+        proof_store.get_sub_proofs(&block2),
+        vec![sub_proof(block2, "merger")]
+    );
+
+    proof_store.clear_sub_proofs();
+    assert!(proof_store.get_sub_proofs(&block2).is_empty());
+}
@@ -5,7 +5,7 @@ use nimiq_block::MacroBlock;
 use nimiq_database_value::{AsDatabaseBytes, FromDatabaseBytes};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_utils::zkp_test_data::ZKP_TEST_KEYS_PATH;
-use nimiq_zkp_component::types::{ProofInput, ZKPState, ZKProof};
+use nimiq_zkp_component::types::{ProofInput, SubProof, ZKPState, ZKProof};
 
 #[test]
 fn it_serializes_and_deserializes_zk_proof() {
@@ -72,6 +72,11 @@ fn it_serializes_and_deserializes_proof_input() {
         final_block: MacroBlock::default(),
         genesis_header_hash: [2; 32],
         prover_keys_path: PathBuf::from(ZKP_TEST_KEYS_PATH),
+        sub_proofs: vec![SubProof {
+            final_header_hash: [3; 32],
+            name: "pk_tree_5_0".to_string(),
+            proof: vec![1, 2, 3],
+        }],
     };
     let serialized = Serialize::serialize_to_vec(&proof_input);
     let deserialized = ProofInput::deserialize_from_vec(&serialized).unwrap();
@@ -83,6 +88,7 @@ fn it_serializes_and_deserializes_proof_input() {
         final_block: MacroBlock::default(),
         genesis_header_hash: [0; 32],
         prover_keys_path: PathBuf::from(ZKP_TEST_KEYS_PATH),
+        sub_proofs: vec![],
     };
     let serialized = Serialize::serialize_to_vec(&proof_input);
     let deserialized = ProofInput::deserialize_from_vec(&serialized).unwrap();
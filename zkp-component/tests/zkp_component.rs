@@ -1,8 +1,9 @@
 use std::{path::Path, sync::Arc};
 
+use ark_groth16::Proof;
 use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
 use nimiq_blockchain_proxy::BlockchainProxy;
-use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
+use nimiq_database::{mdbx::MdbxDatabase, memory::MemoryDatabase};
 use nimiq_network_mock::MockHub;
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_test_log::test;
@@ -14,7 +15,7 @@ use nimiq_test_utils::{
 use nimiq_utils::time::OffsetTime;
 use nimiq_zkp::ZKP_VERIFYING_DATA;
 use nimiq_zkp_component::{
-    proof_store::{DBProofStore, ProofStore},
+    proof_store::{DBProofStore, ProofHistoryRetention, ProofStore},
     proof_utils::validate_proof,
     types::ZKProof,
     zkp_component::ZKPComponent,
@@ -65,13 +66,7 @@ async fn loads_valid_zkp_state_from_db() {
     let mut hub = MockHub::new();
     let network = Arc::new(hub.new_network());
 
-    let proof_store = DBProofStore::new(
-        MdbxDatabase::new_volatile(DatabaseConfig {
-            max_tables: Some(2),
-            ..Default::default()
-        })
-        .unwrap(),
-    );
+    let proof_store = DBProofStore::new(MemoryDatabase::new());
     let producer = BlockProducer::new(signing_key(), voting_key());
     produce_macro_blocks_with_rng(
         &producer,
@@ -111,13 +106,7 @@ async fn does_not_load_invalid_zkp_state_from_db() {
     let mut hub = MockHub::new();
     let network = Arc::new(hub.new_network());
 
-    let env = MdbxDatabase::new_volatile(DatabaseConfig {
-        max_tables: Some(2),
-        ..Default::default()
-    })
-    .unwrap();
-
-    let proof_store = DBProofStore::new(env);
+    let proof_store = DBProofStore::new(MemoryDatabase::new());
     let new_proof = ZKProof {
         block_number: Policy::blocks_per_epoch(),
         proof: None,
@@ -140,3 +129,28 @@ async fn does_not_load_invalid_zkp_state_from_db() {
         "The load of the zkp state should have failed"
     );
 }
+
+#[test]
+fn keeps_proof_history_within_retention() {
+    let proof_store = DBProofStore::with_history_retention(
+        MemoryDatabase::new(),
+        ProofHistoryRetention::Epochs(2),
+    );
+
+    let election_block =
+        |epoch| Policy::genesis_block_number() + epoch * Policy::blocks_per_epoch();
+    for epoch in 1..=3 {
+        proof_store.set_zkp(&ZKProof {
+            block_number: election_block(epoch),
+            proof: Some(Proof::default()),
+        });
+    }
+
+    assert_eq!(
+        proof_store.get_zkp().map(|proof| proof.block_number),
+        Some(election_block(3))
+    );
+    assert!(proof_store.get_zkp_at(election_block(1)).is_none());
+    assert!(proof_store.get_zkp_at(election_block(2)).is_some());
+    assert!(proof_store.get_zkp_at(election_block(3)).is_some());
+}
@@ -1,10 +1,20 @@
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
+
 use nimiq_test_log::test;
-use nimiq_test_utils::zkp_test_data::zkp_test_exe;
+use nimiq_test_utils::zkp_test_data::{zkp_test_exe, ZKP_TEST_KEYS_PATH};
+use nimiq_utils::{spawn, Sensitive};
 use nimiq_zkp_component::{
     proof_gen_utils::launch_generate_new_proof,
-    types::{ProofInput, ZKProofGenerationError},
+    remote_prover::{launch_remote_proof_generation, ProverServer},
+    types::{
+        ProofInput, ProverResources, ProverServerConfig, RemoteProverConfig, ZKProofGenerationError,
+    },
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::{mpsc, oneshot},
 };
-use tokio::sync::oneshot;
 
 #[test]
 fn can_locate_prover_binary() {
@@ -16,21 +26,87 @@ async fn can_launch_process_and_parse_output() {
     let (_send, recv) = oneshot::channel();
     let proof_input: ProofInput = Default::default();
 
-    let result = launch_generate_new_proof(recv, proof_input, Some(zkp_test_exe())).await;
+    let (sub_proof_tx, _sub_proof_rx) = mpsc::unbounded_channel();
+
+    let result = launch_generate_new_proof(
+        recv,
+        proof_input,
+        Some(zkp_test_exe()),
+        ProverResources::default(),
+        sub_proof_tx,
+    )
+    .await;
 
     // Should fail because verifying keys cannot be loaded.
     assert!(matches!(result, Err(ZKProofGenerationError::NanoZKP(_))));
 }
 
+#[test(tokio::test)]
+async fn can_launch_process_with_restricted_resources() {
+    let (_send, recv) = oneshot::channel();
+    let proof_input: ProofInput = Default::default();
+
+    let (sub_proof_tx, _sub_proof_rx) = mpsc::unbounded_channel();
+
+    let resources = ProverResources {
+        max_threads: Some(1),
+        niceness: Some(10),
+        cpu_affinity: None,
+        max_memory: Some(16 << 30),
+    };
+    let result = launch_generate_new_proof(
+        recv,
+        proof_input,
+        Some(zkp_test_exe()),
+        resources,
+        sub_proof_tx,
+    )
+    .await;
+
+    // Should fail because verifying keys cannot be loaded.
+    assert!(matches!(result, Err(ZKProofGenerationError::NanoZKP(_))));
+}
+
+#[cfg(target_os = "linux")]
+#[test(tokio::test)]
+async fn rejects_invalid_cpu_affinity() {
+    let (_send, recv) = oneshot::channel();
+    let proof_input: ProofInput = Default::default();
+
+    let (sub_proof_tx, _sub_proof_rx) = mpsc::unbounded_channel();
+
+    let resources = ProverResources {
+        cpu_affinity: Some(vec![usize::MAX]),
+        ..Default::default()
+    };
+    let result = launch_generate_new_proof(
+        recv,
+        proof_input,
+        Some(zkp_test_exe()),
+        resources,
+        sub_proof_tx,
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(ZKProofGenerationError::ProcessError(_))
+    ));
+}
+
 #[test(tokio::test)]
 async fn can_launch_process_and_kill() {
     let (send, recv) = oneshot::channel();
     let proof_input: ProofInput = Default::default();
 
+    let (sub_proof_tx, _sub_proof_rx) = mpsc::unbounded_channel();
+
     let result = tokio::spawn(launch_generate_new_proof(
         recv,
         proof_input,
         Some(zkp_test_exe()),
+        ProverResources::default(),
+        sub_proof_tx,
     ));
     send.send(()).unwrap();
 
@@ -39,3 +115,78 @@ async fn can_launch_process_and_kill() {
         Err(ZKProofGenerationError::ChannelError)
     );
 }
+
+async fn launch_prover_server(auth_token: &str) -> SocketAddr {
+    let server = ProverServer::bind(ProverServerConfig {
+        listen_address: "127.0.0.1:0".parse().unwrap(),
+        auth_token: Sensitive(auth_token.to_owned()),
+        prover_keys_path: PathBuf::from(ZKP_TEST_KEYS_PATH),
+        prover_path: Some(zkp_test_exe()),
+        resources: ProverResources::default(),
+        max_queued_jobs: 1,
+        max_connections: 4,
+    })
+    .await
+    .unwrap();
+    let address = server.local_addr().unwrap();
+    spawn(server.run());
+    address
+}
+
+fn remote_prover_config(servers: Vec<String>, auth_token: &str) -> RemoteProverConfig {
+    RemoteProverConfig {
+        servers,
+        auth_token: Sensitive(auth_token.to_owned()),
+        connect_timeout: Duration::from_secs(1),
+        proof_timeout: Duration::from_secs(60),
+        retries: 1,
+        retry_delay: Duration::from_millis(10),
+    }
+}
+
+#[test(tokio::test)]
+async fn can_generate_proof_on_remote_server() {
+    let address = launch_prover_server("secret").await;
+
+    // The first server is not reachable, so the job is sent to the second one.
+    let (_send, recv) = oneshot::channel();
+    let config = remote_prover_config(
+        vec!["127.0.0.1:1".to_owned(), address.to_string()],
+        "secret",
+    );
+    let (sub_proof_tx, _sub_proof_rx) = mpsc::unbounded_channel();
+    let result =
+        launch_remote_proof_generation(recv, Default::default(), config, sub_proof_tx).await;
+
+    // Should fail because verifying keys cannot be loaded.
+    assert!(matches!(result, Err(ZKProofGenerationError::NanoZKP(_))));
+}
+
+#[test(tokio::test)]
+async fn remote_server_rejects_invalid_auth_token() {
+    let address = launch_prover_server("secret").await;
+
+    let (_send, recv) = oneshot::channel();
+    let config = remote_prover_config(vec![address.to_string()], "wrong");
+    let (sub_proof_tx, _sub_proof_rx) = mpsc::unbounded_channel();
+    let result =
+        launch_remote_proof_generation(recv, Default::default(), config, sub_proof_tx).await;
+
+    assert_eq!(
+        result,
+        Err(ZKProofGenerationError::RemoteProverError(
+            "Authentication failed".to_owned()
+        ))
+    );
+}
+
+#[test(tokio::test)]
+async fn remote_server_rejects_oversized_auth_token() {
+    let address = launch_prover_server("secret").await;
+
+    // The server closes the connection without waiting for the announced token.
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    stream.write_u32(1024 * 1024).await.unwrap();
+    let mut buffer = [0u8; 1];
+    assert_eq!(stream.read(&mut buffer).await.unwrap(), 0);
+}
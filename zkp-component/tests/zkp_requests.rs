@@ -3,7 +3,7 @@ use std::{path::Path, sync::Arc};
 use futures::StreamExt;
 use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
 use nimiq_blockchain_proxy::BlockchainProxy;
-use nimiq_database::mdbx::MdbxDatabase;
+use nimiq_database::{mdbx::MdbxDatabase, memory::MemoryDatabase};
 use nimiq_network_interface::network::Network;
 use nimiq_network_mock::MockHub;
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
@@ -16,7 +16,7 @@ use nimiq_test_utils::{
 use nimiq_utils::time::OffsetTime;
 use nimiq_zkp::ZKP_VERIFYING_DATA;
 use nimiq_zkp_component::{
-    proof_store::{DBProofStore, ProofStore},
+    proof_store::{DBProofStore, ProofHistoryRetention, ProofStore},
     proof_utils::validate_proof,
     zkp_requests::ZKPRequests,
     ZKPComponent,
@@ -229,3 +229,89 @@ async fn peers_reply_with_valid_proof_and_election_block() {
         );
     }
 }
+
+#[test(tokio::test)]
+async fn peers_reply_with_retained_proofs_of_past_election_blocks() {
+    let blockchain = blockchain();
+    let blockchain2 = blockchain();
+    let mut hub = MockHub::new();
+    let network = Arc::new(hub.new_network());
+    let network2 = Arc::new(hub.new_network());
+    network.dial_address(network2.address()).await.unwrap();
+
+    // The peer keeps the proofs of the last two epochs.
+    let store2 = DBProofStore::with_history_retention(
+        MemoryDatabase::new(),
+        ProofHistoryRetention::Epochs(2),
+    );
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    let mut rng = get_base_seed();
+    let mut election_blocks = vec![];
+    for _ in 0..3 {
+        produce_macro_blocks_with_rng(
+            &producer,
+            &blockchain2,
+            Policy::batches_per_epoch() as usize,
+            &mut rng,
+        );
+        let proof = simulate_merger_wrapper(
+            Path::new(ZKP_TEST_KEYS_PATH),
+            &blockchain2,
+            &ZKP_VERIFYING_DATA,
+            &mut rng,
+        );
+        store2.set_zkp(&proof);
+        election_blocks.push(proof.block_number);
+    }
+
+    let proof_store_2: Option<Box<dyn ProofStore>> = Some(Box::new(store2));
+    let zkp_prover2 = ZKPComponent::new(
+        BlockchainProxy::from(&blockchain2),
+        Arc::clone(&network2),
+        proof_store_2,
+    )
+    .await;
+    let zkp_prover = ZKPComponent::new(
+        BlockchainProxy::from(&blockchain),
+        Arc::clone(&network),
+        None,
+    )
+    .await;
+
+    // The proofs of the peer's proof history are served, older ones expired.
+    let zkp_proxy2 = zkp_prover2.proxy();
+    assert!(zkp_proxy2
+        .get_zkp_by_block_number(election_blocks[0])
+        .is_none());
+    for &block_number in &election_blocks[1..] {
+        assert_eq!(
+            zkp_proxy2
+                .get_zkp_by_block_number(block_number)
+                .map(|proof| proof.block_number),
+            Some(block_number)
+        );
+    }
+
+    // Retained proofs are verified against the election block sent along.
+    let zkp_proxy = zkp_prover.proxy();
+    let zkp_state = zkp_proxy
+        .request_zkp_at_block_from_peer(network2.peer_id(), election_blocks[1], true)
+        .await
+        .unwrap()
+        .expect("The peer should send its retained proof");
+    assert_eq!(zkp_state.latest_block.block_number(), election_blocks[1]);
+    assert!(zkp_state.latest_proof.is_some());
+
+    // Expired proofs are not found.
+    assert!(zkp_proxy
+        .request_zkp_at_block_from_peer(network2.peer_id(), election_blocks[0], true)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Our own state is not changed by the requests.
+    assert_eq!(
+        zkp_proxy.get_zkp_state().latest_block.block_number(),
+        Policy::genesis_block_number()
+    );
+}
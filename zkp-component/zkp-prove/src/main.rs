@@ -121,6 +121,7 @@ async fn produce_two_consecutive_valid_zk_proofs(network_id: NetworkId) {
         block,
         genesis_header_hash,
         &keys_path,
+        &|_, _| {},
     )
     .unwrap();
     println!(
@@ -151,6 +152,7 @@ async fn produce_two_consecutive_valid_zk_proofs(network_id: NetworkId) {
         block,
         genesis_header_hash,
         &keys_path,
+        &|_, _| {},
     )
     .unwrap();
     println!(
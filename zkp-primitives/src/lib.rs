@@ -34,4 +34,6 @@ pub enum NanoZKPError {
     InvalidBlock,
     #[error("invalid metadata")]
     InvalidMetadata,
+    #[error("invalid keys: {0}")]
+    InvalidKeys(String),
 }
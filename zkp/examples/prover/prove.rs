@@ -113,6 +113,7 @@ fn main() {
             true,
             true,
             path,
+            &|_, _| {},
         )
         .unwrap();
 
@@ -1,6 +1,7 @@
 use std::{
     fs,
     fs::{DirBuilder, File},
+    io::Write,
     path::Path,
 };
 
@@ -69,6 +70,37 @@ pub fn update_proof_cache(
     Ok(())
 }
 
+/// The number of sub-proofs that are generated for a new epoch: one for every node of the public
+/// key tree, plus the macro block, macro block wrapper, merger and merger wrapper proofs.
+pub const NUM_SUB_PROOFS: usize = (1 << (PK_TREE_DEPTH + 1)) - 1 + 4;
+
+/// Writes previously generated sub-proofs into the proof cache, so that a subsequent call to
+/// `prove` with the same inputs and proof caching enabled resumes from there.
+///
+/// The sub-proofs are identified by the names passed to the `on_sub_proof` callback of `prove`.
+pub fn restore_sub_proofs<'a>(
+    prover_keys_path: &Path,
+    current_header_hash: &[u8; 32],
+    sub_proofs: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+) -> Result<(), NanoZKPError> {
+    update_proof_cache(prover_keys_path, current_header_hash)?;
+
+    let proofs = prover_keys_path.join("proofs");
+    for (name, proof) in sub_proofs {
+        // Only accept plain file names.
+        if Path::new(name).file_name() != Some(name.as_ref()) {
+            log::warn!(name, "Ignoring sub-proof with invalid name");
+            continue;
+        }
+
+        let mut file = File::create(proofs.join(format!("{name}.bin")))?;
+        file.write_all(proof)?;
+        file.sync_all()?;
+    }
+
+    Ok(())
+}
+
 /// This function generates a proof for a new epoch, it uses the entire light macro sync. Note
 /// that the proof generation can easily take longer than 12 hours.
 pub fn prove(
@@ -88,6 +120,9 @@ pub fn prove(
     debug_mode: bool,
     // The path to where the `prover_keys` folder is stored in.
     prover_keys_path: &Path,
+    // This is called with the name and the serialized proof of every sub-proof right after it was
+    // cached. It allows to persist the progress elsewhere, see `restore_sub_proofs`.
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<Proof<MNT6_753>, NanoZKPError> {
     // Make sure proofs cache is up-to-date.
     update_proof_cache(prover_keys_path, &final_block.hash_blake2s().0)?;
@@ -120,6 +155,7 @@ pub fn prove(
             debug_mode,
             proof_caching,
             prover_keys_path,
+            on_sub_proof,
         )?;
     }
 
@@ -139,6 +175,7 @@ pub fn prove(
             final_header_hash,
             debug_mode,
             prover_keys_path,
+            on_sub_proof,
         )?;
     }
 
@@ -159,6 +196,7 @@ pub fn prove(
             genesis_data.clone(),
             debug_mode,
             prover_keys_path,
+            on_sub_proof,
         )?;
     }
 
@@ -178,6 +216,7 @@ pub fn prove(
         genesis_data,
         debug_mode,
         prover_keys_path,
+        on_sub_proof,
     )?;
 
     // Delete cached proofs.
@@ -195,6 +234,7 @@ fn prove_pk_tree_leaf<R: CryptoRng + Rng>(
     debug_mode: bool,
     proof_caching: bool,
     dir_path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<[u8; 32], NanoZKPError> {
     assert_eq!(pks.len(), signer_bitmap.len());
 
@@ -268,7 +308,7 @@ fn prove_pk_tree_leaf<R: CryptoRng + Rng>(
     }
 
     // Cache proof to file.
-    proof_to_file(proof, name, Some(position), dir_path)?;
+    proof_to_file(proof, name, Some(position), dir_path, on_sub_proof)?;
 
     Ok(pk_node_hash)
 }
@@ -283,6 +323,7 @@ fn prove_pk_tree_node_mnt4<R: CryptoRng + Rng>(
     debug_mode: bool,
     proof_caching: bool,
     dir_path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<([u8; 32], [u8; 32]), NanoZKPError> {
     assert_eq!(pks.len(), signer_bitmap.len());
 
@@ -307,6 +348,7 @@ fn prove_pk_tree_node_mnt4<R: CryptoRng + Rng>(
             debug_mode,
             proof_caching,
             dir_path,
+            on_sub_proof,
         )?;
 
         r_pk_node_hash = prove_pk_tree_leaf(
@@ -317,6 +359,7 @@ fn prove_pk_tree_node_mnt4<R: CryptoRng + Rng>(
             debug_mode,
             proof_caching,
             dir_path,
+            on_sub_proof,
         )?;
     } else {
         // Next level is an inner node.
@@ -330,6 +373,7 @@ fn prove_pk_tree_node_mnt4<R: CryptoRng + Rng>(
             debug_mode,
             proof_caching,
             dir_path,
+            on_sub_proof,
         )?;
 
         r_pk_node_hash = prove_pk_tree_node_mnt6(
@@ -342,6 +386,7 @@ fn prove_pk_tree_node_mnt4<R: CryptoRng + Rng>(
             debug_mode,
             proof_caching,
             dir_path,
+            on_sub_proof,
         )?;
     }
 
@@ -450,7 +495,7 @@ fn prove_pk_tree_node_mnt4<R: CryptoRng + Rng>(
     }
 
     // Cache proof to file.
-    proof_to_file(proof, &name, Some(position), dir_path)?;
+    proof_to_file(proof, &name, Some(position), dir_path, on_sub_proof)?;
     Ok((l_pk_node_hash, r_pk_node_hash))
 }
 
@@ -464,6 +509,7 @@ fn prove_pk_tree_node_mnt6<R: CryptoRng + Rng>(
     debug_mode: bool,
     proof_caching: bool,
     dir_path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<[u8; 32], NanoZKPError> {
     assert_eq!(pks.len(), signer_bitmap.len());
 
@@ -498,6 +544,7 @@ fn prove_pk_tree_node_mnt6<R: CryptoRng + Rng>(
         debug_mode,
         proof_caching,
         dir_path,
+        on_sub_proof,
     )?;
 
     let (rl_pk_node_hash, rr_pk_node_hash) = prove_pk_tree_node_mnt4(
@@ -510,6 +557,7 @@ fn prove_pk_tree_node_mnt6<R: CryptoRng + Rng>(
         debug_mode,
         proof_caching,
         dir_path,
+        on_sub_proof,
     )?;
 
     // Calculate the node hash.
@@ -648,7 +696,7 @@ fn prove_pk_tree_node_mnt6<R: CryptoRng + Rng>(
     }
 
     // Cache proof to file.
-    proof_to_file(proof, &name, Some(position), dir_path)?;
+    proof_to_file(proof, &name, Some(position), dir_path, on_sub_proof)?;
 
     Ok(pk_node_hash)
 }
@@ -661,6 +709,7 @@ fn prove_macro_block<R: CryptoRng + Rng>(
     debug_mode: bool,
     proof_caching: bool,
     path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<(), NanoZKPError> {
     // Calculate previous public key tree root.
     let prev_validators = prev_block
@@ -690,6 +739,7 @@ fn prove_macro_block<R: CryptoRng + Rng>(
         debug_mode,
         proof_caching,
         path,
+        on_sub_proof,
     )?;
 
     let proving_keys = path.join("proving_keys");
@@ -758,7 +808,7 @@ fn prove_macro_block<R: CryptoRng + Rng>(
     }
 
     // Cache proof to file.
-    proof_to_file(proof, "macro_block", None, path)
+    proof_to_file(proof, "macro_block", None, path, on_sub_proof)
 }
 
 fn prove_macro_block_wrapper<R: CryptoRng + Rng>(
@@ -768,6 +818,7 @@ fn prove_macro_block_wrapper<R: CryptoRng + Rng>(
     final_header_hash: [u8; 32],
     debug_mode: bool,
     path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<(), NanoZKPError> {
     let proving_keys = path.join("proving_keys");
     let verifying_keys = path.join("verifying_keys");
@@ -809,7 +860,7 @@ fn prove_macro_block_wrapper<R: CryptoRng + Rng>(
     }
 
     // Cache proof to file.
-    proof_to_file(proof, "macro_block_wrapper", None, path)
+    proof_to_file(proof, "macro_block_wrapper", None, path, on_sub_proof)
 }
 
 fn prove_merger<R: CryptoRng + Rng>(
@@ -820,6 +871,7 @@ fn prove_merger<R: CryptoRng + Rng>(
     genesis_data: Option<(Proof<MNT6_753>, [u8; 32])>,
     debug_mode: bool,
     path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<(), NanoZKPError> {
     let proving_keys = path.join("proving_keys");
     let verifying_keys = path.join("verifying_keys");
@@ -885,7 +937,7 @@ fn prove_merger<R: CryptoRng + Rng>(
     }
 
     // Cache proof to file.
-    proof_to_file(proof, "merger", None, path)
+    proof_to_file(proof, "merger", None, path, on_sub_proof)
 }
 
 fn prove_merger_wrapper<R: CryptoRng + Rng>(
@@ -896,6 +948,7 @@ fn prove_merger_wrapper<R: CryptoRng + Rng>(
     genesis_data: Option<(Proof<MNT6_753>, [u8; 32])>,
     debug_mode: bool,
     path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<Proof<MNT6_753>, NanoZKPError> {
     let proving_keys = path.join("proving_keys");
     let verifying_keys = path.join("verifying_keys");
@@ -942,7 +995,7 @@ fn prove_merger_wrapper<R: CryptoRng + Rng>(
     }
 
     // Cache proof to file.
-    proof_to_file(proof.clone(), "merger_wrapper", None, path)?;
+    proof_to_file(proof.clone(), "merger_wrapper", None, path, on_sub_proof)?;
 
     Ok(proof)
 }
@@ -953,6 +1006,7 @@ fn proof_to_file<T: Pairing>(
     name: &str,
     number: Option<usize>,
     path: &Path,
+    on_sub_proof: &dyn Fn(&str, &[u8]),
 ) -> Result<(), NanoZKPError> {
     let proofs = path.join("proofs");
     if !proofs.is_dir() {
@@ -964,9 +1018,14 @@ fn proof_to_file<T: Pairing>(
         Some(n) => format!("_{n}"),
     };
 
+    let mut serialized_proof = Vec::with_capacity(pk.uncompressed_size());
+    pk.serialize_uncompressed(&mut serialized_proof)?;
+
     let mut file = File::create(proofs.join(format!("{name}{suffix}.bin")))?;
-    pk.serialize_uncompressed(&mut file)?;
+    file.write_all(&serialized_proof)?;
     file.sync_all()?;
 
+    on_sub_proof(&format!("{name}{suffix}"), &serialized_proof);
+
     Ok(())
 }
@@ -2,6 +2,7 @@ use ark_crypto_primitives::snark::SNARK;
 use ark_ff::ToConstraintField;
 use ark_groth16::{Groth16, Proof};
 use ark_mnt6_753::MNT6_753;
+use nimiq_block::MacroBlock;
 use nimiq_hash::Blake2sHash;
 use nimiq_zkp_primitives::{NanoZKPError, VerifyingData};
 
@@ -30,3 +31,28 @@ pub fn verify(
     // Return result.
     Ok(result)
 }
+
+/// This function verifies a proof for an election block, i.e. that the election block is the result
+/// of a valid chain starting at the genesis block with the given header hash. This is the entry point
+/// for verifying the chain state outside of a node, the verifying data for a network can be loaded
+/// with `ZKPVerifyingKey::embedded`.
+pub fn verify_election_block(
+    // The header hash of the genesis block.
+    genesis_header_hash: Blake2sHash,
+    // The election block the proof was generated for.
+    election_block: &MacroBlock,
+    // The SNARK proof for this election block.
+    proof: Proof<MNT6_753>,
+    verifying_data: &VerifyingData,
+) -> Result<bool, NanoZKPError> {
+    if !election_block.is_election() {
+        return Err(NanoZKPError::InvalidBlock);
+    }
+
+    verify(
+        genesis_header_hash,
+        election_block.hash_blake2s(),
+        proof,
+        verifying_data,
+    )
+}
@@ -4,7 +4,7 @@ use ark_groth16::VerifyingKey;
 use ark_serialize::CanonicalDeserialize;
 use nimiq_primitives::networks::NetworkId;
 use nimiq_zkp_circuits::metadata::VerifyingKeyMetadata;
-use nimiq_zkp_primitives::VerifyingData;
+use nimiq_zkp_primitives::{NanoZKPError, VerifyingData};
 use once_cell::sync::OnceCell;
 
 #[derive(Default)]
@@ -19,15 +19,19 @@ impl ZKPVerifyingKey {
         }
     }
 
-    pub fn init_with_network_id(&self, network_id: NetworkId) {
-        self.init_with_data(Self::init_verifying_key(network_id))
+    pub fn init_with_network_id(&self, network_id: NetworkId) -> Result<(), NanoZKPError> {
+        self.init_with_data(Self::embedded(network_id)?);
+        Ok(())
     }
 
     pub fn init_with_data(&self, verifying_data: VerifyingData) {
         assert!(self.cell.set(verifying_data).is_ok())
     }
 
-    fn init_verifying_key(network_id: NetworkId) -> VerifyingData {
+    /// Loads the verifying data embedded in the binary for the given network.
+    ///
+    /// Fails if there is no valid verifying key for the network.
+    pub fn embedded(network_id: NetworkId) -> Result<VerifyingData, NanoZKPError> {
         let (key_bytes, metadata_bytes) = match network_id {
             NetworkId::DevAlbatross => (
                 include_bytes!(concat!(
@@ -69,24 +73,27 @@ impl ZKPVerifyingKey {
                     "/../.zkp_tests/meta_data.json"
                 )),
             ),
-            _ => panic!("Network id {:?} does not have a verifying key!", network_id),
+            _ => {
+                return Err(NanoZKPError::InvalidKeys(format!(
+                    "network {network_id} does not have a verifying key"
+                )))
+            }
         };
-        let metadata: VerifyingKeyMetadata = serde_json::from_str(metadata_bytes)
-            .expect("Invalid metadata. Please rebuild the ZKP keys.");
+        let metadata: VerifyingKeyMetadata =
+            serde_json::from_str(metadata_bytes).map_err(|_| NanoZKPError::InvalidMetadata)?;
 
-        assert!(
-            metadata.matches(network_id),
-            "ZKP metadata does not match current network. Please rebuild the ZKP keys."
-        );
+        // The keys must have been generated for the current policy.
+        if !metadata.matches(network_id) {
+            return Err(NanoZKPError::InvalidMetadata);
+        }
 
         let mut serialized_cursor = Cursor::new(key_bytes);
-        VerifyingData {
+        Ok(VerifyingData {
             merger_wrapper_vk: VerifyingKey::deserialize_uncompressed_unchecked(
                 &mut serialized_cursor,
-            )
-            .expect("Invalid verifying key. Please rebuild the client."),
+            )?,
             keys_commitment: *metadata.vks_commitment(),
-        }
+        })
     }
 }
 
@@ -94,7 +101,7 @@ impl std::ops::Deref for ZKPVerifyingKey {
     type Target = VerifyingData;
     fn deref(&self) -> &VerifyingData {
         self.cell
-            .get_or_init(|| Self::init_verifying_key(NetworkId::UnitAlbatross))
+            .get_or_init(|| Self::embedded(NetworkId::UnitAlbatross).unwrap())
     }
 }
 
@@ -1,2 +1,3 @@
 #[cfg(feature = "zkp-prover")]
 mod prover;
+mod verify;
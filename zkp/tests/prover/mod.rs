@@ -1 +1,2 @@
+mod proof_cache;
 mod recursive_input;
@@ -0,0 +1,61 @@
+use std::fs;
+
+use nimiq_test_log::test;
+use nimiq_zkp::prove::{restore_sub_proofs, update_proof_cache};
+
+#[test]
+fn restores_only_sub_proofs_with_plain_names() {
+    let prover_keys = tempfile::tempdir().unwrap();
+    let header_hash = [1u8; 32];
+
+    restore_sub_proofs(
+        prover_keys.path(),
+        &header_hash,
+        [
+            ("pk_tree_5_3", &[1, 2, 3][..]),
+            ("../merger", &[4][..]),
+            ("nested/merger", &[5][..]),
+            ("..", &[6][..]),
+            ("", &[7][..]),
+        ],
+    )
+    .unwrap();
+
+    let proofs = prover_keys.path().join("proofs");
+    assert_eq!(
+        fs::read(proofs.join("pk_tree_5_3.bin")).unwrap(),
+        vec![1, 2, 3]
+    );
+    assert!(!prover_keys.path().join("merger.bin").exists());
+    assert!(!proofs.join("nested").exists());
+
+    // Only the metadata and the valid sub-proof were written.
+    let mut files: Vec<_> = fs::read_dir(&proofs)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    files.sort();
+    assert_eq!(files, ["meta_data.bin", "pk_tree_5_3.bin"]);
+}
+
+#[test]
+fn restored_sub_proofs_are_resumed_for_the_same_election_block() {
+    let prover_keys = tempfile::tempdir().unwrap();
+    let proof_file = prover_keys.path().join("proofs").join("merger.bin");
+
+    restore_sub_proofs(prover_keys.path(), &[1u8; 32], [("merger", &[1][..])]).unwrap();
+
+    // The proof generation first updates the cache for its election block. This keeps the
+    // restored sub-proofs, so that they are not generated again.
+    update_proof_cache(prover_keys.path(), &[1u8; 32]).unwrap();
+    assert_eq!(fs::read(&proof_file).unwrap(), vec![1]);
+
+    // The sub-proofs of another election block are discarded.
+    update_proof_cache(prover_keys.path(), &[2u8; 32]).unwrap();
+    assert!(!proof_file.exists());
+
+    // Restoring the sub-proofs of an outdated election block discards the cached ones, too.
+    restore_sub_proofs(prover_keys.path(), &[2u8; 32], [("merger", &[2][..])]).unwrap();
+    restore_sub_proofs(prover_keys.path(), &[3u8; 32], []).unwrap();
+    assert!(!proof_file.exists());
+}
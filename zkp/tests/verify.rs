@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use ark_ff::ToConstraintField;
+use ark_groth16::Proof;
+use ark_mnt6_753::MNT6_753;
+use nimiq_block::MacroBlock;
+use nimiq_genesis::{NetworkId, NetworkInfo};
+use nimiq_hash::Blake2sHash;
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    test_rng::test_rng,
+    zkp_test_data::{load_merger_wrapper_simulator, ZKP_TEST_KEYS_PATH},
+};
+use nimiq_zkp::{verify::verify_election_block, ZKPVerifyingKey, ZKP_VERIFYING_DATA};
+use nimiq_zkp_primitives::{NanoZKPError, VerifyingData};
+
+/// Simulates a proof for the given election block using the toxic waste of the unit test keys.
+fn simulate_proof(
+    genesis_header_hash: &Blake2sHash,
+    election_block: &MacroBlock,
+    verifying_data: &VerifyingData,
+) -> Proof<MNT6_753> {
+    let toxic_waste =
+        load_merger_wrapper_simulator(Path::new(ZKP_TEST_KEYS_PATH)).expect("Missing toxic waste.");
+
+    let mut inputs = vec![];
+    inputs.append(&mut genesis_header_hash.0.to_field_elements().unwrap());
+    inputs.append(&mut election_block.hash_blake2s().0.to_field_elements().unwrap());
+    inputs.append(&mut verifying_data.keys_commitment.to_field_elements().unwrap());
+
+    toxic_waste.simulate_proof(&inputs, &mut test_rng(true))
+}
+
+#[test]
+fn rejects_proof_for_non_election_block() {
+    let result = verify_election_block(
+        Blake2sHash::default(),
+        &MacroBlock::default(),
+        Proof::default(),
+        &ZKP_VERIFYING_DATA,
+    );
+    assert!(matches!(result, Err(NanoZKPError::InvalidBlock)));
+}
+
+#[test]
+fn verifies_valid_proof_and_rejects_tampered_proof() {
+    let verifying_data = ZKPVerifyingKey::embedded(NetworkId::UnitAlbatross).unwrap();
+    let genesis_block = NetworkInfo::from_network_id(NetworkId::UnitAlbatross)
+        .genesis_block()
+        .unwrap_macro();
+    let genesis_header_hash = genesis_block.hash_blake2s();
+
+    let proof = simulate_proof(&genesis_header_hash, &genesis_block, &verifying_data);
+    assert!(verify_election_block(
+        genesis_header_hash.clone(),
+        &genesis_block,
+        proof.clone(),
+        &verifying_data,
+    )
+    .unwrap());
+
+    // A tampered proof is rejected.
+    let mut tampered_proof = proof.clone();
+    std::mem::swap(&mut tampered_proof.a, &mut tampered_proof.c);
+    assert!(!verify_election_block(
+        genesis_header_hash,
+        &genesis_block,
+        tampered_proof,
+        &verifying_data,
+    )
+    .unwrap());
+
+    // The proof is only valid for the genesis block it was generated for.
+    assert!(!verify_election_block(
+        Blake2sHash::default(),
+        &genesis_block,
+        proof,
+        &verifying_data,
+    )
+    .unwrap());
+}
+
+#[test]
+fn embedded_verifying_key_requires_supported_network() {
+    assert!(ZKPVerifyingKey::embedded(NetworkId::UnitAlbatross).is_ok());
+    assert!(matches!(
+        ZKPVerifyingKey::embedded(NetworkId::Dummy),
+        Err(NanoZKPError::InvalidKeys(_))
+    ));
+}